@@ -2,7 +2,7 @@ use std::fs;
 use std::path::Path;
 
 use thiserror::Error;
-use wit_parser::{Resolve, WorldId, WorldItem};
+use wit_parser::{Resolve, WorldId, WorldItem, WorldKey};
 
 use crate::lifecycle::Lifecycle;
 use crate::wasm::{self, WorldSource};
@@ -64,6 +64,40 @@ pub fn check_world_base(wasm_path: &Path, expected: &str) -> Result<String, AbiE
     Ok(found)
 }
 
+/// World a binary declares (or `root:component/root` once componentized) with the names of
+/// the functions and interfaces it imports and exports: `namespace:package/interface@version`
+/// for interfaces, the plain name for bare functions. Type-only interfaces are left out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorldInterfaces {
+    pub world: String,
+    pub source: WorldSource,
+    pub imports: Vec<String>,
+    pub exports: Vec<String>,
+}
+
+pub fn world_interfaces(wasm_path: &Path) -> Result<WorldInterfaces, AbiError> {
+    let bytes = fs::read(wasm_path)?;
+    let (decoded, found) = decode_world(&bytes)?;
+    let resolve = &decoded.resolve;
+    let world = &resolve.worlds[decoded.world];
+    let names = |items: &mut dyn Iterator<Item = (&WorldKey, &WorldItem)>| {
+        items
+            .filter(|(_, item)| match item {
+                WorldItem::Function(_) => true,
+                WorldItem::Interface { id, .. } => !resolve.interfaces[*id].functions.is_empty(),
+                WorldItem::Type { .. } => false,
+            })
+            .map(|(key, _)| resolve.name_world_key(key))
+            .collect::<Vec<_>>()
+    };
+    Ok(WorldInterfaces {
+        world: found,
+        source: decoded.source,
+        imports: names(&mut world.imports.iter()),
+        exports: names(&mut world.exports.iter()),
+    })
+}
+
 pub fn has_lifecycle(wasm_path: &Path) -> Result<Lifecycle, AbiError> {
     let bytes = fs::read(wasm_path)?;
     let names = extract_export_names(&bytes).unwrap_or_default();
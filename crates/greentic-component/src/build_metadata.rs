@@ -0,0 +1,103 @@
+//! Build metadata custom section (`greentic.manifest`).
+//!
+//! `build` appends the canonical manifest JSON together with the git commit, build time, and
+//! template version, so a deployed artifact says where it came from without its checkout.
+//! The manifest is stored without `hashes`: the artifact hash cannot cover itself.
+
+use anyhow::{Result, anyhow, bail};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use time::OffsetDateTime;
+use wasm_encoder::{CustomSection, Encode, Section};
+use wasmparser::{Parser, Payload};
+
+pub const BUILD_METADATA_SECTION: &str = "greentic.manifest";
+pub const BUILD_METADATA_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BuildMetadata {
+    pub version: u32,
+    /// Canonical manifest JSON (sorted keys) without `hashes`.
+    pub manifest: JsonValue,
+    pub build: BuildInfo,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BuildInfo {
+    pub builder: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_commit: Option<String>,
+    /// Tracked files had uncommitted changes when the artifact was built.
+    #[serde(default)]
+    pub git_dirty: bool,
+    #[serde(with = "time::serde::rfc3339")]
+    pub built_at_utc: OffsetDateTime,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template_version: Option<String>,
+}
+
+impl BuildMetadata {
+    pub fn new(manifest: &JsonValue, build: BuildInfo) -> Self {
+        Self {
+            version: BUILD_METADATA_VERSION,
+            manifest: manifest_for_metadata(manifest),
+            build,
+        }
+    }
+
+    /// Whether the embedded manifest matches `manifest`, ignoring `hashes`.
+    pub fn manifest_matches(&self, manifest: &JsonValue) -> bool {
+        self.manifest == manifest_for_metadata(manifest)
+    }
+}
+
+fn manifest_for_metadata(manifest: &JsonValue) -> JsonValue {
+    let mut manifest = manifest.clone();
+    if let Some(fields) = manifest.as_object_mut() {
+        fields.remove("hashes");
+    }
+    manifest
+}
+
+pub fn encode_build_metadata(metadata: &BuildMetadata) -> Result<Vec<u8>> {
+    serde_json::to_vec(metadata).map_err(|err| anyhow!("failed to encode build metadata: {err}"))
+}
+
+pub fn decode_build_metadata(bytes: &[u8]) -> Result<BuildMetadata> {
+    let metadata: BuildMetadata = serde_json::from_slice(bytes)
+        .map_err(|err| anyhow!("failed to decode build metadata: {err}"))?;
+    if metadata.version != BUILD_METADATA_VERSION {
+        bail!("unsupported build metadata version `{}`", metadata.version);
+    }
+    Ok(metadata)
+}
+
+pub fn append_build_metadata_section(
+    wasm_bytes: &[u8],
+    metadata: &BuildMetadata,
+) -> Result<Vec<u8>> {
+    let mut output = wasm_bytes.to_vec();
+    let data = encode_build_metadata(metadata)?;
+    let section = CustomSection {
+        name: BUILD_METADATA_SECTION.into(),
+        data: data.into(),
+    };
+    output.push(section.id());
+    section.encode(&mut output);
+    Ok(output)
+}
+
+/// Reads the last `greentic.manifest` section: rebuilding an unchanged artifact appends a new
+/// one rather than rewriting the module, and the newest describes the current build.
+pub fn read_build_metadata_section(wasm_bytes: &[u8]) -> Result<Option<BuildMetadata>> {
+    let mut latest = None;
+    for payload in Parser::new(0).parse_all(wasm_bytes) {
+        let payload = payload.map_err(|err| anyhow!("failed to parse wasm: {err}"))?;
+        if let Payload::CustomSection(section) = payload
+            && section.name() == BUILD_METADATA_SECTION
+        {
+            latest = Some(section.data().to_vec());
+        }
+    }
+    latest.as_deref().map(decode_build_metadata).transpose()
+}
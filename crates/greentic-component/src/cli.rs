@@ -7,8 +7,8 @@ use clap::{Arg, ArgAction, CommandFactory, FromArgMatches, Parser, Subcommand};
 use crate::cmd::store::StoreCommand;
 use crate::cmd::{
     self, build::BuildArgs, doctor::DoctorArgs, flow::FlowCommand, hash::HashArgs,
-    inspect::InspectArgs, new::NewArgs, templates::TemplatesArgs, test::TestArgs,
-    wizard::WizardCliArgs,
+    i18n_tools::I18nCommand, inspect::InspectArgs, new::NewArgs, qa::QaCommand,
+    schema::SchemaCommand, templates::TemplatesArgs, test::TestArgs, wizard::WizardCliArgs,
 };
 use crate::scaffold::engine::ScaffoldEngine;
 
@@ -52,6 +52,15 @@ See docs/component-developer-guide.md for a walkthrough."
     /// Flow utilities (config flow regeneration)
     #[command(subcommand)]
     Flow(FlowCommand),
+    /// Localization utilities for component locale bundles
+    #[command(subcommand)]
+    I18n(I18nCommand),
+    /// QA form spec authoring utilities
+    #[command(subcommand)]
+    Qa(QaCommand),
+    /// Export JSON Schemas for spec file formats
+    #[command(subcommand)]
+    Schema(SchemaCommand),
     /// Interact with the component store
     #[cfg(feature = "store")]
     #[command(subcommand)]
@@ -90,6 +99,9 @@ pub fn main() -> Result<()> {
         Commands::Build(args) => cmd::build::run(args),
         Commands::Test(args) => cmd::test::run(*args),
         Commands::Flow(flow_cmd) => cmd::flow::run(flow_cmd),
+        Commands::I18n(i18n_cmd) => cmd::i18n_tools::run(i18n_cmd),
+        Commands::Qa(qa_cmd) => cmd::qa::run(qa_cmd),
+        Commands::Schema(schema_cmd) => cmd::schema::run(schema_cmd),
         #[cfg(feature = "store")]
         Commands::Store(store_cmd) => cmd::store::run(store_cmd),
     }
@@ -175,7 +187,7 @@ mod tests {
         assert_eq!(cli.locale.as_deref(), Some("nl"));
         match cli.command {
             Commands::New(args) => {
-                assert_eq!(args.name, "demo");
+                assert_eq!(args.name.as_deref(), Some("demo"));
                 assert!(args.json);
                 assert!(!args.no_check);
                 assert!(!args.no_git);
@@ -208,6 +220,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parses_new_multi_spec_instead_of_name() {
+        let cli = Cli::try_parse_from(["greentic-component", "new", "--multi", "spec.yaml"])
+            .expect("expected CLI to parse");
+        match cli.command {
+            Commands::New(args) => {
+                assert_eq!(args.name, None);
+                assert_eq!(
+                    args.multi.as_deref(),
+                    Some(std::path::Path::new("spec.yaml"))
+                );
+            }
+            _ => panic!("expected new args"),
+        }
+        assert!(
+            Cli::try_parse_from([
+                "greentic-component",
+                "new",
+                "--name",
+                "demo",
+                "--multi",
+                "spec.yaml",
+            ])
+            .is_err()
+        );
+        assert!(Cli::try_parse_from(["greentic-component", "new"]).is_err());
+    }
+
     #[test]
     fn parses_wizard_command() {
         let cli = Cli::try_parse_from([
@@ -279,6 +319,52 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parses_wizard_validate_answers_command() {
+        let cli = Cli::try_parse_from([
+            "greentic-component",
+            "wizard",
+            "validate-answers",
+            "answers.json",
+        ])
+        .expect("expected CLI to parse");
+        match cli.command {
+            Commands::Wizard(args) => match args.command {
+                Some(crate::cmd::wizard::WizardSubcommand::ValidateAnswers(validate)) => {
+                    assert_eq!(validate.answers, std::path::PathBuf::from("answers.json"));
+                    assert!(!validate.json);
+                }
+                _ => panic!("expected wizard validate-answers subcommand"),
+            },
+            _ => panic!("expected wizard args"),
+        }
+    }
+
+    #[test]
+    fn parses_wizard_keys_trust_command() {
+        let cli = Cli::try_parse_from([
+            "greentic-component",
+            "wizard",
+            "keys",
+            "trust",
+            "release",
+            "abcd",
+        ])
+        .expect("expected CLI to parse");
+        match cli.command {
+            Commands::Wizard(args) => match args.command {
+                Some(crate::cmd::wizard::WizardSubcommand::Keys(
+                    crate::cmd::wizard::WizardKeysCommand::Trust { key_id, public_key },
+                )) => {
+                    assert_eq!(key_id, "release");
+                    assert_eq!(public_key, "abcd");
+                }
+                _ => panic!("expected wizard keys trust subcommand"),
+            },
+            _ => panic!("expected wizard args"),
+        }
+    }
+
     #[test]
     fn parses_wizard_validate_flag() {
         let cli = Cli::try_parse_from([
@@ -333,6 +419,171 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parses_i18n_check_command() {
+        let cli = Cli::try_parse_from([
+            "greentic-component",
+            "i18n",
+            "check",
+            "--project-root",
+            "demo",
+            "--json",
+        ])
+        .expect("expected CLI to parse");
+        match cli.command {
+            Commands::I18n(crate::cmd::i18n_tools::I18nCommand::Check(args)) => {
+                assert_eq!(args.project_root, std::path::PathBuf::from("demo"));
+                assert_eq!(args.i18n_dir, std::path::PathBuf::from("assets/i18n"));
+                assert!(args.json);
+            }
+            _ => panic!("expected i18n check args"),
+        }
+    }
+
+    #[test]
+    fn i18n_translate_sets_up_codex_only_on_request() {
+        let cli = Cli::try_parse_from(["greentic-component", "i18n", "translate"])
+            .expect("expected CLI to parse");
+        match cli.command {
+            Commands::I18n(crate::cmd::i18n_tools::I18nCommand::Translate(args)) => {
+                assert!(!args.codex_setup);
+            }
+            _ => panic!("expected i18n translate args"),
+        }
+        assert!(
+            Cli::try_parse_from([
+                "greentic-component",
+                "i18n",
+                "translate",
+                "--codex-setup",
+                "--skip-codex-check",
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn parses_qa_import_schema_command() {
+        let cli = Cli::try_parse_from([
+            "greentic-component",
+            "qa",
+            "import-schema",
+            "component.manifest.json",
+            "--pointer",
+            "/config_schema",
+            "--out",
+            "setup.yaml",
+        ])
+        .expect("expected CLI to parse");
+        match cli.command {
+            Commands::Qa(QaCommand::ImportSchema(args)) => {
+                assert_eq!(
+                    args.schema,
+                    std::path::PathBuf::from("component.manifest.json")
+                );
+                assert_eq!(args.pointer.as_deref(), Some("/config_schema"));
+                assert_eq!(args.form_version, "0.1.0");
+                assert_eq!(args.out, Some(std::path::PathBuf::from("setup.yaml")));
+            }
+            _ => panic!("expected qa import-schema args"),
+        }
+    }
+
+    #[test]
+    fn parses_qa_import_openapi_command() {
+        let cli = Cli::try_parse_from([
+            "greentic-component",
+            "qa",
+            "import-openapi",
+            "openapi.yaml",
+            "--operation",
+            "createPet",
+            "--operation",
+            "deletePet",
+            "--format",
+            "yaml",
+        ])
+        .expect("expected CLI to parse");
+        match cli.command {
+            Commands::Qa(QaCommand::ImportOpenapi(args)) => {
+                assert_eq!(args.spec, std::path::PathBuf::from("openapi.yaml"));
+                assert_eq!(args.operations, ["createPet", "deletePet"]);
+                assert_eq!(args.out_dir, std::path::PathBuf::from("forms"));
+                assert_eq!(args.format, crate::cmd::qa::SpecFormat::Yaml);
+            }
+            _ => panic!("expected qa import-openapi args"),
+        }
+    }
+
+    #[test]
+    fn parses_qa_lint_command() {
+        let cli = Cli::try_parse_from([
+            "greentic-component",
+            "qa",
+            "lint",
+            "forms/setup.yaml",
+            "forms/update.json",
+            "--i18n-dir",
+            "assets/i18n",
+            "--watch",
+        ])
+        .expect("expected CLI to parse");
+        match cli.command {
+            Commands::Qa(QaCommand::Lint(args)) => {
+                assert_eq!(args.specs.len(), 2);
+                assert_eq!(args.i18n_dir, Some(std::path::PathBuf::from("assets/i18n")));
+                assert!(args.watch);
+            }
+            _ => panic!("expected qa lint args"),
+        }
+    }
+
+    #[test]
+    fn parses_qa_rpc_command() {
+        let cli = Cli::try_parse_from(["greentic-component", "qa", "rpc", "--form", "setup.yaml"])
+            .expect("expected CLI to parse");
+        match cli.command {
+            Commands::Qa(QaCommand::Rpc(args)) => {
+                assert_eq!(args.form, std::path::PathBuf::from("setup.yaml"))
+            }
+            _ => panic!("expected qa rpc args"),
+        }
+        assert!(Cli::try_parse_from(["greentic-component", "qa", "rpc"]).is_err());
+    }
+
+    #[cfg(feature = "qa-serve")]
+    #[test]
+    fn parses_qa_serve_command() {
+        let cli = Cli::try_parse_from([
+            "greentic-component",
+            "qa",
+            "serve",
+            "--form",
+            "forms/setup.json",
+        ])
+        .expect("expected CLI to parse");
+        match cli.command {
+            Commands::Qa(QaCommand::Serve(args)) => {
+                assert_eq!(args.bind.to_string(), "127.0.0.1:8787");
+                assert_eq!(args.form, std::path::PathBuf::from("forms/setup.json"));
+                assert_eq!(args.allow_origin, None);
+            }
+            _ => panic!("expected qa serve args"),
+        }
+    }
+
+    #[test]
+    fn parses_schema_export_command() {
+        let cli = Cli::try_parse_from(["greentic-component", "schema", "export"])
+            .expect("expected CLI to parse");
+        match cli.command {
+            Commands::Schema(SchemaCommand::Export(args)) => {
+                assert_eq!(args.out_dir, std::path::PathBuf::from("schemas/qa"));
+            }
+            _ => panic!("expected schema export args"),
+        }
+    }
+
     #[cfg(feature = "store")]
     #[test]
     fn parses_store_fetch_command() {
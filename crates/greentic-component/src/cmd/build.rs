@@ -1,18 +1,28 @@
 #![cfg(feature = "cli")]
 
+use std::collections::BTreeMap;
 use std::env;
+use std::ffi::{OsStr, OsString};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use anyhow::{Context, Result, anyhow, bail};
-use clap::Args;
+use clap::{Args, Parser};
 use serde_json::Value as JsonValue;
 use wasmtime::component::{Component, Linker, Val};
 use wasmtime::{Engine, Store};
 use wasmtime_wasi::{ResourceTable, WasiCtx, WasiCtxBuilder, WasiCtxView, WasiView};
 
 use crate::abi::{self, AbiError};
+use crate::build_metadata::{BuildInfo, BuildMetadata, append_build_metadata_section};
+use crate::cmd::build_oci::{
+    OciArtifact, OciLayer, OciReport, SBOM_LAYER_MEDIA_TYPE, WASM_LAYER_MEDIA_TYPE,
+    push_oci_artifact, write_oci_layout,
+};
+use crate::cmd::build_opt::{OptimizePreset, OptimizeReport, optimize_wasm, resolve_wasm_opt};
+use crate::cmd::build_repro::{REPRO_TARGET_SUBDIR, ReproEnv, ReproReport, compare_builds};
+use crate::cmd::build_sbom::{CargoDependencies, cargo_dependencies, sbom_document};
 use crate::cmd::component_world::{canonical_component_world, is_fallback_world};
 use crate::cmd::flow::{
     FlowUpdateResult, manifest_component_id, resolve_operation, update_with_manifest,
@@ -28,8 +38,12 @@ use crate::path_safety::normalize_under_root;
 use crate::schema_quality::{SchemaQualityMode, validate_operation_schemas};
 use greentic_types::cbor::canonical;
 use greentic_types::schemas::component::v0_6_0::ComponentDescribe;
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
 
 const DEFAULT_MANIFEST: &str = "component.manifest.json";
+/// Reproducible-builds convention for pinning the recorded build time.
+const SOURCE_DATE_EPOCH_ENV: &str = "SOURCE_DATE_EPOCH";
 
 #[derive(Args, Debug, Clone)]
 pub struct BuildArgs {
@@ -60,6 +74,103 @@ pub struct BuildArgs {
     /// Allow empty operation schemas (warnings only)
     #[arg(long)]
     pub permissive: bool,
+    /// Run wasm-opt over the built artifact with this preset
+    #[arg(long = "optimize", value_enum, value_name = "PRESET")]
+    pub optimize: Option<OptimizePreset>,
+    /// Path to the wasm-opt binary (fallback: $WASM_OPT, then `wasm-opt` on PATH)
+    #[arg(long = "wasm-opt", value_name = "PATH", requires = "optimize")]
+    pub wasm_opt: Option<PathBuf>,
+    /// Leave `hashes` in the manifest untouched (run `greentic-component hash` later)
+    #[arg(long = "no-update-hashes")]
+    pub no_update_hashes: bool,
+    /// Build twice with a normalized environment and fail unless the artifacts are identical
+    #[arg(long = "verify-repro")]
+    pub verify_repro: bool,
+    /// Skip the CycloneDX SBOM (dist/<name>__<abi>.cdx.json)
+    #[arg(long = "no-sbom")]
+    pub no_sbom: bool,
+    /// Package wasm, manifest and SBOM as an OCI artifact tagged REF (dist/<name>__<abi>.oci)
+    #[arg(long = "oci", value_name = "REF")]
+    pub oci: Option<String>,
+    /// Push the OCI artifact to its registry ($GREENTIC_OCI_USERNAME/$GREENTIC_OCI_PASSWORD)
+    #[arg(long = "oci-push", requires = "oci")]
+    pub oci_push: bool,
+    /// Cargo profile to build with (default: release; other profiles add `__<profile>` to dist files)
+    #[arg(long = "profile", value_name = "PROFILE")]
+    pub profile: Option<String>,
+    /// Comma-separated cargo features to enable
+    #[arg(long = "features", value_name = "FEATURES", value_delimiter = ',')]
+    pub features: Vec<String>,
+    /// Build without the crate's default features
+    #[arg(long = "no-default-features")]
+    pub no_default_features: bool,
+    /// Cargo target directory (fallback: $CARGO_TARGET_DIR, then target/)
+    #[arg(long = "target-dir", value_name = "PATH")]
+    pub target_dir: Option<PathBuf>,
+}
+
+impl Default for BuildArgs {
+    /// The arguments of a bare `greentic-component build`, taken from the clap defaults.
+    fn default() -> Self {
+        #[derive(Parser)]
+        struct BuildCli {
+            #[command(flatten)]
+            args: BuildArgs,
+        }
+        BuildCli::parse_from(["build"]).args
+    }
+}
+
+/// Profile, feature and target-dir selection forwarded to cargo.
+#[derive(Debug, Clone)]
+struct CargoOptions {
+    profile: Option<String>,
+    features: Vec<String>,
+    no_default_features: bool,
+    target_dir: Option<PathBuf>,
+}
+
+impl CargoOptions {
+    fn from_args(args: &BuildArgs, cwd: &Path) -> Self {
+        Self {
+            profile: args.profile.clone(),
+            features: args.features.clone(),
+            no_default_features: args.no_default_features,
+            target_dir: args.target_dir.as_ref().map(|dir| cwd.join(dir)),
+        }
+    }
+
+    fn profile(&self) -> &str {
+        self.profile.as_deref().unwrap_or("release")
+    }
+
+    /// Directory under `target/<triple>/` cargo writes the profile's artifacts to.
+    fn profile_dir(&self) -> &str {
+        match self.profile() {
+            "dev" | "test" => "debug",
+            "bench" => "release",
+            profile => profile,
+        }
+    }
+
+    fn args(&self) -> Vec<OsString> {
+        let mut args = match self.profile() {
+            "release" => vec![OsString::from("--release")],
+            profile => vec![OsString::from("--profile"), OsString::from(profile)],
+        };
+        if !self.features.is_empty() {
+            args.push("--features".into());
+            args.push(self.features.join(",").into());
+        }
+        if self.no_default_features {
+            args.push("--no-default-features".into());
+        }
+        if let Some(target_dir) = &self.target_dir {
+            args.push("--target-dir".into());
+            args.push(target_dir.into());
+        }
+        args
+    }
 }
 
 #[derive(Debug, serde::Serialize)]
@@ -67,10 +178,20 @@ struct BuildSummary {
     manifest: PathBuf,
     wasm_path: PathBuf,
     wasm_hash: String,
+    hashes_updated: bool,
     config_source: ConfigSchemaSource,
     schema_written: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     flows: Option<FlowUpdateResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    optimization: Option<OptimizeReport>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reproducibility: Option<ReproReport>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sbom: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    oci: Option<OciReport>,
+    build: BuildInfo,
 }
 
 pub fn run(args: BuildArgs) -> Result<()> {
@@ -142,9 +263,57 @@ pub fn run(args: BuildArgs) -> Result<()> {
     .context("failed to parse canonical manifest for embedded descriptor")?;
 
     let manifest_dir = manifest_path.parent().unwrap_or_else(|| Path::new("."));
-    build_wasm(manifest_dir, &cargo_bin, &manifest_to_write)?;
-    check_canonical_world_export(manifest_dir, &manifest_to_write)?;
-    let wasm_path_for_embedding = resolve_wasm_path(manifest_dir, &manifest_to_write)?;
+    let cargo_options = CargoOptions::from_args(&args, &cwd);
+    let repro_env = args.verify_repro.then(|| {
+        ReproEnv::new(
+            manifest_dir,
+            &cargo_target_dir(manifest_dir, &cargo_options),
+            repro_source_date_epoch(manifest_dir),
+        )
+    });
+    build_wasm(
+        manifest_dir,
+        &cargo_bin,
+        &manifest_to_write,
+        &cargo_options,
+        repro_env.as_ref(),
+    )?;
+    check_canonical_world_export(manifest_dir, &manifest_to_write, &cargo_options)?;
+    let wasm_path_for_embedding =
+        resolve_wasm_path(manifest_dir, &manifest_to_write, &cargo_options)?;
+    let dist_base = dist_basename(
+        manifest_dir,
+        &manifest_to_write,
+        &wasm_path_for_embedding,
+        &cargo_options,
+    );
+    let reproducibility = match &repro_env {
+        Some(repro_env) => Some(verify_reproducible(
+            manifest_dir,
+            &cargo_bin,
+            &manifest_to_write,
+            &cargo_options,
+            repro_env,
+            &wasm_path_for_embedding,
+        )?),
+        None => None,
+    };
+    let optimization = match args.optimize {
+        Some(preset) => {
+            let wasm_opt = resolve_wasm_opt(args.wasm_opt.as_deref());
+            let report = optimize_wasm(&wasm_path_for_embedding, &wasm_opt, preset)
+                .context("wasm-opt post-processing failed")?;
+            if !args.json {
+                println!(
+                    "Optimized wasm with {}: {}",
+                    wasm_opt.display(),
+                    report.summary()
+                );
+            }
+            Some(report)
+        }
+        None => None,
+    };
     embed_and_verify_wasm(&wasm_path_for_embedding, &canonical_manifest)
         .context("failed to embed canonical manifest into built wasm")?;
 
@@ -153,24 +322,108 @@ pub fn run(args: BuildArgs) -> Result<()> {
             .as_object_mut()
             .map(|obj| obj.remove("config_schema"));
     }
-    let (wasm_path, wasm_hash) = update_manifest_hashes(manifest_dir, &mut manifest_to_write)?;
-    emit_describe_artifacts(manifest_dir, &manifest_to_write, &wasm_path)?;
+    let sbom_dependencies = if args.no_sbom {
+        None
+    } else {
+        match cargo_dependencies(&cargo_bin, manifest_dir) {
+            Ok(dependencies) => Some(dependencies),
+            Err(err) => {
+                eprintln!("warning: skipping SBOM ({err:#})");
+                None
+            }
+        }
+    };
+    let sbom_path = reference_sbom(
+        manifest_dir,
+        &mut manifest_to_write,
+        &dist_base,
+        sbom_dependencies.is_some(),
+    )?;
+    let build_info =
+        embed_build_metadata(&wasm_path_for_embedding, manifest_dir, &manifest_to_write)
+            .context("failed to embed build metadata into built wasm")?;
+    let (wasm_path, wasm_hash) =
+        record_wasm_artifact(manifest_dir, &mut manifest_to_write, &cargo_options)?;
+    if let (Some(dependencies), Some(sbom_path)) = (&sbom_dependencies, &sbom_path) {
+        write_sbom(
+            sbom_path,
+            dependencies,
+            &manifest_to_write,
+            &wasm_path,
+            build_info.built_at_utc,
+        )?;
+    }
+    if args.no_update_hashes {
+        warn_if_hash_stale(&manifest_to_write, &wasm_hash);
+    } else {
+        update_manifest_hashes(manifest_dir, &mut manifest_to_write, &wasm_hash)?;
+    }
+    emit_describe_artifacts(manifest_dir, &manifest_to_write, &wasm_path, &dist_base)?;
     write_manifest(&manifest_path, &manifest_to_write)?;
+    let oci = match &args.oci {
+        Some(reference) => {
+            let artifact = oci_artifact(
+                &manifest_path,
+                &manifest_to_write,
+                &wasm_path,
+                sbom_path.as_deref(),
+                &build_info,
+            )?;
+            let layout = dist_path(manifest_dir, &dist_base, "oci");
+            Some(package_oci(reference, args.oci_push, layout, &artifact)?)
+        }
+        None => None,
+    };
 
     if args.json {
         let payload = BuildSummary {
             manifest: manifest_path.clone(),
             wasm_path,
             wasm_hash,
+            hashes_updated: !args.no_update_hashes,
             config_source: config.source,
             schema_written: config.schema_written && config.persist_schema,
             flows: flow_outcome.as_ref().map(|outcome| outcome.result),
+            optimization,
+            reproducibility,
+            sbom: sbom_path,
+            oci,
+            build: build_info,
         };
         serde_json::to_writer_pretty(std::io::stdout(), &payload)?;
         println!();
     } else {
         println!("Built wasm artifact at {}", wasm_path.display());
-        println!("Updated {} hashes (blake3)", manifest_path.display());
+        if let Some(report) = &reproducibility {
+            println!(
+                "Reproducible build verified ({}, SOURCE_DATE_EPOCH={})",
+                report.first_hash, report.source_date_epoch
+            );
+        }
+        if args.no_update_hashes {
+            println!("Manifest hashes left untouched (--no-update-hashes)");
+        } else {
+            println!("Updated {} hashes (blake3)", manifest_path.display());
+        }
+        if let Some(sbom_path) = &sbom_path {
+            println!("Wrote CycloneDX SBOM to {}", sbom_path.display());
+        }
+        if let Some(oci) = &oci {
+            println!(
+                "Wrote OCI artifact {} to {} ({})",
+                oci.reference,
+                oci.layout.display(),
+                oci.manifest_digest
+            );
+            if let Some(url) = &oci.pushed {
+                println!("Pushed {} ({url})", oci.reference);
+            }
+        }
+        println!(
+            "Embedded build metadata (commit: {}{})",
+            build_info.git_commit.as_deref().unwrap_or("<none>"),
+            if build_info.git_dirty { ", dirty" } else { "" }
+        );
         if config.schema_written && config.persist_schema {
             println!(
                 "Updated {} with inferred config_schema ({:?})",
@@ -192,7 +445,13 @@ pub fn run(args: BuildArgs) -> Result<()> {
     Ok(())
 }
 
-fn build_wasm(manifest_dir: &Path, cargo_bin: &Path, manifest: &JsonValue) -> Result<()> {
+fn build_wasm(
+    manifest_dir: &Path,
+    cargo_bin: &Path,
+    manifest: &JsonValue,
+    cargo_options: &CargoOptions,
+    repro: Option<&ReproEnv>,
+) -> Result<()> {
     let resolved_world = manifest.get("world").and_then(|v| v.as_str()).unwrap_or("");
     if resolved_world.is_empty() {
         println!("Resolved manifest world: <missing>");
@@ -200,6 +459,12 @@ fn build_wasm(manifest_dir: &Path, cargo_bin: &Path, manifest: &JsonValue) -> Re
         println!("Resolved manifest world: {resolved_world}");
     }
     let require_component = resolved_world.contains("component@0.6.0");
+    let cargo_args = cargo_options.args();
+    let cargo_args_display = cargo_args
+        .iter()
+        .map(|arg| arg.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join(" ");
 
     if require_component {
         if cargo_component_available(cargo_bin) {
@@ -209,15 +474,13 @@ fn build_wasm(manifest_dir: &Path, cargo_bin: &Path, manifest: &JsonValue) -> Re
                 manifest_dir.display()
             );
             let mut cmd = Command::new(cargo_bin);
-            if let Some(flags) = resolved_wasm_rustflags() {
-                cmd.env("RUSTFLAGS", sanitize_wasm_rustflags(&flags));
-            }
+            apply_build_env(&mut cmd, repro);
             let status = cmd
                 .arg("component")
                 .arg("build")
                 .arg("--target")
                 .arg("wasm32-wasip2")
-                .arg("--release")
+                .args(&cargo_args)
                 .current_dir(manifest_dir)
                 .status()
                 .with_context(|| {
@@ -228,8 +491,7 @@ fn build_wasm(manifest_dir: &Path, cargo_bin: &Path, manifest: &JsonValue) -> Re
                 })?;
             if !status.success() {
                 bail!(
-                    "cargo component build --target wasm32-wasip2 --release failed with status {}",
-                    status
+                    "cargo component build --target wasm32-wasip2 {cargo_args_display} failed with status {status}"
                 );
             }
             return Ok(());
@@ -245,22 +507,19 @@ fn build_wasm(manifest_dir: &Path, cargo_bin: &Path, manifest: &JsonValue) -> Re
         manifest_dir.display()
     );
     let mut cmd = Command::new(cargo_bin);
-    if let Some(flags) = resolved_wasm_rustflags() {
-        cmd.env("RUSTFLAGS", sanitize_wasm_rustflags(&flags));
-    }
+    apply_build_env(&mut cmd, repro);
     let status = cmd
         .arg("build")
         .arg("--target")
         .arg("wasm32-wasip2")
-        .arg("--release")
+        .args(&cargo_args)
         .current_dir(manifest_dir)
         .status()
         .with_context(|| format!("failed to run cargo build via {}", cargo_bin.display()))?;
 
     if !status.success() {
         bail!(
-            "cargo build --target wasm32-wasip2 --release failed with status {}",
-            status
+            "cargo build --target wasm32-wasip2 {cargo_args_display} failed with status {status}"
         );
     }
     Ok(())
@@ -275,6 +534,93 @@ fn cargo_component_available(cargo_bin: &Path) -> bool {
         .unwrap_or(false)
 }
 
+fn apply_build_env(cmd: &mut Command, repro: Option<&ReproEnv>) {
+    let flags = resolved_wasm_rustflags().map(|flags| sanitize_wasm_rustflags(&flags));
+    match repro {
+        Some(repro) => repro.apply(cmd, flags.as_deref()),
+        None => {
+            if let Some(flags) = flags {
+                cmd.env("RUSTFLAGS", flags);
+            }
+        }
+    }
+}
+
+/// Rebuild into a separate target dir and compare the artifact with the first build.
+fn verify_reproducible(
+    manifest_dir: &Path,
+    cargo_bin: &Path,
+    manifest: &JsonValue,
+    cargo_options: &CargoOptions,
+    repro_env: &ReproEnv,
+    first_wasm: &Path,
+) -> Result<ReproReport> {
+    let target_dir = cargo_target_dir(manifest_dir, cargo_options);
+    let target_dir = target_dir.canonicalize().unwrap_or(target_dir);
+    let relative = first_wasm.strip_prefix(&target_dir).with_context(|| {
+        format!(
+            "--verify-repro needs the wasm artifact inside the cargo target dir {}, found {}",
+            target_dir.display(),
+            first_wasm.display()
+        )
+    })?;
+    let second_target = target_dir.join(REPRO_TARGET_SUBDIR);
+    println!(
+        "Verifying reproducibility: rebuilding in {}",
+        second_target.display()
+    );
+    // `--target-dir` would override the CARGO_TARGET_DIR the repro env points at the rebuild.
+    let second_options = CargoOptions {
+        target_dir: None,
+        ..cargo_options.clone()
+    };
+    build_wasm(
+        manifest_dir,
+        cargo_bin,
+        manifest,
+        &second_options,
+        Some(&repro_env.with_target_dir(second_target.clone())),
+    )?;
+    let second_wasm = second_target.join(relative);
+    let report = compare_builds(first_wasm, &second_wasm, repro_env)?;
+    if !report.reproducible {
+        bail!(
+            "build is not reproducible ({} vs {}):\n  - {}",
+            report.first_hash,
+            report.second_hash,
+            report.differences.join("\n  - ")
+        );
+    }
+    Ok(report)
+}
+
+fn cargo_target_dir(manifest_dir: &Path, cargo_options: &CargoOptions) -> PathBuf {
+    if let Some(target_dir) = &cargo_options.target_dir {
+        return target_dir.clone();
+    }
+    match env::var_os("CARGO_TARGET_DIR") {
+        Some(dir) => manifest_dir.join(dir),
+        None => manifest_dir.join("target"),
+    }
+}
+
+/// `SOURCE_DATE_EPOCH` if set, else the HEAD commit time, else the unix epoch.
+fn repro_source_date_epoch(manifest_dir: &Path) -> String {
+    env::var(SOURCE_DATE_EPOCH_ENV)
+        .ok()
+        .or_else(|| {
+            Command::new("git")
+                .args(["log", "-1", "--format=%ct"])
+                .current_dir(manifest_dir)
+                .output()
+                .ok()
+                .filter(|output| output.status.success())
+                .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+                .filter(|epoch| !epoch.is_empty())
+        })
+        .unwrap_or_else(|| "0".to_string())
+}
+
 /// Reads the wasm-specific rustflags that CI exports for wasm builds.
 fn resolved_wasm_rustflags() -> Option<String> {
     env::var("WASM_RUSTFLAGS")
@@ -293,12 +639,16 @@ fn sanitize_wasm_rustflags(flags: &str) -> String {
         .join(" ")
 }
 
-fn check_canonical_world_export(manifest_dir: &Path, manifest: &JsonValue) -> Result<()> {
+fn check_canonical_world_export(
+    manifest_dir: &Path,
+    manifest: &JsonValue,
+    cargo_options: &CargoOptions,
+) -> Result<()> {
     if env::var_os("GREENTIC_SKIP_NODE_EXPORT_CHECK").is_some() {
         println!("World export check skipped (GREENTIC_SKIP_NODE_EXPORT_CHECK=1)");
         return Ok(());
     }
-    let wasm_path = resolve_wasm_path(manifest_dir, manifest)?;
+    let wasm_path = resolve_wasm_path(manifest_dir, manifest, cargo_options)?;
     let canonical_world = canonical_component_world();
     match abi::check_world_base(&wasm_path, canonical_world) {
         Ok(exported) => println!("Exported world: {exported}"),
@@ -315,20 +665,240 @@ fn check_canonical_world_export(manifest_dir: &Path, manifest: &JsonValue) -> Re
     Ok(())
 }
 
-fn update_manifest_hashes(
+fn embed_build_metadata(
+    wasm_path: &Path,
+    manifest_dir: &Path,
+    manifest: &JsonValue,
+) -> Result<BuildInfo> {
+    // Record the manifest as `record_wasm_artifact` is about to write it.
+    let mut manifest = manifest.clone();
+    manifest["artifacts"]["component_wasm"] =
+        JsonValue::String(path_string_relative(manifest_dir, wasm_path)?);
+    let (git_commit, git_dirty) = git_state(manifest_dir);
+    let metadata = BuildMetadata::new(
+        &manifest,
+        BuildInfo {
+            builder: format!("greentic-component {}", env!("CARGO_PKG_VERSION")),
+            git_commit,
+            git_dirty,
+            built_at_utc: build_timestamp()?,
+            template_version: read_greentic_metadata(manifest_dir, "template_version"),
+        },
+    );
+    let wasm_bytes = fs::read(wasm_path)
+        .with_context(|| format!("failed to read wasm at {}", wasm_path.display()))?;
+    let patched = append_build_metadata_section(&wasm_bytes, &metadata)?;
+    fs::write(wasm_path, patched)
+        .with_context(|| format!("failed to write build metadata to {}", wasm_path.display()))?;
+    Ok(metadata.build)
+}
+
+/// HEAD commit and whether tracked files are modified; `(None, false)` outside a git checkout.
+fn git_state(dir: &Path) -> (Option<String>, bool) {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+    let Some(commit) = git(&["rev-parse", "HEAD"]).filter(|commit| !commit.is_empty()) else {
+        return (None, false);
+    };
+    let dirty = git(&["status", "--porcelain", "--untracked-files=no"])
+        .is_some_and(|status| !status.is_empty());
+    (Some(commit), dirty)
+}
+
+fn build_timestamp() -> Result<OffsetDateTime> {
+    let Ok(raw) = env::var(SOURCE_DATE_EPOCH_ENV) else {
+        return Ok(OffsetDateTime::now_utc()
+            .replace_nanosecond(0)
+            .expect("zero nanoseconds is always valid"));
+    };
+    let seconds = raw.trim().parse::<i64>().with_context(|| {
+        format!("{SOURCE_DATE_EPOCH_ENV} must be a unix timestamp, got `{raw}`")
+    })?;
+    OffsetDateTime::from_unix_timestamp(seconds)
+        .with_context(|| format!("{SOURCE_DATE_EPOCH_ENV} `{raw}` is out of range"))
+}
+
+/// Point `artifacts.sbom` at the SBOM this build writes, or drop a stale reference when it
+/// writes none.
+fn reference_sbom(
     manifest_dir: &Path,
     manifest: &mut JsonValue,
-) -> Result<(PathBuf, String)> {
-    let artifact_path = resolve_wasm_path(manifest_dir, manifest)?;
-    let wasm_bytes = fs::read(&artifact_path)
-        .with_context(|| format!("failed to read wasm at {}", artifact_path.display()))?;
-    let digest = blake3::hash(&wasm_bytes).to_hex().to_string();
+    dist_base: &str,
+    enabled: bool,
+) -> Result<Option<PathBuf>> {
+    if !enabled {
+        for section in ["artifacts", "hashes"] {
+            if let Some(fields) = manifest.get_mut(section).and_then(JsonValue::as_object_mut) {
+                fields.remove("sbom");
+            }
+        }
+        return Ok(None);
+    }
+    let sbom_path = dist_path(manifest_dir, dist_base, "cdx.json");
+    manifest["artifacts"]["sbom"] =
+        JsonValue::String(path_string_relative(manifest_dir, &sbom_path)?);
+    Ok(Some(sbom_path))
+}
+
+/// `dist/<dist_base>.<extension>`, next to the describe artifacts.
+fn dist_path(manifest_dir: &Path, dist_base: &str, extension: &str) -> PathBuf {
+    manifest_dir
+        .join("dist")
+        .join(format!("{dist_base}.{extension}"))
+}
+
+/// Manifest as config, wasm and SBOM as layers, annotated from the build metadata.
+fn oci_artifact(
+    manifest_path: &Path,
+    manifest: &JsonValue,
+    wasm_path: &Path,
+    sbom_path: Option<&Path>,
+    build_info: &BuildInfo,
+) -> Result<OciArtifact> {
+    let layer = |media_type, path: &Path| -> Result<OciLayer> {
+        Ok(OciLayer {
+            media_type,
+            title: path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            data: fs::read(path).with_context(|| format!("failed to read {}", path.display()))?,
+        })
+    };
+    let mut layers = vec![layer(WASM_LAYER_MEDIA_TYPE, wasm_path)?];
+    if let Some(sbom_path) = sbom_path {
+        layers.push(layer(SBOM_LAYER_MEDIA_TYPE, sbom_path)?);
+    }
+    let mut annotations = BTreeMap::from([(
+        "org.opencontainers.image.created".to_string(),
+        build_info
+            .built_at_utc
+            .format(&Rfc3339)
+            .context("failed to format build time")?,
+    )]);
+    for (annotation, field) in [
+        ("org.opencontainers.image.title", "id"),
+        ("org.opencontainers.image.version", "version"),
+    ] {
+        if let Some(value) = manifest.get(field).and_then(JsonValue::as_str) {
+            annotations.insert(annotation.to_string(), value.to_string());
+        }
+    }
+    if let Some(commit) = &build_info.git_commit {
+        annotations.insert(
+            "org.opencontainers.image.revision".to_string(),
+            commit.clone(),
+        );
+    }
+    Ok(OciArtifact {
+        config: fs::read(manifest_path)
+            .with_context(|| format!("failed to read {}", manifest_path.display()))?,
+        layers,
+        annotations,
+    })
+}
+
+/// Write the OCI image layout for `reference` and push it when asked.
+fn package_oci(
+    reference: &str,
+    push: bool,
+    layout: PathBuf,
+    artifact: &OciArtifact,
+) -> Result<OciReport> {
+    let manifest_digest = write_oci_layout(&layout, reference, artifact)?;
+    let pushed = if push {
+        Some(push_oci_artifact(reference, artifact)?)
+    } else {
+        None
+    };
+    Ok(OciReport {
+        reference: reference.to_string(),
+        layout,
+        manifest_digest,
+        pushed,
+    })
+}
+
+fn write_sbom(
+    sbom_path: &Path,
+    dependencies: &CargoDependencies,
+    manifest: &JsonValue,
+    wasm_path: &Path,
+    built_at: OffsetDateTime,
+) -> Result<()> {
+    let wasm_bytes = fs::read(wasm_path)
+        .with_context(|| format!("failed to read wasm at {}", wasm_path.display()))?;
+    let artifact = manifest["artifacts"]["component_wasm"]
+        .as_str()
+        .unwrap_or_default();
+    let sbom = sbom_document(dependencies, manifest, artifact, &wasm_bytes, built_at)?;
+    if let Some(parent) = sbom_path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    fs::write(sbom_path, serde_json::to_string_pretty(&sbom)? + "\n")
+        .with_context(|| format!("failed to write {}", sbom_path.display()))
+}
 
+/// Point `artifacts.component_wasm` at the built wasm and return its path and blake3 hash.
+fn record_wasm_artifact(
+    manifest_dir: &Path,
+    manifest: &mut JsonValue,
+    cargo_options: &CargoOptions,
+) -> Result<(PathBuf, String)> {
+    let artifact_path = resolve_wasm_path(manifest_dir, manifest, cargo_options)?;
+    let wasm_hash = blake3_file(&artifact_path)?;
     manifest["artifacts"]["component_wasm"] =
         JsonValue::String(path_string_relative(manifest_dir, &artifact_path)?);
-    manifest["hashes"]["component_wasm"] = JsonValue::String(format!("blake3:{digest}"));
+    Ok((artifact_path, wasm_hash))
+}
+
+/// Rewrite `hashes.component_wasm`, plus `hashes.<name>` for every other `artifacts.<name>`
+/// file that exists next to the manifest.
+fn update_manifest_hashes(
+    manifest_dir: &Path,
+    manifest: &mut JsonValue,
+    wasm_hash: &str,
+) -> Result<()> {
+    manifest["hashes"]["component_wasm"] = JsonValue::String(wasm_hash.to_string());
+    let others = manifest
+        .get("artifacts")
+        .and_then(JsonValue::as_object)
+        .into_iter()
+        .flatten()
+        .filter(|(name, _)| name.as_str() != "component_wasm")
+        .filter_map(|(name, path)| Some((name.clone(), manifest_dir.join(path.as_str()?))))
+        .filter(|(_, path)| path.is_file())
+        .collect::<Vec<_>>();
+    for (name, path) in others {
+        manifest["hashes"][name.as_str()] = JsonValue::String(blake3_file(&path)?);
+    }
+    Ok(())
+}
 
-    Ok((artifact_path, format!("blake3:{digest}")))
+fn warn_if_hash_stale(manifest: &JsonValue, wasm_hash: &str) {
+    let recorded = manifest
+        .get("hashes")
+        .and_then(|hashes| hashes.get("component_wasm"))
+        .and_then(JsonValue::as_str);
+    if recorded != Some(wasm_hash) {
+        eprintln!(
+            "warning: hashes.component_wasm ({}) does not match the built wasm ({wasm_hash}); run `greentic-component hash` before publishing",
+            recorded.unwrap_or("<missing>")
+        );
+    }
+}
+
+fn blake3_file(path: &Path) -> Result<String> {
+    let bytes = fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+    Ok(format!("blake3:{}", blake3::hash(&bytes).to_hex()))
 }
 
 fn path_string_relative(base: &Path, target: &Path) -> Result<String> {
@@ -338,7 +908,11 @@ fn path_string_relative(base: &Path, target: &Path) -> Result<String> {
         .ok_or_else(|| anyhow!("failed to stringify path {}", target.display()))
 }
 
-fn resolve_wasm_path(manifest_dir: &Path, manifest: &JsonValue) -> Result<PathBuf> {
+fn resolve_wasm_path(
+    manifest_dir: &Path,
+    manifest: &JsonValue,
+    cargo_options: &CargoOptions,
+) -> Result<PathBuf> {
     let manifest_root = manifest_dir
         .canonicalize()
         .with_context(|| format!("failed to canonicalize {}", manifest_dir.display()))?;
@@ -356,6 +930,7 @@ fn resolve_wasm_path(manifest_dir: &Path, manifest: &JsonValue) -> Result<PathBu
             let sanitized = raw_name.replace(['-', '.'], "_");
             manifest_dir.join(format!("target/wasm32-wasip2/release/{sanitized}.wasm"))
         });
+    let candidate = retarget_wasm_path(manifest_dir, &candidate, cargo_options);
     if candidate.exists() {
         let normalized = normalize_under_root(&manifest_root, &candidate).or_else(|_| {
             if candidate.is_absolute() {
@@ -397,6 +972,33 @@ fn resolve_wasm_path(manifest_dir: &Path, manifest: &JsonValue) -> Result<PathBu
     Ok(normalized)
 }
 
+/// Point a `target/wasm32-wasip2/<profile>/<file>` artifact path at the selected profile and
+/// `--target-dir`; any other path is returned unchanged.
+fn retarget_wasm_path(
+    manifest_dir: &Path,
+    candidate: &Path,
+    cargo_options: &CargoOptions,
+) -> PathBuf {
+    let (Some(file_name), Some(triple_dir)) = (
+        candidate.file_name(),
+        candidate.parent().and_then(Path::parent),
+    ) else {
+        return candidate.to_path_buf();
+    };
+    if triple_dir.file_name() != Some(OsStr::new("wasm32-wasip2")) {
+        return candidate.to_path_buf();
+    }
+    let retargeted = triple_dir.join(cargo_options.profile_dir()).join(file_name);
+    let Some(target_dir) = &cargo_options.target_dir else {
+        return retargeted;
+    };
+    let relative = retargeted.strip_prefix(manifest_dir).unwrap_or(&retargeted);
+    match relative.strip_prefix("target") {
+        Ok(rest) => target_dir.join(rest),
+        Err(_) => retargeted,
+    }
+}
+
 fn write_manifest(manifest_path: &Path, manifest: &JsonValue) -> Result<()> {
     let formatted = serde_json::to_string_pretty(manifest)?;
     fs::write(manifest_path, formatted + "\n")
@@ -407,6 +1009,7 @@ fn emit_describe_artifacts(
     manifest_dir: &Path,
     manifest: &JsonValue,
     wasm_path: &Path,
+    dist_base: &str,
 ) -> Result<()> {
     let abi_version = read_abi_version(manifest_dir);
     let require_describe = abi_version.as_deref() == Some("0.6.0");
@@ -421,13 +1024,7 @@ fn emit_describe_artifacts(
             if require_describe {
                 match from_wit_world(wasm_path, manifest_model.world.as_str()) {
                     Ok(payload) => {
-                        write_wit_describe_artifacts(
-                            manifest_dir,
-                            manifest,
-                            wasm_path,
-                            abi_version.as_deref(),
-                            &payload,
-                        )?;
+                        write_wit_describe_artifacts(manifest_dir, wasm_path, dist_base, &payload)?;
                         eprintln!(
                             "warning: describe export unavailable, emitted WIT-derived describe.json instead ({err})"
                         );
@@ -455,18 +1052,16 @@ fn emit_describe_artifacts(
     fs::create_dir_all(&dist_dir)
         .with_context(|| format!("failed to create {}", dist_dir.display()))?;
 
-    let (name, abi_underscore) = artifact_basename(manifest, wasm_path, abi_version.as_deref());
-    let base = format!("{name}__{abi_underscore}");
-    let describe_cbor_path = dist_dir.join(format!("{base}.describe.cbor"));
+    let describe_cbor_path = dist_dir.join(format!("{dist_base}.describe.cbor"));
     fs::write(&describe_cbor_path, &canonical_bytes)
         .with_context(|| format!("failed to write {}", describe_cbor_path.display()))?;
 
-    let describe_json_path = dist_dir.join(format!("{base}.describe.json"));
+    let describe_json_path = dist_dir.join(format!("{dist_base}.describe.json"));
     let json = serde_json::to_string_pretty(&describe)?;
     fs::write(&describe_json_path, json + "\n")
         .with_context(|| format!("failed to write {}", describe_json_path.display()))?;
 
-    let wasm_out = dist_dir.join(format!("{base}.wasm"));
+    let wasm_out = dist_dir.join(format!("{dist_base}.wasm"));
     if wasm_out != wasm_path {
         let _ = fs::copy(wasm_path, &wasm_out);
     }
@@ -476,23 +1071,20 @@ fn emit_describe_artifacts(
 
 fn write_wit_describe_artifacts(
     manifest_dir: &Path,
-    manifest: &JsonValue,
     wasm_path: &Path,
-    abi_version: Option<&str>,
+    dist_base: &str,
     payload: &crate::describe::DescribePayload,
 ) -> Result<()> {
     let dist_dir = manifest_dir.join("dist");
     fs::create_dir_all(&dist_dir)
         .with_context(|| format!("failed to create {}", dist_dir.display()))?;
 
-    let (name, abi_underscore) = artifact_basename(manifest, wasm_path, abi_version);
-    let base = format!("{name}__{abi_underscore}");
-    let describe_json_path = dist_dir.join(format!("{base}.describe.json"));
+    let describe_json_path = dist_dir.join(format!("{dist_base}.describe.json"));
     let json = serde_json::to_string_pretty(payload)?;
     fs::write(&describe_json_path, json + "\n")
         .with_context(|| format!("failed to write {}", describe_json_path.display()))?;
 
-    let wasm_out = dist_dir.join(format!("{base}.wasm"));
+    let wasm_out = dist_dir.join(format!("{dist_base}.wasm"));
     if wasm_out != wasm_path {
         let _ = fs::copy(wasm_path, &wasm_out);
     }
@@ -501,17 +1093,37 @@ fn write_wit_describe_artifacts(
 }
 
 fn read_abi_version(manifest_dir: &Path) -> Option<String> {
+    read_greentic_metadata(manifest_dir, "abi_version")
+}
+
+/// Reads a string from `[package.metadata.greentic]` in the component's Cargo.toml.
+fn read_greentic_metadata(manifest_dir: &Path, key: &str) -> Option<String> {
     let cargo_path = manifest_dir.join("Cargo.toml");
     let contents = fs::read_to_string(cargo_path).ok()?;
     let doc: toml::Value = toml::from_str(&contents).ok()?;
     doc.get("package")
         .and_then(|pkg| pkg.get("metadata"))
         .and_then(|meta| meta.get("greentic"))
-        .and_then(|g| g.get("abi_version"))
+        .and_then(|g| g.get(key))
         .and_then(|v| v.as_str())
         .map(|s| s.to_string())
 }
 
+/// `<name>__<abi>`, suffixed with `__<profile>` unless building the release profile.
+fn dist_basename(
+    manifest_dir: &Path,
+    manifest: &JsonValue,
+    wasm_path: &Path,
+    cargo_options: &CargoOptions,
+) -> String {
+    let abi_version = read_abi_version(manifest_dir);
+    let (name, abi_underscore) = artifact_basename(manifest, wasm_path, abi_version.as_deref());
+    match cargo_options.profile() {
+        "release" => format!("{name}__{abi_underscore}"),
+        profile => format!("{name}__{abi_underscore}__{}", sanitize_name(profile)),
+    }
+}
+
 fn artifact_basename(
     manifest: &JsonValue,
     wasm_path: &Path,
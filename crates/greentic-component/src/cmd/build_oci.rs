@@ -0,0 +1,252 @@
+//! `build --oci <ref>`: package the build output as an OCI artifact.
+//!
+//! The component manifest is the config blob, so registries and `oras manifest fetch` show it
+//! without pulling anything else; the wasm and the SBOM are layers. The artifact is always
+//! written as an OCI image layout under `dist/`, and `--oci-push` uploads the same blobs.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use serde_json::{Value as JsonValue, json};
+use sha2::{Digest, Sha256};
+
+pub const OCI_MANIFEST_MEDIA_TYPE: &str = "application/vnd.oci.image.manifest.v1+json";
+pub const OCI_INDEX_MEDIA_TYPE: &str = "application/vnd.oci.image.index.v1+json";
+pub const COMPONENT_CONFIG_MEDIA_TYPE: &str = "application/vnd.greentic.component.manifest.v1+json";
+pub const WASM_LAYER_MEDIA_TYPE: &str = "application/wasm";
+pub const SBOM_LAYER_MEDIA_TYPE: &str = "application/vnd.cyclonedx+json";
+
+const TITLE_ANNOTATION: &str = "org.opencontainers.image.title";
+const REF_NAME_ANNOTATION: &str = "org.opencontainers.image.ref.name";
+
+/// Registry credentials for `--oci-push`; anonymous when unset.
+pub const OCI_USERNAME_ENV: &str = "GREENTIC_OCI_USERNAME";
+pub const OCI_PASSWORD_ENV: &str = "GREENTIC_OCI_PASSWORD";
+
+#[derive(Debug, Clone)]
+pub struct OciLayer {
+    pub media_type: &'static str,
+    /// File name a client such as `oras pull` writes the layer to.
+    pub title: String,
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug, Clone)]
+pub struct OciArtifact {
+    /// Bytes of `component.manifest.json`.
+    pub config: Vec<u8>,
+    pub layers: Vec<OciLayer>,
+    pub annotations: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OciReport {
+    pub reference: String,
+    pub layout: PathBuf,
+    pub manifest_digest: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pushed: Option<String>,
+}
+
+fn sha256_digest(data: &[u8]) -> String {
+    format!("sha256:{}", hex::encode(Sha256::digest(data)))
+}
+
+fn descriptor(media_type: &str, data: &[u8]) -> JsonValue {
+    json!({
+        "mediaType": media_type,
+        "digest": sha256_digest(data),
+        "size": data.len(),
+    })
+}
+
+impl OciArtifact {
+    /// OCI image manifest referencing the config and layer blobs.
+    pub fn manifest(&self) -> JsonValue {
+        let layers = self
+            .layers
+            .iter()
+            .map(|layer| {
+                let mut layer_descriptor = descriptor(layer.media_type, &layer.data);
+                layer_descriptor["annotations"] = json!({ TITLE_ANNOTATION: layer.title });
+                layer_descriptor
+            })
+            .collect::<Vec<_>>();
+        let mut manifest = json!({
+            "schemaVersion": 2,
+            "mediaType": OCI_MANIFEST_MEDIA_TYPE,
+            "config": descriptor(COMPONENT_CONFIG_MEDIA_TYPE, &self.config),
+            "layers": layers,
+        });
+        if !self.annotations.is_empty() {
+            manifest["annotations"] = json!(self.annotations);
+        }
+        manifest
+    }
+}
+
+/// Write `artifact` as an OCI image layout at `dir`, replacing whatever was there.
+pub fn write_oci_layout(dir: &Path, reference: &str, artifact: &OciArtifact) -> Result<String> {
+    if dir.exists() {
+        fs::remove_dir_all(dir)
+            .with_context(|| format!("failed to clear OCI layout at {}", dir.display()))?;
+    }
+    let blobs = dir.join("blobs").join("sha256");
+    fs::create_dir_all(&blobs).with_context(|| format!("failed to create {}", blobs.display()))?;
+    let write_blob = |data: &[u8]| -> Result<String> {
+        let digest = sha256_digest(data);
+        let path = blobs.join(digest.trim_start_matches("sha256:"));
+        fs::write(&path, data).with_context(|| format!("failed to write {}", path.display()))?;
+        Ok(digest)
+    };
+    write_blob(&artifact.config)?;
+    for layer in &artifact.layers {
+        write_blob(&layer.data)?;
+    }
+    let manifest = serde_json::to_vec(&artifact.manifest())?;
+    let manifest_digest = write_blob(&manifest)?;
+
+    let mut manifest_descriptor = descriptor(OCI_MANIFEST_MEDIA_TYPE, &manifest);
+    manifest_descriptor["annotations"] = json!({ REF_NAME_ANNOTATION: reference });
+    let index = json!({
+        "schemaVersion": 2,
+        "mediaType": OCI_INDEX_MEDIA_TYPE,
+        "manifests": [manifest_descriptor],
+    });
+    fs::write(
+        dir.join("index.json"),
+        serde_json::to_string_pretty(&index)? + "\n",
+    )
+    .with_context(|| format!("failed to write {}/index.json", dir.display()))?;
+    fs::write(
+        dir.join("oci-layout"),
+        json!({ "imageLayoutVersion": "1.0.0" }).to_string(),
+    )
+    .with_context(|| format!("failed to write {}/oci-layout", dir.display()))?;
+    Ok(manifest_digest)
+}
+
+/// Push `artifact` to `reference` and return the manifest URL.
+#[cfg(feature = "oci")]
+pub fn push_oci_artifact(reference: &str, artifact: &OciArtifact) -> Result<String> {
+    use anyhow::anyhow;
+    use oci_distribution::Reference;
+    use oci_distribution::client::{Client, ClientConfig, Config, ImageLayer};
+    use oci_distribution::manifest::OciImageManifest;
+    use oci_distribution::secrets::RegistryAuth;
+
+    let parsed: Reference = reference
+        .parse()
+        .map_err(|err| anyhow!("invalid OCI reference '{reference}': {err}"))?;
+    let auth = match (
+        std::env::var(OCI_USERNAME_ENV),
+        std::env::var(OCI_PASSWORD_ENV),
+    ) {
+        (Ok(username), Ok(password)) => RegistryAuth::Basic(username, password),
+        _ => RegistryAuth::Anonymous,
+    };
+    let layers = artifact
+        .layers
+        .iter()
+        .map(|layer| ImageLayer::new(layer.data.clone(), layer.media_type.to_string(), None))
+        .collect::<Vec<_>>();
+    let config = Config::new(
+        artifact.config.clone(),
+        COMPONENT_CONFIG_MEDIA_TYPE.to_string(),
+        None,
+    );
+    // Push the manifest written to the layout so layer titles and annotations survive.
+    let manifest: OciImageManifest = serde_json::from_value(artifact.manifest())
+        .context("failed to convert OCI manifest for push")?;
+
+    let runtime = tokio::runtime::Runtime::new().context("failed to create async runtime")?;
+    let response = runtime
+        .block_on(async {
+            Client::new(ClientConfig::default())
+                .push(&parsed, &layers, config, &auth, Some(manifest))
+                .await
+        })
+        .with_context(|| format!("failed to push {reference}"))?;
+    Ok(response.manifest_url)
+}
+
+#[cfg(not(feature = "oci"))]
+pub fn push_oci_artifact(reference: &str, _artifact: &OciArtifact) -> Result<String> {
+    anyhow::bail!("cannot push {reference}: greentic-component was built without the `oci` feature")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn artifact() -> OciArtifact {
+        OciArtifact {
+            config: br#"{"id":"ai.greentic.example"}"#.to_vec(),
+            layers: vec![
+                OciLayer {
+                    media_type: WASM_LAYER_MEDIA_TYPE,
+                    title: "component.wasm".into(),
+                    data: b"\0asm\x0d\0\x01\0".to_vec(),
+                },
+                OciLayer {
+                    media_type: SBOM_LAYER_MEDIA_TYPE,
+                    title: "example.cdx.json".into(),
+                    data: br#"{"bomFormat":"CycloneDX"}"#.to_vec(),
+                },
+            ],
+            annotations: BTreeMap::from([(
+                "org.opencontainers.image.version".to_string(),
+                "0.1.0".to_string(),
+            )]),
+        }
+    }
+
+    #[test]
+    fn layout_indexes_manifest_and_stores_every_blob() {
+        let dir = tempfile::tempdir().unwrap();
+        let layout = dir.path().join("example.oci");
+        let artifact = artifact();
+        let digest = write_oci_layout(&layout, "ghcr.io/acme/example:0.1.0", &artifact).unwrap();
+
+        let index: JsonValue =
+            serde_json::from_str(&fs::read_to_string(layout.join("index.json")).unwrap()).unwrap();
+        let entry = &index["manifests"][0];
+        assert_eq!(entry["digest"], digest);
+        assert_eq!(
+            entry["annotations"][REF_NAME_ANNOTATION],
+            "ghcr.io/acme/example:0.1.0"
+        );
+
+        let blob = |digest: &str| {
+            fs::read(
+                layout
+                    .join("blobs/sha256")
+                    .join(digest.trim_start_matches("sha256:")),
+            )
+            .unwrap()
+        };
+        let manifest: JsonValue = serde_json::from_slice(&blob(&digest)).unwrap();
+        assert_eq!(manifest, artifact.manifest());
+        assert_eq!(manifest["config"]["mediaType"], COMPONENT_CONFIG_MEDIA_TYPE);
+        assert_eq!(
+            blob(manifest["config"]["digest"].as_str().unwrap()),
+            artifact.config
+        );
+        assert_eq!(manifest["layers"][0]["mediaType"], WASM_LAYER_MEDIA_TYPE);
+        assert_eq!(
+            manifest["layers"][1]["annotations"][TITLE_ANNOTATION],
+            "example.cdx.json"
+        );
+        assert_eq!(
+            blob(manifest["layers"][1]["digest"].as_str().unwrap()),
+            artifact.layers[1].data
+        );
+        assert_eq!(
+            manifest["annotations"]["org.opencontainers.image.version"],
+            "0.1.0"
+        );
+    }
+}
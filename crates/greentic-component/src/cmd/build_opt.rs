@@ -0,0 +1,188 @@
+//! Optional `wasm-opt` pass for `build --optimize`.
+//!
+//! Binaryen only reads core modules, so for a component every nested core module is optimized
+//! on its own and spliced back in place; all other sections are copied byte for byte.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result, anyhow, bail};
+use clap::ValueEnum;
+use serde::Serialize;
+use wasm_encoder::Encode;
+use wasmparser::{Chunk, Parser, Payload};
+
+/// Section ids inside a component binary.
+const COMPONENT_CORE_MODULE_SECTION: u8 = 1;
+const COMPONENT_COMPONENT_SECTION: u8 = 4;
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OptimizePreset {
+    /// Smallest artifact (`-Oz`)
+    Size,
+    /// Fastest code (`-O3`)
+    Speed,
+}
+
+impl OptimizePreset {
+    fn as_str(self) -> &'static str {
+        match self {
+            OptimizePreset::Size => "size",
+            OptimizePreset::Speed => "speed",
+        }
+    }
+
+    fn flags(self) -> &'static [&'static str] {
+        match self {
+            OptimizePreset::Size => &["-Oz", "--strip-debug", "--strip-producers"],
+            OptimizePreset::Speed => &["-O3", "--strip-debug"],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OptimizeReport {
+    pub preset: OptimizePreset,
+    pub before_bytes: u64,
+    pub after_bytes: u64,
+    /// Core modules passed through wasm-opt (1 for a plain module).
+    pub modules: usize,
+}
+
+impl OptimizeReport {
+    pub fn summary(&self) -> String {
+        let saved = self.before_bytes.saturating_sub(self.after_bytes);
+        let percent = if self.before_bytes == 0 {
+            0.0
+        } else {
+            saved as f64 * 100.0 / self.before_bytes as f64
+        };
+        format!(
+            "{} -> {} bytes ({percent:.1}% smaller, {} module(s), preset {})",
+            self.before_bytes,
+            self.after_bytes,
+            self.modules,
+            self.preset.as_str()
+        )
+    }
+}
+
+/// Resolve the wasm-opt binary: explicit flag, then `$WASM_OPT`, then `wasm-opt` on PATH.
+pub fn resolve_wasm_opt(explicit: Option<&Path>) -> PathBuf {
+    explicit
+        .map(Path::to_path_buf)
+        .or_else(|| env::var_os("WASM_OPT").map(PathBuf::from))
+        .unwrap_or_else(|| PathBuf::from("wasm-opt"))
+}
+
+/// Optimize `wasm_path` in place.
+pub fn optimize_wasm(
+    wasm_path: &Path,
+    wasm_opt: &Path,
+    preset: OptimizePreset,
+) -> Result<OptimizeReport> {
+    let bytes = fs::read(wasm_path)
+        .with_context(|| format!("failed to read wasm at {}", wasm_path.display()))?;
+    let scratch = tempfile::tempdir().context("failed to create wasm-opt scratch dir")?;
+    let mut modules = 0;
+    let optimized = rewrite_core_modules(&bytes, &mut |module| {
+        modules += 1;
+        run_wasm_opt(wasm_opt, preset, module, scratch.path(), modules)
+    })?;
+    fs::write(wasm_path, &optimized)
+        .with_context(|| format!("failed to write optimized wasm to {}", wasm_path.display()))?;
+    Ok(OptimizeReport {
+        preset,
+        before_bytes: bytes.len() as u64,
+        after_bytes: optimized.len() as u64,
+        modules,
+    })
+}
+
+fn run_wasm_opt(
+    wasm_opt: &Path,
+    preset: OptimizePreset,
+    module: &[u8],
+    scratch: &Path,
+    index: usize,
+) -> Result<Vec<u8>> {
+    let input = scratch.join(format!("module-{index}.wasm"));
+    let output = scratch.join(format!("module-{index}.opt.wasm"));
+    fs::write(&input, module).with_context(|| format!("failed to write {}", input.display()))?;
+    let result = Command::new(wasm_opt)
+        .args(preset.flags())
+        .arg(&input)
+        .arg("-o")
+        .arg(&output)
+        .output()
+        .with_context(|| {
+            format!(
+                "failed to run {} (install binaryen or pass --wasm-opt <path>)",
+                wasm_opt.display()
+            )
+        })?;
+    if !result.status.success() {
+        bail!(
+            "{} failed with status {}: {}",
+            wasm_opt.display(),
+            result.status,
+            String::from_utf8_lossy(&result.stderr).trim()
+        );
+    }
+    fs::read(&output).with_context(|| format!("failed to read {}", output.display()))
+}
+
+/// Apply `optimize` to a core module, or to every core module nested in a component.
+fn rewrite_core_modules(
+    bytes: &[u8],
+    optimize: &mut dyn FnMut(&[u8]) -> Result<Vec<u8>>,
+) -> Result<Vec<u8>> {
+    if !Parser::is_component(bytes) {
+        return optimize(bytes);
+    }
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut parser = Parser::new(0);
+    let mut offset = 0;
+    loop {
+        let (payload, consumed) = match parser
+            .parse(&bytes[offset..], true)
+            .map_err(|err| anyhow!("failed to parse component: {err}"))?
+        {
+            Chunk::NeedMoreData(_) => bail!("component is truncated"),
+            Chunk::Parsed { payload, consumed } => (payload, consumed),
+        };
+        let nested = match payload {
+            Payload::End(_) => break,
+            Payload::ModuleSection {
+                unchecked_range, ..
+            } => Some((COMPONENT_CORE_MODULE_SECTION, unchecked_range)),
+            Payload::ComponentSection {
+                unchecked_range, ..
+            } => Some((COMPONENT_COMPONENT_SECTION, unchecked_range)),
+            _ => None,
+        };
+        match nested {
+            Some((id, range)) => {
+                // The parser has already stepped over the nested section's body.
+                let inner = &bytes[range.clone()];
+                let rewritten = if id == COMPONENT_CORE_MODULE_SECTION {
+                    optimize(inner)?
+                } else {
+                    rewrite_core_modules(inner, optimize)?
+                };
+                out.push(id);
+                rewritten.len().encode(&mut out);
+                out.extend_from_slice(&rewritten);
+                offset = range.end;
+            }
+            None => {
+                out.extend_from_slice(&bytes[offset..offset + consumed]);
+                offset += consumed;
+            }
+        }
+    }
+    Ok(out)
+}
@@ -0,0 +1,388 @@
+//! `build --verify-repro`: build twice and compare the cargo artifacts.
+//!
+//! Both builds run with the same normalized environment, and every host path that can leak into
+//! an artifact is remapped with `--remap-path-prefix`. The second build uses its own target dir,
+//! so a path that slips through shows up as a difference instead of hiding in a shared cache.
+
+use std::collections::BTreeSet;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result, anyhow};
+use serde::Serialize;
+use wasmparser::{Encoding, Parser, Payload};
+
+/// Target dir of the second build, relative to the first build's target dir.
+pub const REPRO_TARGET_SUBDIR: &str = "greentic-repro";
+
+/// Separator between flags in `CARGO_ENCODED_RUSTFLAGS`.
+const ENCODED_FLAG_SEPARATOR: char = '\x1f';
+
+/// Environment shared by both builds.
+#[derive(Debug, Clone)]
+pub struct ReproEnv {
+    vars: Vec<(&'static str, String)>,
+    remaps: Vec<(PathBuf, &'static str)>,
+    target_dir: Option<PathBuf>,
+}
+
+impl ReproEnv {
+    /// `target_dir` is where the first build writes; the second one writes below it.
+    pub fn new(manifest_dir: &Path, target_dir: &Path, source_date_epoch: String) -> Self {
+        // rustc applies the last matching remap, so the most specific prefixes go last.
+        let mut remaps = Vec::new();
+        if let Some(rustup_home) = rustup_home() {
+            remaps.push((rustup_home, "/rustup"));
+        }
+        if let Some(cargo_home) = cargo_home() {
+            remaps.push((cargo_home, "/cargo"));
+        }
+        remaps.extend([
+            (manifest_dir.to_path_buf(), "/build"),
+            (target_dir.to_path_buf(), "/target"),
+            (target_dir.join(REPRO_TARGET_SUBDIR), "/target"),
+        ]);
+        Self {
+            vars: vec![
+                ("SOURCE_DATE_EPOCH", source_date_epoch),
+                ("CARGO_INCREMENTAL", "0".to_string()),
+                ("TZ", "UTC".to_string()),
+                ("LC_ALL", "C".to_string()),
+                ("LANG", "C".to_string()),
+            ],
+            remaps,
+            target_dir: None,
+        }
+    }
+
+    /// The same environment, building into `target_dir`.
+    pub fn with_target_dir(&self, target_dir: PathBuf) -> Self {
+        Self {
+            target_dir: Some(target_dir),
+            ..self.clone()
+        }
+    }
+
+    pub fn source_date_epoch(&self) -> &str {
+        self.vars
+            .iter()
+            .find(|(name, _)| *name == "SOURCE_DATE_EPOCH")
+            .map(|(_, value)| value.as_str())
+            .unwrap_or_default()
+    }
+
+    /// Set the normalized variables, appending the remap flags to `rustflags`.
+    ///
+    /// The flags go through `CARGO_ENCODED_RUSTFLAGS`, so host paths with spaces stay one
+    /// argument. Cargo prefers an encoded value already in the environment over `RUSTFLAGS`,
+    /// so the remaps are appended to that one when it is set.
+    pub fn apply(&self, cmd: &mut Command, rustflags: Option<&str>) {
+        for (name, value) in &self.vars {
+            cmd.env(name, value);
+        }
+        let encoded = self.encoded_rustflags(
+            env::var("CARGO_ENCODED_RUSTFLAGS").ok().as_deref(),
+            rustflags,
+        );
+        cmd.env_remove("RUSTFLAGS");
+        cmd.env("CARGO_ENCODED_RUSTFLAGS", encoded);
+        if let Some(target_dir) = &self.target_dir {
+            cmd.env("CARGO_TARGET_DIR", target_dir);
+        }
+    }
+
+    fn encoded_rustflags(&self, encoded: Option<&str>, rustflags: Option<&str>) -> String {
+        let mut flags = match encoded {
+            Some(encoded) => encoded
+                .split(ENCODED_FLAG_SEPARATOR)
+                .filter(|flag| !flag.is_empty())
+                .map(str::to_string)
+                .collect::<Vec<_>>(),
+            None => rustflags
+                .unwrap_or_default()
+                .split_whitespace()
+                .map(str::to_string)
+                .collect(),
+        };
+        for (from, to) in &self.remaps {
+            flags.push(format!("--remap-path-prefix={}={to}", from.display()));
+        }
+        flags.join(&ENCODED_FLAG_SEPARATOR.to_string())
+    }
+
+    fn host_paths(&self) -> impl Iterator<Item = &Path> {
+        self.remaps.iter().map(|(from, _)| from.as_path())
+    }
+}
+
+fn cargo_home() -> Option<PathBuf> {
+    env::var_os("CARGO_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cargo")))
+}
+
+fn rustup_home() -> Option<PathBuf> {
+    env::var_os("RUSTUP_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".rustup")))
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReproReport {
+    pub reproducible: bool,
+    pub first_hash: String,
+    pub second_hash: String,
+    pub source_date_epoch: String,
+    /// Likely sources of nondeterminism; empty when the artifacts match.
+    pub differences: Vec<String>,
+}
+
+/// Compare the two build outputs section by section.
+pub fn compare_builds(first: &Path, second: &Path, env: &ReproEnv) -> Result<ReproReport> {
+    let first_bytes =
+        fs::read(first).with_context(|| format!("failed to read {}", first.display()))?;
+    let second_bytes =
+        fs::read(second).with_context(|| format!("failed to read {}", second.display()))?;
+    let first_hash = format!("blake3:{}", blake3::hash(&first_bytes).to_hex());
+    let second_hash = format!("blake3:{}", blake3::hash(&second_bytes).to_hex());
+    let mut differences = Vec::new();
+    if first_hash != second_hash {
+        differences.extend(section_differences(&first_bytes, &second_bytes)?);
+        for (build, bytes) in [("first", &first_bytes), ("second", &second_bytes)] {
+            for path in env.host_paths() {
+                let needle = path.to_string_lossy();
+                if !needle.is_empty() && contains(bytes, needle.as_bytes()) {
+                    differences.push(format!(
+                        "{build} build embeds host path `{needle}` despite remapping (build scripts or `file!()`/`env!()` values)"
+                    ));
+                }
+            }
+        }
+        if differences.is_empty() {
+            differences.push("artifacts differ outside any parsed section".to_string());
+        }
+    }
+    Ok(ReproReport {
+        reproducible: differences.is_empty(),
+        first_hash,
+        second_hash,
+        source_date_epoch: env.source_date_epoch().to_string(),
+        differences,
+    })
+}
+
+fn section_differences(first: &[u8], second: &[u8]) -> Result<Vec<String>> {
+    let first = section_digests(first)?;
+    let second = section_digests(second)?;
+    let same_layout =
+        first.len() == second.len() && first.iter().zip(&second).all(|(lhs, rhs)| lhs.0 == rhs.0);
+    if !same_layout {
+        let first_labels = first
+            .iter()
+            .map(|(label, _)| label)
+            .collect::<BTreeSet<_>>();
+        let second_labels = second
+            .iter()
+            .map(|(label, _)| label)
+            .collect::<BTreeSet<_>>();
+        let mut differences = vec![format!(
+            "section layout differs ({} vs {} sections)",
+            first.len(),
+            second.len()
+        )];
+        differences.extend(
+            first_labels
+                .symmetric_difference(&second_labels)
+                .map(|label| format!("{label} exists in only one build")),
+        );
+        return Ok(differences);
+    }
+    Ok(first
+        .iter()
+        .zip(&second)
+        .filter(|(lhs, rhs)| lhs.1 != rhs.1)
+        .map(|((label, _), _)| format!("{label} differs: {}", hint(label)))
+        .collect())
+}
+
+/// `(label, blake3)` for every section, nested modules and components included.
+fn section_digests(bytes: &[u8]) -> Result<Vec<(String, blake3::Hash)>> {
+    let mut units = 0usize;
+    // Label prefix of each module or component being parsed; the outermost one has none.
+    let mut prefixes = Vec::<String>::new();
+    let mut digests = Vec::new();
+    for payload in Parser::new(0).parse_all(bytes) {
+        let payload = payload.map_err(|err| anyhow!("failed to parse wasm: {err}"))?;
+        match &payload {
+            Payload::Version { encoding, .. } => {
+                let prefix = if prefixes.is_empty() {
+                    String::new()
+                } else {
+                    let kind = match encoding {
+                        Encoding::Module => "core module",
+                        Encoding::Component => "component",
+                    };
+                    format!("{kind} #{units}: ")
+                };
+                units += 1;
+                prefixes.push(prefix);
+                continue;
+            }
+            Payload::End(_) => {
+                prefixes.pop();
+                continue;
+            }
+            _ => {}
+        }
+        let Some(label) = section_label(&payload) else {
+            continue;
+        };
+        let Some((_, range)) = payload.as_section() else {
+            continue;
+        };
+        let prefix = prefixes.last().map(String::as_str).unwrap_or_default();
+        digests.push((format!("{prefix}{label}"), blake3::hash(&bytes[range])));
+    }
+    Ok(digests)
+}
+
+/// Nested module and component sections are skipped: their own sections are compared instead.
+fn section_label(payload: &Payload) -> Option<String> {
+    let label = match payload {
+        Payload::CustomSection(section) => {
+            return Some(format!("custom section `{}`", section.name()));
+        }
+        Payload::TypeSection { .. } | Payload::CoreTypeSection { .. } => "type section",
+        Payload::ImportSection { .. } | Payload::ComponentImportSection { .. } => "import section",
+        Payload::FunctionSection { .. } => "function section",
+        Payload::TableSection { .. } => "table section",
+        Payload::MemorySection { .. } => "memory section",
+        Payload::TagSection { .. } => "tag section",
+        Payload::GlobalSection { .. } => "global section",
+        Payload::ExportSection { .. } | Payload::ComponentExportSection { .. } => "export section",
+        Payload::StartSection { .. } | Payload::ComponentStartSection { .. } => "start section",
+        Payload::ElementSection { .. } => "element section",
+        Payload::DataCountSection { .. } => "data count section",
+        Payload::DataSection { .. } => "data section",
+        Payload::CodeSectionStart { .. } => "code section",
+        Payload::InstanceSection { .. } | Payload::ComponentInstanceSection { .. } => {
+            "instance section"
+        }
+        Payload::ComponentAliasSection { .. } => "alias section",
+        Payload::ComponentTypeSection { .. } => "component type section",
+        Payload::ComponentCanonicalSection { .. } => "canonical section",
+        _ => return None,
+    };
+    Some(label.to_string())
+}
+
+fn hint(label: &str) -> &'static str {
+    if label.contains("custom section `.debug") || label.contains("custom section `name`") {
+        "debug info (unremapped paths or build ids); build without debug info or strip it"
+    } else if label.contains("custom section `producers`") {
+        "toolchain metadata; pin the toolchain with rust-toolchain.toml"
+    } else if label.contains("custom section") {
+        "metadata written by a build step; check what produces this section"
+    } else if label.ends_with("data section") {
+        "static data (embedded paths, timestamps, or `env!`/`include_str!` values)"
+    } else if label.ends_with("code section") {
+        "code generation (build-script output, proc macros, or hash-map iteration order)"
+    } else {
+        "module structure changed between builds"
+    }
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_encoder::{CustomSection, Module};
+
+    fn module_with(name: &str, data: &[u8]) -> Vec<u8> {
+        let mut module = Module::new();
+        module.section(&CustomSection {
+            name: name.into(),
+            data: data.into(),
+        });
+        module.finish()
+    }
+
+    #[test]
+    fn reports_differing_sections_and_leaked_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let env = ReproEnv::new(
+            Path::new("/work/component"),
+            &dir.path().join("target"),
+            "0".into(),
+        );
+        let first = dir.path().join("first.wasm");
+        let second = dir.path().join("second.wasm");
+        fs::write(&first, module_with("producers", b"rustc 1.0")).unwrap();
+        fs::write(&second, module_with("producers", b"/work/component/src")).unwrap();
+
+        let report = compare_builds(&first, &second, &env).unwrap();
+        assert!(!report.reproducible);
+        assert_eq!(
+            report.differences,
+            vec![
+                "custom section `producers` differs: toolchain metadata; pin the toolchain with rust-toolchain.toml".to_string(),
+                "second build embeds host path `/work/component` despite remapping (build scripts or `file!()`/`env!()` values)".to_string(),
+            ]
+        );
+
+        let same = compare_builds(&first, &first, &env).unwrap();
+        assert!(same.reproducible);
+        assert_eq!(same.first_hash, same.second_hash);
+    }
+
+    #[test]
+    fn remap_flags_follow_existing_rustflags() {
+        let env = ReproEnv::new(
+            Path::new("/work/my component"),
+            Path::new("/work/target"),
+            "42".into(),
+        )
+        .with_target_dir(PathBuf::from("/work/target/greentic-repro"));
+        let mut cmd = Command::new("cargo");
+        env.apply(&mut cmd, Some("-C opt-level=s"));
+        let envs = cmd
+            .get_envs()
+            .map(|(name, value)| {
+                (
+                    name.to_string_lossy().into_owned(),
+                    value.map(|value| value.to_string_lossy().into_owned()),
+                )
+            })
+            .collect::<std::collections::BTreeMap<_, _>>();
+        assert_eq!(envs["RUSTFLAGS"], None);
+        assert!(envs.contains_key("CARGO_ENCODED_RUSTFLAGS"));
+        assert_eq!(envs["SOURCE_DATE_EPOCH"].as_deref(), Some("42"));
+        assert_eq!(
+            envs["CARGO_TARGET_DIR"].as_deref(),
+            Some("/work/target/greentic-repro")
+        );
+
+        let remaps = [
+            "--remap-path-prefix=/work/my component=/build",
+            "--remap-path-prefix=/work/target=/target",
+            "--remap-path-prefix=/work/target/greentic-repro=/target",
+        ];
+        let flags = env.encoded_rustflags(None, Some("-C  opt-level=s"));
+        let flags = flags.split('\x1f').collect::<Vec<_>>();
+        assert_eq!(flags[..2], ["-C", "opt-level=s"]);
+        assert!(flags.ends_with(&remaps), "{flags:?}");
+
+        // An encoded value wins over RUSTFLAGS, as it does in cargo.
+        let flags = env.encoded_rustflags(Some("-Clink-arg=a b\x1f-Copt-level=z"), Some("-C x"));
+        let flags = flags.split('\x1f').collect::<Vec<_>>();
+        assert_eq!(flags[..2], ["-Clink-arg=a b", "-Copt-level=z"]);
+        assert!(flags.ends_with(&remaps), "{flags:?}");
+    }
+}
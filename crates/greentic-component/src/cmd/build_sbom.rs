@@ -0,0 +1,317 @@
+//! CycloneDX SBOM written by `build`.
+//!
+//! The subject is the built wasm, identified by its digests. Its components are the crates
+//! `cargo metadata` resolves for `wasm32-wasip2`, following normal dependencies only: build and
+//! dev dependencies never end up in the artifact.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{Context, Result, bail};
+use serde_json::{Value as JsonValue, json};
+use sha2::{Digest, Sha256};
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+
+pub const SBOM_SPEC_VERSION: &str = "1.5";
+const SBOM_TARGET: &str = "wasm32-wasip2";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CratePackage {
+    pub name: String,
+    pub version: String,
+    pub license: Option<String>,
+}
+
+impl CratePackage {
+    fn purl(&self) -> String {
+        format!("pkg:cargo/{}@{}", self.name, self.version)
+    }
+
+    fn component(&self) -> JsonValue {
+        let mut component = json!({
+            "type": "library",
+            "bom-ref": self.purl(),
+            "name": self.name,
+            "version": self.version,
+            "purl": self.purl(),
+        });
+        if let Some(license) = &self.license {
+            component["licenses"] = json!([{ "expression": license }]);
+        }
+        component
+    }
+}
+
+/// The crate graph that ends up in the wasm artifact.
+#[derive(Debug, Clone)]
+pub struct CargoDependencies {
+    pub root: CratePackage,
+    /// Every crate reachable from `root`, sorted by purl.
+    pub packages: Vec<CratePackage>,
+    /// purl -> purls it depends on, `root` included.
+    pub dependencies: BTreeMap<String, BTreeSet<String>>,
+}
+
+pub fn cargo_dependencies(cargo_bin: &Path, manifest_dir: &Path) -> Result<CargoDependencies> {
+    let output = Command::new(cargo_bin)
+        .args(["metadata", "--format-version", "1", "--filter-platform"])
+        .arg(SBOM_TARGET)
+        .current_dir(manifest_dir)
+        .output()
+        .with_context(|| format!("failed to run cargo metadata via {}", cargo_bin.display()))?;
+    if !output.status.success() {
+        bail!(
+            "cargo metadata failed with status {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let metadata: JsonValue =
+        serde_json::from_slice(&output.stdout).context("cargo metadata printed invalid JSON")?;
+    parse_cargo_metadata(&metadata)
+}
+
+pub fn parse_cargo_metadata(metadata: &JsonValue) -> Result<CargoDependencies> {
+    let mut packages = BTreeMap::new();
+    for package in metadata
+        .get("packages")
+        .and_then(JsonValue::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let (Some(id), Some(name), Some(version)) = (
+            package.get("id").and_then(JsonValue::as_str),
+            package.get("name").and_then(JsonValue::as_str),
+            package.get("version").and_then(JsonValue::as_str),
+        ) else {
+            continue;
+        };
+        packages.insert(
+            id,
+            CratePackage {
+                name: name.to_string(),
+                version: version.to_string(),
+                license: package
+                    .get("license")
+                    .and_then(JsonValue::as_str)
+                    .map(str::to_string),
+            },
+        );
+    }
+    let resolve = metadata
+        .get("resolve")
+        .filter(|resolve| !resolve.is_null())
+        .context("cargo metadata has no dependency resolution")?;
+    let root_id = resolve
+        .get("root")
+        .and_then(JsonValue::as_str)
+        .context("cargo metadata has no root package (virtual workspace manifest?)")?;
+    let mut edges = BTreeMap::<&str, Vec<&str>>::new();
+    for node in resolve
+        .get("nodes")
+        .and_then(JsonValue::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let Some(id) = node.get("id").and_then(JsonValue::as_str) else {
+            continue;
+        };
+        let deps = node
+            .get("deps")
+            .and_then(JsonValue::as_array)
+            .into_iter()
+            .flatten()
+            .filter(|dep| is_normal_dependency(dep))
+            .filter_map(|dep| dep.get("pkg").and_then(JsonValue::as_str))
+            .collect();
+        edges.insert(id, deps);
+    }
+
+    let purl = |id: &str| -> Result<String> {
+        packages
+            .get(id)
+            .map(CratePackage::purl)
+            .with_context(|| format!("cargo metadata does not list package `{id}`"))
+    };
+    let mut reachable = BTreeSet::new();
+    let mut queue = vec![root_id];
+    let mut dependencies = BTreeMap::new();
+    while let Some(id) = queue.pop() {
+        let deps = edges.get(id).map(Vec::as_slice).unwrap_or_default();
+        let depends_on = deps
+            .iter()
+            .map(|dep| purl(dep))
+            .collect::<Result<BTreeSet<_>>>()?;
+        dependencies.insert(purl(id)?, depends_on);
+        for dep in deps {
+            if reachable.insert(*dep) {
+                queue.push(*dep);
+            }
+        }
+    }
+    let root = packages
+        .get(root_id)
+        .cloned()
+        .with_context(|| format!("cargo metadata does not list package `{root_id}`"))?;
+    let mut crates = reachable
+        .into_iter()
+        .filter(|id| *id != root_id)
+        .filter_map(|id| packages.get(id).cloned())
+        .collect::<Vec<_>>();
+    crates.sort_by_key(CratePackage::purl);
+    crates.dedup();
+    Ok(CargoDependencies {
+        root,
+        packages: crates,
+        dependencies,
+    })
+}
+
+/// `dep_kinds` lists one entry per way a crate is depended on; `kind: null` is a normal dependency.
+fn is_normal_dependency(dep: &JsonValue) -> bool {
+    match dep.get("dep_kinds").and_then(JsonValue::as_array) {
+        Some(kinds) => kinds
+            .iter()
+            .any(|kind| kind.get("kind").is_none_or(JsonValue::is_null)),
+        None => true,
+    }
+}
+
+/// CycloneDX JSON document describing `wasm_bytes`, built from `deps`.
+pub fn sbom_document(
+    deps: &CargoDependencies,
+    manifest: &JsonValue,
+    wasm_artifact: &str,
+    wasm_bytes: &[u8],
+    timestamp: OffsetDateTime,
+) -> Result<JsonValue> {
+    let root_ref = deps.root.purl();
+    let component_id = manifest
+        .get("id")
+        .and_then(JsonValue::as_str)
+        .unwrap_or(&deps.root.name);
+    let version = manifest
+        .get("version")
+        .and_then(JsonValue::as_str)
+        .unwrap_or(&deps.root.version);
+    let mut subject = json!({
+        "type": "application",
+        "bom-ref": root_ref,
+        "name": component_id,
+        "version": version,
+        "purl": root_ref,
+        "hashes": [
+            { "alg": "BLAKE3", "content": blake3::hash(wasm_bytes).to_hex().to_string() },
+            { "alg": "SHA-256", "content": hex::encode(Sha256::digest(wasm_bytes)) }
+        ],
+        "properties": [
+            { "name": "greentic:artifact", "value": wasm_artifact },
+            { "name": "greentic:target", "value": SBOM_TARGET }
+        ]
+    });
+    if let Some(license) = &deps.root.license {
+        subject["licenses"] = json!([{ "expression": license }]);
+    }
+    Ok(json!({
+        "bomFormat": "CycloneDX",
+        "specVersion": SBOM_SPEC_VERSION,
+        "version": 1,
+        "metadata": {
+            "timestamp": timestamp
+                .format(&Rfc3339)
+                .context("failed to format SBOM timestamp")?,
+            "tools": {
+                "components": [{
+                    "type": "application",
+                    "name": "greentic-component",
+                    "version": env!("CARGO_PKG_VERSION")
+                }]
+            },
+            "component": subject
+        },
+        "components": deps.packages.iter().map(CratePackage::component).collect::<Vec<_>>(),
+        "dependencies": deps
+            .dependencies
+            .iter()
+            .map(|(bom_ref, depends_on)| json!({ "ref": bom_ref, "dependsOn": depends_on }))
+            .collect::<Vec<_>>()
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata() -> JsonValue {
+        let normal = json!([{ "kind": null, "target": null }]);
+        json!({
+            "packages": [
+                { "id": "example 0.1.0", "name": "example", "version": "0.1.0", "license": "MIT" },
+                { "id": "serde 1.0.0", "name": "serde", "version": "1.0.0", "license": "MIT OR Apache-2.0" },
+                { "id": "serde_derive 1.0.0", "name": "serde_derive", "version": "1.0.0", "license": null },
+                { "id": "cc 1.0.0", "name": "cc", "version": "1.0.0", "license": "MIT" },
+                { "id": "insta 1.0.0", "name": "insta", "version": "1.0.0", "license": "Apache-2.0" }
+            ],
+            "resolve": {
+                "root": "example 0.1.0",
+                "nodes": [
+                    {
+                        "id": "example 0.1.0",
+                        "deps": [
+                            { "pkg": "serde 1.0.0", "dep_kinds": normal },
+                            { "pkg": "cc 1.0.0", "dep_kinds": [{ "kind": "build", "target": null }] },
+                            { "pkg": "insta 1.0.0", "dep_kinds": [{ "kind": "dev", "target": null }] }
+                        ]
+                    },
+                    { "id": "serde 1.0.0", "deps": [{ "pkg": "serde_derive 1.0.0", "dep_kinds": normal }] },
+                    { "id": "serde_derive 1.0.0", "deps": [] },
+                    { "id": "cc 1.0.0", "deps": [] },
+                    { "id": "insta 1.0.0", "deps": [] }
+                ]
+            }
+        })
+    }
+
+    #[test]
+    fn follows_normal_dependencies_only() {
+        let deps = parse_cargo_metadata(&metadata()).unwrap();
+        assert_eq!(deps.root.name, "example");
+        assert_eq!(
+            deps.packages
+                .iter()
+                .map(|package| package.name.as_str())
+                .collect::<Vec<_>>(),
+            ["serde", "serde_derive"]
+        );
+        assert_eq!(
+            deps.dependencies["pkg:cargo/example@0.1.0"],
+            BTreeSet::from(["pkg:cargo/serde@1.0.0".to_string()])
+        );
+        assert!(deps.dependencies["pkg:cargo/serde_derive@1.0.0"].is_empty());
+    }
+
+    #[test]
+    fn document_identifies_the_wasm_artifact() {
+        let deps = parse_cargo_metadata(&metadata()).unwrap();
+        let manifest = json!({ "id": "ai.greentic.example", "version": "0.1.0" });
+        let timestamp = OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap();
+        let sbom = sbom_document(&deps, &manifest, "component.wasm", b"\0asm", timestamp).unwrap();
+        assert_eq!(sbom["bomFormat"], "CycloneDX");
+        assert_eq!(sbom["metadata"]["timestamp"], "2023-11-14T22:13:20Z");
+        let subject = &sbom["metadata"]["component"];
+        assert_eq!(subject["name"], "ai.greentic.example");
+        assert_eq!(subject["purl"], "pkg:cargo/example@0.1.0");
+        assert_eq!(
+            subject["hashes"][0]["content"],
+            blake3::hash(b"\0asm").to_hex().to_string()
+        );
+        assert_eq!(
+            sbom["components"][0]["licenses"][0]["expression"],
+            "MIT OR Apache-2.0"
+        );
+        assert!(sbom["components"][1].get("licenses").is_none());
+        assert_eq!(sbom["dependencies"].as_array().unwrap().len(), 3);
+    }
+}
@@ -3,20 +3,27 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 use clap::{Args, Parser, ValueEnum};
+use once_cell::sync::Lazy;
+use regex::Regex;
 use serde::Serialize;
 use serde_json::Value as JsonValue;
 use wasmtime::component::{Component, Func, Linker, Val};
 use wasmtime::{Engine, Store};
 use wasmtime_wasi::{ResourceTable, WasiCtx, WasiCtxBuilder, WasiCtxView, WasiView};
 
+use super::doctor_baseline::{BASELINE_FILE, BaselineEntry, DoctorBaseline};
+use super::doctor_rules::{DoctorRules, RuleSeverity, discover_rules};
 use super::path::strip_file_scheme;
+use crate::build_metadata::{BUILD_METADATA_SECTION, read_build_metadata_section};
+use crate::capabilities::FilesystemMode;
 use crate::cmd::component_world::is_fallback_world;
 use crate::embedded_compare::{compare_embedded_with_describe, compare_embedded_with_manifest};
 use crate::embedded_descriptor::{
     VerifiedEmbeddedDescriptorV1, read_and_verify_embedded_component_manifest_section_v1,
 };
+use crate::i18n_check::{self, SOURCE_LOCALE};
 use crate::test_harness::{HarnessConfig, TestHarness};
-use crate::{ComponentError, abi, loader, parse_manifest};
+use crate::{Capabilities, ComponentError, abi, compute_wasm_hash, loader, parse_manifest};
 
 use greentic_types::cbor::canonical;
 use greentic_types::schemas::common::schema_ir::{AdditionalProperties, SchemaIr};
@@ -29,6 +36,10 @@ const COMPONENT_WORLD_V0_6_0: &str = "greentic:component/component@0.6.0";
 const SELF_DESCRIBE_TAG: [u8; 3] = [0xd9, 0xd9, 0xf7];
 const EMPTY_CBOR_MAP: [u8; 1] = [0xa0];
 
+/// Scaffolded QA code names its i18n keys with string literals under `qa.`.
+static QA_KEY_LITERAL: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#""(qa\.[A-Za-z0-9_.-]+)""#).expect("valid qa key regex"));
+
 #[derive(Args, Debug, Clone)]
 #[command(about = "Run health checks against a Greentic component artifact")]
 pub struct DoctorArgs {
@@ -40,12 +51,38 @@ pub struct DoctorArgs {
     /// Output format
     #[arg(long, value_enum, default_value = "human")]
     pub format: DoctorFormat,
+    /// Rewrite a stale `hashes.component_wasm` in the manifest
+    #[arg(long)]
+    pub fix: bool,
+    /// Fail when a locale in assets/i18n is translated below this percentage
+    #[arg(long, value_name = "PERCENT")]
+    pub i18n_min_coverage: Option<f64>,
+    /// Workspace rules file; defaults to the nearest doctor.rules.json above the manifest
+    #[arg(long, value_name = "PATH")]
+    pub rules: Option<PathBuf>,
+    /// Accepted-findings file; defaults to .greentic-doctor-baseline.json next to the manifest
+    #[arg(long, value_name = "PATH")]
+    pub baseline: Option<PathBuf>,
+    /// Record every current finding in the baseline
+    #[arg(long)]
+    pub update_baseline: bool,
+    /// Fail when the wasm artifact is larger than this many KiB
+    #[arg(long, value_name = "KIB")]
+    pub max_wasm_kib: Option<u64>,
+    /// Fail when the manifest's limits.memory_mb exceeds this budget
+    #[arg(long, value_name = "MB")]
+    pub max_memory_mb: Option<u32>,
+    /// Fail when the manifest's limits.wall_time_ms exceeds this budget
+    #[arg(long, value_name = "MS")]
+    pub max_wall_time_ms: Option<u64>,
 }
 
 #[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DoctorFormat {
     Human,
     Json,
+    /// SARIF 2.1.0, for code-scanning dashboards.
+    Sarif,
 }
 
 #[derive(Parser, Debug)]
@@ -58,17 +95,61 @@ pub fn parse_from_cli() -> DoctorArgs {
     DoctorCli::parse().args
 }
 
+impl DoctorArgs {
+    /// `greentic-component doctor <target>` with every option at its default.
+    pub fn for_target(target: &str) -> Self {
+        DoctorCli::parse_from(["doctor", "--", target]).args
+    }
+}
+
 pub fn run(args: DoctorArgs) -> Result<(), ComponentError> {
     let target_path = strip_file_scheme(Path::new(&args.target));
     let wasm_path = resolve_wasm_path(&args.target, &target_path, args.manifest.as_deref())
         .map_err(ComponentError::Doctor)?;
     let manifest_path = discover_manifest_path(&wasm_path, &target_path, args.manifest.as_deref());
 
-    let report = DoctorReport::from_wasm(&wasm_path, manifest_path.as_deref())
+    let options = DoctorOptions {
+        fix: args.fix,
+        i18n_min_coverage: args.i18n_min_coverage,
+        rules: args.rules,
+        max_wasm_kib: args.max_wasm_kib,
+        max_memory_mb: args.max_memory_mb,
+        max_wall_time_ms: args.max_wall_time_ms,
+    };
+    let mut report = DoctorReport::from_wasm(&wasm_path, manifest_path.as_deref(), options)
         .map_err(ComponentError::Doctor)?;
+
+    let baseline_path = args.baseline.unwrap_or_else(|| {
+        manifest_path
+            .as_deref()
+            .unwrap_or(&wasm_path)
+            .parent()
+            .unwrap_or(Path::new("."))
+            .join(BASELINE_FILE)
+    });
+    if args.update_baseline {
+        let baseline =
+            DoctorBaseline::from_entries(report.diagnostics.iter().map(|diag| BaselineEntry {
+                id: diag.id.clone(),
+                code: diag.code.clone(),
+                message: diag.message.clone(),
+            }));
+        baseline
+            .write(&baseline_path)
+            .map_err(ComponentError::Doctor)?;
+        eprintln!(
+            "doctor: recorded {} finding(s) in {}",
+            baseline.findings.len(),
+            baseline_path.display()
+        );
+    }
+    if let Some(baseline) = DoctorBaseline::load(&baseline_path).map_err(ComponentError::Doctor)? {
+        report.apply_baseline(&baseline);
+    }
     match args.format {
         DoctorFormat::Human => report.emit_human(),
         DoctorFormat::Json => report.emit_json()?,
+        DoctorFormat::Sarif => report.emit_sarif()?,
     }
 
     if report.has_errors() {
@@ -173,16 +254,66 @@ fn collect_wasm_files(dir: &Path, out: &mut Vec<PathBuf>) -> Result<(), String>
     Ok(())
 }
 
+/// Finding codes that point at the manifest rather than the wasm artifact.
+const MANIFEST_FINDINGS: &[&str] = &[
+    "doctor.build_metadata.manifest_mismatch",
+    "doctor.capabilities.describe_undeclared",
+    "doctor.capabilities.unused",
+    "doctor.embedded.manifest_mismatch",
+    "doctor.limits.memory_over_budget",
+    "doctor.limits.unset",
+    "doctor.limits.wall_time_over_budget",
+    "doctor.manifest.artifact_missing",
+    "doctor.manifest.hash_fixed",
+    "doctor.manifest.hash_stale",
+    "doctor.world.undeclared_import",
+];
+
+/// Settings for the opt-in parts of the report.
+#[derive(Debug, Clone, Default)]
+struct DoctorOptions {
+    fix: bool,
+    i18n_min_coverage: Option<f64>,
+    rules: Option<PathBuf>,
+    max_wasm_kib: Option<u64>,
+    max_memory_mb: Option<u32>,
+    max_wall_time_ms: Option<u64>,
+}
+
 #[derive(Default, Serialize)]
 struct DoctorReport {
     diagnostics: Vec<DoctorDiagnostic>,
+    #[serde(skip)]
+    wasm_file: Option<String>,
+    #[serde(skip)]
+    manifest_file: Option<String>,
+    #[serde(skip)]
+    options: DoctorOptions,
+    #[serde(skip)]
+    baseline_applied: bool,
 }
 
 impl DoctorReport {
-    fn from_wasm(wasm_path: &Path, manifest_path: Option<&Path>) -> Result<Self, String> {
-        let mut report = DoctorReport::default();
-        report.validate_world(wasm_path);
+    fn from_wasm(
+        wasm_path: &Path,
+        manifest_path: Option<&Path>,
+        options: DoctorOptions,
+    ) -> Result<Self, String> {
+        let mut report = DoctorReport {
+            diagnostics: Vec::new(),
+            wasm_file: Some(location_uri(wasm_path)),
+            manifest_file: manifest_path.map(location_uri),
+            options,
+            baseline_applied: false,
+        };
+        report.validate_world(wasm_path, manifest_path);
+        if let Some(manifest_path) = manifest_path {
+            report.validate_manifest_hash(manifest_path);
+            report.validate_custom_rules(manifest_path);
+        }
+        report.validate_size(wasm_path, manifest_path);
         let embedded = report.validate_embedded_metadata(wasm_path, manifest_path)?;
+        report.validate_build_metadata(wasm_path, manifest_path);
 
         let mut caller = ComponentCaller::new(wasm_path)
             .map_err(|err| format!("doctor: failed to load component: {err}"))?;
@@ -264,6 +395,9 @@ impl DoctorReport {
                 Ok(describe) => {
                     report.validate_info(&describe.info, "describe");
                     report.validate_describe(&describe, &bytes);
+                    if let Some(manifest) = read_manifest(manifest_path) {
+                        report.validate_describe_capabilities(&describe, &manifest.capabilities);
+                    }
                     if let Some(embedded) = embedded.as_ref() {
                         report.validate_embedded_against_describe(&embedded.manifest, &describe);
                     } else {
@@ -275,6 +409,7 @@ impl DoctorReport {
                         );
                     }
                     report.validate_i18n(&i18n_keys, &qa_specs);
+                    report.validate_i18n_assets(manifest_path, &i18n_keys);
                     report.validate_apply_answers(&mut caller, &describe, &bytes);
                 }
                 Err(err) => report.error(
@@ -374,6 +509,7 @@ impl DoctorReport {
             }
         }
         self.validate_i18n(&i18n_keys, &qa_specs);
+        self.validate_i18n_assets(Some(manifest_path), &i18n_keys);
 
         for (_mode, mode_name) in qa_modes() {
             let payload = sample_apply_answers_payload(mode_name);
@@ -479,6 +615,49 @@ impl DoctorReport {
         Ok(Some(embedded))
     }
 
+    /// The `greentic.manifest` section is optional (older builds lack it), but when present it
+    /// must decode and should describe the manifest shipped next to the artifact.
+    fn validate_build_metadata(&mut self, wasm_path: &Path, manifest_path: Option<&Path>) {
+        let Ok(wasm_bytes) = fs::read(wasm_path) else {
+            return;
+        };
+        let metadata = match read_build_metadata_section(&wasm_bytes) {
+            Ok(Some(metadata)) => metadata,
+            Ok(None) => return,
+            Err(err) => {
+                self.error(
+                    "doctor.build_metadata.invalid",
+                    format!("{BUILD_METADATA_SECTION} section is invalid: {err}"),
+                    "build_metadata",
+                    Some("rebuild with `greentic-component build`".to_string()),
+                );
+                return;
+            }
+        };
+        let Some(manifest) = manifest_path
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|raw| serde_json::from_str::<JsonValue>(&raw).ok())
+        else {
+            return;
+        };
+        if !metadata.manifest_matches(&manifest) {
+            self.warning(
+                "doctor.build_metadata.manifest_mismatch",
+                format!(
+                    "{BUILD_METADATA_SECTION} manifest (built {}) differs from the manifest next to the artifact",
+                    metadata
+                        .build
+                        .git_commit
+                        .as_deref()
+                        .map(|commit| format!("from {commit}"))
+                        .unwrap_or_else(|| "outside git".to_string())
+                ),
+                "build_metadata.manifest",
+                Some("rebuild with `greentic-component build` after editing the manifest".to_string()),
+            );
+        }
+    }
+
     fn validate_embedded_against_describe(
         &mut self,
         embedded: &crate::embedded_descriptor::EmbeddedComponentManifestV1,
@@ -507,18 +686,410 @@ impl DoctorReport {
         }
     }
 
-    fn validate_world(&mut self, wasm_path: &Path) {
-        if let Err(err) = abi::check_world_base(wasm_path, COMPONENT_WORLD_V0_6_0) {
+    fn validate_world(&mut self, wasm_path: &Path, manifest_path: Option<&Path>) {
+        let manifest = read_manifest(manifest_path);
+        let declared = manifest
+            .as_ref()
+            .map(|manifest| manifest.world.as_str())
+            .unwrap_or(COMPONENT_WORLD_V0_6_0);
+        if let Err(err) = abi::check_world_base(wasm_path, declared) {
             match err {
                 abi::AbiError::WorldMismatch { found, .. } if is_fallback_world(&found) => {}
                 other => self.error(
                     "doctor.world.mismatch",
                     format!("component world mismatch: {other}"),
                     "world",
-                    Some("expected component@0.6.0 world".to_string()),
+                    Some(format!("expected {declared} world")),
+                ),
+            }
+        }
+        let Some(manifest) = &manifest else {
+            return;
+        };
+        match abi::world_interfaces(wasm_path) {
+            Ok(interfaces) => {
+                self.validate_world_interfaces(declared, &interfaces, &manifest.capabilities);
+                self.validate_unused_capabilities(&interfaces, &manifest.capabilities);
+            }
+            Err(err) => self.error(
+                "doctor.world.decode_failed",
+                format!("failed to read component imports/exports: {err}"),
+                "world",
+                None,
+            ),
+        }
+    }
+
+    /// `hashes.component_wasm` must be the blake3 of the file `artifacts.component_wasm` points
+    /// at (relative to the manifest); with `--fix`, a stale hash is rewritten instead.
+    fn validate_manifest_hash(&mut self, manifest_path: &Path) {
+        let Some(manifest) = read_manifest(Some(manifest_path)) else {
+            return;
+        };
+        let artifact = manifest_path
+            .parent()
+            .unwrap_or(Path::new("."))
+            .join(manifest.artifacts.component_wasm());
+        if !artifact.is_file() {
+            self.warning(
+                "doctor.manifest.artifact_missing",
+                format!(
+                    "artifacts.component_wasm points at {}, which does not exist",
+                    artifact.display()
+                ),
+                "hashes.component_wasm",
+                Some("run `greentic-component build` to produce the artifact".to_string()),
+            );
+            return;
+        }
+        let actual = match compute_wasm_hash(&artifact) {
+            Ok(actual) => actual,
+            Err(err) => {
+                self.error(
+                    "doctor.manifest.hash_failed",
+                    format!("failed to hash {}: {err}", artifact.display()),
+                    "hashes.component_wasm",
+                    None,
+                );
+                return;
+            }
+        };
+        let expected = manifest.hashes.component_wasm.as_str();
+        if actual == expected {
+            return;
+        }
+        if self.options.fix {
+            match rewrite_manifest_hash(manifest_path, &actual) {
+                Ok(()) => self.warning(
+                    "doctor.manifest.hash_fixed",
+                    format!("updated hashes.component_wasm from {expected} to {actual}"),
+                    "hashes.component_wasm",
+                    None,
+                ),
+                Err(err) => self.error(
+                    "doctor.manifest.hash_stale",
+                    format!("hashes.component_wasm is stale and could not be updated: {err}"),
+                    "hashes.component_wasm",
+                    None,
+                ),
+            }
+            return;
+        }
+        self.error(
+            "doctor.manifest.hash_stale",
+            format!(
+                "hashes.component_wasm is {expected}, but {} hashes to {actual}",
+                artifact.display()
+            ),
+            "hashes.component_wasm",
+            Some(format!(
+                "run `greentic-component hash {}` or rerun doctor with --fix",
+                manifest_path.display()
+            )),
+        );
+    }
+
+    /// Artifact size and manifest limits against the `--max-*` budgets. DWARF sections are
+    /// always flagged, since they usually mean a debug build is about to be published.
+    fn validate_size(&mut self, wasm_path: &Path, manifest_path: Option<&Path>) {
+        let Ok(bytes) = fs::read(wasm_path) else {
+            return;
+        };
+        let sections = match crate::wasm::custom_section_sizes(&bytes) {
+            Ok(sections) => sections,
+            Err(err) => {
+                self.error(
+                    "doctor.size.parse_failed",
+                    format!("failed to read custom sections: {err}"),
+                    "artifact",
+                    None,
+                );
+                return;
+            }
+        };
+        let largest = sections
+            .iter()
+            .take(3)
+            .map(|(name, size)| format!("{name} {}", format_size(*size as u64)))
+            .collect::<Vec<_>>();
+        let debug = sections
+            .iter()
+            .filter(|(name, _)| name.starts_with(".debug_"))
+            .map(|(_, size)| *size as u64)
+            .sum::<u64>();
+        if debug > 0 {
+            self.warning(
+                "doctor.size.debug_sections",
+                format!(
+                    "artifact carries {} of DWARF debug sections",
+                    format_size(debug)
+                ),
+                "artifact.custom_sections",
+                Some("build with --release or strip debug info before publishing".to_string()),
+            );
+        }
+        let size = bytes.len() as u64;
+        if let Some(budget) = self.options.max_wasm_kib
+            && size > budget * 1024
+        {
+            let breakdown = if largest.is_empty() {
+                "no custom sections".to_string()
+            } else {
+                format!("largest custom sections: {}", largest.join(", "))
+            };
+            self.error(
+                "doctor.size.wasm_over_budget",
+                format!(
+                    "artifact is {}, over the {budget} KiB budget ({breakdown})",
+                    format_size(size)
+                ),
+                "artifact.size",
+                Some("strip custom sections or trim dependencies".to_string()),
+            );
+        }
+
+        if self.options.max_memory_mb.is_none() && self.options.max_wall_time_ms.is_none() {
+            return;
+        }
+        let Some(manifest) = read_manifest(manifest_path) else {
+            return;
+        };
+        let Some(limits) = manifest.limits else {
+            self.warning(
+                "doctor.limits.unset",
+                "manifest declares no limits, so the limit budgets cannot be checked".to_string(),
+                "limits",
+                Some(
+                    "set limits.memory_mb and limits.wall_time_ms in component.manifest.json"
+                        .to_string(),
+                ),
+            );
+            return;
+        };
+        if let Some(budget) = self.options.max_memory_mb
+            && limits.memory_mb > budget
+        {
+            self.error(
+                "doctor.limits.memory_over_budget",
+                format!(
+                    "limits.memory_mb is {}, over the {budget} MB budget",
+                    limits.memory_mb
+                ),
+                "limits.memory_mb",
+                None,
+            );
+        }
+        if let Some(budget) = self.options.max_wall_time_ms
+            && limits.wall_time_ms > budget
+        {
+            self.error(
+                "doctor.limits.wall_time_over_budget",
+                format!(
+                    "limits.wall_time_ms is {}, over the {budget} ms budget",
+                    limits.wall_time_ms
+                ),
+                "limits.wall_time_ms",
+                None,
+            );
+        }
+    }
+
+    /// Workspace rules from `--rules` or the nearest `doctor.rules.json`, checked against the
+    /// raw manifest. Findings use the rule id as their code.
+    fn validate_custom_rules(&mut self, manifest_path: &Path) {
+        let Some(rules_path) = self
+            .options
+            .rules
+            .clone()
+            .or_else(|| discover_rules(manifest_path))
+        else {
+            return;
+        };
+        let Some(manifest) = fs::read_to_string(manifest_path)
+            .ok()
+            .and_then(|raw| serde_json::from_str::<JsonValue>(&raw).ok())
+        else {
+            return;
+        };
+        let violations =
+            match DoctorRules::load(&rules_path).and_then(|rules| rules.evaluate(&manifest)) {
+                Ok(violations) => violations,
+                Err(err) => {
+                    self.error_in(&rules_path, "doctor.rules.invalid", err, "rules", None);
+                    return;
+                }
+            };
+        for violation in violations {
+            let severity = match violation.severity {
+                RuleSeverity::Error => Severity::Error,
+                RuleSeverity::Warning => Severity::Warning,
+            };
+            self.push(
+                severity,
+                violation.rule,
+                violation.message,
+                violation.path,
+                Some(format!("rule from {}", rules_path.display())),
+            );
+            if let Some(diag) = self.diagnostics.last_mut() {
+                diag.file = self.manifest_file.clone();
+            }
+        }
+    }
+
+    /// Exports must come from the declared world's package, and every import outside it must
+    /// be WASI or a host interface whose capability the manifest declares.
+    fn validate_world_interfaces(
+        &mut self,
+        declared: &str,
+        interfaces: &abi::WorldInterfaces,
+        capabilities: &Capabilities,
+    ) {
+        let Some(world_package) = interface_package(declared) else {
+            return;
+        };
+        // Bare functions belong to whichever world the binary was built for.
+        let in_world =
+            |name: &str| interface_package(name).is_none_or(|package| package == world_package);
+
+        if !interfaces.exports.iter().any(|name| in_world(name)) {
+            self.error(
+                "doctor.world.exports_missing",
+                format!(
+                    "component exports nothing from `{declared}` (exports: {})",
+                    interfaces.exports.join(", ")
+                ),
+                "world.exports",
+                None,
+            );
+        }
+        for export in interfaces.exports.iter().filter(|name| !in_world(name)) {
+            self.error(
+                "doctor.world.export_mismatch",
+                format!("export `{export}` is not part of `{declared}`"),
+                format!("world.exports.{export}"),
+                Some(format!(
+                    "rebuild against `{declared}` or update `world` in component.manifest.json"
+                )),
+            );
+        }
+
+        for import in &interfaces.imports {
+            let capability = import_capability(import);
+            let allowed = match capability {
+                Some(capability) => capability_declared(capabilities, capability),
+                None => {
+                    in_world(import)
+                        || import.starts_with("wasi:")
+                        || UNGATED_HOST_IMPORTS.contains(&interface_name(import))
+                }
+            };
+            if allowed {
+                continue;
+            }
+            let (message, hint) = match capability {
+                Some(capability) => (
+                    format!(
+                        "import `{import}` needs `capabilities.{capability}`, which the manifest does not declare"
+                    ),
+                    format!("declare capabilities.{capability} in component.manifest.json"),
+                ),
+                None => (
+                    format!(
+                        "import `{import}` is neither part of `{declared}` nor a known host interface"
+                    ),
+                    "remove the dependency that pulls in this import".to_string(),
                 ),
+            };
+            self.error(
+                "doctor.world.undeclared_import",
+                message,
+                format!("world.imports.{import}"),
+                Some(hint),
+            );
+        }
+    }
+
+    /// Host capabilities and WASI filesystem access the manifest grants but no import uses.
+    fn validate_unused_capabilities(
+        &mut self,
+        interfaces: &abi::WorldInterfaces,
+        capabilities: &Capabilities,
+    ) {
+        // runner-host proxies several host services, so host.* usage can't be told apart.
+        let host_proxied = interfaces
+            .imports
+            .iter()
+            .any(|import| interface_name(import) == "runner-host");
+        for capability in checked_capabilities(capabilities) {
+            let used = if capability == WASI_FILESYSTEM {
+                interfaces
+                    .imports
+                    .iter()
+                    .any(|import| import.starts_with("wasi:filesystem/"))
+            } else {
+                host_proxied
+                    || interfaces
+                        .imports
+                        .iter()
+                        .any(|import| import_capability(import) == Some(capability))
+            };
+            if !used {
+                self.warning(
+                    "doctor.capabilities.unused",
+                    format!(
+                        "manifest declares capabilities.{capability}, but the component imports nothing that uses it"
+                    ),
+                    format!("capabilities.{capability}"),
+                    Some("remove it from component.manifest.json to keep the grant minimal".to_string()),
+                );
+            }
+        }
+    }
+
+    /// `describe()` and the manifest must agree on the capabilities doctor can check. An empty
+    /// `required_capabilities` list means describe doesn't report them, so only a non-empty one
+    /// is compared.
+    fn validate_describe_capabilities(
+        &mut self,
+        describe: &ComponentDescribe,
+        capabilities: &Capabilities,
+    ) {
+        if describe.required_capabilities.is_empty() {
+            return;
+        }
+        let declared = checked_capabilities(capabilities);
+        let reported = describe
+            .required_capabilities
+            .iter()
+            .map(|capability| capability.trim())
+            .collect::<BTreeSet<_>>();
+        for capability in &reported {
+            let checkable = *capability == WASI_FILESYSTEM
+                || CAPABILITY_IMPORTS.iter().any(|(_, id)| id == capability);
+            if checkable && !declared.contains(*capability) {
+                self.error(
+                    "doctor.capabilities.describe_undeclared",
+                    format!(
+                        "describe() requires {capability}, which the manifest does not declare"
+                    ),
+                    format!("capabilities.{capability}"),
+                    Some(format!(
+                        "declare capabilities.{capability} in component.manifest.json"
+                    )),
+                );
             }
         }
+        for capability in declared.iter().filter(|id| !reported.contains(**id)) {
+            self.warning(
+                "doctor.capabilities.describe_missing",
+                format!(
+                    "manifest declares capabilities.{capability}, but describe() does not list it in required_capabilities"
+                ),
+                format!("describe.required_capabilities.{capability}"),
+                None,
+            );
+        }
     }
 
     fn validate_info(&mut self, info: &ComponentInfo, source: &str) {
@@ -644,6 +1215,108 @@ impl DoctorReport {
         }
     }
 
+    /// Keys from `i18n-keys` and `src/qa.rs` must exist in `assets/i18n/en.json`; with
+    /// `--i18n-min-coverage`, every target locale must also be translated that far.
+    fn validate_i18n_assets(
+        &mut self,
+        manifest_path: Option<&Path>,
+        i18n_keys: &Option<BTreeSet<String>>,
+    ) {
+        let Some(root) = manifest_path.and_then(Path::parent) else {
+            return;
+        };
+        let dir = root.join("assets").join("i18n");
+        if !dir.is_dir() {
+            return;
+        }
+        let source_path = dir.join(format!("{SOURCE_LOCALE}.json"));
+        let bundle = match i18n_check::load_locale_dir(&dir) {
+            Ok(bundle) => bundle,
+            Err(err) => {
+                self.error_in(
+                    &dir,
+                    "doctor.i18n.bundle_invalid",
+                    err.to_string(),
+                    "i18n",
+                    None,
+                );
+                return;
+            }
+        };
+        let Some(source) = bundle.get(SOURCE_LOCALE) else {
+            self.error_in(
+                &dir,
+                "doctor.i18n.source_missing",
+                format!("{} not found", source_path.display()),
+                "i18n",
+                None,
+            );
+            return;
+        };
+
+        let mut referenced = BTreeMap::new();
+        for key in i18n_keys.iter().flatten() {
+            referenced.insert(key.clone(), "i18n-keys");
+        }
+        let qa_path = root.join("src").join("qa.rs");
+        if let Ok(qa_source) = fs::read_to_string(&qa_path) {
+            for literal in QA_KEY_LITERAL.captures_iter(&qa_source) {
+                referenced
+                    .entry(literal[1].to_string())
+                    .or_insert("src/qa.rs");
+            }
+        }
+        for (key, origin) in referenced {
+            if !source.contains_key(&key) {
+                self.error_in(
+                    &source_path,
+                    "doctor.i18n.source_key_missing",
+                    format!("i18n key {key} referenced by {origin} is missing from en.json"),
+                    format!("i18n.{SOURCE_LOCALE}.{key}"),
+                    Some("add the key to assets/i18n/en.json".to_string()),
+                );
+            }
+        }
+
+        let Some(min_coverage) = self.options.i18n_min_coverage else {
+            return;
+        };
+        let coverage = match i18n_check::coverage_locale_dir(&dir) {
+            Ok(coverage) => coverage,
+            Err(err) => {
+                self.error_in(
+                    &dir,
+                    "doctor.i18n.bundle_invalid",
+                    err.to_string(),
+                    "i18n",
+                    None,
+                );
+                return;
+            }
+        };
+        for locale in coverage
+            .locales
+            .iter()
+            .filter(|locale| locale.coverage_percent < min_coverage)
+        {
+            self.error_in(
+                &dir.join(format!("{}.json", locale.locale)),
+                "doctor.i18n.locale_incomplete",
+                format!(
+                    "locale {} is {}% translated ({}/{} keys), below the {min_coverage}% threshold",
+                    locale.locale,
+                    locale.coverage_percent,
+                    locale.translated_keys,
+                    locale.total_keys
+                ),
+                format!("i18n.{}", locale.locale),
+                Some(
+                    "run `greentic-component i18n coverage` for the untranslated keys".to_string(),
+                ),
+            );
+        }
+    }
+
     fn validate_apply_answers(
         &mut self,
         caller: &mut ComponentCaller,
@@ -861,13 +1534,28 @@ impl DoctorReport {
         path: impl Into<String>,
         hint: Option<String>,
     ) {
-        self.diagnostics.push(DoctorDiagnostic {
-            severity: Severity::Error,
-            code: code.into(),
-            message: message.into(),
-            path: path.into(),
+        self.push(
+            Severity::Error,
+            code.into(),
+            message.into(),
+            path.into(),
             hint,
-        });
+        );
+    }
+
+    /// [`Self::error`] for a finding in a project file other than the wasm or the manifest.
+    fn error_in(
+        &mut self,
+        file: &Path,
+        code: impl Into<String>,
+        message: impl Into<String>,
+        path: impl Into<String>,
+        hint: Option<String>,
+    ) {
+        self.error(code, message, path, hint);
+        if let Some(diag) = self.diagnostics.last_mut() {
+            diag.file = Some(location_uri(file));
+        }
     }
 
     fn warning(
@@ -877,12 +1565,39 @@ impl DoctorReport {
         path: impl Into<String>,
         hint: Option<String>,
     ) {
+        self.push(
+            Severity::Warning,
+            code.into(),
+            message.into(),
+            path.into(),
+            hint,
+        );
+    }
+
+    fn push(
+        &mut self,
+        severity: Severity,
+        code: String,
+        message: String,
+        path: String,
+        hint: Option<String>,
+    ) {
+        let file = if MANIFEST_FINDINGS.contains(&code.as_str()) {
+            self.manifest_file
+                .clone()
+                .or_else(|| self.wasm_file.clone())
+        } else {
+            self.wasm_file.clone()
+        };
         self.diagnostics.push(DoctorDiagnostic {
-            severity: Severity::Warning,
-            code: code.into(),
-            message: message.into(),
-            path: path.into(),
+            id: format!("{code}:{path}"),
+            severity,
+            code,
+            message,
+            path,
+            file,
             hint,
+            baselined: false,
         });
     }
 
@@ -891,10 +1606,18 @@ impl DoctorReport {
             .sort_by(|a, b| a.path.cmp(&b.path).then_with(|| a.code.cmp(&b.code)));
     }
 
+    /// Baselined findings stay in the report but no longer fail the run.
+    fn apply_baseline(&mut self, baseline: &DoctorBaseline) {
+        self.baseline_applied = true;
+        for diag in &mut self.diagnostics {
+            diag.baselined = baseline.contains(&diag.id);
+        }
+    }
+
     fn has_errors(&self) -> bool {
         self.diagnostics
             .iter()
-            .any(|diag| diag.severity == Severity::Error)
+            .any(|diag| diag.severity == Severity::Error && !diag.baselined)
     }
 
     fn emit_human(&self) {
@@ -908,8 +1631,9 @@ impl DoctorReport {
                 .as_deref()
                 .map(|hint| format!(" (hint: {hint})"))
                 .unwrap_or_default();
+            let baselined = if diag.baselined { " [baselined]" } else { "" };
             println!(
-                "{severity}[{code}] {path}: {message}{hint}",
+                "{severity}[{code}] {path}: {message}{hint}{baselined}",
                 severity = diag.severity,
                 code = diag.code,
                 path = diag.path,
@@ -925,6 +1649,100 @@ impl DoctorReport {
         println!("{payload}");
         Ok(())
     }
+
+    fn emit_sarif(&self) -> Result<(), ComponentError> {
+        let payload = serde_json::to_string_pretty(&self.to_sarif())
+            .map_err(|err| ComponentError::Doctor(format!("failed to encode sarif: {err}")))?;
+        println!("{payload}");
+        Ok(())
+    }
+
+    /// One SARIF rule per finding code; `id` doubles as the partial fingerprint so dashboards
+    /// track a finding across runs.
+    fn to_sarif(&self) -> JsonValue {
+        let rules = self
+            .diagnostics
+            .iter()
+            .map(|diag| diag.code.as_str())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(|code| serde_json::json!({ "id": code, "name": code }))
+            .collect::<Vec<_>>();
+        let results = self
+            .diagnostics
+            .iter()
+            .map(|diag| {
+                let message = match diag.hint.as_deref() {
+                    Some(hint) => format!("{} (hint: {hint})", diag.message),
+                    None => diag.message.clone(),
+                };
+                let mut location = serde_json::json!({
+                    "logicalLocations": [{ "fullyQualifiedName": diag.path }],
+                });
+                if let Some(file) = diag.file.as_deref() {
+                    location["physicalLocation"] =
+                        serde_json::json!({ "artifactLocation": { "uri": file } });
+                }
+                let mut result = serde_json::json!({
+                    "ruleId": diag.code,
+                    "level": diag.severity.to_string(),
+                    "message": { "text": message },
+                    "locations": [location],
+                    "partialFingerprints": { "doctorFinding/v1": diag.id },
+                });
+                if self.baseline_applied {
+                    result["baselineState"] =
+                        JsonValue::from(if diag.baselined { "unchanged" } else { "new" });
+                }
+                result
+            })
+            .collect::<Vec<_>>();
+        serde_json::json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "greentic-component doctor",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                        "rules": rules,
+                    }
+                },
+                "results": results,
+            }],
+        })
+    }
+}
+
+/// Same rewrite as `greentic-component hash`: only `hashes.component_wasm` changes.
+fn rewrite_manifest_hash(manifest_path: &Path, hash: &str) -> Result<(), String> {
+    let raw = fs::read_to_string(manifest_path)
+        .map_err(|err| format!("failed to read {}: {err}", manifest_path.display()))?;
+    let mut manifest: JsonValue = serde_json::from_str(&raw)
+        .map_err(|err| format!("invalid json in {}: {err}", manifest_path.display()))?;
+    manifest["hashes"]["component_wasm"] = JsonValue::String(hash.to_string());
+    let formatted = serde_json::to_string_pretty(&manifest)
+        .map_err(|err| format!("failed to encode manifest: {err}"))?;
+    fs::write(manifest_path, formatted + "\n")
+        .map_err(|err| format!("failed to write {}: {err}", manifest_path.display()))
+}
+
+fn format_size(bytes: u64) -> String {
+    const KIB: f64 = 1024.0;
+    let size = bytes as f64;
+    if size >= KIB * KIB {
+        format!("{:.1} MiB", size / (KIB * KIB))
+    } else if size >= KIB {
+        format!("{:.1} KiB", size / KIB)
+    } else {
+        format!("{bytes} B")
+    }
+}
+
+/// Forward-slash path for report locations, as SARIF consumers expect.
+fn location_uri(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
 }
 
 #[derive(Debug, Clone, Serialize, PartialEq, Eq)]
@@ -945,12 +1763,20 @@ impl std::fmt::Display for Severity {
 
 #[derive(Debug, Clone, Serialize)]
 struct DoctorDiagnostic {
+    /// Stable across runs: the finding code plus its report path.
+    id: String,
     severity: Severity,
     code: String,
     message: String,
     path: String,
+    /// File the finding is located in (the wasm artifact or the manifest).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     hint: Option<String>,
+    /// Listed in the baseline, so it does not fail the run.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    baselined: bool,
 }
 
 struct ComponentCaller {
@@ -1033,6 +1859,88 @@ fn call_component_func(
     Ok(results)
 }
 
+/// Host interfaces gated by a manifest capability, keyed by interface name.
+const CAPABILITY_IMPORTS: &[(&str, &str)] = &[
+    ("outgoing-handler", "host.http.client"),
+    ("http-client", "host.http.client"),
+    ("secrets-store", "host.secrets"),
+    ("state-store", "host.state"),
+    ("telemetry-logger", "host.telemetry"),
+];
+/// Capability id for WASI filesystem access (`capabilities.wasi.filesystem` not in `none` mode).
+const WASI_FILESYSTEM: &str = "wasi.filesystem";
+/// Greentic host interfaces any component may import.
+const UNGATED_HOST_IMPORTS: &[&str] = &["runner-host", "control"];
+
+/// `namespace:package@version` of an interface or world reference; `None` for bare names.
+fn interface_package(reference: &str) -> Option<String> {
+    let (path, version) = match reference.split_once('@') {
+        Some((path, version)) => (path, Some(version)),
+        None => (reference, None),
+    };
+    let (package, _) = path.split_once('/')?;
+    Some(match version {
+        Some(version) => format!("{package}@{version}"),
+        None => package.to_string(),
+    })
+}
+
+fn interface_name(reference: &str) -> &str {
+    let path = reference.split('@').next().unwrap_or(reference);
+    path.rsplit_once('/').map_or(path, |(_, name)| name)
+}
+
+fn import_capability(import: &str) -> Option<&'static str> {
+    let name = interface_name(import);
+    CAPABILITY_IMPORTS
+        .iter()
+        .find(|(interface, _)| *interface == name)
+        .map(|(_, capability)| *capability)
+}
+
+fn capability_declared(capabilities: &Capabilities, capability: &str) -> bool {
+    let host = &capabilities.host;
+    match capability {
+        "host.http.client" => host.http.as_ref().is_some_and(|http| http.client),
+        // An empty `secrets.required` or an all-false `state` grants nothing.
+        "host.secrets" => host
+            .secrets
+            .as_ref()
+            .is_some_and(|secrets| !secrets.required.is_empty()),
+        "host.state" => host
+            .state
+            .as_ref()
+            .is_some_and(|state| state.read || state.write),
+        "host.telemetry" => host.telemetry.is_some(),
+        _ => false,
+    }
+}
+
+/// Declared capabilities that map onto imports doctor can see.
+fn checked_capabilities(capabilities: &Capabilities) -> BTreeSet<&'static str> {
+    let mut checked = CAPABILITY_IMPORTS
+        .iter()
+        .map(|(_, capability)| *capability)
+        .filter(|capability| capability_declared(capabilities, capability))
+        .collect::<BTreeSet<_>>();
+    if capabilities
+        .wasi
+        .filesystem
+        .as_ref()
+        .is_some_and(|filesystem| filesystem.mode != FilesystemMode::None)
+    {
+        checked.insert(WASI_FILESYSTEM);
+    }
+    checked
+}
+
+/// The manifest at `path`, if it parses; an unreadable manifest is reported by the
+/// embedded-metadata checks.
+fn read_manifest(path: Option<&Path>) -> Option<crate::manifest::ComponentManifest> {
+    let raw = fs::read_to_string(path?).ok()?;
+    parse_manifest(&raw).ok()
+}
+
 fn qa_modes() -> [(QaMode, &'static str); 4] {
     [
         (QaMode::Default, "default"),
@@ -1085,7 +1993,7 @@ fn decode_cbor<T: serde::de::DeserializeOwned>(bytes: &[u8]) -> Result<T, String
     canonical::from_cbor(payload).map_err(|err| format!("CBOR decode failed: {err}"))
 }
 
-fn new_doctor_harness(
+pub(crate) fn new_doctor_harness(
     wasm_path: &Path,
     manifest: &crate::manifest::ComponentManifest,
 ) -> Result<TestHarness, String> {
@@ -1138,7 +2046,7 @@ fn new_doctor_harness(
     .map_err(|err| format!("failed to initialize doctor harness: {err}"))
 }
 
-fn invoke_json(
+pub(crate) fn invoke_json(
     harness: &TestHarness,
     operation: &str,
     payload: &JsonValue,
@@ -2008,4 +2916,301 @@ mod tests {
         assert!(combined.contains("describe_hash="));
         assert!(combined.contains("schema_hashes=[run="));
     }
+
+    #[test]
+    fn world_interfaces_flag_foreign_exports_and_undeclared_imports() {
+        let capabilities: Capabilities = serde_json::from_value(json!({
+            "wasi": {
+                "filesystem": { "mode": "none", "mounts": [] },
+                "random": true,
+                "clocks": true
+            },
+            "host": {
+                "secrets": {
+                    "required": [{
+                        "key": "API_TOKEN",
+                        "required": true,
+                        "scope": { "env": "dev", "tenant": "default" },
+                        "format": "text"
+                    }]
+                }
+            }
+        }))
+        .expect("capabilities");
+        let interfaces = abi::WorldInterfaces {
+            world: "root:component/root".to_string(),
+            source: crate::wasm::WorldSource::Component,
+            imports: vec![
+                "wasi:cli/environment@0.2.6".to_string(),
+                "greentic:secrets-store/secrets-store@1.0.0".to_string(),
+                "greentic:state/state-store@1.0.0".to_string(),
+                "acme:spy/beacon".to_string(),
+            ],
+            exports: vec![
+                "greentic:component/node@0.6.0".to_string(),
+                "greentic:component/node@0.5.0".to_string(),
+            ],
+        };
+        let mut report = DoctorReport::default();
+        report.validate_world_interfaces(COMPONENT_WORLD_V0_6_0, &interfaces, &capabilities);
+        let found = report
+            .diagnostics
+            .iter()
+            .map(|diag| (diag.code.as_str(), diag.path.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            found,
+            vec![
+                (
+                    "doctor.world.export_mismatch",
+                    "world.exports.greentic:component/node@0.5.0"
+                ),
+                (
+                    "doctor.world.undeclared_import",
+                    "world.imports.greentic:state/state-store@1.0.0"
+                ),
+                (
+                    "doctor.world.undeclared_import",
+                    "world.imports.acme:spy/beacon"
+                ),
+            ]
+        );
+        assert!(
+            report.diagnostics[1]
+                .message
+                .contains("capabilities.host.state")
+        );
+    }
+
+    #[test]
+    fn sarif_results_carry_rule_ids_levels_and_locations() {
+        let mut report = DoctorReport {
+            wasm_file: Some("dist/demo.wasm".to_string()),
+            manifest_file: Some("component.manifest.json".to_string()),
+            ..DoctorReport::default()
+        };
+        report.error(
+            "doctor.world.undeclared_import",
+            "import needs a capability",
+            "world.imports.acme:spy/beacon",
+            Some("declare it".to_string()),
+        );
+        report.warning(
+            "doctor.embedded.describe_unavailable",
+            "no describe",
+            "embedded_manifest",
+            None,
+        );
+        report.finalize();
+
+        let sarif = report.to_sarif();
+        assert_eq!(sarif["version"], "2.1.0");
+        let run = &sarif["runs"][0];
+        assert_eq!(run["tool"]["driver"]["rules"].as_array().unwrap().len(), 2);
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results[0]["ruleId"], "doctor.embedded.describe_unavailable");
+        assert_eq!(results[0]["level"], "warning");
+        assert_eq!(
+            results[0]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "dist/demo.wasm"
+        );
+        assert_eq!(results[1]["level"], "error");
+        assert_eq!(
+            results[1]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "component.manifest.json"
+        );
+        assert_eq!(
+            results[1]["partialFingerprints"]["doctorFinding/v1"],
+            "doctor.world.undeclared_import:world.imports.acme:spy/beacon"
+        );
+        assert_eq!(
+            results[1]["message"]["text"],
+            "import needs a capability (hint: declare it)"
+        );
+    }
+
+    #[test]
+    fn i18n_assets_flag_unknown_keys_and_incomplete_locales() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let i18n = temp.path().join("assets").join("i18n");
+        fs::create_dir_all(&i18n).expect("i18n dir");
+        fs::create_dir_all(temp.path().join("src")).expect("src dir");
+        fs::write(
+            i18n.join("en.json"),
+            r#"{"qa.title": "Title", "qa.help": "Help"}"#,
+        )
+        .expect("en");
+        fs::write(i18n.join("fr.json"), r#"{"qa.title": "Titre"}"#).expect("fr");
+        fs::write(
+            i18n.join("de.json"),
+            r#"{"qa.title": "Titel", "qa.help": "Hilfe"}"#,
+        )
+        .expect("de");
+        fs::write(
+            temp.path().join("src").join("qa.rs"),
+            r#"question("name", "qa.title", "qa.field.name.help", true)"#,
+        )
+        .expect("qa.rs");
+
+        let mut report = DoctorReport {
+            options: DoctorOptions {
+                i18n_min_coverage: Some(75.0),
+                ..DoctorOptions::default()
+            },
+            ..DoctorReport::default()
+        };
+        let keys = Some(BTreeSet::from([
+            "qa.help".to_string(),
+            "qa.extra".to_string(),
+        ]));
+        report.validate_i18n_assets(Some(&temp.path().join("component.manifest.json")), &keys);
+        let found = report
+            .diagnostics
+            .iter()
+            .map(|diag| (diag.code.as_str(), diag.path.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            found,
+            vec![
+                ("doctor.i18n.source_key_missing", "i18n.en.qa.extra"),
+                (
+                    "doctor.i18n.source_key_missing",
+                    "i18n.en.qa.field.name.help"
+                ),
+                ("doctor.i18n.locale_incomplete", "i18n.fr"),
+            ]
+        );
+        assert!(report.diagnostics[1].message.contains("src/qa.rs"));
+        assert!(
+            report.diagnostics[2]
+                .file
+                .as_deref()
+                .is_some_and(|file| file.ends_with("fr.json"))
+        );
+    }
+
+    #[test]
+    fn capabilities_are_checked_against_imports_and_describe() {
+        let capabilities: Capabilities = serde_json::from_value(json!({
+            "wasi": {
+                "filesystem": { "mode": "sandbox", "mounts": [] },
+                "random": true,
+                "clocks": true
+            },
+            "host": {
+                "secrets": {
+                    "required": [{
+                        "key": "API_TOKEN",
+                        "required": true,
+                        "scope": { "env": "dev", "tenant": "default" },
+                        "format": "text"
+                    }]
+                },
+                "state": { "read": false, "write": false },
+                "telemetry": { "scope": "node" }
+            }
+        }))
+        .expect("capabilities");
+        let interfaces = abi::WorldInterfaces {
+            world: "root:component/root".to_string(),
+            source: crate::wasm::WorldSource::Component,
+            imports: vec![
+                "wasi:cli/environment@0.2.6".to_string(),
+                "greentic:secrets-store/secrets-store@1.0.0".to_string(),
+            ],
+            exports: vec!["greentic:component/node@0.6.0".to_string()],
+        };
+        let mut report = DoctorReport::default();
+        report.validate_unused_capabilities(&interfaces, &capabilities);
+        let mut describe = good_describe();
+        describe.required_capabilities = vec![
+            "host.secrets".to_string(),
+            "host.state".to_string(),
+            "acme.custom".to_string(),
+        ];
+        report.validate_describe_capabilities(&describe, &capabilities);
+        let found = report
+            .diagnostics
+            .iter()
+            .map(|diag| (diag.code.as_str(), diag.path.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            found,
+            vec![
+                ("doctor.capabilities.unused", "capabilities.host.telemetry"),
+                ("doctor.capabilities.unused", "capabilities.wasi.filesystem"),
+                (
+                    "doctor.capabilities.describe_undeclared",
+                    "capabilities.host.state"
+                ),
+                (
+                    "doctor.capabilities.describe_missing",
+                    "describe.required_capabilities.host.telemetry"
+                ),
+                (
+                    "doctor.capabilities.describe_missing",
+                    "describe.required_capabilities.wasi.filesystem"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn empty_secrets_and_state_blocks_do_not_count_as_declared() {
+        let capabilities: Capabilities = serde_json::from_value(json!({
+            "wasi": {},
+            "host": {
+                "secrets": { "required": [] },
+                "state": { "read": false, "write": false },
+                "telemetry": { "scope": "node" }
+            }
+        }))
+        .expect("capabilities");
+        assert!(!capability_declared(&capabilities, "host.secrets"));
+        assert!(!capability_declared(&capabilities, "host.state"));
+        assert!(capability_declared(&capabilities, "host.telemetry"));
+        assert_eq!(
+            checked_capabilities(&capabilities),
+            BTreeSet::from(["host.telemetry"])
+        );
+    }
+
+    #[test]
+    fn size_budget_reports_largest_custom_sections() {
+        let mut module = wasm_encoder::Module::new();
+        for (name, len) in [(".debug_info", 3072), ("producers", 64)] {
+            module.section(&wasm_encoder::CustomSection {
+                name: name.into(),
+                data: vec![0u8; len].into(),
+            });
+        }
+        let temp = tempfile::tempdir().expect("tempdir");
+        let wasm_path = temp.path().join("component.wasm");
+        fs::write(&wasm_path, module.finish()).expect("write wasm");
+
+        let mut report = DoctorReport {
+            options: DoctorOptions {
+                max_wasm_kib: Some(2),
+                max_memory_mb: Some(64),
+                ..DoctorOptions::default()
+            },
+            ..DoctorReport::default()
+        };
+        report.validate_size(&wasm_path, None);
+        let codes = report
+            .diagnostics
+            .iter()
+            .map(|diag| diag.code.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            codes,
+            vec!["doctor.size.debug_sections", "doctor.size.wasm_over_budget"]
+        );
+        assert!(
+            report.diagnostics[1]
+                .message
+                .contains("largest custom sections: .debug_info 3.0 KiB, producers 64 B")
+        );
+        assert_eq!(format_size(5 * 1024 * 1024 / 2), "2.5 MiB");
+    }
 }
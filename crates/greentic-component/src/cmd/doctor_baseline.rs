@@ -0,0 +1,117 @@
+//! Accepted doctor findings (`.greentic-doctor-baseline.json`).
+//!
+//! A baseline lists finding ids a project has accepted. Doctor still reports them, but only
+//! findings missing from the baseline fail the run, so legacy components can adopt doctor
+//! without fixing everything first.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+/// File name doctor uses next to the manifest unless `--baseline` points elsewhere.
+pub const BASELINE_FILE: &str = ".greentic-doctor-baseline.json";
+
+const BASELINE_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct DoctorBaseline {
+    pub version: u32,
+    pub findings: Vec<BaselineEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct BaselineEntry {
+    /// Finding id (`<code>:<path>`) as printed by `doctor --format json`.
+    pub id: String,
+    pub code: String,
+    /// Message at the time the finding was accepted; informational only.
+    pub message: String,
+}
+
+impl DoctorBaseline {
+    /// Entries are deduplicated by id and sorted, so rewriting an unchanged baseline is a no-op.
+    pub fn from_entries(entries: impl IntoIterator<Item = BaselineEntry>) -> Self {
+        let findings = entries
+            .into_iter()
+            .map(|entry| (entry.id.clone(), entry))
+            .collect::<BTreeMap<_, _>>()
+            .into_values()
+            .collect();
+        DoctorBaseline {
+            version: BASELINE_VERSION,
+            findings,
+        }
+    }
+
+    /// `Ok(None)` when there is no baseline at `path`.
+    pub fn load(path: &Path) -> Result<Option<Self>, String> {
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let raw = fs::read_to_string(path)
+            .map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+        let baseline: DoctorBaseline = serde_json::from_str(&raw)
+            .map_err(|err| format!("invalid baseline {}: {err}", path.display()))?;
+        if baseline.version != BASELINE_VERSION {
+            return Err(format!(
+                "unsupported baseline version {} in {} (expected {BASELINE_VERSION})",
+                baseline.version,
+                path.display()
+            ));
+        }
+        Ok(Some(baseline))
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), String> {
+        let payload = serde_json::to_string_pretty(self)
+            .map_err(|err| format!("failed to encode baseline: {err}"))?;
+        fs::write(path, payload + "\n")
+            .map_err(|err| format!("failed to write {}: {err}", path.display()))
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.findings.iter().any(|entry| entry.id == id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str) -> BaselineEntry {
+        BaselineEntry {
+            id: id.to_string(),
+            code: id.split(':').next().unwrap_or_default().to_string(),
+            message: "accepted".to_string(),
+        }
+    }
+
+    #[test]
+    fn round_trips_sorted_unique_entries() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let path = temp.path().join(BASELINE_FILE);
+        assert_eq!(DoctorBaseline::load(&path).unwrap(), None);
+
+        let baseline = DoctorBaseline::from_entries([
+            entry("doctor.world.mismatch:world"),
+            entry("doctor.embedded.missing:embedded_manifest"),
+            entry("doctor.world.mismatch:world"),
+        ]);
+        baseline.write(&path).expect("write");
+        let loaded = DoctorBaseline::load(&path).unwrap().expect("baseline");
+        assert_eq!(loaded, baseline);
+        assert_eq!(loaded.findings.len(), 2);
+        assert_eq!(
+            loaded.findings[0].id,
+            "doctor.embedded.missing:embedded_manifest"
+        );
+        assert!(loaded.contains("doctor.world.mismatch:world"));
+
+        fs::write(&path, r#"{"version": 2, "findings": []}"#).expect("write");
+        assert!(DoctorBaseline::load(&path).is_err());
+    }
+}
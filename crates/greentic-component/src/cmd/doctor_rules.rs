@@ -0,0 +1,287 @@
+//! Workspace-local doctor rules (`doctor.rules.json`).
+//!
+//! Each rule points at a value in `component.manifest.json` with a JSON pointer and asserts
+//! something about it: that it exists, a numeric range, a regex, a list of entries it must
+//! include, or a JSON schema. Violations become doctor findings with the rule id as their code,
+//! so platform teams can enforce org policies next to the built-in checks.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use jsonschema::validator_for;
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+
+/// File name doctor looks for next to the manifest and in its parent directories.
+pub const RULES_FILE: &str = "doctor.rules.json";
+
+/// Prefix reserved for built-in finding codes.
+const BUILTIN_PREFIX: &str = "doctor.";
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DoctorRules {
+    #[serde(default)]
+    pub rules: Vec<DoctorRule>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DoctorRule {
+    /// Finding code reported on violation, e.g. `acme.limits.memory`.
+    pub id: String,
+    #[serde(default)]
+    pub severity: RuleSeverity,
+    /// Replaces the generated message when set.
+    #[serde(default)]
+    pub message: Option<String>,
+    /// JSON pointer into the manifest; empty for the whole document.
+    #[serde(default)]
+    pub pointer: String,
+    /// Fail when the pointer resolves to nothing. Other assertions skip missing values.
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default)]
+    pub min: Option<f64>,
+    #[serde(default)]
+    pub max: Option<f64>,
+    /// Regex a string value must match.
+    #[serde(default)]
+    pub pattern: Option<String>,
+    /// Entries an array must contain, compared with each item or its `name`/`id` field.
+    #[serde(default)]
+    pub includes: Vec<String>,
+    /// JSON schema the value must satisfy.
+    #[serde(default)]
+    pub schema: Option<JsonValue>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleSeverity {
+    #[default]
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleViolation {
+    pub rule: String,
+    pub severity: RuleSeverity,
+    pub message: String,
+    /// Dotted manifest path, e.g. `limits.memory_mb`.
+    pub path: String,
+}
+
+impl DoctorRules {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let raw = fs::read_to_string(path)
+            .map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+        let rules: DoctorRules = serde_json::from_str(&raw)
+            .map_err(|err| format!("invalid {}: {err}", path.display()))?;
+        let mut ids = BTreeSet::new();
+        for rule in &rules.rules {
+            if rule.id.trim().is_empty() || rule.id.starts_with(BUILTIN_PREFIX) {
+                return Err(format!(
+                    "{}: rule id `{}` must be non-empty and must not start with `{BUILTIN_PREFIX}`",
+                    path.display(),
+                    rule.id
+                ));
+            }
+            if !ids.insert(rule.id.as_str()) {
+                return Err(format!(
+                    "{}: rule id `{}` is defined more than once",
+                    path.display(),
+                    rule.id
+                ));
+            }
+        }
+        Ok(rules)
+    }
+
+    /// Check every rule against the raw manifest JSON. Rules that cannot run (a bad regex or
+    /// schema) are errors rather than violations.
+    pub fn evaluate(&self, manifest: &JsonValue) -> Result<Vec<RuleViolation>, String> {
+        let mut violations = Vec::new();
+        for rule in &self.rules {
+            violations.extend(rule.evaluate(manifest)?);
+        }
+        Ok(violations)
+    }
+}
+
+impl DoctorRule {
+    fn evaluate(&self, manifest: &JsonValue) -> Result<Vec<RuleViolation>, String> {
+        let Some(value) = manifest.pointer(&self.pointer) else {
+            return Ok(if self.required {
+                vec![self.violation("is missing".to_string())]
+            } else {
+                Vec::new()
+            });
+        };
+
+        let mut problems = Vec::new();
+        if self.min.is_some() || self.max.is_some() {
+            match value.as_f64() {
+                Some(number) => {
+                    if let Some(min) = self.min.filter(|min| number < *min) {
+                        problems.push(format!("is {number}, below the minimum of {min}"));
+                    }
+                    if let Some(max) = self.max.filter(|max| number > *max) {
+                        problems.push(format!("is {number}, above the maximum of {max}"));
+                    }
+                }
+                None => problems.push("is not a number".to_string()),
+            }
+        }
+        if let Some(pattern) = &self.pattern {
+            let regex = Regex::new(pattern)
+                .map_err(|err| format!("rule `{}`: invalid pattern: {err}", self.id))?;
+            match value.as_str() {
+                Some(text) if regex.is_match(text) => {}
+                Some(text) => problems.push(format!("`{text}` does not match `{pattern}`")),
+                None => problems.push("is not a string".to_string()),
+            }
+        }
+        if !self.includes.is_empty() {
+            match value.as_array() {
+                Some(items) => {
+                    for expected in &self.includes {
+                        if !items.iter().any(|item| item_matches(item, expected)) {
+                            problems.push(format!("does not include `{expected}`"));
+                        }
+                    }
+                }
+                None => problems.push("is not an array".to_string()),
+            }
+        }
+        if let Some(schema) = &self.schema {
+            let validator = validator_for(schema)
+                .map_err(|err| format!("rule `{}`: invalid schema: {err}", self.id))?;
+            problems.extend(
+                validator
+                    .iter_errors(value)
+                    .map(|err| format!("violates the rule schema: {err}")),
+            );
+        }
+        Ok(problems
+            .into_iter()
+            .map(|problem| self.violation(problem))
+            .collect())
+    }
+
+    fn violation(&self, problem: String) -> RuleViolation {
+        let path = self.path();
+        let label = if path.is_empty() { "manifest" } else { &path };
+        RuleViolation {
+            rule: self.id.clone(),
+            severity: self.severity,
+            message: self
+                .message
+                .clone()
+                .unwrap_or_else(|| format!("{label} {problem}")),
+            path,
+        }
+    }
+
+    fn path(&self) -> String {
+        self.pointer
+            .split('/')
+            .skip(1)
+            .map(|segment| segment.replace("~1", "/").replace("~0", "~"))
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+}
+
+fn item_matches(item: &JsonValue, expected: &str) -> bool {
+    item.as_str() == Some(expected)
+        || ["name", "id"]
+            .iter()
+            .any(|key| item.get(key).and_then(JsonValue::as_str) == Some(expected))
+}
+
+/// `doctor.rules.json` in the manifest's directory or the nearest parent that has one,
+/// stopping at the repository root.
+pub fn discover_rules(manifest_path: &Path) -> Option<PathBuf> {
+    for dir in manifest_path.parent()?.ancestors() {
+        let candidate = dir.join(RULES_FILE);
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+        if dir.join(".git").exists() {
+            break;
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn rules_report_limit_naming_and_operation_violations() {
+        let rules: DoctorRules = serde_json::from_value(json!({
+            "rules": [
+                { "id": "acme.memory", "pointer": "/limits/memory_mb", "max": 128 },
+                { "id": "acme.naming", "pointer": "/id", "pattern": "^com\\.acme\\." },
+                {
+                    "id": "acme.ops",
+                    "severity": "warning",
+                    "pointer": "/operations",
+                    "includes": ["handle_message", "health"]
+                },
+                { "id": "acme.telemetry", "pointer": "/telemetry", "required": true },
+                { "id": "acme.optional", "pointer": "/nope", "max": 1 }
+            ]
+        }))
+        .expect("rules");
+        let manifest = json!({
+            "id": "com.example.demo",
+            "limits": { "memory_mb": 256 },
+            "operations": [{ "name": "handle_message" }]
+        });
+        let violations = rules.evaluate(&manifest).expect("evaluate");
+        let found = violations
+            .iter()
+            .map(|violation| {
+                (
+                    violation.rule.as_str(),
+                    violation.severity,
+                    violation.path.as_str(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            found,
+            vec![
+                ("acme.memory", RuleSeverity::Error, "limits.memory_mb"),
+                ("acme.naming", RuleSeverity::Error, "id"),
+                ("acme.ops", RuleSeverity::Warning, "operations"),
+                ("acme.telemetry", RuleSeverity::Error, "telemetry"),
+            ]
+        );
+        assert_eq!(
+            violations[0].message,
+            "limits.memory_mb is 256, above the maximum of 128"
+        );
+        assert_eq!(
+            violations[2].message,
+            "operations does not include `health`"
+        );
+    }
+
+    #[test]
+    fn rejects_builtin_and_duplicate_rule_ids() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let path = temp.path().join(RULES_FILE);
+        fs::write(&path, r#"{"rules": [{"id": "doctor.world.mismatch"}]}"#).expect("write");
+        assert!(DoctorRules::load(&path).is_err());
+        fs::write(&path, r#"{"rules": [{"id": "acme.a"}, {"id": "acme.a"}]}"#).expect("write");
+        assert!(DoctorRules::load(&path).is_err());
+    }
+}
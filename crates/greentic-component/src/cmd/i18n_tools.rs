@@ -0,0 +1,763 @@
+#![cfg(feature = "cli")]
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use anyhow::{Context, Result, bail};
+use clap::{Args, Subcommand, ValueEnum};
+use greentic_types::schemas::component::v0_6_0::ComponentQaSpec;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+
+use super::qa::{SpecFormat, read_spec_document};
+use crate::i18n_check::{self, I18nCheckReport, I18nCoverageReport, LocaleMessages, SOURCE_LOCALE};
+
+const DEFAULT_I18N_DIR: &str = "assets/i18n";
+const DEFAULT_MANIFEST: &str = "component.manifest.json";
+const QA_SOURCE: &str = "src/qa.rs";
+const LOCALES_FILE: &str = "locales.json";
+const DEFAULT_TRANSLATOR: &str = "greentic-i18n-translator";
+
+// Dotted string literals (`"qa.field.region.label"`) are treated as i18n keys in src/qa.rs.
+static QA_KEY_LITERAL_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#""([a-z][a-z0-9_]*(?:\.[a-z0-9_-]+)+)""#)
+        .expect("i18n key literal regex compile should never fail")
+});
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum I18nCommand {
+    /// Compare every locale bundle against en.json (missing, extra, and empty keys)
+    Check(I18nCheckArgs),
+    /// Collect i18n keys from QA specs, src/qa.rs, and the manifest and merge them into en.json
+    Extract(I18nExtractArgs),
+    /// Translate en.json into every locale listed in locales.json via greentic-i18n-translator
+    Translate(I18nTranslateArgs),
+    /// Report per-locale translation coverage, untranslated keys, and stale keys
+    Coverage(I18nCoverageArgs),
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct I18nCheckArgs {
+    /// Component project root
+    #[arg(long = "project-root", value_name = "PATH", default_value = ".")]
+    pub project_root: PathBuf,
+    /// Locale bundle directory, relative to the project root
+    #[arg(long = "i18n-dir", value_name = "PATH", default_value = DEFAULT_I18N_DIR)]
+    pub i18n_dir: PathBuf,
+    /// Emit JSON instead of human-readable output
+    #[arg(long = "json")]
+    pub json: bool,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct I18nExtractArgs {
+    /// Component project root
+    #[arg(long = "project-root", value_name = "PATH", default_value = ".")]
+    pub project_root: PathBuf,
+    /// Locale bundle directory, relative to the project root
+    #[arg(long = "i18n-dir", value_name = "PATH", default_value = DEFAULT_I18N_DIR)]
+    pub i18n_dir: PathBuf,
+    /// Component manifest, relative to the project root
+    #[arg(long = "manifest", value_name = "PATH", default_value = DEFAULT_MANIFEST)]
+    pub manifest: PathBuf,
+    /// QA spec JSON or YAML file to scan for I18nText keys (repeatable)
+    #[arg(long = "qa-spec", value_name = "PATH")]
+    pub qa_specs: Vec<PathBuf>,
+    /// Encoding of the --qa-spec files
+    #[arg(long = "spec-format", value_enum, default_value = "auto")]
+    pub spec_format: SpecFormat,
+    /// Report differences without writing en.json
+    #[arg(long = "dry-run")]
+    pub dry_run: bool,
+    /// Emit JSON instead of human-readable output
+    #[arg(long = "json")]
+    pub json: bool,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct I18nTranslateArgs {
+    /// Component project root
+    #[arg(long = "project-root", value_name = "PATH", default_value = ".")]
+    pub project_root: PathBuf,
+    /// Locale bundle directory, relative to the project root
+    #[arg(long = "i18n-dir", value_name = "PATH", default_value = DEFAULT_I18N_DIR)]
+    pub i18n_dir: PathBuf,
+    /// Only translate these locales (repeat or pass comma-separated values); defaults to locales.json
+    #[arg(long = "langs", value_name = "LOCALE", value_delimiter = ',')]
+    pub langs: Vec<String>,
+    /// Translator binary to invoke
+    #[arg(long = "translator", value_name = "BIN", default_value = DEFAULT_TRANSLATOR)]
+    pub translator: String,
+    /// Set up the Codex CLI first: install it with npm or brew when missing and run `codex login`
+    #[arg(long = "codex-setup", conflicts_with = "skip_codex_check")]
+    pub codex_setup: bool,
+    /// Kept for older scripts; the Codex checks only run with --codex-setup
+    #[arg(long = "skip-codex-check", hide = true)]
+    pub skip_codex_check: bool,
+    /// Skip the translator validate/status passes after translating
+    #[arg(long = "skip-checks")]
+    pub skip_checks: bool,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct I18nCoverageArgs {
+    /// Component project root
+    #[arg(long = "project-root", value_name = "PATH", default_value = ".")]
+    pub project_root: PathBuf,
+    /// Locale bundle directory, relative to the project root
+    #[arg(long = "i18n-dir", value_name = "PATH", default_value = DEFAULT_I18N_DIR)]
+    pub i18n_dir: PathBuf,
+    /// Output format
+    #[arg(long = "format", value_enum, default_value = "text")]
+    pub format: CoverageFormat,
+    /// Write the report to a file instead of stdout
+    #[arg(long = "out", value_name = "PATH")]
+    pub out: Option<PathBuf>,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoverageFormat {
+    Text,
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Serialize)]
+pub struct I18nExtractReport {
+    pub bundle: String,
+    pub scanned: Vec<String>,
+    pub referenced_keys: usize,
+    pub missing_in_bundle: Vec<String>,
+    pub unreferenced_in_bundle: Vec<String>,
+    pub written: bool,
+}
+
+pub fn run(command: I18nCommand) -> Result<()> {
+    match command {
+        I18nCommand::Check(args) => check(args),
+        I18nCommand::Extract(args) => {
+            let report = extract(&args)?;
+            if args.json {
+                print_json(&report)?;
+            } else {
+                print_extract_human(&report, args.dry_run);
+            }
+            Ok(())
+        }
+        I18nCommand::Translate(args) => translate(&args),
+        I18nCommand::Coverage(args) => coverage(&args),
+    }
+}
+
+pub fn check(args: I18nCheckArgs) -> Result<()> {
+    let dir = resolve_i18n_dir(&args.project_root, &args.i18n_dir);
+    let report = i18n_check::check_locale_dir(&dir)
+        .with_context(|| format!("failed to check locale bundles in {}", dir.display()))?;
+    if args.json {
+        print_json(&report)?;
+    } else {
+        print_check_human(&dir, &report);
+    }
+    if !report.ok {
+        bail!(
+            "i18n check: {} locale(s) have missing, extra, or empty keys",
+            report.failing_locales().count()
+        );
+    }
+    Ok(())
+}
+
+pub fn coverage(args: &I18nCoverageArgs) -> Result<()> {
+    let dir = resolve_i18n_dir(&args.project_root, &args.i18n_dir);
+    let report = i18n_check::coverage_locale_dir(&dir)
+        .with_context(|| format!("failed to compute coverage in {}", dir.display()))?;
+    let rendered = match args.format {
+        CoverageFormat::Text => render_coverage_text(&report),
+        CoverageFormat::Json => format!("{}\n", serde_json::to_string_pretty(&report)?),
+        CoverageFormat::Csv => render_coverage_csv(&report),
+    };
+    match &args.out {
+        Some(path) => {
+            fs::write(path, rendered)
+                .with_context(|| format!("failed to write {}", path.display()))?;
+            println!("wrote {}", path.display());
+        }
+        None => print!("{rendered}"),
+    }
+    Ok(())
+}
+
+/// Scan the project for referenced i18n keys and merge missing ones into `en.json`.
+///
+/// New keys are written with empty values so `i18n check` keeps flagging them until the
+/// English text is filled in. Keys present in the bundle but never referenced are only reported.
+pub fn extract(args: &I18nExtractArgs) -> Result<I18nExtractReport> {
+    let mut scanned = Vec::new();
+    let mut referenced = BTreeSet::new();
+
+    let qa_source = args.project_root.join(QA_SOURCE);
+    if qa_source.is_file() {
+        let source = fs::read_to_string(&qa_source)
+            .with_context(|| format!("failed to read {}", qa_source.display()))?;
+        referenced.extend(keys_from_rust_source(&source));
+        scanned.push(qa_source.display().to_string());
+    }
+
+    let manifest_path = args.project_root.join(&args.manifest);
+    if manifest_path.is_file() {
+        let raw = fs::read_to_string(&manifest_path)
+            .with_context(|| format!("failed to read {}", manifest_path.display()))?;
+        let manifest: JsonValue = serde_json::from_str(&raw)
+            .with_context(|| format!("invalid json: {}", manifest_path.display()))?;
+        collect_manifest_keys(&manifest, &mut referenced);
+        scanned.push(manifest_path.display().to_string());
+    }
+
+    for spec_path in &args.qa_specs {
+        let spec_path = args.project_root.join(spec_path);
+        let spec: ComponentQaSpec = read_spec_document(&spec_path, args.spec_format)?;
+        referenced.extend(spec.i18n_keys());
+        scanned.push(spec_path.display().to_string());
+    }
+
+    let bundle_path =
+        resolve_i18n_dir(&args.project_root, &args.i18n_dir).join(format!("{SOURCE_LOCALE}.json"));
+    let mut bundle = if bundle_path.is_file() {
+        let raw = fs::read_to_string(&bundle_path)
+            .with_context(|| format!("failed to read {}", bundle_path.display()))?;
+        serde_json::from_str::<LocaleMessages>(&raw)
+            .with_context(|| format!("invalid locale bundle: {}", bundle_path.display()))?
+    } else {
+        LocaleMessages::new()
+    };
+
+    let missing_in_bundle = referenced
+        .iter()
+        .filter(|key| !bundle.contains_key(*key))
+        .cloned()
+        .collect::<Vec<_>>();
+    let unreferenced_in_bundle = bundle
+        .keys()
+        .filter(|key| !referenced.contains(*key))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    let written = !args.dry_run && !missing_in_bundle.is_empty();
+    if written {
+        for key in &missing_in_bundle {
+            bundle.insert(key.clone(), String::new());
+        }
+        if let Some(parent) = bundle_path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create {}", parent.display()))?;
+        }
+        let formatted = serde_json::to_string_pretty(&bundle)?;
+        fs::write(&bundle_path, formatted + "\n")
+            .with_context(|| format!("failed to write {}", bundle_path.display()))?;
+    }
+
+    Ok(I18nExtractReport {
+        bundle: bundle_path.display().to_string(),
+        scanned,
+        referenced_keys: referenced.len(),
+        missing_in_bundle,
+        unreferenced_in_bundle,
+        written,
+    })
+}
+
+/// Run the translator for every target locale, then its validate/status passes when available.
+pub fn translate(args: &I18nTranslateArgs) -> Result<()> {
+    let dir = resolve_i18n_dir(&args.project_root, &args.i18n_dir);
+    let locales_file = dir.join(LOCALES_FILE);
+    let source_file = dir.join(format!("{SOURCE_LOCALE}.json"));
+    if !locales_file.is_file() {
+        bail!("missing locales file: {}", locales_file.display());
+    }
+    if !source_file.is_file() {
+        bail!("missing source locale file: {}", source_file.display());
+    }
+    let locales = target_locales(&locales_file, &args.langs)?;
+
+    if args.codex_setup {
+        ensure_codex()?;
+        ensure_codex_login()?;
+    }
+    probe_translator(&args.translator)?;
+
+    let total = locales.len();
+    for (idx, locale) in locales.iter().enumerate() {
+        log(&format!(
+            "translating locale: {locale} ({}/{total})",
+            idx + 1
+        ));
+        if !run_translator(&args.translator, "translate", locale, &source_file)? {
+            bail!("translate failed for locale {locale}");
+        }
+    }
+
+    if !args.skip_checks {
+        run_translator_checks(&args.translator, &locales, &source_file)?;
+    }
+    log("translations updated. Run cargo build to embed translations into WASM");
+    Ok(())
+}
+
+fn target_locales(locales_file: &Path, langs: &[String]) -> Result<Vec<String>> {
+    let locales = if langs.is_empty() {
+        let raw = fs::read_to_string(locales_file)
+            .with_context(|| format!("failed to read {}", locales_file.display()))?;
+        serde_json::from_str::<Vec<String>>(&raw).with_context(|| {
+            format!(
+                "{} must be a JSON array of locale tags",
+                locales_file.display()
+            )
+        })?
+    } else {
+        langs.to_vec()
+    };
+    Ok(locales
+        .into_iter()
+        .map(|locale| locale.trim().to_string())
+        .filter(|locale| !locale.is_empty() && locale != SOURCE_LOCALE)
+        .collect())
+}
+
+fn ensure_codex() -> Result<()> {
+    if command_succeeds("codex", &["--version"]) {
+        return Ok(());
+    }
+    if command_succeeds("npm", &["--version"]) {
+        log("installing Codex CLI via npm");
+        if !run_inherited("npm", &["i", "-g", "@openai/codex"])? {
+            bail!("failed to install Codex CLI via npm");
+        }
+    } else if command_succeeds("brew", &["--version"]) {
+        log("installing Codex CLI via brew");
+        if !run_inherited("brew", &["install", "codex"])? {
+            bail!("failed to install Codex CLI via brew");
+        }
+    } else {
+        bail!("Codex CLI not found and no supported installer available (npm or brew)");
+    }
+    Ok(())
+}
+
+fn ensure_codex_login() -> Result<()> {
+    if command_succeeds("codex", &["login", "status"]) {
+        return Ok(());
+    }
+    if !(io::stdin().is_terminal() && io::stdout().is_terminal()) {
+        bail!("Codex CLI is not logged in; run `codex login` in a terminal first");
+    }
+    log("Codex login status unavailable or not logged in; starting login flow");
+    if !run_inherited("codex", &["login"])? {
+        bail!("Codex login failed");
+    }
+    Ok(())
+}
+
+fn probe_translator(translator: &str) -> Result<()> {
+    tool_command(translator)
+        .arg("--help")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .with_context(|| format!("{translator} not found. Install it and rerun this command."))?;
+    if !command_succeeds(translator, &["translate", "--help"]) {
+        bail!("translator subcommand 'translate' is required but unavailable");
+    }
+    Ok(())
+}
+
+fn run_translator_checks(translator: &str, locales: &[String], source_file: &Path) -> Result<()> {
+    for check in ["validate", "status"] {
+        if !command_succeeds(translator, &[check, "--help"]) {
+            log(&format!(
+                "warning: translator {check} command not available; skipping"
+            ));
+            continue;
+        }
+        log(&format!("running translator {check}"));
+        let mut failed = Vec::new();
+        for locale in locales {
+            if !run_translator(translator, check, locale, source_file)? {
+                log(&format!("{check} failed for locale: {locale}"));
+                failed.push(locale.as_str());
+            }
+        }
+        if !failed.is_empty() {
+            bail!("translator {check} failed for: {}", failed.join(", "));
+        }
+    }
+    Ok(())
+}
+
+fn run_translator(
+    translator: &str,
+    subcommand: &str,
+    locale: &str,
+    source_file: &Path,
+) -> Result<bool> {
+    let status = tool_command(translator)
+        .arg(subcommand)
+        .arg("--langs")
+        .arg(locale)
+        .arg("--en")
+        .arg(source_file)
+        .status()
+        .with_context(|| format!("failed to run `{translator} {subcommand}`"))?;
+    Ok(status.success())
+}
+
+fn command_succeeds(program: &str, args: &[&str]) -> bool {
+    tool_command(program)
+        .args(args)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false)
+}
+
+fn run_inherited(program: &str, args: &[&str]) -> Result<bool> {
+    let status = tool_command(program)
+        .args(args)
+        .status()
+        .with_context(|| format!("failed to run `{program}`"))?;
+    Ok(status.success())
+}
+
+/// `Command` for a tool found on PATH. npm installs tools such as `codex` as `.cmd` shims on
+/// Windows, which a bare program name does not resolve to, so those run through `cmd /C`.
+fn tool_command(program: &str) -> Command {
+    #[cfg(windows)]
+    {
+        if let Some(shim) = windows_shim(program) {
+            let mut command = Command::new("cmd");
+            command.arg("/C").arg(shim);
+            return command;
+        }
+    }
+    Command::new(program)
+}
+
+/// The `.cmd`/`.bat` file `program` resolves to on PATH, unless an executable comes first.
+#[cfg(windows)]
+fn windows_shim(program: &str) -> Option<PathBuf> {
+    if Path::new(program).extension().is_some() {
+        return None;
+    }
+    let path = std::env::var_os("PATH")?;
+    for dir in std::env::split_paths(&path) {
+        if ["exe", "com"]
+            .iter()
+            .any(|ext| dir.join(format!("{program}.{ext}")).is_file())
+        {
+            return None;
+        }
+        if let Some(shim) = ["cmd", "bat"]
+            .iter()
+            .map(|ext| dir.join(format!("{program}.{ext}")))
+            .find(|candidate| candidate.is_file())
+        {
+            return Some(shim);
+        }
+    }
+    None
+}
+
+fn log(message: &str) {
+    println!("[i18n] {message}");
+}
+
+fn keys_from_rust_source(source: &str) -> BTreeSet<String> {
+    QA_KEY_LITERAL_RE
+        .captures_iter(source)
+        .map(|caps| caps[1].to_string())
+        .collect()
+}
+
+// Manifest schemas reference translations through `*_i18n_key` string fields.
+fn collect_manifest_keys(value: &JsonValue, out: &mut BTreeSet<String>) {
+    match value {
+        JsonValue::Object(map) => {
+            for (key, child) in map {
+                if key.ends_with("i18n_key")
+                    && let Some(text) = child.as_str()
+                {
+                    out.insert(text.to_string());
+                }
+                collect_manifest_keys(child, out);
+            }
+        }
+        JsonValue::Array(items) => {
+            for item in items {
+                collect_manifest_keys(item, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn resolve_i18n_dir(project_root: &Path, i18n_dir: &Path) -> PathBuf {
+    project_root.join(i18n_dir)
+}
+
+fn print_json<T: Serialize>(value: &T) -> Result<()> {
+    let mut handle = std::io::stdout();
+    serde_json::to_writer_pretty(&mut handle, value)?;
+    handle.write_all(b"\n").ok();
+    Ok(())
+}
+
+fn print_check_human(dir: &Path, report: &I18nCheckReport) {
+    println!(
+        "i18n check: {} ({}: {} keys, {} locale(s))",
+        dir.display(),
+        report.source_locale,
+        report.source_keys,
+        report.locales.len()
+    );
+    for locale in &report.locales {
+        if locale.is_clean() {
+            println!("  {}: ok", locale.locale);
+            continue;
+        }
+        println!(
+            "  {}: {} missing, {} extra, {} empty",
+            locale.locale,
+            locale.missing_keys.len(),
+            locale.extra_keys.len(),
+            locale.empty_values.len()
+        );
+        print_key_list("missing", &locale.missing_keys);
+        print_key_list("extra", &locale.extra_keys);
+        print_key_list("empty", &locale.empty_values);
+    }
+    if report.ok {
+        println!("all locales match {}", report.source_locale);
+    }
+}
+
+fn render_coverage_text(report: &I18nCoverageReport) -> String {
+    let mut out = format!(
+        "i18n coverage against {} ({} key(s))\n",
+        report.source_locale, report.source_keys
+    );
+    for locale in &report.locales {
+        out.push_str(&format!(
+            "  {}: {:.2}% ({}/{}), {} untranslated, {} stale\n",
+            locale.locale,
+            locale.coverage_percent,
+            locale.translated_keys,
+            locale.total_keys,
+            locale.untranslated_keys.len(),
+            locale.stale_keys.len()
+        ));
+        if !locale.untranslated_keys.is_empty() {
+            out.push_str(&format!(
+                "    untranslated: {}\n",
+                locale.untranslated_keys.join(", ")
+            ));
+        }
+        if !locale.stale_keys.is_empty() {
+            out.push_str(&format!("    stale: {}\n", locale.stale_keys.join(", ")));
+        }
+    }
+    out
+}
+
+// One row per locale; key lists are `;`-separated inside a single quoted cell.
+fn render_coverage_csv(report: &I18nCoverageReport) -> String {
+    let mut out = String::from(
+        "locale,total_keys,translated_keys,coverage_percent,untranslated_keys,stale_keys\n",
+    );
+    for locale in &report.locales {
+        out.push_str(&format!(
+            "{},{},{},{:.2},{},{}\n",
+            csv_field(&locale.locale),
+            locale.total_keys,
+            locale.translated_keys,
+            locale.coverage_percent,
+            csv_field(&locale.untranslated_keys.join(";")),
+            csv_field(&locale.stale_keys.join(";"))
+        ));
+    }
+    out
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', ';']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn print_key_list(label: &str, keys: &[String]) {
+    if !keys.is_empty() {
+        println!("    {label}: {}", keys.join(", "));
+    }
+}
+
+fn print_extract_human(report: &I18nExtractReport, dry_run: bool) {
+    println!(
+        "i18n extract: {} referenced key(s) from {} source(s)",
+        report.referenced_keys,
+        report.scanned.len()
+    );
+    for source in &report.scanned {
+        println!("  - {source}");
+    }
+    if report.missing_in_bundle.is_empty() {
+        println!("{}: all referenced keys present", report.bundle);
+    } else if report.written {
+        println!(
+            "{}: added {} key(s) with empty values; fill in the English text",
+            report.bundle,
+            report.missing_in_bundle.len()
+        );
+        print_key_list("added", &report.missing_in_bundle);
+    } else {
+        let note = if dry_run { " (dry run)" } else { "" };
+        println!(
+            "{}: {} referenced key(s) missing{note}",
+            report.bundle,
+            report.missing_in_bundle.len()
+        );
+        print_key_list("missing", &report.missing_in_bundle);
+    }
+    if !report.unreferenced_in_bundle.is_empty() {
+        println!(
+            "{} bundle key(s) are not referenced by the scanned sources",
+            report.unreferenced_in_bundle.len()
+        );
+        print_key_list("unreferenced", &report.unreferenced_in_bundle);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_keys_from_qa_source_and_manifest() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join("assets/i18n")).unwrap();
+        fs::write(
+            root.join("src/qa.rs"),
+            r#"question("region", "qa.field.region.label", "qa.field.region.help", true);"#,
+        )
+        .unwrap();
+        fs::write(
+            root.join("component.manifest.json"),
+            r#"{"schema": {"title_i18n_key": "qa.install.title"}}"#,
+        )
+        .unwrap();
+        fs::write(
+            root.join("assets/i18n/en.json"),
+            r#"{"qa.field.region.label": "Region", "qa.stale": "Stale"}"#,
+        )
+        .unwrap();
+
+        let args = I18nExtractArgs {
+            project_root: root.to_path_buf(),
+            i18n_dir: PathBuf::from(DEFAULT_I18N_DIR),
+            manifest: PathBuf::from(DEFAULT_MANIFEST),
+            qa_specs: Vec::new(),
+            spec_format: SpecFormat::Auto,
+            dry_run: false,
+            json: false,
+        };
+        let report = extract(&args).unwrap();
+        assert_eq!(report.referenced_keys, 3);
+        assert_eq!(
+            report.missing_in_bundle,
+            vec!["qa.field.region.help", "qa.install.title"]
+        );
+        assert_eq!(report.unreferenced_in_bundle, vec!["qa.stale"]);
+        assert!(report.written);
+
+        let merged: LocaleMessages =
+            serde_json::from_str(&fs::read_to_string(root.join("assets/i18n/en.json")).unwrap())
+                .unwrap();
+        assert_eq!(merged["qa.field.region.label"], "Region");
+        assert_eq!(merged["qa.install.title"], "");
+        assert!(merged.contains_key("qa.stale"));
+    }
+
+    #[test]
+    fn extracts_keys_from_yaml_qa_spec() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        fs::write(
+            root.join("setup.qa.yaml"),
+            "mode: setup\ntitle:\n  key: qa.setup.title\nquestions:\n  - id: region\n    label:\n      key: qa.field.region.label\n    kind:\n      type: text\n    required: true\ndefaults: {}\n",
+        )
+        .unwrap();
+
+        let args = I18nExtractArgs {
+            project_root: root.to_path_buf(),
+            i18n_dir: PathBuf::from(DEFAULT_I18N_DIR),
+            manifest: PathBuf::from(DEFAULT_MANIFEST),
+            qa_specs: vec![PathBuf::from("setup.qa.yaml")],
+            spec_format: SpecFormat::Auto,
+            dry_run: true,
+            json: false,
+        };
+        let report = extract(&args).unwrap();
+        assert_eq!(
+            report.missing_in_bundle,
+            vec!["qa.field.region.label", "qa.setup.title"]
+        );
+        assert!(!report.written);
+    }
+
+    #[test]
+    fn coverage_csv_quotes_key_lists() {
+        let report = I18nCoverageReport {
+            source_locale: "en".to_string(),
+            source_keys: 3,
+            locales: vec![i18n_check::LocaleCoverage {
+                locale: "de".to_string(),
+                total_keys: 3,
+                translated_keys: 1,
+                coverage_percent: 33.33,
+                untranslated_keys: vec!["a".to_string(), "b".to_string()],
+                stale_keys: Vec::new(),
+            }],
+        };
+
+        let csv = render_coverage_csv(&report);
+        let rows = csv.lines().collect::<Vec<_>>();
+        assert_eq!(
+            rows[0],
+            "locale,total_keys,translated_keys,coverage_percent,untranslated_keys,stale_keys"
+        );
+        assert_eq!(rows[1], "de,3,1,33.33,\"a;b\",");
+    }
+
+    #[test]
+    fn target_locales_skip_source_and_honor_langs() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let locales_file = temp.path().join(LOCALES_FILE);
+        fs::write(&locales_file, r#"["en", "de", " fr ", ""]"#).unwrap();
+
+        assert_eq!(
+            target_locales(&locales_file, &[]).unwrap(),
+            vec!["de", "fr"]
+        );
+        assert_eq!(
+            target_locales(&locales_file, &["nl".to_string(), "en".to_string()]).unwrap(),
+            vec!["nl"]
+        );
+    }
+}
@@ -1,14 +1,19 @@
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use clap::{Args, Parser};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use serde::Serialize;
 use serde_json::Value;
+use time::format_description::well_known::Rfc3339;
 use wasmtime::component::{Component, Linker, Val};
 use wasmtime::{Engine, Store};
 use wasmtime_wasi::{ResourceTable, WasiCtx, WasiCtxBuilder, WasiCtxView, WasiView};
 
+use super::inspect_diff::{ComponentDiff, DiffInput};
+use super::inspect_qa::{QaPreview, Translations};
+use super::inspect_schema;
 use super::path::strip_file_scheme;
+use crate::build_metadata::{BUILD_METADATA_SECTION, BuildInfo, read_build_metadata_section};
 use crate::describe::from_wit_world;
 use crate::embedded_compare::{
     EmbeddedManifestComparisonReport, compare_embedded_with_describe,
@@ -17,14 +22,23 @@ use crate::embedded_compare::{
 use crate::embedded_descriptor::{
     EMBEDDED_COMPONENT_MANIFEST_SECTION_V1, read_and_verify_embedded_component_manifest_section_v1,
 };
+use crate::i18n_check::{self, LocaleBundle, SOURCE_LOCALE};
+use crate::manifest::ComponentManifest;
+use crate::test_harness::TestHarness;
 use crate::{ComponentError, PreparedComponent, parse_manifest, prepare_component_with_manifest};
 use greentic_types::cbor::canonical;
 use greentic_types::schemas::common::schema_ir::{AdditionalProperties, SchemaIr};
-use greentic_types::schemas::component::v0_6_0::{ComponentDescribe, schema_hash};
+use greentic_types::schemas::component::v0_6_0::{ComponentDescribe, ComponentQaSpec, schema_hash};
 
 #[derive(Args, Debug, Clone)]
-#[command(about = "Inspect a Greentic component artifact")]
+#[command(
+    about = "Inspect a Greentic component artifact",
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
+)]
 pub struct InspectArgs {
+    #[command(subcommand)]
+    pub command: Option<InspectCommand>,
     /// Path or identifier resolvable by the loader
     #[arg(value_name = "TARGET", required_unless_present = "describe")]
     pub target: Option<String>,
@@ -34,9 +48,35 @@ pub struct InspectArgs {
     /// Inspect a pre-generated describe CBOR file (skip WASM execution)
     #[arg(long)]
     pub describe: Option<PathBuf>,
-    /// Emit structured JSON instead of human output
-    #[arg(long)]
+    /// Instantiate the wasm, dump the full describe() schemas and cross-check manifest operations
+    #[arg(long, conflicts_with = "describe")]
+    pub live: bool,
+    /// With --live, also print Rust type sketches for each schema
+    #[arg(long, requires = "live")]
+    pub rust_types: bool,
+    /// Render the setup wizard each QA mode would show, as text and as an Adaptive Card
+    #[arg(long, conflicts_with_all = ["describe", "live"])]
+    pub qa_preview: bool,
+    /// With --qa-preview, only render these modes (default: all)
+    #[arg(long = "qa-mode", value_enum, requires = "qa_preview")]
+    pub qa_modes: Vec<QaPreviewMode>,
+    /// With --qa-preview, only render these locales (default: every locale in assets/i18n)
+    #[arg(long = "qa-locale", value_name = "LOCALE", requires = "qa_preview")]
+    pub qa_locales: Vec<String>,
+    /// Emit structured JSON instead of human output (same as `--output json`)
+    #[arg(long, conflicts_with = "output")]
     pub json: bool,
+    /// Emit a structured report in this format instead of human output
+    #[arg(long, value_enum, value_name = "FORMAT")]
+    pub output: Option<InspectOutput>,
+    /// Only emit these manifest sections (repeatable); implies `--output json` unless set
+    #[arg(
+        long = "section",
+        value_enum,
+        value_name = "SECTION",
+        conflicts_with_all = ["describe", "live", "qa_preview"]
+    )]
+    pub sections: Vec<InspectSection>,
     /// Verify schema_hash values against typed SchemaIR
     #[arg(long)]
     pub verify: bool,
@@ -45,6 +85,78 @@ pub struct InspectArgs {
     pub strict: bool,
 }
 
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InspectOutput {
+    Json,
+    Yaml,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum InspectSection {
+    Ops,
+    Capabilities,
+    Schemas,
+    I18n,
+}
+
+impl InspectArgs {
+    fn output_format(&self) -> Option<InspectOutput> {
+        self.output
+            .or_else(|| (self.json || !self.sections.is_empty()).then_some(InspectOutput::Json))
+    }
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QaPreviewMode {
+    Default,
+    Setup,
+    Update,
+    Remove,
+}
+
+impl QaPreviewMode {
+    const ALL: [QaPreviewMode; 4] = [
+        QaPreviewMode::Default,
+        QaPreviewMode::Setup,
+        QaPreviewMode::Update,
+        QaPreviewMode::Remove,
+    ];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            QaPreviewMode::Default => "default",
+            QaPreviewMode::Setup => "setup",
+            QaPreviewMode::Update => "update",
+            QaPreviewMode::Remove => "remove",
+        }
+    }
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum InspectCommand {
+    /// Compare two versions of a component (wasm, directory, or manifest)
+    Diff(InspectDiffArgs),
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct InspectDiffArgs {
+    /// Previous version: wasm, component directory, or component.manifest.json
+    #[arg(value_name = "OLD")]
+    pub old: String,
+    /// New version: wasm, component directory, or component.manifest.json
+    #[arg(value_name = "NEW")]
+    pub new: String,
+    /// Emit structured JSON instead of human output (same as `--output json`)
+    #[arg(long, conflicts_with = "output")]
+    pub json: bool,
+    /// Emit the diff in this format instead of human output
+    #[arg(long, value_enum, value_name = "FORMAT")]
+    pub output: Option<InspectOutput>,
+    /// Exit with an error when any change is breaking
+    #[arg(long)]
+    pub fail_on_breaking: bool,
+}
+
 #[derive(Parser, Debug)]
 struct InspectCli {
     #[command(flatten)]
@@ -61,10 +173,26 @@ pub struct InspectResult {
 }
 
 pub fn run(args: &InspectArgs) -> Result<InspectResult, ComponentError> {
+    if let Some(InspectCommand::Diff(diff_args)) = &args.command {
+        return inspect_diff(diff_args);
+    }
+
+    if args.live {
+        return inspect_live(args);
+    }
+
+    if args.qa_preview {
+        return inspect_qa_preview(args);
+    }
+
     if args.describe.is_some() {
         return inspect_describe(args);
     }
 
+    if !args.sections.is_empty() {
+        return inspect_sections(args);
+    }
+
     if should_inspect_wasm_artifact(args) {
         return inspect_artifact(args);
     }
@@ -75,10 +203,8 @@ pub fn run(args: &InspectArgs) -> Result<InspectResult, ComponentError> {
         .ok_or_else(|| ComponentError::Doctor("inspect target is required".to_string()))?;
     let manifest_override = args.manifest.as_deref().map(strip_file_scheme);
     let prepared = prepare_component_with_manifest(target, manifest_override.as_deref())?;
-    if args.json {
-        let json = serde_json::to_string_pretty(&build_report(&prepared))
-            .expect("serializing inspect report");
-        println!("{json}");
+    if let Some(format) = args.output_format() {
+        emit_structured(&build_report(&prepared), format)?;
     } else {
         println!("component: {}", prepared.manifest.id.as_str());
         println!("  wasm: {}", prepared.wasm_path.display());
@@ -162,6 +288,17 @@ struct ArtifactInspectReport {
     #[serde(skip_serializing_if = "Option::is_none")]
     describe: Option<ArtifactDescribeStatus>,
     embedded: EmbeddedInspectStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    build: Option<BuildMetadataStatus>,
+}
+
+#[derive(Debug, Serialize)]
+struct BuildMetadataStatus {
+    section_name: String,
+    #[serde(flatten)]
+    info: BuildInfo,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    manifest_matches: Option<bool>,
 }
 
 #[derive(Debug, Serialize)]
@@ -226,6 +363,16 @@ fn inspect_artifact(args: &InspectArgs) -> Result<InspectResult, ComponentError>
     let mut describe_status = None;
     let present = verified.is_some();
     let hash_verified = verified.is_some();
+    let build_metadata = match read_build_metadata_section(&wasm_bytes) {
+        Ok(metadata) => metadata,
+        Err(err) => {
+            warnings.push(format!(
+                "{BUILD_METADATA_SECTION} section unreadable: {err}"
+            ));
+            None
+        }
+    };
+    let mut build_manifest_matches = None;
 
     if let Some(manifest_path) = manifest_path.as_ref() {
         let raw = fs::read_to_string(manifest_path).map_err(|err| {
@@ -242,6 +389,18 @@ fn inspect_artifact(args: &InspectArgs) -> Result<InspectResult, ComponentError>
         })?;
         external_manifest_summary =
             Some((parsed.id.as_str().to_string(), parsed.version.to_string()));
+        if let (Some(metadata), Ok(json)) =
+            (build_metadata.as_ref(), serde_json::from_str::<Value>(&raw))
+        {
+            let matches = metadata.manifest_matches(&json);
+            if !matches {
+                warnings.push(format!(
+                    "{BUILD_METADATA_SECTION} manifest differs from {}; rebuild to refresh it",
+                    manifest_path.display()
+                ));
+            }
+            build_manifest_matches = Some(matches);
+        }
         if let Some(verified) = verified.as_ref() {
             compare_manifest = Some(compare_embedded_with_manifest(&verified.manifest, &parsed));
         }
@@ -398,12 +557,15 @@ fn inspect_artifact(args: &InspectArgs) -> Result<InspectResult, ComponentError>
             compare_describe,
             warnings: warnings.clone(),
         },
+        build: build_metadata.map(|metadata| BuildMetadataStatus {
+            section_name: BUILD_METADATA_SECTION.to_string(),
+            info: metadata.build,
+            manifest_matches: build_manifest_matches,
+        }),
     };
 
-    if args.json {
-        let json = serde_json::to_string_pretty(&report)
-            .map_err(|err| ComponentError::Doctor(format!("failed to encode json: {err}")))?;
-        println!("{json}");
+    if let Some(format) = args.output_format() {
+        emit_structured(&report, format)?;
     } else {
         println!("wasm: {}", report.wasm_path.display());
         if let Some(manifest) = &report.manifest {
@@ -471,6 +633,31 @@ fn inspect_artifact(args: &InspectArgs) -> Result<InspectResult, ComponentError>
                 println!("  telemetry emit node spans: {}", telemetry.emit_node_spans);
             }
         }
+        match &report.build {
+            Some(build) => {
+                println!("build metadata: present");
+                println!("  section: {}", build.section_name);
+                println!("  builder: {}", build.info.builder);
+                println!(
+                    "  git commit: {}{}",
+                    build.info.git_commit.as_deref().unwrap_or("<none>"),
+                    if build.info.git_dirty { " (dirty)" } else { "" }
+                );
+                let built_at = build
+                    .info
+                    .built_at_utc
+                    .format(&Rfc3339)
+                    .unwrap_or_else(|_| build.info.built_at_utc.to_string());
+                println!("  built at: {built_at}");
+                if let Some(template_version) = &build.info.template_version {
+                    println!("  template version: {template_version}");
+                }
+                if let Some(matches) = build.manifest_matches {
+                    println!("  matches manifest: {matches}");
+                }
+            }
+            None => println!("build metadata: missing"),
+        }
         if let Some(describe) = &report.describe {
             println!("describe: {}", describe.status);
             if let Some(source) = &describe.source {
@@ -509,6 +696,17 @@ fn inspect_artifact(args: &InspectArgs) -> Result<InspectResult, ComponentError>
     Ok(InspectResult { warnings })
 }
 
+fn emit_structured<T: Serialize>(value: &T, format: InspectOutput) -> Result<(), ComponentError> {
+    let rendered = match format {
+        InspectOutput::Json => serde_json::to_string_pretty(value)
+            .map_err(|err| ComponentError::Doctor(format!("failed to encode json: {err}")))?,
+        InspectOutput::Yaml => serde_norway::to_string(value)
+            .map_err(|err| ComponentError::Doctor(format!("failed to encode yaml: {err}")))?,
+    };
+    println!("{}", rendered.trim_end());
+    Ok(())
+}
+
 pub fn emit_warnings(warnings: &[String]) {
     for warning in warnings {
         eprintln!("warning: {warning}");
@@ -620,10 +818,8 @@ fn inspect_describe(args: &InspectArgs) -> Result<InspectResult, ComponentError>
     let mut report = DescribeReport::from(describe, args.verify)?;
     report.wasm_path = wasm_path;
 
-    if args.json {
-        let json = serde_json::to_string_pretty(&report)
-            .map_err(|err| ComponentError::Doctor(format!("failed to encode json: {err}")))?;
-        println!("{json}");
+    if let Some(format) = args.output_format() {
+        emit_structured(&report, format)?;
     } else {
         emit_describe_human(&report);
     }
@@ -706,6 +902,818 @@ impl DescribeReport {
     }
 }
 
+fn inspect_diff(args: &InspectDiffArgs) -> Result<InspectResult, ComponentError> {
+    let (old_label, old) = load_component_input(&args.old)?;
+    let (new_label, new) = load_component_input(&args.new)?;
+    let diff = ComponentDiff::new(old_label, &old, new_label, &new);
+
+    let format = args
+        .output
+        .or_else(|| args.json.then_some(InspectOutput::Json));
+    if let Some(format) = format {
+        emit_structured(&diff, format)?;
+    } else {
+        print!("{}", diff.render_text());
+    }
+
+    if args.fail_on_breaking && diff.summary.breaking > 0 {
+        return Err(ComponentError::Doctor(format!(
+            "{} breaking change(s) between {} and {}",
+            diff.summary.breaking, diff.old, diff.new
+        )));
+    }
+    Ok(InspectResult::default())
+}
+
+/// Resolve a target to its manifest for `diff` and `--section`. A wasm uses the manifest next
+/// to it, falling back to its embedded manifest when there is none.
+fn load_component_input(target: &str) -> Result<(String, DiffInput), ComponentError> {
+    let path = strip_file_scheme(Path::new(target));
+    let is_wasm = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("wasm"));
+    let manifest_path = if path.is_dir() {
+        let candidate = path.join("component.manifest.json");
+        if candidate.is_file() {
+            Some(candidate)
+        } else {
+            let wasm_path = resolve_wasm_path(target).map_err(ComponentError::Doctor)?;
+            return load_embedded_input(&wasm_path);
+        }
+    } else if is_wasm {
+        discover_manifest_path(&path, &path)
+    } else {
+        Some(path.clone())
+    };
+    match manifest_path {
+        Some(manifest_path) => {
+            let input =
+                DiffInput::from_manifest_path(&manifest_path).map_err(ComponentError::Doctor)?;
+            Ok((manifest_path.display().to_string(), input))
+        }
+        None => load_embedded_input(&path),
+    }
+}
+
+fn load_embedded_input(wasm_path: &Path) -> Result<(String, DiffInput), ComponentError> {
+    let bytes = fs::read(wasm_path).map_err(|err| {
+        ComponentError::Doctor(format!("failed to read {}: {err}", wasm_path.display()))
+    })?;
+    let verified = read_and_verify_embedded_component_manifest_section_v1(&bytes)
+        .map_err(|err| ComponentError::Doctor(format!("failed to read embedded manifest: {err}")))?
+        .ok_or_else(|| {
+            ComponentError::Doctor(format!(
+                "{} has no component.manifest.json next to it and no embedded manifest",
+                wasm_path.display()
+            ))
+        })?;
+    let manifest = serde_json::to_value(&verified.manifest)
+        .map_err(|err| ComponentError::Doctor(format!("failed to encode manifest: {err}")))?;
+    Ok((
+        wasm_path.display().to_string(),
+        DiffInput {
+            manifest,
+            i18n_keys: None,
+        },
+    ))
+}
+
+fn inspect_sections(args: &InspectArgs) -> Result<InspectResult, ComponentError> {
+    let target = args
+        .target
+        .as_ref()
+        .ok_or_else(|| ComponentError::Doctor("inspect target is required".to_string()))?;
+    let (source, input) = match args.manifest.as_deref() {
+        Some(path) => {
+            let path = strip_file_scheme(path);
+            let input = DiffInput::from_manifest_path(&path).map_err(ComponentError::Doctor)?;
+            (path.display().to_string(), input)
+        }
+        None => load_component_input(target)?,
+    };
+    let report = section_report(&source, &input, &args.sections);
+    let format = args.output_format().unwrap_or(InspectOutput::Json);
+    emit_structured(&report, format)?;
+    Ok(InspectResult::default())
+}
+
+/// Build the `--section` view of a manifest: identity plus only the requested sections.
+fn section_report(source: &str, input: &DiffInput, sections: &[InspectSection]) -> Value {
+    let manifest = &input.manifest;
+    let operations = manifest
+        .get("operations")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    let mut report = serde_json::Map::new();
+    report.insert("source".into(), Value::from(source));
+    for key in ["id", "version"] {
+        report.insert(
+            key.into(),
+            manifest.get(key).cloned().unwrap_or(Value::Null),
+        );
+    }
+    let mut sections = sections.to_vec();
+    sections.sort();
+    sections.dedup();
+    for section in sections {
+        match section {
+            InspectSection::Ops => {
+                let ops = operations
+                    .iter()
+                    .map(|op| {
+                        let mut op = op.clone();
+                        if let Some(fields) = op.as_object_mut() {
+                            fields.remove("input_schema");
+                            fields.remove("output_schema");
+                        }
+                        op
+                    })
+                    .collect::<Vec<_>>();
+                report.insert(
+                    "default_operation".into(),
+                    manifest_field(manifest, "default_operation"),
+                );
+                report.insert("operations".into(), Value::Array(ops));
+            }
+            InspectSection::Capabilities => {
+                report.insert(
+                    "capabilities".into(),
+                    manifest_field(manifest, "capabilities"),
+                );
+                report.insert(
+                    "secret_requirements".into(),
+                    manifest_field(manifest, "secret_requirements"),
+                );
+            }
+            InspectSection::Schemas => {
+                let ops = operations
+                    .iter()
+                    .filter_map(|op| {
+                        let name = op.get("name")?.as_str()?;
+                        Some((
+                            name.to_string(),
+                            serde_json::json!({
+                                "input": manifest_field(op, "input_schema"),
+                                "output": manifest_field(op, "output_schema"),
+                            }),
+                        ))
+                    })
+                    .collect::<serde_json::Map<_, _>>();
+                report.insert(
+                    "schemas".into(),
+                    serde_json::json!({
+                        "config": manifest_field(manifest, "config_schema"),
+                        "operations": ops,
+                    }),
+                );
+            }
+            InspectSection::I18n => {
+                report.insert(
+                    "i18n".into(),
+                    serde_json::json!({
+                        "source_locale": SOURCE_LOCALE,
+                        "keys": input.i18n_keys,
+                    }),
+                );
+            }
+        }
+    }
+    Value::Object(report)
+}
+
+fn manifest_field(value: &Value, key: &str) -> Value {
+    value.get(key).cloned().unwrap_or(Value::Null)
+}
+
+/// Node ops every scaffolded component exports for setup tooling; manifests do not list them.
+const NODE_CONTROL_OPERATIONS: [&str; 3] = ["qa-spec", "apply-answers", "i18n-keys"];
+
+fn inspect_live(args: &InspectArgs) -> Result<InspectResult, ComponentError> {
+    let target = args
+        .target
+        .as_ref()
+        .ok_or_else(|| ComponentError::Doctor("inspect target is required".to_string()))?;
+    let wasm_path = resolve_wasm_path(target).map_err(ComponentError::Doctor)?;
+    let manifest_path = args
+        .manifest
+        .as_deref()
+        .map(strip_file_scheme)
+        .or_else(|| discover_manifest_path(&wasm_path, Path::new(target)));
+    let mut warnings = Vec::new();
+
+    let manifest_operations = match manifest_path.as_ref() {
+        Some(path) => {
+            let raw = fs::read_to_string(path).map_err(|err| {
+                ComponentError::Doctor(format!("failed to read manifest {}: {err}", path.display()))
+            })?;
+            let manifest = parse_manifest(&raw).map_err(|err| {
+                ComponentError::Doctor(format!(
+                    "failed to parse manifest {}: {err}",
+                    path.display()
+                ))
+            })?;
+            let names = manifest
+                .operations
+                .iter()
+                .map(|op| op.name.clone())
+                .collect::<Vec<_>>();
+            Some(names)
+        }
+        None => None,
+    };
+
+    let mut report = match call_describe(&wasm_path) {
+        Ok(bytes) => {
+            let payload = strip_self_describe_tag(&bytes);
+            if let Err(err) = ensure_canonical_allow_floats(payload) {
+                warnings.push(format!("describe payload not canonical: {err}"));
+            }
+            let describe: ComponentDescribe = canonical::from_cbor(payload)
+                .map_err(|err| ComponentError::Doctor(format!("describe decode failed: {err}")))?;
+            LiveReport::from_describe(describe, wasm_path, args.verify, args.rust_types)?
+        }
+        Err(err) if err.contains("missing export interface component-descriptor") => {
+            let descriptor = call_export(&wasm_path, "node", "describe", &[])
+                .and_then(|val| NodeDescriptor::from_val(&val))
+                .map_err(|err| ComponentError::Doctor(format!("node describe failed: {err}")))?;
+            LiveReport::from_node(descriptor, wasm_path, args.rust_types, &mut warnings)
+        }
+        Err(err) => return Err(ComponentError::Doctor(err)),
+    };
+    report.manifest_path = manifest_path;
+    if let Some(names) = manifest_operations {
+        let cross = report.cross_reference(&names);
+        for id in &cross.missing_in_manifest {
+            warnings.push(format!(
+                "describe operation `{id}` is not declared in the manifest"
+            ));
+        }
+        for name in &cross.missing_in_describe {
+            warnings.push(format!(
+                "manifest operation `{name}` is not reported by describe()"
+            ));
+        }
+        report.manifest_operations = Some(cross);
+    }
+
+    if let Some(format) = args.output_format() {
+        emit_structured(&report, format)?;
+    } else {
+        emit_live_human(&report)?;
+    }
+
+    let verify_failed = report
+        .operations
+        .iter()
+        .any(|op| matches!(op.schema_hash_valid, Some(false)));
+    if verify_failed {
+        return Err(ComponentError::Doctor(
+            "schema_hash verification failed".to_string(),
+        ));
+    }
+
+    Ok(InspectResult { warnings })
+}
+
+fn inspect_qa_preview(args: &InspectArgs) -> Result<InspectResult, ComponentError> {
+    let target = args
+        .target
+        .as_ref()
+        .ok_or_else(|| ComponentError::Doctor("inspect target is required".to_string()))?;
+    let wasm_path = resolve_wasm_path(target).map_err(ComponentError::Doctor)?;
+    let manifest_path = args
+        .manifest
+        .as_deref()
+        .map(strip_file_scheme)
+        .or_else(|| discover_manifest_path(&wasm_path, Path::new(target)));
+    let manifest = match manifest_path.as_ref() {
+        Some(path) => {
+            let raw = fs::read_to_string(path).map_err(|err| {
+                ComponentError::Doctor(format!("failed to read manifest {}: {err}", path.display()))
+            })?;
+            Some(parse_manifest(&raw).map_err(|err| {
+                ComponentError::Doctor(format!(
+                    "failed to parse manifest {}: {err}",
+                    path.display()
+                ))
+            })?)
+        }
+        None => None,
+    };
+
+    let i18n_dir = manifest_path
+        .as_deref()
+        .and_then(Path::parent)
+        .unwrap_or_else(|| Path::new(target))
+        .join("assets")
+        .join("i18n");
+    let bundle = if i18n_dir.is_dir() {
+        i18n_check::load_locale_dir(&i18n_dir)
+            .map_err(|err| ComponentError::Doctor(err.to_string()))?
+    } else {
+        LocaleBundle::new()
+    };
+    let locales = if !args.qa_locales.is_empty() {
+        args.qa_locales.clone()
+    } else if bundle.is_empty() {
+        vec![SOURCE_LOCALE.to_string()]
+    } else {
+        bundle.keys().cloned().collect()
+    };
+    let modes = if args.qa_modes.is_empty() {
+        QaPreviewMode::ALL.to_vec()
+    } else {
+        args.qa_modes.clone()
+    };
+
+    let mut warnings = Vec::new();
+    for locale in &locales {
+        if !bundle.is_empty() && !bundle.contains_key(locale) {
+            warnings.push(format!(
+                "locale `{locale}` has no bundle in {}; showing {SOURCE_LOCALE} text",
+                i18n_dir.display()
+            ));
+        }
+    }
+
+    let mut source = QaSpecSource::Descriptor;
+    let mut previews = Vec::new();
+    for mode in modes {
+        let spec = source
+            .qa_spec(&wasm_path, manifest.as_ref(), mode)
+            .map_err(|err| {
+                ComponentError::Doctor(format!("qa-spec({}) failed: {err}", mode.as_str()))
+            })?;
+        for locale in &locales {
+            let messages = Translations {
+                locale: bundle.get(locale),
+                source: bundle.get(SOURCE_LOCALE),
+            };
+            previews.push(QaPreview::render(mode.as_str(), locale, &spec, &messages));
+        }
+    }
+
+    if let Some(format) = args.output_format() {
+        emit_structured(&previews, format)?;
+    } else {
+        for preview in &previews {
+            println!("== {} ({}) ==", preview.mode, preview.locale);
+            print!("{}", preview.text);
+            let card = serde_json::to_string_pretty(&preview.card)
+                .map_err(|err| ComponentError::Doctor(format!("failed to encode card: {err}")))?;
+            println!("\ncard:");
+            for line in card.lines() {
+                println!("  {line}");
+            }
+            println!();
+        }
+    }
+
+    Ok(InspectResult { warnings })
+}
+
+/// Where qa-spec comes from: the 0.6 `component-qa` export, or the node world's `qa-spec`
+/// control operation when that export is missing.
+enum QaSpecSource {
+    Descriptor,
+    Node(Box<TestHarness>),
+}
+
+impl QaSpecSource {
+    fn qa_spec(
+        &mut self,
+        wasm_path: &Path,
+        manifest: Option<&ComponentManifest>,
+        mode: QaPreviewMode,
+    ) -> Result<Value, String> {
+        let payload = serde_json::json!({ "mode": mode.as_str() });
+        if let QaSpecSource::Node(harness) = self {
+            return super::doctor::invoke_json(harness, "qa-spec", &payload);
+        }
+        match call_export(
+            wasm_path,
+            "component-qa",
+            "qa-spec",
+            &[Val::Enum(mode.as_str().to_string())],
+        ) {
+            Ok(val) => {
+                let bytes = val_to_bytes(&val)?;
+                let spec: ComponentQaSpec = canonical::from_cbor(strip_self_describe_tag(&bytes))
+                    .map_err(|err| format!("decode failed: {err}"))?;
+                serde_json::to_value(spec).map_err(|err| format!("failed to encode spec: {err}"))
+            }
+            Err(err) if err.contains("missing export interface component-qa") => {
+                let manifest = manifest.ok_or_else(|| {
+                    "node components need component.manifest.json (pass --manifest)".to_string()
+                })?;
+                let harness = super::doctor::new_doctor_harness(wasm_path, manifest)?;
+                let spec = super::doctor::invoke_json(&harness, "qa-spec", &payload);
+                *self = QaSpecSource::Node(Box::new(harness));
+                spec
+            }
+            Err(err) => Err(err),
+        }
+    }
+}
+
+fn emit_live_human(report: &LiveReport) -> Result<(), ComponentError> {
+    println!("wasm: {}", report.wasm_path.display());
+    if let Some(path) = &report.manifest_path {
+        println!("manifest: {}", path.display());
+    }
+    println!("component: {}", report.info.id);
+    println!("  version: {}", report.info.version);
+    if let Some(role) = &report.info.role {
+        println!("  role: {role}");
+    }
+    println!("  source: {}", report.source);
+    println!("  operations: {}", report.operations.len());
+    for op in &report.operations {
+        let declared = match op.in_manifest {
+            Some(true) => " [in manifest]",
+            Some(false) => " [not in manifest]",
+            None => "",
+        };
+        match &op.schema_hash {
+            Some(hash) => println!("  - {} ({hash}){declared}", op.id),
+            None => println!("  - {}{declared}", op.id),
+        }
+        if let Some(summary) = &op.summary {
+            println!("    summary: {summary}");
+        }
+        if let Some(status) = op.schema_hash_valid {
+            println!("    schema_hash ok: {status}");
+        }
+        println!("    input:");
+        print_live_schema(&op.input, "      ")?;
+        println!("    output:");
+        print_live_schema(&op.output, "      ")?;
+    }
+    if let Some(config) = &report.config_schema {
+        println!("  config:");
+        print_live_schema(config, "    ")?;
+    }
+    if let Some(cross) = &report.manifest_operations {
+        println!("manifest operations:");
+        println!("  matched: {}", display_names(&cross.matched));
+        println!(
+            "  missing in manifest: {}",
+            display_names(&cross.missing_in_manifest)
+        );
+        println!(
+            "  missing in describe: {}",
+            display_names(&cross.missing_in_describe)
+        );
+    }
+    Ok(())
+}
+
+fn print_live_schema(schema: &LiveSchema, indent: &str) -> Result<(), ComponentError> {
+    let json = serde_json::to_string_pretty(&schema.schema)
+        .map_err(|err| ComponentError::Doctor(format!("failed to encode json: {err}")))?;
+    for line in json.lines() {
+        println!("{indent}{line}");
+    }
+    if let Some(rust) = &schema.rust {
+        println!("{indent}rust:");
+        for line in rust.lines() {
+            println!("{indent}  {line}");
+        }
+    }
+    Ok(())
+}
+
+fn display_names(names: &[String]) -> String {
+    if names.is_empty() {
+        "<none>".to_string()
+    } else {
+        names.join(", ")
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct LiveReport {
+    wasm_path: PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    manifest_path: Option<PathBuf>,
+    /// Export the descriptor came from: `component-descriptor` or `node`.
+    source: &'static str,
+    info: LiveComponentInfo,
+    operations: Vec<LiveOperation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    config_schema: Option<LiveSchema>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    manifest_operations: Option<OperationCrossReference>,
+}
+
+#[derive(Debug, Serialize)]
+struct LiveComponentInfo {
+    id: String,
+    version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    role: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct LiveOperation {
+    id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    schema_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    schema_hash_valid: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    in_manifest: Option<bool>,
+    input: LiveSchema,
+    output: LiveSchema,
+}
+
+#[derive(Debug, Serialize)]
+struct LiveSchema {
+    /// JSON Schema-like rendering of the SchemaIR.
+    schema: Value,
+    /// Rust type sketch, with `--rust-types`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rust: Option<String>,
+}
+
+impl LiveSchema {
+    fn new(name: &str, schema: &SchemaIr, rust_types: bool) -> Self {
+        Self {
+            schema: inspect_schema::to_json_schema(schema),
+            rust: rust_types.then(|| inspect_schema::rust_sketch(name, schema)),
+        }
+    }
+
+    fn from_node(
+        name: &str,
+        schema: &NodeSchema,
+        rust_types: bool,
+        warnings: &mut Vec<String>,
+    ) -> Self {
+        match schema {
+            NodeSchema::InlineCbor(bytes) => match inspect_schema::decode_inline_cbor(bytes) {
+                Ok(schema) => Self::new(name, &schema, rust_types),
+                Err(err) => {
+                    warnings.push(format!("{name} schema: {err}"));
+                    Self {
+                        schema: Value::Null,
+                        rust: None,
+                    }
+                }
+            },
+            NodeSchema::Reference { kind, target } => Self {
+                schema: serde_json::json!({ "$ref": target, "source": kind }),
+                rust: None,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+struct OperationCrossReference {
+    matched: Vec<String>,
+    missing_in_manifest: Vec<String>,
+    missing_in_describe: Vec<String>,
+}
+
+impl OperationCrossReference {
+    fn new(describe_ids: &[String], manifest_names: &[String]) -> Self {
+        let mut cross = Self::default();
+        for id in describe_ids {
+            if manifest_names.contains(id) {
+                cross.matched.push(id.clone());
+            } else {
+                cross.missing_in_manifest.push(id.clone());
+            }
+        }
+        cross.missing_in_describe = manifest_names
+            .iter()
+            .filter(|name| !describe_ids.contains(name))
+            .cloned()
+            .collect();
+        cross
+    }
+}
+
+impl LiveReport {
+    fn from_describe(
+        describe: ComponentDescribe,
+        wasm_path: PathBuf,
+        verify: bool,
+        rust_types: bool,
+    ) -> Result<Self, ComponentError> {
+        let mut operations = Vec::new();
+        for op in &describe.operations {
+            let schema_hash_valid = if verify {
+                let expected =
+                    schema_hash(&op.input.schema, &op.output.schema, &describe.config_schema)
+                        .map_err(|err| {
+                            ComponentError::Doctor(format!("schema_hash failed: {err}"))
+                        })?;
+                Some(expected == op.schema_hash)
+            } else {
+                None
+            };
+            operations.push(LiveOperation {
+                id: op.id.clone(),
+                summary: None,
+                schema_hash: Some(op.schema_hash.clone()),
+                schema_hash_valid,
+                in_manifest: None,
+                input: LiveSchema::new(&format!("{} input", op.id), &op.input.schema, rust_types),
+                output: LiveSchema::new(
+                    &format!("{} output", op.id),
+                    &op.output.schema,
+                    rust_types,
+                ),
+            });
+        }
+        Ok(Self {
+            wasm_path,
+            manifest_path: None,
+            source: "component-descriptor",
+            config_schema: Some(LiveSchema::new(
+                "config",
+                &describe.config_schema,
+                rust_types,
+            )),
+            info: LiveComponentInfo {
+                id: describe.info.id,
+                version: describe.info.version,
+                role: Some(describe.info.role),
+            },
+            operations,
+            manifest_operations: None,
+        })
+    }
+
+    fn from_node(
+        descriptor: NodeDescriptor,
+        wasm_path: PathBuf,
+        rust_types: bool,
+        warnings: &mut Vec<String>,
+    ) -> Self {
+        let operations = descriptor
+            .ops
+            .iter()
+            .map(|op| LiveOperation {
+                id: op.name.clone(),
+                summary: op.summary.clone(),
+                schema_hash: None,
+                schema_hash_valid: None,
+                in_manifest: None,
+                input: LiveSchema::from_node(
+                    &format!("{} input", op.name),
+                    &op.input,
+                    rust_types,
+                    warnings,
+                ),
+                output: LiveSchema::from_node(
+                    &format!("{} output", op.name),
+                    &op.output,
+                    rust_types,
+                    warnings,
+                ),
+            })
+            .collect();
+        Self {
+            wasm_path,
+            manifest_path: None,
+            source: "node",
+            info: LiveComponentInfo {
+                id: descriptor.name,
+                version: descriptor.version,
+                role: None,
+            },
+            operations,
+            config_schema: None,
+            manifest_operations: None,
+        }
+    }
+
+    /// Compare operation ids with the manifest's `operations[].name`, marking each operation.
+    /// Node control ops are not expected in the manifest and are left out.
+    fn cross_reference(&mut self, manifest_names: &[String]) -> OperationCrossReference {
+        let is_control = |id: &str| self.source == "node" && NODE_CONTROL_OPERATIONS.contains(&id);
+        let describe_ids = self
+            .operations
+            .iter()
+            .map(|op| op.id.clone())
+            .filter(|id| !is_control(id.as_str()))
+            .collect::<Vec<_>>();
+        let cross = OperationCrossReference::new(&describe_ids, manifest_names);
+        for op in &mut self.operations {
+            if describe_ids.contains(&op.id) {
+                op.in_manifest = Some(manifest_names.contains(&op.id));
+            }
+        }
+        cross
+    }
+}
+
+/// The parts of a node-world `component-descriptor` record that `--live` reports.
+struct NodeDescriptor {
+    name: String,
+    version: String,
+    ops: Vec<NodeOp>,
+}
+
+struct NodeOp {
+    name: String,
+    summary: Option<String>,
+    input: NodeSchema,
+    output: NodeSchema,
+}
+
+enum NodeSchema {
+    InlineCbor(Vec<u8>),
+    /// Any other `schema-source` case, e.g. a schema id or pack path.
+    Reference {
+        kind: String,
+        target: String,
+    },
+}
+
+impl NodeDescriptor {
+    fn from_val(val: &Val) -> Result<Self, String> {
+        let ops = match record_field(val, "ops")? {
+            Val::List(ops) => ops
+                .iter()
+                .map(NodeOp::from_val)
+                .collect::<Result<Vec<_>, _>>()?,
+            _ => return Err("field `ops` is not a list".to_string()),
+        };
+        Ok(Self {
+            name: string_field(val, "name")?,
+            version: string_field(val, "version")?,
+            ops,
+        })
+    }
+}
+
+impl NodeOp {
+    fn from_val(val: &Val) -> Result<Self, String> {
+        let name = string_field(val, "name")?;
+        let summary = match record_field(val, "summary")? {
+            Val::Option(Some(summary)) => match &**summary {
+                Val::String(summary) => Some(summary.clone()),
+                _ => None,
+            },
+            _ => None,
+        };
+        let input = NodeSchema::from_io(record_field(val, "input")?)
+            .map_err(|err| format!("op `{name}` input: {err}"))?;
+        let output = NodeSchema::from_io(record_field(val, "output")?)
+            .map_err(|err| format!("op `{name}` output: {err}"))?;
+        Ok(Self {
+            name,
+            summary,
+            input,
+            output,
+        })
+    }
+}
+
+impl NodeSchema {
+    fn from_io(io: &Val) -> Result<Self, String> {
+        match record_field(io, "schema")? {
+            Val::Variant(case, Some(payload)) if case == "inline-cbor" => {
+                Ok(NodeSchema::InlineCbor(val_to_bytes(payload)?))
+            }
+            Val::Variant(case, payload) => Ok(NodeSchema::Reference {
+                kind: case.clone(),
+                target: match payload.as_deref() {
+                    Some(Val::String(target)) => target.clone(),
+                    _ => String::new(),
+                },
+            }),
+            _ => Err("field `schema` is not a schema-source".to_string()),
+        }
+    }
+}
+
+fn record_field<'a>(val: &'a Val, name: &str) -> Result<&'a Val, String> {
+    match val {
+        Val::Record(fields) => fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| value)
+            .ok_or_else(|| format!("missing field `{name}`")),
+        _ => Err(format!("expected a record with field `{name}`")),
+    }
+}
+
+fn string_field(val: &Val, name: &str) -> Result<String, String> {
+    match record_field(val, name)? {
+        Val::String(value) => Ok(value.clone()),
+        _ => Err(format!("field `{name}` is not a string")),
+    }
+}
+
 #[derive(Debug, Serialize)]
 struct ComponentInfoSummary {
     id: String,
@@ -850,6 +1858,16 @@ fn collect_wasm_files(dir: &Path, out: &mut Vec<PathBuf>) -> Result<(), String>
 }
 
 fn call_describe(wasm_path: &Path) -> Result<Vec<u8>, String> {
+    let val = call_export(wasm_path, "component-descriptor", "describe", &[])?;
+    val_to_bytes(&val)
+}
+
+fn call_export(
+    wasm_path: &Path,
+    interface: &str,
+    func_name: &str,
+    params: &[Val],
+) -> Result<Val, String> {
     let mut config = wasmtime::Config::new();
     config.wasm_component_model(true);
     let engine = Engine::new(&config).map_err(|err| format!("engine init failed: {err}"))?;
@@ -862,21 +1880,21 @@ fn call_describe(wasm_path: &Path) -> Result<Vec<u8>, String> {
     let instance = linker
         .instantiate(&mut store, &component)
         .map_err(|err| format!("failed to instantiate: {err}"))?;
-    let instance_index = resolve_interface_index(&instance, &mut store, "component-descriptor")
-        .ok_or_else(|| "missing export interface component-descriptor".to_string())?;
+    let instance_index = resolve_interface_index(&instance, &mut store, interface)
+        .ok_or_else(|| format!("missing export interface {interface}"))?;
     let func_index = instance
-        .get_export_index(&mut store, Some(&instance_index), "describe")
-        .ok_or_else(|| "missing export component-descriptor.describe".to_string())?;
+        .get_export_index(&mut store, Some(&instance_index), func_name)
+        .ok_or_else(|| format!("missing export {interface}.{func_name}"))?;
     let func = instance
         .get_func(&mut store, func_index)
-        .ok_or_else(|| "describe export is not callable".to_string())?;
+        .ok_or_else(|| format!("{func_name} export is not callable"))?;
     let mut results = vec![Val::Bool(false); func.ty(&mut store).results().len()];
-    func.call(&mut store, &[], &mut results)
-        .map_err(|err| format!("describe call failed: {err}"))?;
-    let val = results
-        .first()
-        .ok_or_else(|| "describe returned no value".to_string())?;
-    val_to_bytes(val)
+    func.call(&mut store, params, &mut results)
+        .map_err(|err| format!("{func_name} call failed: {err}"))?;
+    results
+        .into_iter()
+        .next()
+        .ok_or_else(|| format!("{func_name} returned no value"))
 }
 
 fn resolve_interface_index(
@@ -957,3 +1975,22 @@ impl WasiView for InspectWasi {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cross_reference_splits_describe_and_manifest_operations() {
+        let describe = vec!["handle_message".to_string(), "render".to_string()];
+        let manifest = vec!["handle_message".to_string(), "health".to_string()];
+        assert_eq!(
+            OperationCrossReference::new(&describe, &manifest),
+            OperationCrossReference {
+                matched: vec!["handle_message".to_string()],
+                missing_in_manifest: vec!["render".to_string()],
+                missing_in_describe: vec!["health".to_string()],
+            }
+        );
+    }
+}
@@ -0,0 +1,662 @@
+//! `inspect diff`: compare two versions of a component for upgrade reviews.
+//!
+//! Both sides are reduced to their manifest JSON plus the source-locale i18n keys, then compared
+//! section by section. A change is marked breaking when existing callers, hosts, or translations
+//! may stop working without changes on their side: removed operations, input fields that became
+//! required, output fields that went away, type changes, new or widened capabilities, raised
+//! limits, and removed i18n keys.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+
+use crate::i18n_check::{self, SOURCE_LOCALE};
+
+/// One side of a diff.
+#[derive(Debug, Clone)]
+pub struct DiffInput {
+    pub manifest: JsonValue,
+    /// Keys of `assets/i18n/en.json`; `None` when the component has no locale bundle.
+    pub i18n_keys: Option<BTreeSet<String>>,
+}
+
+impl DiffInput {
+    /// Read a `component.manifest.json` and the locale bundle next to it.
+    pub fn from_manifest_path(path: &Path) -> Result<Self, String> {
+        let raw = fs::read_to_string(path)
+            .map_err(|err| format!("failed to read manifest {}: {err}", path.display()))?;
+        let manifest = serde_json::from_str(&raw)
+            .map_err(|err| format!("failed to parse manifest {}: {err}", path.display()))?;
+        let i18n_keys = match path.parent() {
+            Some(root) => source_locale_keys(&root.join("assets").join("i18n"))?,
+            None => None,
+        };
+        Ok(Self {
+            manifest,
+            i18n_keys,
+        })
+    }
+}
+
+fn source_locale_keys(dir: &Path) -> Result<Option<BTreeSet<String>>, String> {
+    if !dir.is_dir() {
+        return Ok(None);
+    }
+    let bundle = i18n_check::load_locale_dir(dir).map_err(|err| err.to_string())?;
+    Ok(bundle
+        .get(SOURCE_LOCALE)
+        .map(|messages| messages.keys().cloned().collect()))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffSection {
+    Operations,
+    Schemas,
+    Capabilities,
+    Limits,
+    I18n,
+}
+
+impl DiffSection {
+    fn label(self) -> &'static str {
+        match self {
+            DiffSection::Operations => "operations",
+            DiffSection::Schemas => "schemas",
+            DiffSection::Capabilities => "capabilities",
+            DiffSection::Limits => "limits",
+            DiffSection::I18n => "i18n",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DiffChange {
+    pub section: DiffSection,
+    pub kind: ChangeKind,
+    pub breaking: bool,
+    /// Dotted location, e.g. `operations.render.input.title`.
+    pub path: String,
+    pub message: String,
+}
+
+impl DiffChange {
+    fn group(&self) -> &'static str {
+        match self.kind {
+            _ if self.breaking => "breaking",
+            ChangeKind::Added => "added",
+            ChangeKind::Removed => "removed",
+            ChangeKind::Changed => "changed",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ComponentDiff {
+    pub old: String,
+    pub new: String,
+    pub summary: DiffSummary,
+    pub changes: Vec<DiffChange>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct DiffSummary {
+    pub added: usize,
+    pub removed: usize,
+    pub changed: usize,
+    pub breaking: usize,
+}
+
+impl ComponentDiff {
+    pub fn new(old_label: String, old: &DiffInput, new_label: String, new: &DiffInput) -> Self {
+        let mut changes = Changes::default();
+        changes.operations(&old.manifest, &new.manifest);
+        changes.capabilities(&old.manifest, &new.manifest);
+        changes.limits(&old.manifest, &new.manifest);
+        if let (Some(old_keys), Some(new_keys)) = (&old.i18n_keys, &new.i18n_keys) {
+            changes.i18n(old_keys, new_keys);
+        }
+        let changes = changes.0;
+        let mut summary = DiffSummary::default();
+        for change in &changes {
+            match change.kind {
+                ChangeKind::Added => summary.added += 1,
+                ChangeKind::Removed => summary.removed += 1,
+                ChangeKind::Changed => summary.changed += 1,
+            }
+            if change.breaking {
+                summary.breaking += 1;
+            }
+        }
+        Self {
+            old: old_label,
+            new: new_label,
+            summary,
+            changes,
+        }
+    }
+
+    /// Breaking changes first, then the rest grouped by kind.
+    pub fn render_text(&self) -> String {
+        let mut out = format!("diff: {} -> {}\n", self.old, self.new);
+        if self.changes.is_empty() {
+            out.push_str("no differences\n");
+            return out;
+        }
+        for title in ["breaking", "added", "removed", "changed"] {
+            let group = self
+                .changes
+                .iter()
+                .filter(|change| change.group() == title)
+                .collect::<Vec<_>>();
+            if group.is_empty() {
+                continue;
+            }
+            out.push_str(&format!("{title} ({}):\n", group.len()));
+            for change in group {
+                out.push_str(&format!(
+                    "  - [{}] {}: {}\n",
+                    change.section.label(),
+                    change.path,
+                    change.message
+                ));
+            }
+        }
+        out
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Input,
+    Output,
+}
+
+#[derive(Default)]
+struct Changes(Vec<DiffChange>);
+
+impl Changes {
+    fn push(
+        &mut self,
+        section: DiffSection,
+        kind: ChangeKind,
+        breaking: bool,
+        path: &str,
+        message: String,
+    ) {
+        self.0.push(DiffChange {
+            section,
+            kind,
+            breaking,
+            path: path.to_string(),
+            message,
+        });
+    }
+
+    fn operations(&mut self, old: &JsonValue, new: &JsonValue) {
+        let old_ops = operations_by_name(old);
+        let new_ops = operations_by_name(new);
+        for (name, old_op) in &old_ops {
+            let path = format!("operations.{name}");
+            match new_ops.get(name) {
+                None => self.push(
+                    DiffSection::Operations,
+                    ChangeKind::Removed,
+                    true,
+                    &path,
+                    format!("operation `{name}` was removed"),
+                ),
+                Some(new_op) => {
+                    for (field, direction) in [
+                        ("input_schema", Direction::Input),
+                        ("output_schema", Direction::Output),
+                    ] {
+                        self.schema(
+                            &format!("{path}.{}", direction.label()),
+                            old_op.get(field).unwrap_or(&JsonValue::Null),
+                            new_op.get(field).unwrap_or(&JsonValue::Null),
+                            direction,
+                        );
+                    }
+                }
+            }
+        }
+        for name in new_ops.keys().filter(|name| !old_ops.contains_key(*name)) {
+            self.push(
+                DiffSection::Operations,
+                ChangeKind::Added,
+                false,
+                &format!("operations.{name}"),
+                format!("operation `{name}` was added"),
+            );
+        }
+        let old_default = old.get("default_operation");
+        let new_default = new.get("default_operation");
+        if old_default != new_default {
+            self.push(
+                DiffSection::Operations,
+                ChangeKind::Changed,
+                old_default.is_some(),
+                "default_operation",
+                format!(
+                    "default operation changed from {} to {}",
+                    display(old_default),
+                    display(new_default)
+                ),
+            );
+        }
+    }
+
+    fn schema(&mut self, path: &str, old: &JsonValue, new: &JsonValue, direction: Direction) {
+        if old == new {
+            return;
+        }
+        let old_type = old.get("type");
+        let new_type = new.get("type");
+        if old_type != new_type {
+            self.push(
+                DiffSection::Schemas,
+                ChangeKind::Changed,
+                true,
+                path,
+                format!(
+                    "type changed from {} to {}",
+                    display(old_type),
+                    display(new_type)
+                ),
+            );
+            return;
+        }
+
+        let old_props = properties(old);
+        let new_props = properties(new);
+        let old_required = required(old);
+        let new_required = required(new);
+        for (name, old_prop) in &old_props {
+            let prop_path = format!("{path}.{name}");
+            match new_props.get(name) {
+                None => self.push(
+                    DiffSection::Schemas,
+                    ChangeKind::Removed,
+                    true,
+                    &prop_path,
+                    format!("{} field `{name}` was removed", direction.label()),
+                ),
+                Some(new_prop) => self.schema(&prop_path, old_prop, new_prop, direction),
+            }
+        }
+        for name in new_props
+            .keys()
+            .filter(|name| !old_props.contains_key(*name))
+        {
+            let is_required = new_required.contains(name);
+            self.push(
+                DiffSection::Schemas,
+                ChangeKind::Added,
+                direction == Direction::Input && is_required,
+                &format!("{path}.{name}"),
+                format!(
+                    "{} {} field `{name}` was added",
+                    if is_required { "required" } else { "optional" },
+                    direction.label()
+                ),
+            );
+        }
+        for name in new_required.difference(&old_required) {
+            if old_props.contains_key(name) && new_props.contains_key(name) {
+                self.push(
+                    DiffSection::Schemas,
+                    ChangeKind::Changed,
+                    direction == Direction::Input,
+                    &format!("{path}.{name}"),
+                    format!("{} field `{name}` is now required", direction.label()),
+                );
+            }
+        }
+        for name in old_required.difference(&new_required) {
+            if old_props.contains_key(name) && new_props.contains_key(name) {
+                self.push(
+                    DiffSection::Schemas,
+                    ChangeKind::Changed,
+                    direction == Direction::Output,
+                    &format!("{path}.{name}"),
+                    format!("{} field `{name}` is now optional", direction.label()),
+                );
+            }
+        }
+        if let (Some(old_items), Some(new_items)) = (old.get("items"), new.get("items")) {
+            self.schema(&format!("{path}[]"), old_items, new_items, direction);
+        }
+
+        let keys = object_keys(old)
+            .chain(object_keys(new))
+            .filter(|key| !matches!(*key, "type" | "properties" | "required" | "items"))
+            .collect::<BTreeSet<_>>();
+        for key in keys {
+            let old_value = old.get(key);
+            let new_value = new.get(key);
+            if old_value != new_value {
+                self.push(
+                    DiffSection::Schemas,
+                    ChangeKind::Changed,
+                    false,
+                    path,
+                    format!(
+                        "`{key}` changed from {} to {}",
+                        display(old_value),
+                        display(new_value)
+                    ),
+                );
+            }
+        }
+    }
+
+    fn capabilities(&mut self, old: &JsonValue, new: &JsonValue) {
+        let old_caps = leaves(old.get("capabilities"), "capabilities");
+        let new_caps = leaves(new.get("capabilities"), "capabilities");
+        for (path, old_value) in &old_caps {
+            match new_caps.get(path) {
+                None => self.push(
+                    DiffSection::Capabilities,
+                    ChangeKind::Removed,
+                    false,
+                    path,
+                    format!("no longer requests {old_value}"),
+                ),
+                Some(new_value) if new_value != old_value => self.push(
+                    DiffSection::Capabilities,
+                    ChangeKind::Changed,
+                    !is_disabled(new_value),
+                    path,
+                    format!("changed from {old_value} to {new_value}"),
+                ),
+                Some(_) => {}
+            }
+        }
+        for (path, new_value) in &new_caps {
+            if !old_caps.contains_key(path) {
+                self.push(
+                    DiffSection::Capabilities,
+                    ChangeKind::Added,
+                    !is_disabled(new_value),
+                    path,
+                    format!("now requests {new_value}"),
+                );
+            }
+        }
+    }
+
+    fn limits(&mut self, old: &JsonValue, new: &JsonValue) {
+        let old_limits = leaves(old.get("limits"), "limits");
+        let new_limits = leaves(new.get("limits"), "limits");
+        for (path, old_value) in &old_limits {
+            match new_limits.get(path) {
+                None => self.push(
+                    DiffSection::Limits,
+                    ChangeKind::Removed,
+                    false,
+                    path,
+                    format!("limit {old_value} was removed"),
+                ),
+                Some(new_value) if new_value != old_value => {
+                    let raised = matches!(
+                        (old_value.as_f64(), new_value.as_f64()),
+                        (Some(old), Some(new)) if new > old
+                    );
+                    self.push(
+                        DiffSection::Limits,
+                        ChangeKind::Changed,
+                        raised,
+                        path,
+                        format!(
+                            "{} from {old_value} to {new_value}",
+                            if raised { "raised" } else { "changed" }
+                        ),
+                    );
+                }
+                Some(_) => {}
+            }
+        }
+        for (path, new_value) in &new_limits {
+            if !old_limits.contains_key(path) {
+                self.push(
+                    DiffSection::Limits,
+                    ChangeKind::Added,
+                    false,
+                    path,
+                    format!("limit set to {new_value}"),
+                );
+            }
+        }
+    }
+
+    fn i18n(&mut self, old: &BTreeSet<String>, new: &BTreeSet<String>) {
+        for key in old.difference(new) {
+            self.push(
+                DiffSection::I18n,
+                ChangeKind::Removed,
+                true,
+                &format!("i18n.{key}"),
+                format!("key `{key}` was removed from {SOURCE_LOCALE}.json"),
+            );
+        }
+        for key in new.difference(old) {
+            self.push(
+                DiffSection::I18n,
+                ChangeKind::Added,
+                false,
+                &format!("i18n.{key}"),
+                format!("key `{key}` was added to {SOURCE_LOCALE}.json"),
+            );
+        }
+    }
+}
+
+impl Direction {
+    fn label(self) -> &'static str {
+        match self {
+            Direction::Input => "input",
+            Direction::Output => "output",
+        }
+    }
+}
+
+fn operations_by_name(manifest: &JsonValue) -> BTreeMap<String, &JsonValue> {
+    manifest
+        .get("operations")
+        .and_then(JsonValue::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|op| {
+            let name = op.get("name").or_else(|| op.get("id"))?.as_str()?;
+            Some((name.to_string(), op))
+        })
+        .collect()
+}
+
+fn properties(schema: &JsonValue) -> BTreeMap<String, &JsonValue> {
+    schema
+        .get("properties")
+        .and_then(JsonValue::as_object)
+        .into_iter()
+        .flatten()
+        .map(|(name, schema)| (name.clone(), schema))
+        .collect()
+}
+
+fn required(schema: &JsonValue) -> BTreeSet<String> {
+    schema
+        .get("required")
+        .and_then(JsonValue::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|name| name.as_str().map(str::to_string))
+        .collect()
+}
+
+fn object_keys(value: &JsonValue) -> impl Iterator<Item = &str> {
+    value
+        .as_object()
+        .into_iter()
+        .flat_map(|object| object.keys().map(String::as_str))
+}
+
+/// Scalar and array leaves of a JSON object, keyed by dotted path.
+fn leaves(value: Option<&JsonValue>, prefix: &str) -> BTreeMap<String, JsonValue> {
+    let mut out = BTreeMap::new();
+    if let Some(value) = value {
+        collect_leaves(value, prefix, &mut out);
+    }
+    out
+}
+
+fn collect_leaves(value: &JsonValue, path: &str, out: &mut BTreeMap<String, JsonValue>) {
+    match value {
+        JsonValue::Object(object) => {
+            for (key, value) in object {
+                collect_leaves(value, &format!("{path}.{key}"), out);
+            }
+        }
+        JsonValue::Null => {}
+        other => {
+            out.insert(path.to_string(), other.clone());
+        }
+    }
+}
+
+fn is_disabled(value: &JsonValue) -> bool {
+    matches!(value, JsonValue::Bool(false))
+        || value.as_str() == Some("none")
+        || value.as_array().is_some_and(Vec::is_empty)
+}
+
+fn display(value: Option<&JsonValue>) -> String {
+    value
+        .map(JsonValue::to_string)
+        .unwrap_or_else(|| "<none>".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn input(manifest: JsonValue, keys: &[&str]) -> DiffInput {
+        DiffInput {
+            manifest,
+            i18n_keys: Some(keys.iter().map(|key| key.to_string()).collect()),
+        }
+    }
+
+    #[test]
+    fn classifies_operation_schema_capability_limit_and_i18n_changes() {
+        let old = input(
+            json!({
+                "operations": [
+                    {
+                        "name": "render",
+                        "input_schema": {
+                            "type": "object",
+                            "properties": { "title": { "type": "string" } },
+                            "required": []
+                        },
+                        "output_schema": {
+                            "type": "object",
+                            "properties": { "html": { "type": "string" }, "size": { "type": "integer" } },
+                            "required": ["html"]
+                        }
+                    },
+                    { "name": "health", "input_schema": {}, "output_schema": {} }
+                ],
+                "capabilities": { "wasi": { "random": true }, "host": { "http": { "client": false } } },
+                "limits": { "memory_mb": 64, "wall_time_ms": 1000 }
+            }),
+            &["render.title", "render.legacy"],
+        );
+        let new = input(
+            json!({
+                "operations": [
+                    {
+                        "name": "render",
+                        "input_schema": {
+                            "type": "object",
+                            "properties": {
+                                "title": { "type": "string", "maxLength": 80 },
+                                "locale": { "type": "string" }
+                            },
+                            "required": ["title", "locale"]
+                        },
+                        "output_schema": {
+                            "type": "object",
+                            "properties": { "html": { "type": "string" } },
+                            "required": ["html"]
+                        }
+                    },
+                    { "name": "preview", "input_schema": {}, "output_schema": {} }
+                ],
+                "capabilities": { "wasi": { "random": true }, "host": { "http": { "client": true } } },
+                "limits": { "memory_mb": 128, "wall_time_ms": 500 }
+            }),
+            &["render.title", "render.locale"],
+        );
+
+        let diff = ComponentDiff::new("old".into(), &old, "new".into(), &new);
+        let found = diff
+            .changes
+            .iter()
+            .map(|change| (change.path.as_str(), change.kind, change.breaking))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            found,
+            vec![
+                ("operations.health", ChangeKind::Removed, true),
+                ("operations.render.input.title", ChangeKind::Changed, false),
+                ("operations.render.input.locale", ChangeKind::Added, true),
+                ("operations.render.input.title", ChangeKind::Changed, true),
+                ("operations.render.output.size", ChangeKind::Removed, true),
+                ("operations.preview", ChangeKind::Added, false),
+                ("capabilities.host.http.client", ChangeKind::Changed, true),
+                ("limits.memory_mb", ChangeKind::Changed, true),
+                ("limits.wall_time_ms", ChangeKind::Changed, false),
+                ("i18n.render.legacy", ChangeKind::Removed, true),
+                ("i18n.render.locale", ChangeKind::Added, false),
+            ]
+        );
+        assert_eq!(
+            diff.summary,
+            DiffSummary {
+                added: 3,
+                removed: 3,
+                changed: 5,
+                breaking: 7,
+            }
+        );
+
+        let text = diff.render_text();
+        assert!(text.starts_with("diff: old -> new\nbreaking (7):\n"));
+        assert!(text.contains(
+            "  - [schemas] operations.render.input.title: input field `title` is now required\n"
+        ));
+        assert!(text.contains("added (2):\n"));
+        assert!(text.contains("changed (2):\n"));
+    }
+
+    #[test]
+    fn identical_inputs_have_no_differences() {
+        let side = input(json!({ "operations": [{ "name": "run" }] }), &["a"]);
+        let diff = ComponentDiff::new("a".into(), &side, "b".into(), &side);
+        assert!(diff.changes.is_empty());
+        assert_eq!(diff.render_text(), "diff: a -> b\nno differences\n");
+    }
+}
@@ -0,0 +1,299 @@
+//! `inspect --qa-preview`: show what the setup wizard will ask, without deploying.
+//!
+//! A QA spec is rendered twice per mode and locale: as plain text, the way a terminal wizard
+//! walks through it, and as an Adaptive Card, the way chat surfaces present it. Both work on
+//! the spec's JSON form, so every question kind renders even when this crate does not know it.
+
+use serde::Serialize;
+use serde_json::{Value as JsonValue, json};
+
+use crate::i18n_check::LocaleMessages;
+
+const ADAPTIVE_CARD_SCHEMA: &str = "http://adaptivecards.io/schemas/adaptive-card.json";
+const ADAPTIVE_CARD_VERSION: &str = "1.5";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct QaPreview {
+    pub mode: String,
+    pub locale: String,
+    pub text: String,
+    pub card: JsonValue,
+}
+
+impl QaPreview {
+    pub fn render(mode: &str, locale: &str, spec: &JsonValue, messages: &Translations) -> Self {
+        Self {
+            mode: mode.to_string(),
+            locale: locale.to_string(),
+            text: render_text(spec, messages),
+            card: render_card(spec, messages),
+        }
+    }
+}
+
+/// Resolves `I18nText` values for one locale, falling back to the source locale, then to the
+/// text's own fallback, then to the key itself so untranslated strings stay visible.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Translations<'a> {
+    pub locale: Option<&'a LocaleMessages>,
+    pub source: Option<&'a LocaleMessages>,
+}
+
+impl Translations<'_> {
+    fn resolve(&self, text: Option<&JsonValue>) -> Option<String> {
+        let text = text.filter(|text| !text.is_null())?;
+        if let Some(literal) = text.as_str() {
+            return Some(literal.to_string());
+        }
+        let key = text.get("key").and_then(JsonValue::as_str)?;
+        self.locale
+            .and_then(|messages| messages.get(key))
+            .or_else(|| self.source.and_then(|messages| messages.get(key)))
+            .cloned()
+            .or_else(|| {
+                text.get("fallback")
+                    .and_then(JsonValue::as_str)
+                    .map(str::to_string)
+            })
+            .or_else(|| Some(key.to_string()))
+    }
+}
+
+fn questions(spec: &JsonValue) -> impl Iterator<Item = &JsonValue> {
+    spec.get("questions")
+        .and_then(JsonValue::as_array)
+        .into_iter()
+        .flatten()
+}
+
+fn kind_type(question: &JsonValue) -> &str {
+    question
+        .get("kind")
+        .and_then(|kind| kind.get("type").or(Some(kind)))
+        .and_then(JsonValue::as_str)
+        .unwrap_or("text")
+}
+
+fn choice_options<'a>(question: &'a JsonValue, messages: &Translations) -> Vec<(&'a str, String)> {
+    question
+        .get("kind")
+        .and_then(|kind| kind.get("options"))
+        .and_then(JsonValue::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|option| {
+            let value = option.get("value")?.as_str()?;
+            let label = messages
+                .resolve(option.get("label"))
+                .unwrap_or_else(|| value.to_string());
+            Some((value, label))
+        })
+        .collect()
+}
+
+/// Terminal rendering: title, description, then one numbered entry per question.
+pub fn render_text(spec: &JsonValue, messages: &Translations) -> String {
+    let mut out = String::new();
+    if let Some(title) = messages.resolve(spec.get("title")) {
+        out.push_str(&format!("# {title}\n"));
+    }
+    if let Some(description) = messages.resolve(spec.get("description")) {
+        out.push_str(&format!("{description}\n"));
+    }
+    for (idx, question) in questions(spec).enumerate() {
+        let id = question
+            .get("id")
+            .and_then(JsonValue::as_str)
+            .unwrap_or("?");
+        let label = messages
+            .resolve(question.get("label"))
+            .unwrap_or_else(|| id.to_string());
+        let required = question
+            .get("required")
+            .and_then(JsonValue::as_bool)
+            .unwrap_or(false);
+        out.push_str(&format!(
+            "\n{}. {label}{}\n",
+            idx + 1,
+            if required { " (required)" } else { "" }
+        ));
+        if let Some(help) = messages.resolve(question.get("help")) {
+            out.push_str(&format!("   {help}\n"));
+        }
+        let kind = kind_type(question);
+        let options = choice_options(question, messages);
+        if options.is_empty() {
+            out.push_str(&format!("   [{kind}] {id}\n"));
+        } else {
+            let options = options
+                .iter()
+                .map(|(value, label)| format!("{value} ({label})"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            out.push_str(&format!("   [{kind}] {id}: {options}\n"));
+        }
+        if let Some(default) = question.get("default").filter(|value| !value.is_null()) {
+            out.push_str(&format!("   default: {default}\n"));
+        }
+    }
+    out
+}
+
+/// Adaptive Card rendering with one input per question and a submit action.
+pub fn render_card(spec: &JsonValue, messages: &Translations) -> JsonValue {
+    let mut body = Vec::new();
+    if let Some(title) = messages.resolve(spec.get("title")) {
+        body.push(json!({
+            "type": "TextBlock",
+            "text": title,
+            "size": "Large",
+            "weight": "Bolder",
+            "wrap": true
+        }));
+    }
+    if let Some(description) = messages.resolve(spec.get("description")) {
+        body.push(json!({ "type": "TextBlock", "text": description, "wrap": true }));
+    }
+    for question in questions(spec) {
+        let id = question
+            .get("id")
+            .and_then(JsonValue::as_str)
+            .unwrap_or("?");
+        let label = messages
+            .resolve(question.get("label"))
+            .unwrap_or_else(|| id.to_string());
+        let required = question
+            .get("required")
+            .and_then(JsonValue::as_bool)
+            .unwrap_or(false);
+        let options = choice_options(question, messages);
+        let mut input = match kind_type(question) {
+            "bool" | "boolean" | "toggle" => json!({ "type": "Input.Toggle", "title": label }),
+            "number" | "integer" | "int" | "float" => json!({ "type": "Input.Number" }),
+            _ if !options.is_empty() => json!({
+                "type": "Input.ChoiceSet",
+                "choices": options
+                    .iter()
+                    .map(|(value, title)| json!({ "title": title, "value": value }))
+                    .collect::<Vec<_>>()
+            }),
+            _ => json!({ "type": "Input.Text" }),
+        };
+        input["id"] = json!(id);
+        input["label"] = json!(label);
+        input["isRequired"] = json!(required);
+        if let Some(help) = messages.resolve(question.get("help")) {
+            input["placeholder"] = json!(help);
+        }
+        if let Some(error) = messages.resolve(question.get("error")) {
+            input["errorMessage"] = json!(error);
+        }
+        if let Some(default) = question.get("default").filter(|value| !value.is_null()) {
+            input["value"] = match default {
+                JsonValue::String(_) | JsonValue::Number(_) => default.clone(),
+                other => json!(other.to_string()),
+            };
+        }
+        body.push(input);
+    }
+    json!({
+        "type": "AdaptiveCard",
+        "$schema": ADAPTIVE_CARD_SCHEMA,
+        "version": ADAPTIVE_CARD_VERSION,
+        "body": body,
+        "actions": [{ "type": "Action.Submit", "title": "Submit" }]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec() -> JsonValue {
+        json!({
+            "mode": "setup",
+            "title": { "key": "qa.install.title", "fallback": null },
+            "description": null,
+            "questions": [
+                {
+                    "id": "api_key",
+                    "label": { "key": "qa.field.api_key.label", "fallback": null },
+                    "help": { "key": "qa.field.api_key.help", "fallback": "Your API key" },
+                    "error": null,
+                    "kind": { "type": "text" },
+                    "required": true,
+                    "default": null
+                },
+                {
+                    "id": "region",
+                    "label": { "key": "qa.field.region.label", "fallback": null },
+                    "help": null,
+                    "error": null,
+                    "kind": {
+                        "type": "choice",
+                        "options": [
+                            { "value": "eu", "label": { "key": "qa.region.eu", "fallback": "Europe" } },
+                            { "value": "us", "label": { "key": "qa.region.us", "fallback": null } }
+                        ]
+                    },
+                    "required": false,
+                    "default": "eu"
+                }
+            ],
+            "defaults": {}
+        })
+    }
+
+    fn messages(entries: &[(&str, &str)]) -> LocaleMessages {
+        entries
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn text_preview_resolves_locale_then_source_then_fallback() {
+        let source = messages(&[
+            ("qa.install.title", "Install"),
+            ("qa.field.api_key.label", "API key"),
+            ("qa.field.region.label", "Region"),
+        ]);
+        let nl = messages(&[("qa.install.title", "Installeren")]);
+        let text = render_text(
+            &spec(),
+            &Translations {
+                locale: Some(&nl),
+                source: Some(&source),
+            },
+        );
+        assert_eq!(
+            text,
+            "# Installeren\n\
+             \n1. API key (required)\n   Your API key\n   [text] api_key\n\
+             \n2. Region\n   [choice] region: eu (Europe), us (qa.region.us)\n   default: \"eu\"\n"
+        );
+    }
+
+    #[test]
+    fn card_preview_maps_questions_to_inputs() {
+        let card = render_card(&spec(), &Translations::default());
+        assert_eq!(card["version"], "1.5");
+        assert_eq!(card["body"][0]["text"], "qa.install.title");
+        assert_eq!(
+            card["body"][1],
+            json!({
+                "type": "Input.Text",
+                "id": "api_key",
+                "label": "qa.field.api_key.label",
+                "isRequired": true,
+                "placeholder": "Your API key"
+            })
+        );
+        assert_eq!(card["body"][2]["type"], "Input.ChoiceSet");
+        assert_eq!(card["body"][2]["value"], "eu");
+        assert_eq!(
+            card["body"][2]["choices"][0],
+            json!({ "title": "Europe", "value": "eu" })
+        );
+    }
+}
@@ -0,0 +1,429 @@
+//! Readable renderings of SchemaIR for `inspect`.
+//!
+//! Node descriptors carry op schemas as `SchemaSource::InlineCbor` bytes, which are opaque in
+//! any raw dump. These helpers decode them and render a JSON Schema-like document, or a sketch
+//! of the Rust types a component author would write to match.
+
+use std::collections::BTreeSet;
+
+use greentic_types::cbor::canonical;
+use greentic_types::schemas::common::schema_ir::{AdditionalProperties, SchemaIr};
+use serde::Serialize;
+use serde_json::{Map, Value as JsonValue};
+
+/// Decode the bytes of a `SchemaSource::InlineCbor` schema.
+pub fn decode_inline_cbor(bytes: &[u8]) -> Result<SchemaIr, String> {
+    canonical::from_cbor(bytes).map_err(|err| format!("invalid inline CBOR schema: {err}"))
+}
+
+/// JSON Schema-like view of `schema`: `type`, `properties`, `minLength`, `oneOf`, and so on.
+pub fn to_json_schema(schema: &SchemaIr) -> JsonValue {
+    let mut out = Map::new();
+    match schema {
+        SchemaIr::Object {
+            properties,
+            required,
+            additional,
+        } => {
+            insert(&mut out, "type", "object");
+            let properties = properties
+                .iter()
+                .map(|(name, schema)| (name.clone(), to_json_schema(schema)))
+                .collect::<Map<_, _>>();
+            out.insert("properties".to_string(), JsonValue::Object(properties));
+            if !required.is_empty() {
+                insert(&mut out, "required", required);
+            }
+            let additional = match additional {
+                AdditionalProperties::Allow => JsonValue::Bool(true),
+                AdditionalProperties::Forbid => JsonValue::Bool(false),
+                AdditionalProperties::Schema(schema) => to_json_schema(schema),
+            };
+            out.insert("additionalProperties".to_string(), additional);
+        }
+        SchemaIr::Array {
+            items,
+            min_items,
+            max_items,
+        } => {
+            insert(&mut out, "type", "array");
+            out.insert("items".to_string(), to_json_schema(items));
+            insert_opt(&mut out, "minItems", min_items);
+            insert_opt(&mut out, "maxItems", max_items);
+        }
+        SchemaIr::String {
+            min_len,
+            max_len,
+            regex,
+            format,
+        } => {
+            insert(&mut out, "type", "string");
+            insert_opt(&mut out, "minLength", min_len);
+            insert_opt(&mut out, "maxLength", max_len);
+            insert_opt(&mut out, "pattern", regex);
+            insert_opt(&mut out, "format", format);
+        }
+        SchemaIr::Int { min, max } => {
+            insert(&mut out, "type", "integer");
+            insert_opt(&mut out, "minimum", min);
+            insert_opt(&mut out, "maximum", max);
+        }
+        SchemaIr::Float { min, max } => {
+            insert(&mut out, "type", "number");
+            insert_opt(&mut out, "minimum", min);
+            insert_opt(&mut out, "maximum", max);
+        }
+        SchemaIr::Enum { values } => {
+            let values = values.iter().map(cbor_to_json).collect::<Vec<_>>();
+            out.insert("enum".to_string(), JsonValue::Array(values));
+        }
+        SchemaIr::OneOf { variants } => {
+            let variants = variants.iter().map(to_json_schema).collect::<Vec<_>>();
+            out.insert("oneOf".to_string(), JsonValue::Array(variants));
+        }
+        SchemaIr::Bool => insert(&mut out, "type", "boolean"),
+        SchemaIr::Null => insert(&mut out, "type", "null"),
+        SchemaIr::Bytes => {
+            insert(&mut out, "type", "string");
+            insert(&mut out, "contentEncoding", "base64");
+        }
+        SchemaIr::Ref { id } => insert(&mut out, "$ref", id),
+    }
+    JsonValue::Object(out)
+}
+
+fn insert(out: &mut Map<String, JsonValue>, key: &str, value: impl Serialize) {
+    out.insert(
+        key.to_string(),
+        serde_json::to_value(value).unwrap_or(JsonValue::Null),
+    );
+}
+
+fn insert_opt<T: Serialize>(out: &mut Map<String, JsonValue>, key: &str, value: &Option<T>) {
+    if let Some(value) = value {
+        insert(out, key, value);
+    }
+}
+
+fn cbor_to_json(value: &ciborium::Value) -> JsonValue {
+    match value {
+        ciborium::Value::Integer(int) => {
+            let int = i128::from(*int);
+            i64::try_from(int)
+                .map(JsonValue::from)
+                .or_else(|_| u64::try_from(int).map(JsonValue::from))
+                .unwrap_or_else(|_| JsonValue::String(int.to_string()))
+        }
+        ciborium::Value::Float(float) => serde_json::Number::from_f64(*float)
+            .map(JsonValue::Number)
+            .unwrap_or(JsonValue::Null),
+        ciborium::Value::Text(text) => JsonValue::String(text.clone()),
+        ciborium::Value::Bool(flag) => JsonValue::Bool(*flag),
+        ciborium::Value::Bytes(bytes) => JsonValue::String(hex::encode(bytes)),
+        ciborium::Value::Array(items) => JsonValue::Array(items.iter().map(cbor_to_json).collect()),
+        ciborium::Value::Map(entries) => JsonValue::Object(
+            entries
+                .iter()
+                .map(|(key, value)| {
+                    let key = match key {
+                        ciborium::Value::Text(text) => text.clone(),
+                        other => cbor_to_json(other).to_string(),
+                    };
+                    (key, cbor_to_json(value))
+                })
+                .collect(),
+        ),
+        ciborium::Value::Tag(_, inner) => cbor_to_json(inner),
+        _ => JsonValue::Null,
+    }
+}
+
+/// Rust types a component could use for `schema`, rooted at a type called `name`.
+///
+/// This is a starting point rather than generated code: refs become type names that are not
+/// defined, and non-text enums fall back to `serde_json::Value`.
+pub fn rust_sketch(name: &str, schema: &SchemaIr) -> String {
+    let mut sketch = RustSketch::default();
+    let root = pascal_case(name);
+    let ty = sketch.type_for(&root, schema);
+    if ty != root {
+        sketch.items.push(format!("pub type {root} = {ty};\n"));
+    }
+    sketch
+        .items
+        .iter()
+        .rev()
+        .cloned()
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[derive(Default)]
+struct RustSketch {
+    /// Type definitions, innermost first.
+    items: Vec<String>,
+    names: BTreeSet<String>,
+}
+
+impl RustSketch {
+    fn type_for(&mut self, name: &str, schema: &SchemaIr) -> String {
+        match schema {
+            SchemaIr::Object {
+                properties,
+                required,
+                additional,
+            } => {
+                if properties.is_empty() {
+                    return match additional {
+                        AdditionalProperties::Schema(value) => format!(
+                            "BTreeMap<String, {}>",
+                            self.type_for(&format!("{name}Value"), value)
+                        ),
+                        _ => "serde_json::Map<String, serde_json::Value>".to_string(),
+                    };
+                }
+                let name = self.claim(name);
+                let mut body = String::new();
+                for (field, schema) in properties {
+                    let ty = self.type_for(&format!("{name}{}", pascal_case(field)), schema);
+                    let ty = if required.contains(field) {
+                        ty
+                    } else {
+                        format!("Option<{ty}>")
+                    };
+                    if let Some(doc) = constraint_doc(schema) {
+                        body.push_str(&format!("    /// {doc}\n"));
+                    }
+                    let ident = rust_ident(field);
+                    if ident.trim_start_matches("r#") != field {
+                        body.push_str(&format!("    #[serde(rename = {field:?})]\n"));
+                    }
+                    body.push_str(&format!("    pub {ident}: {ty},\n"));
+                }
+                let deny = if matches!(additional, AdditionalProperties::Forbid) {
+                    "#[serde(deny_unknown_fields)]\n"
+                } else {
+                    ""
+                };
+                self.items.push(format!(
+                    "#[derive(Debug, Clone, Serialize, Deserialize)]\n{deny}pub struct {name} {{\n{body}}}\n"
+                ));
+                name
+            }
+            SchemaIr::Array { items, .. } => {
+                format!("Vec<{}>", self.type_for(&format!("{name}Item"), items))
+            }
+            SchemaIr::String { .. } => "String".to_string(),
+            SchemaIr::Int { .. } => "i64".to_string(),
+            SchemaIr::Float { .. } => "f64".to_string(),
+            SchemaIr::Bool => "bool".to_string(),
+            SchemaIr::Null => "()".to_string(),
+            SchemaIr::Bytes => "Vec<u8>".to_string(),
+            SchemaIr::Enum { values } => {
+                let texts = values
+                    .iter()
+                    .map(|value| value.as_text())
+                    .collect::<Option<Vec<_>>>();
+                let Some(texts) = texts.filter(|texts| !texts.is_empty()) else {
+                    return "serde_json::Value".to_string();
+                };
+                let name = self.claim(name);
+                let mut body = String::new();
+                for text in texts {
+                    body.push_str(&format!(
+                        "    #[serde(rename = {text:?})]\n    {},\n",
+                        pascal_case(text)
+                    ));
+                }
+                self.items.push(format!(
+                    "#[derive(Debug, Clone, Serialize, Deserialize)]\npub enum {name} {{\n{body}}}\n"
+                ));
+                name
+            }
+            SchemaIr::OneOf { variants } => {
+                let name = self.claim(name);
+                let mut body = String::new();
+                for (idx, variant) in variants.iter().enumerate() {
+                    let ty = self.type_for(&format!("{name}Variant{idx}"), variant);
+                    body.push_str(&format!("    Variant{idx}({ty}),\n"));
+                }
+                self.items.push(format!(
+                    "#[derive(Debug, Clone, Serialize, Deserialize)]\n#[serde(untagged)]\npub enum {name} {{\n{body}}}\n"
+                ));
+                name
+            }
+            SchemaIr::Ref { id } => pascal_case(id),
+        }
+    }
+
+    /// `name`, or `name2`, `name3`, ... when an earlier type already uses it.
+    fn claim(&mut self, name: &str) -> String {
+        let mut candidate = name.to_string();
+        let mut suffix = 2;
+        while !self.names.insert(candidate.clone()) {
+            candidate = format!("{name}{suffix}");
+            suffix += 1;
+        }
+        candidate
+    }
+}
+
+fn constraint_doc(schema: &SchemaIr) -> Option<String> {
+    let mut parts = Vec::new();
+    match schema {
+        SchemaIr::String {
+            min_len,
+            max_len,
+            regex,
+            format,
+        } => {
+            push_opt(&mut parts, "min_len", min_len);
+            push_opt(&mut parts, "max_len", max_len);
+            if let Some(regex) = regex {
+                parts.push(format!("pattern `{regex}`"));
+            }
+            push_opt(&mut parts, "format", format);
+        }
+        SchemaIr::Int { min, max } => {
+            push_opt(&mut parts, "min", min);
+            push_opt(&mut parts, "max", max);
+        }
+        SchemaIr::Float { min, max } => {
+            push_opt(&mut parts, "min", min);
+            push_opt(&mut parts, "max", max);
+        }
+        SchemaIr::Array {
+            min_items,
+            max_items,
+            ..
+        } => {
+            push_opt(&mut parts, "min_items", min_items);
+            push_opt(&mut parts, "max_items", max_items);
+        }
+        _ => {}
+    }
+    (!parts.is_empty()).then(|| parts.join(", "))
+}
+
+fn push_opt<T: std::fmt::Display>(parts: &mut Vec<String>, label: &str, value: &Option<T>) {
+    if let Some(value) = value {
+        parts.push(format!("{label}: {value}"));
+    }
+}
+
+fn pascal_case(raw: &str) -> String {
+    let mut out = String::new();
+    for word in raw.split(|ch: char| !ch.is_ascii_alphanumeric()) {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            out.push(first.to_ascii_uppercase());
+            out.extend(chars);
+        }
+    }
+    if out.is_empty() || out.starts_with(|ch: char| ch.is_ascii_digit()) {
+        out.insert(0, 'T');
+    }
+    out
+}
+
+fn rust_ident(raw: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+        "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move",
+        "mut", "pub", "ref", "return", "static", "struct", "trait", "true", "type", "unsafe",
+        "use", "where", "while",
+    ];
+    let mut ident = raw
+        .chars()
+        .map(|ch| if ch.is_ascii_alphanumeric() { ch } else { '_' })
+        .collect::<String>();
+    if ident.is_empty() || ident.starts_with(|ch: char| ch.is_ascii_digit()) {
+        ident.insert(0, '_');
+    }
+    if KEYWORDS.contains(&ident.as_str()) {
+        ident.insert_str(0, "r#");
+    }
+    ident
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::BTreeMap;
+
+    fn message_schema() -> SchemaIr {
+        let mut properties = BTreeMap::new();
+        properties.insert(
+            "message".to_string(),
+            SchemaIr::String {
+                min_len: Some(1),
+                max_len: Some(1024),
+                regex: None,
+                format: None,
+            },
+        );
+        properties.insert(
+            "mode".to_string(),
+            SchemaIr::Enum {
+                values: vec![
+                    ciborium::Value::Text("fast".to_string()),
+                    ciborium::Value::Text("safe".to_string()),
+                ],
+            },
+        );
+        SchemaIr::Object {
+            properties,
+            required: vec!["message".to_string()],
+            additional: AdditionalProperties::Forbid,
+        }
+    }
+
+    #[test]
+    fn inline_cbor_renders_as_json_schema() {
+        let bytes =
+            canonical::to_canonical_cbor_allow_floats(&message_schema()).expect("encode schema");
+        let schema = decode_inline_cbor(&bytes).expect("decode schema");
+        assert_eq!(
+            to_json_schema(&schema),
+            json!({
+                "type": "object",
+                "properties": {
+                    "message": { "type": "string", "minLength": 1, "maxLength": 1024 },
+                    "mode": { "enum": ["fast", "safe"] }
+                },
+                "required": ["message"],
+                "additionalProperties": false
+            })
+        );
+        assert!(decode_inline_cbor(&[0xff]).is_err());
+    }
+
+    #[test]
+    fn rust_sketch_names_nested_types_after_fields() {
+        let sketch = rust_sketch("handle_message input", &message_schema());
+        assert_eq!(
+            sketch,
+            r#"#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HandleMessageInput {
+    /// min_len: 1, max_len: 1024
+    pub message: String,
+    pub mode: Option<HandleMessageInputMode>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum HandleMessageInputMode {
+    #[serde(rename = "fast")]
+    Fast,
+    #[serde(rename = "safe")]
+    Safe,
+}
+"#
+        );
+        assert_eq!(
+            rust_sketch("flag", &SchemaIr::Bool),
+            "pub type Flag = bool;\n"
+        );
+    }
+}
@@ -1,15 +1,35 @@
 pub mod build;
+pub mod build_oci;
+pub mod build_opt;
+pub mod build_repro;
+pub mod build_sbom;
 pub mod component_world;
 pub mod doctor;
+pub mod doctor_baseline;
+pub mod doctor_rules;
 pub mod flow;
 pub mod hash;
 pub mod i18n;
+pub mod i18n_tools;
 pub mod inspect;
+pub mod inspect_diff;
+pub mod inspect_qa;
+pub mod inspect_schema;
 pub mod new;
 pub mod path;
 pub mod post;
+pub mod qa;
+pub mod qa_lint;
+pub mod qa_openapi;
+pub mod qa_ops;
+pub mod qa_rpc;
+#[cfg(feature = "qa-serve")]
+pub mod qa_serve;
+pub mod schema;
 #[cfg(feature = "store")]
 pub mod store;
 pub mod templates;
 pub mod test;
+#[cfg(feature = "fuzz")]
+pub mod test_qa_fuzz;
 pub mod wizard;
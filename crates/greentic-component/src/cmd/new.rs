@@ -13,17 +13,19 @@ use serde::Serialize;
 use serde_json::json;
 
 use crate::cmd::i18n;
-use crate::cmd::post::{self, GitInitStatus, PostInitReport};
+use crate::cmd::post::{self, GitInitStatus, PostHookEvent, PostInitOptions, PostInitReport};
 use crate::scaffold::config_schema::{ConfigSchemaInput, parse_config_field};
 use crate::scaffold::deps::DependencyMode;
 use crate::scaffold::engine::{
     DEFAULT_WIT_WORLD, ScaffoldEngine, ScaffoldOutcome, ScaffoldRequest,
 };
+use crate::scaffold::license::LicenseKind;
 use crate::scaffold::runtime_capabilities::{
     RuntimeCapabilitiesInput, parse_filesystem_mode, parse_filesystem_mount, parse_secret_format,
     parse_telemetry_attributes, parse_telemetry_scope,
 };
 use crate::scaffold::validate::{self, ComponentName, OrgNamespace, ValidationError};
+use crate::wizard::{MultiSpec, apply_multi_scaffold};
 
 type ValidationResult<T> = std::result::Result<T, ValidationError>;
 const SKIP_GIT_ENV: &str = "GREENTIC_SKIP_GIT";
@@ -31,9 +33,16 @@ const SKIP_GIT_ENV: &str = "GREENTIC_SKIP_GIT";
 #[derive(Args, Debug, Clone)]
 pub struct NewArgs {
     /// Name for the component (kebab-or-snake case)
-    #[arg(long = "name", value_name = "kebab_or_snake", required = true)]
-    pub name: String,
-    /// Path to create the component (defaults to ./<name>)
+    #[arg(
+        long = "name",
+        value_name = "kebab_or_snake",
+        required_unless_present = "multi"
+    )]
+    pub name: Option<String>,
+    /// Scaffold a workspace with every component listed in a YAML spec instead of one component
+    #[arg(long = "multi", value_name = "spec.yaml", conflicts_with = "name")]
+    pub multi: Option<PathBuf>,
+    /// Path to create the component (defaults to ./<name>, or ./<workspace> with `--multi`)
     #[arg(long = "path", value_name = "dir")]
     pub path: Option<PathBuf>,
     /// Template identifier to scaffold from
@@ -53,9 +62,12 @@ pub struct NewArgs {
     /// Initial component version
     #[arg(long = "version", default_value = "0.1.0", value_name = "semver")]
     pub version: String,
-    /// License to embed into generated sources
+    /// License for the generated `LICENSE` file and `Cargo.toml` (MIT, Apache-2.0, or proprietary)
     #[arg(long = "license", default_value = "MIT", value_name = "id")]
     pub license: String,
+    /// Author written to `Cargo.toml` `authors` and the copyright line (defaults to the git author)
+    #[arg(long = "author", value_name = "name")]
+    pub author: Option<String>,
     /// Exported WIT world name
     #[arg(
         long = "wit-world",
@@ -139,12 +151,21 @@ pub struct NewArgs {
     /// Skip git initialization after scaffolding
     #[arg(long = "no-git")]
     pub no_git: bool,
+    /// Run `cargo fmt` on the generated project before the initial commit
+    #[arg(long = "fmt")]
+    pub fmt: bool,
+    /// Open the generated project in `$EDITOR` once the other post-steps finish
+    #[arg(long = "open")]
+    pub open: bool,
     /// Emit JSON instead of human-readable output
     #[arg(long = "json")]
     pub json: bool,
 }
 
 pub fn run(args: NewArgs, engine: &ScaffoldEngine) -> Result<()> {
+    if let Some(spec) = &args.multi {
+        return run_multi(&args, spec);
+    }
     let request = match build_request(&args) {
         Ok(req) => req,
         Err(err) => {
@@ -177,8 +198,12 @@ pub fn run(args: NewArgs, engine: &ScaffoldEngine) -> Result<()> {
         stdout().flush().ok();
     }
     let post_started = Instant::now();
-    let skip_git = should_skip_git(&args);
-    let post_init = post::run_post_init(&outcome, skip_git);
+    let post_options = PostInitOptions {
+        skip_git: should_skip_git(&args),
+        cargo_fmt: args.fmt,
+        open_editor: args.open,
+    };
+    let mut post_init = post::run_post_init(&outcome, &post_options);
     if !args.json && !args.no_check {
         println!(
             "{}",
@@ -189,6 +214,9 @@ pub fn run(args: NewArgs, engine: &ScaffoldEngine) -> Result<()> {
         stdout().flush().ok();
     }
     let compile_check = run_compile_check(&outcome.path, args.no_check)?;
+    if post_options.open_editor {
+        post::open_in_editor(&outcome.path, &mut post_init.events);
+    }
     if args.json {
         let payload = NewCliOutput {
             scaffold: &outcome,
@@ -213,8 +241,80 @@ pub fn run(args: NewArgs, engine: &ScaffoldEngine) -> Result<()> {
     Ok(())
 }
 
+/// `new --multi`: one plan for the whole workspace, then the usual post-steps at its root.
+fn run_multi(args: &NewArgs, spec_path: &Path) -> Result<()> {
+    let spec = MultiSpec::load(spec_path)?;
+    let target = std::path::absolute(
+        args.path
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(&spec.workspace)),
+    )
+    .context("failed to resolve the workspace path")?;
+    validate::ensure_path_available(&target)?;
+    let members = spec
+        .components
+        .iter()
+        .map(|component| format!("components/{}", component.name.trim()))
+        .collect::<Vec<_>>();
+    if !args.json {
+        println!(
+            "{}",
+            i18n::tr_lit("scaffolding {count} components into {path}...")
+                .replace("{count}", &members.len().to_string())
+                .replace("{path}", &target.display().to_string())
+        );
+        stdout().flush().ok();
+    }
+    let result = apply_multi_scaffold(spec, target.clone(), false)?;
+    let post_options = PostInitOptions {
+        skip_git: should_skip_git(args),
+        cargo_fmt: args.fmt,
+        open_editor: args.open,
+    };
+    let mut post_init = post::run_post_init_in(&target, "multi-component spec", &post_options);
+    post_init.next_steps = vec![
+        format!("cd {}", target.display()),
+        "cargo check --workspace --target wasm32-wasip2".into(),
+        "git status".into(),
+    ];
+    let compile_check = run_compile_check(&target, args.no_check)?;
+    if post_options.open_editor {
+        post::open_in_editor(&target, &mut post_init.events);
+    }
+    if args.json {
+        print_json(&json!({
+            "plan": result.plan,
+            "warnings": result.warnings,
+            "compile_check": compile_check,
+            "post_init": post_init,
+        }))?;
+    } else {
+        println!(
+            "{}",
+            i18n::tr_lit("Workspace {path}").replace("{path}", &target.display().to_string())
+        );
+        for member in std::iter::once("crates/common").chain(members.iter().map(String::as_str)) {
+            println!("{}", i18n::tr_lit("- {path}").replace("{path}", member));
+        }
+        for warning in &result.warnings {
+            println!(
+                "{}",
+                i18n::tr_lit("warning: {warning}").replace("{warning}", warning)
+            );
+        }
+        print_post_and_check(&compile_check, &post_init);
+    }
+    if compile_check.ran && !compile_check.passed {
+        anyhow::bail!(
+            "{}",
+            i18n::tr_lit("cargo check --target wasm32-wasip2 failed")
+        );
+    }
+    Ok(())
+}
+
 fn build_request(args: &NewArgs) -> ValidationResult<ScaffoldRequest> {
-    let component_name = ComponentName::parse(&args.name)?;
+    let component_name = ComponentName::parse(args.name.as_deref().unwrap_or_default())?;
     let org = OrgNamespace::parse(&args.org)?;
     let version = validate::normalize_version(&args.version)?;
     let target_path = resolve_path(&component_name, args.path.as_deref())?;
@@ -224,7 +324,8 @@ fn build_request(args: &NewArgs) -> ValidationResult<ScaffoldRequest> {
         template_id: args.template.clone(),
         org: org.into_string(),
         version,
-        license: args.license.clone(),
+        license: LicenseKind::parse(&args.license)?.id().to_string(),
+        author: args.author.clone(),
         wit_world: args.wit_world.clone(),
         user_operations: resolve_user_operations(args)?,
         default_operation: resolve_default_operation(args)?,
@@ -331,6 +432,11 @@ fn print_human(outcome: &ScaffoldOutcome, check: &CompileCheckReport, post: &Pos
     for path in &outcome.created {
         println!("{}", i18n::tr_lit("- {path}").replace("{path}", path));
     }
+    print_post_and_check(check, post);
+}
+
+fn print_post_and_check(check: &CompileCheckReport, post: &PostInitReport) {
+    print_post_steps(&post.events);
     print_git_summary(&post.git);
     if !check.ran {
         println!(
@@ -367,6 +473,32 @@ fn print_human(outcome: &ScaffoldOutcome, check: &CompileCheckReport, post: &Pos
     }
 }
 
+/// Report the optional post-steps; git has its own summary line.
+fn print_post_steps(events: &[PostHookEvent]) {
+    for event in events {
+        let label = match event.stage.as_str() {
+            "cargo-fmt" => "cargo fmt",
+            "open-editor" => "open in $EDITOR",
+            _ => continue,
+        };
+        match &event.message {
+            Some(message) => println!(
+                "{}",
+                i18n::tr_lit("{label}: {status} ({message})")
+                    .replace("{label}", label)
+                    .replace("{status}", &event.status)
+                    .replace("{message}", message)
+            ),
+            None => println!(
+                "{}",
+                i18n::tr_lit("{label}: {status}")
+                    .replace("{label}", label)
+                    .replace("{status}", &event.status)
+            ),
+        }
+    }
+}
+
 fn print_git_summary(report: &post::GitInitReport) {
     match report.status {
         GitInitStatus::Initialized => {
@@ -562,12 +694,14 @@ mod tests {
     #[test]
     fn default_path_uses_name() {
         let args = NewArgs {
-            name: "demo-component".into(),
+            name: Some("demo-component".into()),
+            multi: None,
             path: None,
             template: "rust-wasi-p2-min".into(),
             org: "ai.greentic".into(),
             version: "0.1.0".into(),
             license: "MIT".into(),
+            author: None,
             wit_world: DEFAULT_WIT_WORLD.into(),
             operation_names: Vec::new(),
             default_operation: None,
@@ -593,23 +727,31 @@ mod tests {
             non_interactive: false,
             no_check: false,
             no_git: false,
+            fmt: false,
+            open: false,
             json: false,
         };
         let request = build_request(&args).unwrap();
         assert!(request.path.ends_with("demo-component"));
         assert_eq!(request.user_operations, vec!["handle_message"]);
         assert_eq!(request.default_operation, "handle_message");
+        assert_eq!(
+            request.runtime_capabilities.manifest_capabilities()["host"],
+            json!({})
+        );
     }
 
     #[test]
     fn build_request_accepts_custom_operations() {
         let args = NewArgs {
-            name: "demo-component".into(),
+            name: Some("demo-component".into()),
+            multi: None,
             path: None,
             template: "rust-wasi-p2-min".into(),
             org: "ai.greentic".into(),
             version: "0.1.0".into(),
             license: "MIT".into(),
+            author: None,
             wit_world: DEFAULT_WIT_WORLD.into(),
             operation_names: vec!["render".into(), "sync-state".into()],
             default_operation: Some("sync-state".into()),
@@ -635,6 +777,8 @@ mod tests {
             non_interactive: false,
             no_check: false,
             no_git: false,
+            fmt: false,
+            open: false,
             json: false,
         };
         let request = build_request(&args).unwrap();
@@ -647,18 +791,23 @@ mod tests {
         assert!(request.runtime_capabilities.http_client);
         assert_eq!(request.runtime_capabilities.telemetry_scope, "pack");
         assert_eq!(request.runtime_capabilities.secret_keys, vec!["API_TOKEN"]);
+        let host = &request.runtime_capabilities.manifest_capabilities()["host"];
+        assert_eq!(host["telemetry"]["scope"], "pack");
+        assert_eq!(host["secrets"]["required"][0]["key"], "API_TOKEN");
         assert_eq!(request.config_schema.fields.len(), 2);
     }
 
     #[test]
     fn build_request_rejects_unknown_default_operation() {
         let args = NewArgs {
-            name: "demo-component".into(),
+            name: Some("demo-component".into()),
+            multi: None,
             path: None,
             template: "rust-wasi-p2-min".into(),
             org: "ai.greentic".into(),
             version: "0.1.0".into(),
             license: "MIT".into(),
+            author: None,
             wit_world: DEFAULT_WIT_WORLD.into(),
             operation_names: vec!["render".into()],
             default_operation: Some("sync-state".into()),
@@ -684,6 +833,8 @@ mod tests {
             non_interactive: false,
             no_check: false,
             no_git: false,
+            fmt: false,
+            open: false,
             json: false,
         };
         let err = build_request(&args).unwrap_err();
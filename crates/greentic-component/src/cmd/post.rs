@@ -29,7 +29,7 @@ pub struct PostHookEvent {
 }
 
 impl PostHookEvent {
-    fn new(stage: &str, status: &str, message: Option<String>) -> Self {
+    pub(crate) fn new(stage: &str, status: &str, message: Option<String>) -> Self {
         Self {
             stage: stage.into(),
             status: status.into(),
@@ -99,9 +99,28 @@ pub enum GitInitStatus {
     Failed,
 }
 
-pub fn run_post_init(outcome: &ScaffoldOutcome, skip_git: bool) -> PostInitReport {
+/// Post-generation steps to run once the scaffold files are on disk.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PostInitOptions {
+    pub skip_git: bool,
+    /// Run `cargo fmt` before the initial commit.
+    pub cargo_fmt: bool,
+    /// Open the project in `$EDITOR` (or `$VISUAL`) once every other step has run.
+    pub open_editor: bool,
+}
+
+pub fn run_post_init(outcome: &ScaffoldOutcome, options: &PostInitOptions) -> PostInitReport {
+    run_post_init_in(&outcome.path, &outcome.template, options)
+}
+
+/// [`run_post_init`] for a project at `path` generated from `template` without a
+/// [`ScaffoldOutcome`], such as a multi-component workspace.
+pub fn run_post_init_in(path: &Path, template: &str, options: &PostInitOptions) -> PostInitReport {
     let mut events = Vec::new();
-    let git = if skip_git {
+    if options.cargo_fmt {
+        run_cargo_fmt(path, &mut events);
+    }
+    let git = if options.skip_git {
         events.push(PostHookEvent::new(
             "git-init",
             "skipped",
@@ -109,9 +128,9 @@ pub fn run_post_init(outcome: &ScaffoldOutcome, skip_git: bool) -> PostInitRepor
         ));
         GitInitReport::skipped("git scaffolding disabled via flag/env")
     } else {
-        initialize_git_repo(&outcome.path, &outcome.template, &mut events)
+        initialize_git_repo(path, template, &mut events)
     };
-    let next_steps = default_next_steps(&outcome.path);
+    let next_steps = default_next_steps(path);
     PostInitReport {
         git,
         next_steps,
@@ -119,6 +138,69 @@ pub fn run_post_init(outcome: &ScaffoldOutcome, skip_git: bool) -> PostInitRepor
     }
 }
 
+/// Launch `$EDITOR` (falling back to `$VISUAL`) on the project directory and wait for it.
+pub fn open_in_editor(path: &Path, events: &mut Vec<PostHookEvent>) {
+    let Some(editor) = ["EDITOR", "VISUAL"]
+        .into_iter()
+        .filter_map(|var| env::var(var).ok())
+        .find(|value| !value.trim().is_empty())
+    else {
+        events.push(PostHookEvent::new(
+            "open-editor",
+            "skipped",
+            Some("neither $EDITOR nor $VISUAL is set".into()),
+        ));
+        return;
+    };
+    // Editors are commonly configured with flags, e.g. `code --wait`.
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or_default();
+    let status = Command::new(program).args(parts).arg(path).status();
+    match status {
+        Ok(status) if status.success() => {
+            events.push(PostHookEvent::new("open-editor", "ok", None));
+        }
+        Ok(status) => events.push(PostHookEvent::new(
+            "open-editor",
+            "failed",
+            Some(format!("`{editor}` exited with {status}")),
+        )),
+        Err(err) => events.push(PostHookEvent::new(
+            "open-editor",
+            "failed",
+            Some(format!("failed to launch `{editor}`: {err}")),
+        )),
+    }
+}
+
+fn run_cargo_fmt(path: &Path, events: &mut Vec<PostHookEvent>) {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let output = Command::new(cargo)
+        .arg("fmt")
+        .arg("--all")
+        .current_dir(path)
+        .output();
+    match output {
+        Ok(out) if out.status.success() => {
+            events.push(PostHookEvent::new("cargo-fmt", "ok", None));
+        }
+        Ok(out) => {
+            let stderr = String::from_utf8_lossy(&out.stderr).trim().to_string();
+            let message = if stderr.is_empty() {
+                format!("cargo fmt failed with exit code {}", out.status)
+            } else {
+                format!("cargo fmt failed: {stderr}")
+            };
+            events.push(PostHookEvent::new("cargo-fmt", "failed", Some(message)));
+        }
+        Err(err) => events.push(PostHookEvent::new(
+            "cargo-fmt",
+            "failed",
+            Some(format!("failed to run cargo fmt: {err}")),
+        )),
+    }
+}
+
 fn default_next_steps(path: &Path) -> Vec<String> {
     let cd = format!("cd {}", path.display());
     vec![
@@ -234,6 +316,19 @@ fn initialize_git_repo(
     }
 }
 
+/// Why a project at `path`, which may not exist yet, should not get a repository of its own:
+/// it already contains `.git`, or its nearest existing directory belongs to a git worktree.
+pub fn existing_git_repo(path: &Path) -> Option<&'static str> {
+    if path.join(".git").exists() {
+        return Some("directory already contains .git");
+    }
+    let path = std::path::absolute(path).ok()?;
+    let probe = path.ancestors().find(|dir| dir.is_dir())?;
+    let git = env::var("GIT").unwrap_or_else(|_| "git".to_owned());
+    matches!(detect_existing_worktree(&git, probe), Ok(true))
+        .then_some("target directory belongs to an existing git worktree")
+}
+
 fn detect_existing_worktree(git: &str, path: &Path) -> Result<bool, GitProbeError> {
     let output = Command::new(git)
         .arg("rev-parse")
@@ -377,7 +472,7 @@ mod tests {
             created: vec!["README.md".into()],
         };
 
-        let report = run_post_init(&outcome, false);
+        let report = run_post_init(&outcome, &PostInitOptions::default());
         assert_eq!(report.git.status, GitInitStatus::Initialized);
         assert!(project.join(".git").exists());
         assert!(report.git.commit.is_some());
@@ -404,7 +499,7 @@ mod tests {
             path: project.clone(),
             created: vec![],
         };
-        let report = run_post_init(&outcome, false);
+        let report = run_post_init(&outcome, &PostInitOptions::default());
         assert!(matches!(
             report.git.status,
             GitInitStatus::AlreadyPresent | GitInitStatus::InsideWorktree
@@ -431,7 +526,11 @@ mod tests {
             path: project.clone(),
             created: vec![],
         };
-        let report = run_post_init(&outcome, true);
+        let options = PostInitOptions {
+            skip_git: true,
+            ..PostInitOptions::default()
+        };
+        let report = run_post_init(&outcome, &options);
         assert_eq!(report.git.status, GitInitStatus::Skipped);
         assert!(
             report
@@ -440,4 +539,35 @@ mod tests {
                 .any(|event| event.stage == "git-init" && event.status == "skipped")
         );
     }
+
+    #[test]
+    fn reports_fmt_failure_without_blocking_git() {
+        let temp = TempDir::new().expect("tempdir");
+        let project = temp.path().join("demo-fmt");
+        std::fs::create_dir_all(&project).expect("mkdir");
+        std::fs::write(project.join("README.md"), "# Demo\n").expect("write");
+
+        let outcome = ScaffoldOutcome {
+            name: "demo-fmt".into(),
+            template: "rust-wasi-p2-min".into(),
+            template_description: None,
+            template_tags: vec![],
+            path: project.clone(),
+            created: vec!["README.md".into()],
+        };
+        let options = PostInitOptions {
+            cargo_fmt: true,
+            ..PostInitOptions::default()
+        };
+        // No Cargo.toml, so `cargo fmt` fails and the remaining steps still run.
+        let report = run_post_init(&outcome, &options);
+        let fmt = report
+            .events
+            .iter()
+            .find(|event| event.stage == "cargo-fmt")
+            .expect("cargo-fmt event");
+        assert_eq!(fmt.status, "failed");
+        assert!(fmt.message.is_some());
+        assert_eq!(report.git.status, GitInitStatus::Initialized);
+    }
 }
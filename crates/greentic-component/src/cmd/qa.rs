@@ -0,0 +1,653 @@
+#![cfg(feature = "cli")]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use clap::{Args, Subcommand, ValueEnum};
+use qa_spec::spec::question::{Constraint, ListSpec, QuestionPolicy};
+use qa_spec::{FormSpec, QuestionSpec, QuestionType};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Map as JsonMap, Value as JsonValue};
+
+pub use super::qa_lint::QaLintArgs;
+pub use super::qa_openapi::QaImportOpenapiArgs;
+pub use super::qa_rpc::QaRpcArgs;
+#[cfg(feature = "qa-serve")]
+pub use super::qa_serve::QaServeArgs;
+
+pub(crate) const DEFAULT_FORM_VERSION: &str = "0.1.0";
+
+/// Patterns used for JSON Schema `format` values that have no QA question type of their own.
+const FORMAT_PATTERNS: &[(&str, &str)] = &[
+    ("email", r"^[^@\s]+@[^@\s]+$"),
+    ("uri", r"^[A-Za-z][A-Za-z0-9+.-]*:\S+$"),
+    ("url", r"^[A-Za-z][A-Za-z0-9+.-]*:\S+$"),
+    (
+        "uuid",
+        r"^[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}$",
+    ),
+    ("date", r"^\d{4}-\d{2}-\d{2}$"),
+    ("date-time", r"^\d{4}-\d{2}-\d{2}[Tt ]\d{2}:\d{2}:\d{2}"),
+    ("ipv4", r"^(\d{1,3}\.){3}\d{1,3}$"),
+    ("hostname", r"^[A-Za-z0-9]([A-Za-z0-9.-]*[A-Za-z0-9])?$"),
+];
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum QaCommand {
+    /// Convert a JSON Schema (such as a component config_schema) into a QA FormSpec
+    ImportSchema(QaImportSchemaArgs),
+    /// Generate a QA FormSpec for each operation of an OpenAPI document from its parameters and JSON request body
+    ImportOpenapi(QaImportOpenapiArgs),
+    /// Check FormSpec files for parse errors, unknown ids, bad expressions and missing i18n keys
+    Lint(QaLintArgs),
+    /// Answer newline-delimited JSON-RPC requests for the component-qa operations on stdin/stdout
+    Rpc(QaRpcArgs),
+    /// Serve the component-qa operations over HTTP for local web UI development
+    #[cfg(feature = "qa-serve")]
+    Serve(QaServeArgs),
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct QaImportSchemaArgs {
+    /// JSON Schema document (JSON or YAML)
+    #[arg(value_name = "SCHEMA")]
+    pub schema: PathBuf,
+    /// JSON pointer to the schema inside the document (e.g. /config_schema of a manifest)
+    #[arg(long = "pointer", value_name = "POINTER")]
+    pub pointer: Option<String>,
+    /// Form id; defaults to the schema file name without its extensions
+    #[arg(long = "id", value_name = "ID")]
+    pub id: Option<String>,
+    /// Form title; defaults to the schema title, then the form id
+    #[arg(long = "title", value_name = "TITLE")]
+    pub title: Option<String>,
+    /// Form version
+    #[arg(long = "form-version", value_name = "VERSION", default_value = DEFAULT_FORM_VERSION)]
+    pub form_version: String,
+    /// Write the FormSpec to a file instead of stdout
+    #[arg(long = "out", value_name = "PATH")]
+    pub out: Option<PathBuf>,
+    /// Output encoding; auto writes YAML for a .yaml/.yml --out and JSON otherwise
+    #[arg(long = "format", value_enum, default_value = "auto")]
+    pub format: SpecFormat,
+    /// Encoding of the schema document
+    #[arg(long = "spec-format", value_enum, default_value = "auto")]
+    pub spec_format: SpecFormat,
+}
+
+/// Encoding of a QA spec document on disk.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SpecFormat {
+    /// `.json` is JSON and `.yaml`/`.yml` is YAML; anything else is sniffed from its content
+    #[default]
+    Auto,
+    Json,
+    Yaml,
+}
+
+impl SpecFormat {
+    pub(crate) fn resolve(self, path: &Path, raw: &str) -> Self {
+        if self != SpecFormat::Auto {
+            return self;
+        }
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("json") => SpecFormat::Json,
+            Some(ext) if ext.eq_ignore_ascii_case("yaml") || ext.eq_ignore_ascii_case("yml") => {
+                SpecFormat::Yaml
+            }
+            // A JSON document opens with an object or array; block YAML never does.
+            _ if raw.trim_start().starts_with(['{', '[']) => SpecFormat::Json,
+            _ => SpecFormat::Yaml,
+        }
+    }
+}
+
+/// A FormSpec synthesized from a JSON Schema, plus the schema parts that could not be mapped.
+#[derive(Debug, Clone)]
+pub struct SchemaImport {
+    pub form: FormSpec,
+    pub warnings: Vec<String>,
+}
+
+pub fn run(command: QaCommand) -> Result<()> {
+    match command {
+        QaCommand::ImportSchema(args) => import_schema(&args),
+        QaCommand::ImportOpenapi(args) => super::qa_openapi::run(&args),
+        QaCommand::Lint(args) => super::qa_lint::run(&args),
+        QaCommand::Rpc(args) => super::qa_rpc::run(&args),
+        #[cfg(feature = "qa-serve")]
+        QaCommand::Serve(args) => super::qa_serve::run(&args),
+    }
+}
+
+pub fn import_schema(args: &QaImportSchemaArgs) -> Result<()> {
+    let document: JsonValue = read_spec_document(&args.schema, args.spec_format)?;
+    let schema = match &args.pointer {
+        Some(pointer) => document
+            .pointer(pointer)
+            .with_context(|| format!("{} has no value at {pointer}", args.schema.display()))?,
+        None => &document,
+    };
+    let id = match &args.id {
+        Some(id) => id.clone(),
+        None => default_form_id(&args.schema),
+    };
+    let import = from_json_schema(schema, &id, args.title.as_deref(), &args.form_version)?;
+    for warning in &import.warnings {
+        eprintln!("warning: {warning}");
+    }
+    write_spec_document(args.out.as_deref(), &import.form, args.format)
+}
+
+/// Synthesize a FormSpec from an object JSON Schema.
+///
+/// Each property becomes a question: `enum` maps to an enum question, `string`, `integer`,
+/// `number` and `boolean` to the matching type, and an array of objects to a list question.
+/// `pattern`, `minLength`/`maxLength`, `minimum`/`maximum` and well-known `format`s become
+/// constraints, and `format: password` or `writeOnly` marks the question secret. Required
+/// properties come first, in `required` order; the rest follow by name. Properties that have
+/// no question equivalent (nested objects, arrays of scalars) are skipped with a warning.
+pub fn from_json_schema(
+    schema: &JsonValue,
+    id: &str,
+    title: Option<&str>,
+    version: &str,
+) -> Result<SchemaImport> {
+    let Some(object) = schema.as_object() else {
+        bail!("json schema must be an object");
+    };
+    if let Some(kind) = schema_type(object)
+        && kind != "object"
+    {
+        bail!("json schema must describe an object, found type `{kind}`");
+    }
+    let mut warnings = Vec::new();
+    let questions = questions_from_properties(object, "", &mut warnings)?;
+    let title = title
+        .map(str::to_string)
+        .or_else(|| string_field(object, "title"))
+        .unwrap_or_else(|| id.to_string());
+    let form = FormSpec {
+        id: id.to_string(),
+        title,
+        version: version.to_string(),
+        description: string_field(object, "description"),
+        presentation: None,
+        progress_policy: None,
+        secrets_policy: None,
+        store: Vec::new(),
+        validations: Vec::new(),
+        includes: Vec::new(),
+        questions,
+    };
+    Ok(SchemaImport { form, warnings })
+}
+
+fn questions_from_properties(
+    object: &JsonMap<String, JsonValue>,
+    path: &str,
+    warnings: &mut Vec<String>,
+) -> Result<Vec<QuestionSpec>> {
+    let Some(properties) = object.get("properties").and_then(JsonValue::as_object) else {
+        bail!("json schema{} has no properties", describe_path(path));
+    };
+    let required = object
+        .get("required")
+        .and_then(JsonValue::as_array)
+        .map(|names| {
+            names
+                .iter()
+                .filter_map(JsonValue::as_str)
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    let ordered = required
+        .iter()
+        .copied()
+        .filter(|name| properties.contains_key(*name))
+        .chain(
+            properties
+                .keys()
+                .map(String::as_str)
+                .filter(|name| !required.contains(name)),
+        );
+
+    let mut questions = Vec::new();
+    for name in ordered {
+        let property_path = format!("{path}/properties/{name}");
+        if let Some(question) = question_from_property(
+            name,
+            &properties[name],
+            required.contains(&name),
+            &property_path,
+            warnings,
+        )? {
+            questions.push(question);
+        }
+    }
+    Ok(questions)
+}
+
+fn question_from_property(
+    name: &str,
+    property: &JsonValue,
+    required: bool,
+    path: &str,
+    warnings: &mut Vec<String>,
+) -> Result<Option<QuestionSpec>> {
+    let Some(property) = property.as_object() else {
+        warnings.push(format!("{path}: property schema is not an object; skipped"));
+        return Ok(None);
+    };
+    let choices = property
+        .get("enum")
+        .and_then(JsonValue::as_array)
+        .map(|values| {
+            values
+                .iter()
+                .filter(|value| !value.is_null())
+                .map(default_text)
+                .collect::<Vec<_>>()
+        });
+    let mut list = None;
+    let kind = if choices.is_some() {
+        QuestionType::Enum
+    } else {
+        match schema_type(property) {
+            Some("string") => QuestionType::String,
+            Some("integer") => QuestionType::Integer,
+            Some("number") => QuestionType::Number,
+            Some("boolean") => QuestionType::Boolean,
+            Some("array") => {
+                let items = property.get("items").and_then(JsonValue::as_object);
+                match items {
+                    Some(items) if items.contains_key("properties") => {
+                        list = Some(ListSpec {
+                            min_items: usize_field(property, "minItems"),
+                            max_items: usize_field(property, "maxItems"),
+                            fields: questions_from_properties(
+                                items,
+                                &format!("{path}/items"),
+                                warnings,
+                            )?,
+                        });
+                        QuestionType::List
+                    }
+                    _ => {
+                        warnings.push(format!(
+                            "{path}: only arrays of objects map to list questions; skipped"
+                        ));
+                        return Ok(None);
+                    }
+                }
+            }
+            Some(other) => {
+                warnings.push(format!(
+                    "{path}: type `{other}` has no question type; skipped"
+                ));
+                return Ok(None);
+            }
+            None => {
+                warnings.push(format!("{path}: no type or enum; skipped"));
+                return Ok(None);
+            }
+        }
+    };
+
+    let format = property.get("format").and_then(JsonValue::as_str);
+    let format_pattern = format.and_then(|format| {
+        FORMAT_PATTERNS
+            .iter()
+            .find(|(name, _)| *name == format)
+            .map(|(_, pattern)| pattern.to_string())
+    });
+    if let Some(format) = format
+        && format != "password"
+        && format_pattern.is_none()
+    {
+        warnings.push(format!("{path}: format `{format}` is not enforced"));
+    }
+    let constraint = Constraint {
+        pattern: string_field(property, "pattern").or(format_pattern),
+        min: property.get("minimum").and_then(JsonValue::as_f64),
+        max: property.get("maximum").and_then(JsonValue::as_f64),
+        min_len: usize_field(property, "minLength"),
+        max_len: usize_field(property, "maxLength"),
+    };
+    let has_constraint = constraint.pattern.is_some()
+        || constraint.min.is_some()
+        || constraint.max.is_some()
+        || constraint.min_len.is_some()
+        || constraint.max_len.is_some();
+
+    Ok(Some(QuestionSpec {
+        id: name.to_string(),
+        kind,
+        title: string_field(property, "title").unwrap_or_else(|| name.to_string()),
+        title_i18n: None,
+        description: string_field(property, "description"),
+        description_i18n: None,
+        required,
+        choices,
+        default_value: property.get("default").map(default_text),
+        secret: format == Some("password")
+            || property.get("writeOnly").and_then(JsonValue::as_bool) == Some(true),
+        visible_if: None,
+        constraint: has_constraint.then_some(constraint),
+        list,
+        computed: None,
+        policy: QuestionPolicy::default(),
+        computed_overridable: false,
+    }))
+}
+
+/// The schema `type`, taking the first non-null entry of a type array (`["string", "null"]`).
+fn schema_type(object: &JsonMap<String, JsonValue>) -> Option<&str> {
+    match object.get("type")? {
+        JsonValue::String(kind) => Some(kind),
+        JsonValue::Array(kinds) => kinds
+            .iter()
+            .filter_map(JsonValue::as_str)
+            .find(|kind| *kind != "null"),
+        _ => None,
+    }
+}
+
+fn string_field(object: &JsonMap<String, JsonValue>, field: &str) -> Option<String> {
+    object
+        .get(field)
+        .and_then(JsonValue::as_str)
+        .map(str::to_string)
+}
+
+fn usize_field(object: &JsonMap<String, JsonValue>, field: &str) -> Option<usize> {
+    object
+        .get(field)
+        .and_then(JsonValue::as_u64)
+        .and_then(|value| usize::try_from(value).ok())
+}
+
+/// QA defaults and choices are strings: keep string values as-is and render the rest as JSON.
+fn default_text(value: &JsonValue) -> String {
+    match value {
+        JsonValue::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn describe_path(path: &str) -> String {
+    if path.is_empty() {
+        String::new()
+    } else {
+        format!(" at {path}")
+    }
+}
+
+fn default_form_id(schema_path: &Path) -> String {
+    let name = schema_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("form");
+    let stem = name.split('.').next().unwrap_or(name);
+    if stem.is_empty() {
+        "form".to_string()
+    } else {
+        stem.to_string()
+    }
+}
+
+/// Read a QA spec document (FormSpec, ComponentQaSpec, ...) written as JSON or YAML.
+pub fn read_spec_document<T: DeserializeOwned>(path: &Path, format: SpecFormat) -> Result<T> {
+    let raw =
+        fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+    parse_spec_document(path, &raw, format)
+}
+
+pub fn parse_spec_document<T: DeserializeOwned>(
+    path: &Path,
+    raw: &str,
+    format: SpecFormat,
+) -> Result<T> {
+    match format.resolve(path, raw) {
+        SpecFormat::Yaml => serde_norway::from_str(raw)
+            .with_context(|| format!("invalid yaml qa spec: {}", path.display())),
+        _ => serde_json::from_str(raw)
+            .with_context(|| format!("invalid json qa spec: {}", path.display())),
+    }
+}
+
+/// Print a spec document to stdout, or write it to `out`. `Auto` picks YAML only for a
+/// `.yaml`/`.yml` output path.
+pub fn write_spec_document<T: Serialize>(
+    out: Option<&Path>,
+    value: &T,
+    format: SpecFormat,
+) -> Result<()> {
+    let yaml = match format {
+        SpecFormat::Auto => {
+            out.is_some_and(|path| SpecFormat::Auto.resolve(path, "{") == SpecFormat::Yaml)
+        }
+        format => format == SpecFormat::Yaml,
+    };
+    let rendered = if yaml {
+        serde_norway::to_string(value).context("failed to encode yaml")?
+    } else {
+        serde_json::to_string_pretty(value)? + "\n"
+    };
+    match out {
+        Some(path) => {
+            if let Some(parent) = path
+                .parent()
+                .filter(|parent| !parent.as_os_str().is_empty())
+            {
+                fs::create_dir_all(parent)
+                    .with_context(|| format!("failed to create {}", parent.display()))?;
+            }
+            fs::write(path, rendered)
+                .with_context(|| format!("failed to write {}", path.display()))?;
+            println!("wrote {}", path.display());
+        }
+        None => print!("{rendered}"),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{Value as JsonValue, json};
+
+    #[test]
+    fn yaml_and_json_specs_parse_to_the_same_value() {
+        let yaml = "id: setup\nquestions:\n  - id: region\n    description: |\n      Pick the region\n      closest to your users.\n";
+        let json = r#"{"id":"setup","questions":[{"id":"region","description":"Pick the region\nclosest to your users.\n"}]}"#;
+
+        let from_yaml: JsonValue =
+            parse_spec_document(Path::new("form.yaml"), yaml, SpecFormat::Auto).unwrap();
+        let from_json: JsonValue =
+            parse_spec_document(Path::new("form.json"), json, SpecFormat::Auto).unwrap();
+        assert_eq!(from_yaml, from_json);
+        assert_eq!(from_yaml["questions"][0]["id"], json!("region"));
+    }
+
+    #[test]
+    fn auto_sniffs_content_without_a_known_extension() {
+        let value: JsonValue =
+            parse_spec_document(Path::new("form.spec"), "id: setup\n", SpecFormat::Auto).unwrap();
+        assert_eq!(value, json!({"id": "setup"}));
+
+        let value: JsonValue = parse_spec_document(
+            Path::new("form.spec"),
+            r#" {"id":"setup"}"#,
+            SpecFormat::Auto,
+        )
+        .unwrap();
+        assert_eq!(value, json!({"id": "setup"}));
+    }
+
+    #[test]
+    fn explicit_format_overrides_the_extension() {
+        let err = parse_spec_document::<JsonValue>(
+            Path::new("form.json"),
+            "id: setup\n",
+            SpecFormat::Json,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("invalid json qa spec"));
+
+        let value: JsonValue =
+            parse_spec_document(Path::new("form.json"), "id: setup\n", SpecFormat::Yaml).unwrap();
+        assert_eq!(value, json!({"id": "setup"}));
+    }
+
+    fn config_schema() -> JsonValue {
+        json!({
+            "type": "object",
+            "title": "Weather settings",
+            "required": ["region", "api_key"],
+            "properties": {
+                "api_key": { "type": "string", "format": "password", "title": "API key" },
+                "contact": { "type": "string", "format": "email" },
+                "enabled": { "type": "boolean", "default": true },
+                "region": { "enum": ["eu", "us"], "description": "Data residency" },
+                "retries": { "type": ["integer", "null"], "minimum": 0, "maximum": 5, "default": 3 },
+                "tags": { "type": "array", "items": { "type": "string" } },
+                "proxy": { "type": "object", "properties": { "url": { "type": "string" } } }
+            }
+        })
+    }
+
+    #[test]
+    fn imports_properties_as_questions() {
+        let import = from_json_schema(&config_schema(), "weather", None, "1.0.0").unwrap();
+        let form = &import.form;
+        assert_eq!(form.id, "weather");
+        assert_eq!(form.title, "Weather settings");
+        assert_eq!(form.version, "1.0.0");
+
+        let ids = form
+            .questions
+            .iter()
+            .map(|q| q.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, ["region", "api_key", "contact", "enabled", "retries"]);
+
+        let region = &form.questions[0];
+        assert_eq!(region.kind, QuestionType::Enum);
+        assert!(region.required);
+        assert_eq!(
+            region.choices.as_deref(),
+            Some(&["eu".to_string(), "us".to_string()][..])
+        );
+        assert_eq!(region.description.as_deref(), Some("Data residency"));
+
+        let api_key = &form.questions[1];
+        assert_eq!(api_key.title, "API key");
+        assert!(api_key.secret);
+        assert!(api_key.constraint.is_none());
+
+        let contact = &form.questions[2];
+        assert!(!contact.required);
+        assert!(contact.constraint.as_ref().unwrap().pattern.is_some());
+
+        assert_eq!(form.questions[3].kind, QuestionType::Boolean);
+        assert_eq!(form.questions[3].default_value.as_deref(), Some("true"));
+
+        let retries = &form.questions[4];
+        assert_eq!(retries.kind, QuestionType::Integer);
+        assert_eq!(retries.default_value.as_deref(), Some("3"));
+        let constraint = retries.constraint.as_ref().unwrap();
+        assert_eq!((constraint.min, constraint.max), (Some(0.0), Some(5.0)));
+
+        assert_eq!(import.warnings.len(), 2, "{:?}", import.warnings);
+        assert!(import.warnings[0].starts_with("/properties/proxy"));
+        assert!(import.warnings[1].starts_with("/properties/tags"));
+
+        let answers =
+            json!({"region": "eu", "api_key": "k", "contact": "ops@example.com", "retries": 2});
+        assert!(qa_spec::validate(form, &answers).valid);
+        let answers = json!({"region": "eu", "api_key": "k", "contact": "not-an-email"});
+        assert!(!qa_spec::validate(form, &answers).valid);
+    }
+
+    #[test]
+    fn imports_arrays_of_objects_as_list_questions() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "routes": {
+                    "type": "array",
+                    "minItems": 1,
+                    "items": {
+                        "type": "object",
+                        "required": ["path"],
+                        "properties": { "path": { "type": "string" }, "weight": { "type": "number" } }
+                    }
+                }
+            }
+        });
+        let import = from_json_schema(&schema, "routing", Some("Routing"), "0.1.0").unwrap();
+        assert!(import.warnings.is_empty());
+        assert_eq!(import.form.title, "Routing");
+        let routes = &import.form.questions[0];
+        assert_eq!(routes.kind, QuestionType::List);
+        let list = routes.list.as_ref().unwrap();
+        assert_eq!(list.min_items, Some(1));
+        assert_eq!(list.fields.len(), 2);
+        assert!(list.fields[0].required);
+        assert_eq!(list.fields[1].kind, QuestionType::Number);
+    }
+
+    #[test]
+    fn rejects_schemas_without_object_properties() {
+        let err = from_json_schema(&json!({"type": "string"}), "x", None, "0.1.0").unwrap_err();
+        assert!(err.to_string().contains("must describe an object"));
+        let err = from_json_schema(&json!({"type": "object"}), "x", None, "0.1.0").unwrap_err();
+        assert!(err.to_string().contains("has no properties"));
+    }
+
+    #[test]
+    fn import_schema_reads_a_manifest_config_schema_and_writes_yaml() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let manifest = temp.path().join("component.manifest.json");
+        fs::write(
+            &manifest,
+            serde_json::to_string(&json!({ "config_schema": config_schema() })).unwrap(),
+        )
+        .unwrap();
+        let out = temp.path().join("forms/setup.yaml");
+
+        import_schema(&QaImportSchemaArgs {
+            schema: manifest,
+            pointer: Some("/config_schema".to_string()),
+            id: Some("setup".to_string()),
+            title: None,
+            form_version: DEFAULT_FORM_VERSION.to_string(),
+            out: Some(out.clone()),
+            format: SpecFormat::Auto,
+            spec_format: SpecFormat::Auto,
+        })
+        .unwrap();
+
+        assert!(
+            !fs::read_to_string(&out)
+                .unwrap()
+                .trim_start()
+                .starts_with('{')
+        );
+        let form: FormSpec = read_spec_document(&out, SpecFormat::Auto).unwrap();
+        assert_eq!(form.id, "setup");
+        assert_eq!(form.questions.len(), 5);
+    }
+
+    #[test]
+    fn default_form_id_strips_every_extension() {
+        assert_eq!(
+            default_form_id(Path::new("schemas/config.schema.json")),
+            "config"
+        );
+        assert_eq!(default_form_id(Path::new(".json")), "form");
+    }
+}
@@ -0,0 +1,570 @@
+#![cfg(feature = "cli")]
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use anyhow::{Context, Result, bail};
+use clap::Args;
+use qa_spec::{Expr, FormSpec, QuestionSpec, QuestionType};
+use regex::Regex;
+use serde::Serialize;
+
+use super::qa::SpecFormat;
+use crate::i18n_check::{LocaleMessages, SOURCE_LOCALE};
+
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Args, Debug, Clone)]
+pub struct QaLintArgs {
+    /// FormSpec files to check (JSON or YAML)
+    #[arg(value_name = "SPEC", required = true)]
+    pub specs: Vec<PathBuf>,
+    /// Locale bundle directory whose en.json must define every i18n key the specs use
+    #[arg(long = "i18n-dir", value_name = "PATH")]
+    pub i18n_dir: Option<PathBuf>,
+    /// Encoding of the spec files
+    #[arg(long = "spec-format", value_enum, default_value = "auto")]
+    pub spec_format: SpecFormat,
+    /// Keep running and re-check whenever a spec or en.json changes
+    #[arg(long = "watch")]
+    pub watch: bool,
+    /// Emit JSON instead of human-readable output
+    #[arg(long = "json")]
+    pub json: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LintSeverity {
+    Error,
+    Warning,
+}
+
+/// One finding in a spec file. `line`/`column` are 1-based. For semantic findings they are
+/// best-effort: the spec is matched back to the source text by the offending value.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LintDiagnostic {
+    pub file: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+    pub severity: LintSeverity,
+    pub code: &'static str,
+    /// JSON pointer into the spec.
+    pub path: String,
+    pub message: String,
+}
+
+pub fn run(args: &QaLintArgs) -> Result<()> {
+    if args.watch {
+        return watch(args);
+    }
+    let diagnostics = lint_all(args)?;
+    print_diagnostics(&diagnostics, args.json)?;
+    let errors = error_count(&diagnostics);
+    if errors > 0 {
+        bail!("qa lint: {errors} error(s)");
+    }
+    Ok(())
+}
+
+fn watch(args: &QaLintArgs) -> Result<()> {
+    let mut watched = args.specs.clone();
+    if let Some(dir) = &args.i18n_dir {
+        watched.push(dir.join(format!("{SOURCE_LOCALE}.json")));
+    }
+    let mut last_seen = None;
+    loop {
+        let stamps = watched.iter().map(modified).collect::<Vec<_>>();
+        if last_seen.as_ref() != Some(&stamps) {
+            last_seen = Some(stamps);
+            match lint_all(args) {
+                Ok(diagnostics) => {
+                    print_diagnostics(&diagnostics, args.json)?;
+                    if !args.json {
+                        println!(
+                            "qa lint: {} error(s), {} warning(s); watching for changes",
+                            error_count(&diagnostics),
+                            diagnostics.len() - error_count(&diagnostics)
+                        );
+                    }
+                }
+                Err(err) => eprintln!("qa lint: {err:#}"),
+            }
+        }
+        thread::sleep(WATCH_INTERVAL);
+    }
+}
+
+fn modified(path: &PathBuf) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+fn error_count(diagnostics: &[LintDiagnostic]) -> usize {
+    diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.severity == LintSeverity::Error)
+        .count()
+}
+
+fn lint_all(args: &QaLintArgs) -> Result<Vec<LintDiagnostic>> {
+    let i18n = match &args.i18n_dir {
+        Some(dir) => {
+            let path = dir.join(format!("{SOURCE_LOCALE}.json"));
+            let raw = fs::read_to_string(&path)
+                .with_context(|| format!("failed to read {}", path.display()))?;
+            Some(
+                serde_json::from_str::<LocaleMessages>(&raw)
+                    .with_context(|| format!("invalid locale bundle: {}", path.display()))?,
+            )
+        }
+        None => None,
+    };
+    let mut diagnostics = Vec::new();
+    for spec in &args.specs {
+        let raw = fs::read_to_string(spec)
+            .with_context(|| format!("failed to read {}", spec.display()))?;
+        diagnostics.extend(lint_source(spec, &raw, args.spec_format, i18n.as_ref()));
+    }
+    Ok(diagnostics)
+}
+
+fn print_diagnostics(diagnostics: &[LintDiagnostic], json: bool) -> Result<()> {
+    if json {
+        println!("{}", serde_json::to_string_pretty(diagnostics)?);
+        return Ok(());
+    }
+    for diagnostic in diagnostics {
+        let position = match (diagnostic.line, diagnostic.column) {
+            (Some(line), Some(column)) => format!(":{line}:{column}"),
+            _ => String::new(),
+        };
+        let severity = match diagnostic.severity {
+            LintSeverity::Error => "error",
+            LintSeverity::Warning => "warning",
+        };
+        println!(
+            "{}{position}: {severity}[{}] {} ({})",
+            diagnostic.file, diagnostic.code, diagnostic.message, diagnostic.path
+        );
+    }
+    Ok(())
+}
+
+/// Parse one FormSpec document and report parse errors or semantic problems.
+pub fn lint_source(
+    file: &Path,
+    raw: &str,
+    format: SpecFormat,
+    i18n: Option<&LocaleMessages>,
+) -> Vec<LintDiagnostic> {
+    let mut lint = Linter {
+        file: file.display().to_string(),
+        raw,
+        diagnostics: Vec::new(),
+    };
+    let parsed = match format.resolve(file, raw) {
+        SpecFormat::Yaml => serde_norway::from_str::<FormSpec>(raw).map_err(|err| {
+            let location = err.location();
+            (
+                err.to_string(),
+                location.as_ref().map(|location| location.line()),
+                location.as_ref().map(|location| location.column()),
+            )
+        }),
+        _ => serde_json::from_str::<FormSpec>(raw)
+            .map_err(|err| (err.to_string(), Some(err.line()), Some(err.column()))),
+    };
+    match parsed {
+        Ok(spec) => lint.check_spec(&spec, i18n),
+        Err((message, line, column)) => lint.diagnostics.push(LintDiagnostic {
+            file: lint.file.clone(),
+            line,
+            column,
+            severity: LintSeverity::Error,
+            code: "qa.lint.parse",
+            path: String::new(),
+            message,
+        }),
+    }
+    lint.diagnostics
+}
+
+struct Linter<'a> {
+    file: String,
+    raw: &'a str,
+    diagnostics: Vec<LintDiagnostic>,
+}
+
+impl Linter<'_> {
+    fn check_spec(&mut self, spec: &FormSpec, i18n: Option<&LocaleMessages>) {
+        let known = spec
+            .questions
+            .iter()
+            .map(|question| question.id.clone())
+            .collect::<BTreeSet<_>>();
+        self.check_questions(&spec.questions, "/questions", &known, i18n);
+        for (index, validation) in spec.validations.iter().enumerate() {
+            let path = format!("/validations/{index}");
+            for (field_index, field) in validation.fields.iter().enumerate() {
+                if !known.contains(field) {
+                    self.report(
+                        LintSeverity::Error,
+                        "qa.lint.unknown_id",
+                        format!("{path}/fields/{field_index}"),
+                        format!("validation field `{field}` is not a question id"),
+                        field,
+                        0,
+                    );
+                }
+            }
+            self.check_expr(&validation.condition, &format!("{path}/condition"), &known);
+        }
+    }
+
+    fn check_questions(
+        &mut self,
+        questions: &[QuestionSpec],
+        base: &str,
+        known: &BTreeSet<String>,
+        i18n: Option<&LocaleMessages>,
+    ) {
+        let mut seen = BTreeMap::new();
+        for (index, question) in questions.iter().enumerate() {
+            let path = format!("{base}/{index}");
+            let id = &question.id;
+            let occurrence = seen.entry(id.as_str()).or_insert(0usize);
+            *occurrence += 1;
+            let occurrence = *occurrence - 1;
+            if occurrence > 0 {
+                self.report(
+                    LintSeverity::Error,
+                    "qa.lint.duplicate_id",
+                    format!("{path}/id"),
+                    format!("question id `{id}` is already used"),
+                    id,
+                    occurrence,
+                );
+            }
+
+            let choices = question.choices.as_deref().unwrap_or_default();
+            if question.kind == QuestionType::Enum && choices.is_empty() {
+                self.report(
+                    LintSeverity::Error,
+                    "qa.lint.enum_choices",
+                    format!("{path}/choices"),
+                    format!("enum question `{id}` has no choices"),
+                    id,
+                    0,
+                );
+            }
+            if question.kind != QuestionType::Enum && !choices.is_empty() {
+                self.report(
+                    LintSeverity::Warning,
+                    "qa.lint.enum_choices",
+                    format!("{path}/choices"),
+                    format!("choices on non-enum question `{id}` are ignored"),
+                    id,
+                    0,
+                );
+            }
+            if let Some(default) = &question.default_value
+                && question.kind == QuestionType::Enum
+                && !choices.is_empty()
+                && !choices.contains(default)
+            {
+                self.report(
+                    LintSeverity::Error,
+                    "qa.lint.default_not_a_choice",
+                    format!("{path}/default_value"),
+                    format!("default `{default}` of `{id}` is not one of its choices"),
+                    default,
+                    0,
+                );
+            }
+            if let Some(pattern) = question
+                .constraint
+                .as_ref()
+                .and_then(|constraint| constraint.pattern.as_deref())
+                && let Err(err) = Regex::new(pattern)
+            {
+                self.report(
+                    LintSeverity::Error,
+                    "qa.lint.bad_pattern",
+                    format!("{path}/constraint/pattern"),
+                    format!("pattern of `{id}` does not compile: {err}"),
+                    pattern,
+                    0,
+                );
+            }
+
+            if let Some(i18n) = i18n {
+                for (field, text) in [
+                    ("title_i18n", &question.title_i18n),
+                    ("description_i18n", &question.description_i18n),
+                ] {
+                    if let Some(text) = text
+                        && !i18n.contains_key(&text.key)
+                    {
+                        self.report(
+                            LintSeverity::Error,
+                            "qa.lint.i18n_missing",
+                            format!("{path}/{field}/key"),
+                            format!("i18n key `{}` is missing from en.json", text.key),
+                            &text.key,
+                            0,
+                        );
+                    }
+                }
+            }
+
+            if let Some(expr) = &question.visible_if {
+                self.check_expr(expr, &format!("{path}/visible_if"), known);
+            }
+            if let Some(expr) = &question.computed {
+                self.check_expr(expr, &format!("{path}/computed"), known);
+            }
+
+            match (&question.kind, &question.list) {
+                (QuestionType::List, Some(list)) => {
+                    // List fields may refer to their siblings as well as to top-level answers.
+                    let mut scope = known.clone();
+                    scope.extend(list.fields.iter().map(|field| field.id.clone()));
+                    self.check_questions(
+                        &list.fields,
+                        &format!("{path}/list/fields"),
+                        &scope,
+                        i18n,
+                    );
+                }
+                (QuestionType::List, None) => self.report(
+                    LintSeverity::Error,
+                    "qa.lint.list_fields",
+                    format!("{path}/list"),
+                    format!("list question `{id}` has no list fields"),
+                    id,
+                    0,
+                ),
+                _ => {}
+            }
+        }
+    }
+
+    fn check_expr(&mut self, expr: &Expr, path: &str, known: &BTreeSet<String>) {
+        match expr {
+            Expr::Literal { .. } => {}
+            Expr::Answer { path: answer } | Expr::IsSet { path: answer } => {
+                self.check_answer_ref(answer, &format!("{path}/path"), known);
+            }
+            Expr::Var { path: var } => {
+                let answer = var
+                    .strip_prefix("answers.")
+                    .or_else(|| var.strip_prefix("/answers/"));
+                if let Some(answer) = answer {
+                    self.check_answer_ref(answer, &format!("{path}/path"), known);
+                }
+            }
+            Expr::And { expressions } | Expr::Or { expressions } => {
+                if expressions.is_empty() {
+                    self.report(
+                        LintSeverity::Warning,
+                        "qa.lint.empty_expression",
+                        format!("{path}/expressions"),
+                        "expression list is empty".to_string(),
+                        "expressions",
+                        0,
+                    );
+                }
+                for (index, expression) in expressions.iter().enumerate() {
+                    self.check_expr(expression, &format!("{path}/expressions/{index}"), known);
+                }
+            }
+            Expr::Not { expression } => {
+                self.check_expr(expression, &format!("{path}/expression"), known)
+            }
+            Expr::Eq { left, right }
+            | Expr::Ne { left, right }
+            | Expr::Lt { left, right }
+            | Expr::Lte { left, right }
+            | Expr::Gt { left, right }
+            | Expr::Gte { left, right } => {
+                self.check_expr(left, &format!("{path}/left"), known);
+                self.check_expr(right, &format!("{path}/right"), known);
+            }
+        }
+    }
+
+    fn check_answer_ref(&mut self, answer: &str, path: &str, known: &BTreeSet<String>) {
+        let Some(id) = answer.split(['/', '.']).find(|segment| !segment.is_empty()) else {
+            return;
+        };
+        if !known.contains(id) {
+            self.report(
+                LintSeverity::Error,
+                "qa.lint.unknown_id",
+                path.to_string(),
+                format!("expression refers to unknown question `{id}`"),
+                answer,
+                0,
+            );
+        }
+    }
+
+    fn report(
+        &mut self,
+        severity: LintSeverity,
+        code: &'static str,
+        path: String,
+        message: String,
+        token: &str,
+        occurrence: usize,
+    ) {
+        let (line, column) = match locate(self.raw, token, occurrence) {
+            Some((line, column)) => (Some(line), Some(column)),
+            None => (None, None),
+        };
+        self.diagnostics.push(LintDiagnostic {
+            file: self.file.clone(),
+            line,
+            column,
+            severity,
+            code,
+            path,
+            message,
+        });
+    }
+}
+
+/// 1-based line and column of the `nth` (0-based) whole-token occurrence of `token` in `raw`.
+fn locate(raw: &str, token: &str, nth: usize) -> Option<(usize, usize)> {
+    if token.is_empty() {
+        return None;
+    }
+    let is_word = |c: char| c.is_alphanumeric() || c == '_' || c == '-' || c == '.';
+    let offset = raw
+        .match_indices(token)
+        .filter(|(start, _)| {
+            let before = raw[..*start].chars().next_back();
+            let after = raw[start + token.len()..].chars().next();
+            !before.is_some_and(is_word) && !after.is_some_and(is_word)
+        })
+        .nth(nth)?
+        .0;
+    let line_start = raw[..offset].rfind('\n').map_or(0, |newline| newline + 1);
+    let line = raw[..offset].matches('\n').count() + 1;
+    Some((line, raw[line_start..offset].chars().count() + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn codes(diagnostics: &[LintDiagnostic]) -> Vec<&str> {
+        diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.code)
+            .collect()
+    }
+
+    #[test]
+    fn clean_spec_has_no_findings() {
+        let raw = r#"{
+  "id": "setup",
+  "title": "Setup",
+  "version": "0.1.0",
+  "questions": [
+    { "id": "region", "type": "enum", "title": "Region", "choices": ["eu", "us"], "default_value": "eu" },
+    { "id": "zone", "type": "string", "title": "Zone",
+      "visible_if": { "op": "eq", "left": { "op": "answer", "path": "region" }, "right": { "op": "literal", "value": "eu" } } }
+  ]
+}"#;
+        assert!(lint_source(Path::new("form.json"), raw, SpecFormat::Auto, None).is_empty());
+    }
+
+    #[test]
+    fn reports_semantic_findings_with_positions() {
+        let raw = "id: setup
+title: Setup
+version: 0.1.0
+questions:
+  - id: region
+    type: enum
+    title: Region
+    choices: [eu, us]
+    default_value: apac
+  - id: region
+    type: string
+    title: Again
+    title_i18n:
+      key: qa.region.again
+    visible_if:
+      op: is_set
+      path: tenant
+    constraint:
+      pattern: \"([a-z]\"
+validations:
+  - message: bad
+    fields: [zone]
+    condition:
+      op: literal
+      value: false
+";
+        let mut i18n = LocaleMessages::new();
+        i18n.insert("qa.other".to_string(), "Other".to_string());
+        let diagnostics = lint_source(Path::new("form.yaml"), raw, SpecFormat::Auto, Some(&i18n));
+        assert_eq!(
+            codes(&diagnostics),
+            [
+                "qa.lint.default_not_a_choice",
+                "qa.lint.duplicate_id",
+                "qa.lint.bad_pattern",
+                "qa.lint.i18n_missing",
+                "qa.lint.unknown_id",
+                "qa.lint.unknown_id",
+            ]
+        );
+
+        let duplicate = &diagnostics[1];
+        assert_eq!(duplicate.path, "/questions/1/id");
+        assert_eq!((duplicate.line, duplicate.column), (Some(10), Some(9)));
+        let unknown = &diagnostics[4];
+        assert_eq!(unknown.path, "/questions/1/visible_if/path");
+        assert_eq!(unknown.line, Some(17));
+        assert_eq!(diagnostics[5].path, "/validations/0/fields/0");
+        assert_eq!(diagnostics[5].line, Some(22));
+    }
+
+    #[test]
+    fn parse_errors_carry_the_parser_position() {
+        let raw = "{\n  \"id\": \"setup\",\n  \"questions\": [\n    { \"id\": \"a\", \"type\": \"dropdown\" }\n  ]\n}";
+        let diagnostics = lint_source(Path::new("form.json"), raw, SpecFormat::Auto, None);
+        assert_eq!(codes(&diagnostics), ["qa.lint.parse"]);
+        assert_eq!(diagnostics[0].line, Some(4));
+
+        let raw = "id: setup\nquestions:\n  - id: a\n    visible_if:\n      op: matches\n";
+        let diagnostics = lint_source(Path::new("form.yaml"), raw, SpecFormat::Auto, None);
+        assert_eq!(codes(&diagnostics), ["qa.lint.parse"]);
+        assert!(diagnostics[0].line.is_some());
+    }
+
+    #[test]
+    fn list_fields_may_reference_siblings() {
+        let raw = r#"{
+  "id": "routes", "title": "Routes", "version": "0.1.0",
+  "questions": [
+    { "id": "routes", "type": "list", "title": "Routes", "list": { "fields": [
+      { "id": "path", "type": "string", "title": "Path" },
+      { "id": "weight", "type": "number", "title": "Weight", "visible_if": { "op": "is_set", "path": "path" } }
+    ] } },
+    { "id": "mode", "type": "enum", "title": "Mode" }
+  ]
+}"#;
+        let diagnostics = lint_source(Path::new("form.json"), raw, SpecFormat::Auto, None);
+        assert_eq!(codes(&diagnostics), ["qa.lint.enum_choices"]);
+        assert_eq!(diagnostics[0].path, "/questions/1/choices");
+    }
+}
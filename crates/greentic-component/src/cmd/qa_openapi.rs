@@ -0,0 +1,443 @@
+#![cfg(feature = "cli")]
+
+//! `qa import-openapi`: one FormSpec per OpenAPI operation, so tool components that wrap an
+//! HTTP API get their invocation wizards from the API description.
+//!
+//! Each operation's parameters and `application/json` request body fields are gathered into one
+//! object JSON Schema and converted by [`from_json_schema`], so the question mapping is the one
+//! `qa import-schema` uses.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result, bail};
+use clap::Args;
+use qa_spec::FormSpec;
+use serde_json::{Map as JsonMap, Value as JsonValue};
+
+use super::qa::{
+    DEFAULT_FORM_VERSION, SpecFormat, from_json_schema, read_spec_document, write_spec_document,
+};
+
+const METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+/// Header parameters OpenAPI says to ignore: the HTTP client sets them itself.
+const IGNORED_HEADERS: [&str; 3] = ["accept", "content-type", "authorization"];
+/// `$ref` chains longer than this are taken to be recursive schemas.
+const MAX_REF_DEPTH: usize = 32;
+
+#[derive(Args, Debug, Clone)]
+pub struct QaImportOpenapiArgs {
+    /// OpenAPI 3 document (JSON or YAML)
+    #[arg(value_name = "OPENAPI")]
+    pub spec: PathBuf,
+    /// Only import this operation (operationId, or method_path for operations without one)
+    #[arg(long = "operation", value_name = "ID")]
+    pub operations: Vec<String>,
+    /// Directory the FormSpecs are written to, one file per operation
+    #[arg(long = "out-dir", value_name = "DIR", default_value = "forms")]
+    pub out_dir: PathBuf,
+    /// Form version
+    #[arg(long = "form-version", value_name = "VERSION", default_value = DEFAULT_FORM_VERSION)]
+    pub form_version: String,
+    /// Output encoding; auto writes JSON
+    #[arg(long = "format", value_enum, default_value = "auto")]
+    pub format: SpecFormat,
+    /// Encoding of the OpenAPI document
+    #[arg(long = "spec-format", value_enum, default_value = "auto")]
+    pub spec_format: SpecFormat,
+}
+
+/// FormSpecs synthesized from an OpenAPI document, plus what could not be mapped.
+#[derive(Debug, Clone)]
+pub struct OpenApiImport {
+    pub forms: Vec<FormSpec>,
+    pub warnings: Vec<String>,
+}
+
+pub fn run(args: &QaImportOpenapiArgs) -> Result<()> {
+    let document: JsonValue = read_spec_document(&args.spec, args.spec_format)?;
+    let import = from_openapi(&document, &args.operations, &args.form_version)
+        .with_context(|| format!("failed to import {}", args.spec.display()))?;
+    for warning in &import.warnings {
+        eprintln!("warning: {warning}");
+    }
+    if import.forms.is_empty() {
+        bail!(
+            "{} has no operation with parameters or a JSON request body",
+            args.spec.display()
+        );
+    }
+    let extension = if args.format == SpecFormat::Yaml {
+        "yaml"
+    } else {
+        "json"
+    };
+    for form in &import.forms {
+        let out = args.out_dir.join(format!("{}.{extension}", slug(&form.id)));
+        write_spec_document(Some(&out), form, args.format)?;
+    }
+    Ok(())
+}
+
+/// Synthesize one FormSpec per operation of an OpenAPI 3 document.
+///
+/// The form id is the `operationId`, or `<method>_<path>` when there is none, and the title is
+/// the operation `summary`. Path parameters and parameters marked `required` become required
+/// questions, as do request body fields the body schema requires. Local `$ref`s are inlined.
+/// Operations without inputs, or whose inputs cannot be mapped, are skipped with a warning.
+/// `operations` limits the import to those ids; an id the document lacks is an error.
+pub fn from_openapi(
+    document: &JsonValue,
+    operations: &[String],
+    version: &str,
+) -> Result<OpenApiImport> {
+    if document.get("swagger").is_some() {
+        bail!("Swagger 2.0 documents are not supported; convert the document to OpenAPI 3 first");
+    }
+    let paths = document
+        .get("paths")
+        .and_then(JsonValue::as_object)
+        .context("openapi document has no paths")?;
+    let mut forms = Vec::new();
+    let mut warnings = Vec::new();
+    let mut seen = Vec::new();
+    for (path, item) in paths {
+        let item = match item.get("$ref").and_then(JsonValue::as_str) {
+            Some(reference) => follow(reference, document)?,
+            None => item,
+        };
+        for method in METHODS {
+            let Some(operation) = item.get(method) else {
+                continue;
+            };
+            let id = operation
+                .get("operationId")
+                .and_then(JsonValue::as_str)
+                .map_or_else(|| slug(&format!("{method}_{path}")), str::to_string);
+            if !operations.is_empty() && !operations.contains(&id) {
+                continue;
+            }
+            seen.push(id.clone());
+            let mut operation_warnings = Vec::new();
+            match import_operation(
+                document,
+                item.get("parameters"),
+                operation,
+                &id,
+                version,
+                &mut operation_warnings,
+            ) {
+                Ok(Some(form)) => forms.push(form),
+                Ok(None) => operation_warnings
+                    .push("no parameters or JSON request body; skipped".to_string()),
+                Err(err) => operation_warnings.push(format!("{err:#}; skipped")),
+            }
+            warnings.extend(
+                operation_warnings
+                    .into_iter()
+                    .map(|warning| format!("{id}: {warning}")),
+            );
+        }
+    }
+    if let Some(missing) = operations.iter().find(|id| !seen.contains(id)) {
+        bail!("openapi document has no operation `{missing}`");
+    }
+    Ok(OpenApiImport { forms, warnings })
+}
+
+fn import_operation(
+    document: &JsonValue,
+    shared: Option<&JsonValue>,
+    operation: &JsonValue,
+    id: &str,
+    version: &str,
+    warnings: &mut Vec<String>,
+) -> Result<Option<FormSpec>> {
+    let Some(schema) = operation_schema(document, shared, operation, warnings)? else {
+        return Ok(None);
+    };
+    let title = operation.get("summary").and_then(JsonValue::as_str);
+    let import = from_json_schema(&schema, id, title, version)?;
+    warnings.extend(import.warnings);
+    Ok(Some(import.form))
+}
+
+/// One object schema holding an operation's parameters and JSON body fields, or `None` when it
+/// has neither. Operation parameters replace path-level ones with the same name and location.
+fn operation_schema(
+    document: &JsonValue,
+    shared: Option<&JsonValue>,
+    operation: &JsonValue,
+    warnings: &mut Vec<String>,
+) -> Result<Option<JsonValue>> {
+    let mut parameters = BTreeMap::new();
+    for list in [shared, operation.get("parameters")].into_iter().flatten() {
+        for parameter in list.as_array().into_iter().flatten() {
+            let parameter = resolve(parameter, document, 0)?;
+            let (Some(name), Some(location)) = (
+                parameter.get("name").and_then(JsonValue::as_str),
+                parameter.get("in").and_then(JsonValue::as_str),
+            ) else {
+                warnings.push("parameter without a name or location; skipped".to_string());
+                continue;
+            };
+            parameters.insert((location.to_string(), name.to_string()), parameter.clone());
+        }
+    }
+
+    let mut properties = JsonMap::new();
+    let mut required = Vec::new();
+    for ((location, name), parameter) in parameters {
+        if location == "header" && IGNORED_HEADERS.contains(&name.to_ascii_lowercase().as_str()) {
+            continue;
+        }
+        let Some(mut schema) = parameter.get("schema").cloned() else {
+            warnings.push(format!("parameter `{name}` has no schema; skipped"));
+            continue;
+        };
+        if properties.contains_key(&name) {
+            warnings.push(format!(
+                "parameter `{name}` appears in more than one location; kept the first"
+            ));
+            continue;
+        }
+        if let (Some(object), Some(description)) =
+            (schema.as_object_mut(), parameter.get("description"))
+        {
+            object
+                .entry("description")
+                .or_insert_with(|| description.clone());
+        }
+        if location == "path" || parameter.get("required") == Some(&JsonValue::Bool(true)) {
+            required.push(JsonValue::String(name.clone()));
+        }
+        properties.insert(name, schema);
+    }
+
+    if let Some(body) = operation.get("requestBody") {
+        let body = resolve(body, document, 0)?;
+        match body
+            .pointer("/content/application~1json/schema")
+            .map(|schema| (schema, schema.get("properties")))
+        {
+            Some((schema, Some(JsonValue::Object(fields)))) => {
+                let body_required = schema.get("required").and_then(JsonValue::as_array);
+                for (name, field) in fields {
+                    if properties.contains_key(name) {
+                        warnings.push(format!(
+                            "request body field `{name}` has the name of a parameter; skipped"
+                        ));
+                        continue;
+                    }
+                    if body_required.is_some_and(|names| names.iter().any(|n| n == name)) {
+                        required.push(JsonValue::String(name.clone()));
+                    }
+                    properties.insert(name.clone(), field.clone());
+                }
+            }
+            Some(_) => warnings.push("request body is not an object schema; skipped".to_string()),
+            None => {
+                warnings.push("request body has no application/json schema; skipped".to_string())
+            }
+        }
+    }
+
+    if properties.is_empty() {
+        return Ok(None);
+    }
+    let mut schema = JsonMap::new();
+    schema.insert("type".into(), "object".into());
+    if let Some(description) = operation.get("description") {
+        schema.insert("description".into(), description.clone());
+    }
+    schema.insert("properties".into(), JsonValue::Object(properties));
+    schema.insert("required".into(), JsonValue::Array(required));
+    Ok(Some(JsonValue::Object(schema)))
+}
+
+/// `value` with every local `$ref` (`#/components/...`) replaced by its target.
+fn resolve(value: &JsonValue, document: &JsonValue, depth: usize) -> Result<JsonValue> {
+    if depth > MAX_REF_DEPTH {
+        bail!("$ref chain deeper than {MAX_REF_DEPTH} (recursive schema?)");
+    }
+    match value {
+        JsonValue::Object(object) => {
+            if let Some(reference) = object.get("$ref").and_then(JsonValue::as_str) {
+                return resolve(follow(reference, document)?, document, depth + 1);
+            }
+            object
+                .iter()
+                .map(|(key, value)| Ok((key.clone(), resolve(value, document, depth)?)))
+                .collect::<Result<JsonMap<_, _>>>()
+                .map(JsonValue::Object)
+        }
+        JsonValue::Array(items) => items
+            .iter()
+            .map(|item| resolve(item, document, depth))
+            .collect::<Result<Vec<_>>>()
+            .map(JsonValue::Array),
+        other => Ok(other.clone()),
+    }
+}
+
+fn follow<'a>(reference: &str, document: &'a JsonValue) -> Result<&'a JsonValue> {
+    let pointer = reference
+        .strip_prefix('#')
+        .with_context(|| format!("only local $refs are supported, found `{reference}`"))?;
+    document
+        .pointer(pointer)
+        .with_context(|| format!("unresolved $ref `{reference}`"))
+}
+
+/// `text` with every run of characters other than ASCII letters, digits, `-` and `_` replaced
+/// by one `_`, for form ids and file names.
+fn slug(text: &str) -> String {
+    let mut slug = String::new();
+    for ch in text.chars() {
+        if ch.is_ascii_alphanumeric() || ch == '-' || ch == '_' {
+            slug.push(ch);
+        } else if !slug.ends_with('_') {
+            slug.push('_');
+        }
+    }
+    let slug = slug.trim_matches('_');
+    if slug.is_empty() {
+        "operation".to_string()
+    } else {
+        slug.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use qa_spec::QuestionType;
+    use serde_json::json;
+
+    fn petstore() -> JsonValue {
+        json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Pets", "version": "1.0.0" },
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "summary": "List pets",
+                        "parameters": [
+                            { "name": "limit", "in": "query", "schema": { "type": "integer" } },
+                            { "name": "Authorization", "in": "header", "schema": { "type": "string" } }
+                        ]
+                    },
+                    "post": {
+                        "operationId": "createPet",
+                        "requestBody": { "$ref": "#/components/requestBodies/Pet" }
+                    }
+                },
+                "/pets/{petId}": {
+                    "parameters": [
+                        { "name": "petId", "in": "path", "description": "Pet to act on", "schema": { "type": "string" } }
+                    ],
+                    "delete": { "operationId": "deletePet" },
+                    "head": { "operationId": "probePet", "parameters": [] }
+                },
+                "/health": { "get": { "operationId": "health" } }
+            },
+            "components": {
+                "requestBodies": {
+                    "Pet": {
+                        "content": {
+                            "application/json": { "schema": { "$ref": "#/components/schemas/Pet" } }
+                        }
+                    }
+                },
+                "schemas": {
+                    "Pet": {
+                        "type": "object",
+                        "required": ["name"],
+                        "properties": {
+                            "name": { "type": "string" },
+                            "kind": { "type": "string", "enum": ["cat", "dog"] },
+                            "vaccinated": { "type": "boolean" }
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn imports_one_form_per_operation_with_inputs() {
+        let import = from_openapi(&petstore(), &[], "0.1.0").unwrap();
+        let ids = import
+            .forms
+            .iter()
+            .map(|form| form.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, ["get_pets", "createPet", "deletePet", "probePet"]);
+
+        let list = &import.forms[0];
+        assert_eq!(list.title, "List pets");
+        assert_eq!(
+            list.questions.len(),
+            1,
+            "the Authorization header is skipped"
+        );
+        assert_eq!(list.questions[0].id, "limit");
+        assert!(!list.questions[0].required);
+
+        let create = &import.forms[1];
+        let questions = create
+            .questions
+            .iter()
+            .map(|q| (q.id.as_str(), q.kind, q.required))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            questions,
+            [
+                ("name", QuestionType::String, true),
+                ("kind", QuestionType::Enum, false),
+                ("vaccinated", QuestionType::Boolean, false),
+            ]
+        );
+
+        let delete = &import.forms[2];
+        assert_eq!(delete.questions[0].id, "petId");
+        assert!(delete.questions[0].required);
+        assert_eq!(
+            delete.questions[0].description.as_deref(),
+            Some("Pet to act on")
+        );
+        assert!(
+            import
+                .warnings
+                .iter()
+                .any(|warning| warning.starts_with("health: no parameters"))
+        );
+    }
+
+    #[test]
+    fn filters_operations_and_reports_unknown_ones() {
+        let operations = ["createPet".to_string()];
+        let import = from_openapi(&petstore(), &operations, "0.1.0").unwrap();
+        assert_eq!(import.forms.len(), 1);
+        assert_eq!(import.forms[0].id, "createPet");
+
+        let err = from_openapi(&petstore(), &["listPets".to_string()], "0.1.0").unwrap_err();
+        assert!(err.to_string().contains("no operation `listPets`"));
+    }
+
+    #[test]
+    fn recursive_refs_skip_the_operation() {
+        let mut document = petstore();
+        document["components"]["schemas"]["Pet"] = json!({ "$ref": "#/components/schemas/Pet" });
+        let import = from_openapi(&document, &[], "0.1.0").unwrap();
+        assert!(import.forms.iter().all(|form| form.id != "createPet"));
+        assert!(
+            import
+                .warnings
+                .iter()
+                .any(|warning| warning.starts_with("createPet:") && warning.contains("$ref"))
+        );
+    }
+}
@@ -0,0 +1,355 @@
+#![cfg(feature = "cli")]
+
+//! The `component_qa` entry points behind one JSON request shape, shared by `qa serve` and
+//! `qa rpc`.
+//!
+//! `component_qa` takes every argument as a JSON string and answers with a JSON string (plain
+//! text for `render_text`). A [`QaCall`] carries those arguments as JSON values so transports
+//! can pass request bodies through unchanged; [`invoke`] fills what the caller left out from
+//! [`QaDefaults`] and parses the answer back.
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::{Value as JsonValue, json};
+use tempfile::TempDir;
+
+use super::qa::{SpecFormat, parse_spec_document};
+
+/// A `component_qa` function, by the name transports expose it under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QaOperation {
+    Describe,
+    Schema,
+    ExampleAnswers,
+    Validate,
+    Next,
+    RenderText,
+    RenderJsonUi,
+    RenderCard,
+    SubmitPatch,
+    SubmitAll,
+    ApplyStore,
+}
+
+impl QaOperation {
+    pub const ALL: [QaOperation; 11] = [
+        QaOperation::Describe,
+        QaOperation::Schema,
+        QaOperation::ExampleAnswers,
+        QaOperation::Validate,
+        QaOperation::Next,
+        QaOperation::RenderText,
+        QaOperation::RenderJsonUi,
+        QaOperation::RenderCard,
+        QaOperation::SubmitPatch,
+        QaOperation::SubmitAll,
+        QaOperation::ApplyStore,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            QaOperation::Describe => "describe",
+            QaOperation::Schema => "schema",
+            QaOperation::ExampleAnswers => "example_answers",
+            QaOperation::Validate => "validate",
+            QaOperation::Next => "next",
+            QaOperation::RenderText => "render_text",
+            QaOperation::RenderJsonUi => "render_json_ui",
+            QaOperation::RenderCard => "render_card",
+            QaOperation::SubmitPatch => "submit_patch",
+            QaOperation::SubmitAll => "submit_all",
+            QaOperation::ApplyStore => "apply_store",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|op| op.name() == name)
+    }
+}
+
+impl fmt::Display for QaOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Arguments of one call. Fields an operation does not take are ignored.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct QaCall {
+    pub form_id: Option<String>,
+    /// Component config. Its `qa_form_asset_path` is ignored: calls only reach the form the
+    /// transport was started with.
+    pub config: Option<JsonValue>,
+    pub ctx: Option<JsonValue>,
+    pub answers: Option<JsonValue>,
+    /// Question answered by `submit_patch`.
+    pub question_id: Option<String>,
+    /// Value submitted for `question_id`.
+    pub value: Option<JsonValue>,
+}
+
+/// Form used when a call names neither a form id nor a config.
+#[derive(Debug, Clone, Default)]
+pub struct QaDefaults {
+    pub form_id: Option<String>,
+    pub config: Option<JsonValue>,
+    /// JSON copy of a YAML form, removed once the last clone of the defaults is dropped.
+    _converted: Option<Arc<TempDir>>,
+}
+
+impl QaDefaults {
+    /// Serve `path` by default: its `id` becomes the default form id and the form is loaded
+    /// through `qa_form_asset_path`.
+    ///
+    /// `component_qa` only loads JSON forms from a path, so a YAML form is served from a JSON
+    /// copy in a private directory, next to a copy of the locale files the engine checks its
+    /// i18n keys against.
+    pub fn for_form(path: &Path) -> Result<Self> {
+        let raw = fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let format = SpecFormat::Auto.resolve(path, &raw);
+        let spec: JsonValue = parse_spec_document(path, &raw, format)?;
+        let form_id = spec
+            .get("id")
+            .and_then(JsonValue::as_str)
+            .with_context(|| format!("{} has no form id", path.display()))?
+            .to_string();
+        let absolute = path
+            .canonicalize()
+            .with_context(|| format!("failed to resolve {}", path.display()))?;
+        let (served, converted) = if format == SpecFormat::Yaml {
+            let (dir, served) = convert_form(&absolute, &spec)?;
+            (served, Some(Arc::new(dir)))
+        } else {
+            (absolute, None)
+        };
+        Ok(Self {
+            form_id: Some(form_id),
+            config: Some(json!({ FORM_PATH_KEY: served.display().to_string() })),
+            _converted: converted,
+        })
+    }
+
+    fn form_path(&self) -> Option<&JsonValue> {
+        self.config.as_ref()?.get(FORM_PATH_KEY)
+    }
+}
+
+const FORM_PATH_KEY: &str = "qa_form_asset_path";
+const FORMS_DIR: &str = "forms";
+
+/// Write `spec` as `forms/form.json` in a new directory, with the locale files of the
+/// original form under `i18n/`, where the engine looks for them.
+fn convert_form(original: &Path, spec: &JsonValue) -> Result<(TempDir, PathBuf)> {
+    let dir = tempfile::tempdir().context("failed to create form directory")?;
+    let forms = dir.path().join(FORMS_DIR);
+    fs::create_dir(&forms).with_context(|| format!("failed to create {}", forms.display()))?;
+    let target = forms.join("form.json");
+    fs::write(&target, spec.to_string())
+        .with_context(|| format!("failed to write {}", target.display()))?;
+
+    let i18n = form_i18n_dir(original);
+    if i18n.is_dir() {
+        let copy = dir.path().join("i18n");
+        fs::create_dir(&copy).with_context(|| format!("failed to create {}", copy.display()))?;
+        for entry in
+            fs::read_dir(&i18n).with_context(|| format!("failed to read {}", i18n.display()))?
+        {
+            let source = entry?.path();
+            if source.extension().and_then(|ext| ext.to_str()) == Some("json") {
+                let name = source
+                    .file_name()
+                    .expect("read_dir entries have a file name");
+                fs::copy(&source, copy.join(name))
+                    .with_context(|| format!("failed to copy {}", source.display()))?;
+            }
+        }
+    }
+    Ok((dir, target))
+}
+
+/// The locale directory `component_qa` checks a form's i18n keys against: `i18n/` beside the
+/// first `forms/` directory on the path, else beside the form itself.
+fn form_i18n_dir(form: &Path) -> PathBuf {
+    let mut prefix = PathBuf::new();
+    for component in form.components() {
+        if component.as_os_str() == FORMS_DIR {
+            return prefix.join("i18n");
+        }
+        prefix.push(component);
+    }
+    form.parent().unwrap_or(Path::new(".")).join("i18n")
+}
+
+/// Run `op` through `component_qa` and return its response document.
+///
+/// Engine failures come back as the engine reports them, an object with an `error` message;
+/// only a call missing required arguments is an `Err`.
+pub fn invoke(op: QaOperation, call: &QaCall, defaults: &QaDefaults) -> Result<JsonValue> {
+    let form_id = call
+        .form_id
+        .as_deref()
+        .or(defaults.form_id.as_deref())
+        .with_context(|| format!("{op}: missing form_id"))?;
+    let config = served_config(call.config.as_ref(), defaults).to_string();
+    let ctx = json_arg(call.ctx.as_ref());
+    let answers = json_arg(call.answers.as_ref());
+    let raw = match op {
+        QaOperation::Describe => component_qa::describe(form_id, &config),
+        QaOperation::Schema => component_qa::get_answer_schema(form_id, &config, &ctx),
+        QaOperation::ExampleAnswers => component_qa::get_example_answers(form_id, &config, &ctx),
+        QaOperation::Validate => component_qa::validate_answers(form_id, &config, &answers),
+        QaOperation::Next => component_qa::next_with_ctx(form_id, &config, &ctx, &answers),
+        QaOperation::RenderText => {
+            let text = component_qa::render_text(form_id, &config, &ctx, &answers);
+            return Ok(match engine_error(&text) {
+                Some(error) => error,
+                None => JsonValue::String(text),
+            });
+        }
+        QaOperation::RenderJsonUi => component_qa::render_json_ui(form_id, &config, &ctx, &answers),
+        QaOperation::RenderCard => component_qa::render_card(form_id, &config, &ctx, &answers),
+        QaOperation::SubmitPatch => {
+            let question_id = call
+                .question_id
+                .as_deref()
+                .with_context(|| format!("{op}: missing question_id"))?;
+            let value = call
+                .value
+                .as_ref()
+                .with_context(|| format!("{op}: missing value"))?;
+            component_qa::submit_patch(
+                form_id,
+                &config,
+                &ctx,
+                &answers,
+                question_id,
+                &value.to_string(),
+            )
+        }
+        QaOperation::SubmitAll => component_qa::submit_all(form_id, &config, &ctx, &answers),
+        // The engine reads both the form config and the store context from one document here.
+        QaOperation::ApplyStore => {
+            let ctx = merged_store_ctx(call.ctx.as_ref(), &config)?;
+            component_qa::apply_store(form_id, &ctx, &answers)
+        }
+    };
+    serde_json::from_str(&raw).with_context(|| format!("{op}: invalid engine response"))
+}
+
+/// The engine's `{"error": ...}` document, when `raw` is one.
+pub fn engine_error(raw: &str) -> Option<JsonValue> {
+    serde_json::from_str(raw).ok().filter(is_engine_error)
+}
+
+/// Whether a response document is the engine's error shape rather than a result.
+pub fn is_engine_error(value: &JsonValue) -> bool {
+    value.as_object().is_some_and(|object| {
+        object.len() == 1 && object.get("error").is_some_and(JsonValue::is_string)
+    })
+}
+
+fn json_arg(value: Option<&JsonValue>) -> String {
+    value.map_or_else(|| "{}".to_string(), JsonValue::to_string)
+}
+
+/// The caller's config, or the default one, with the form path replaced by the default form's.
+///
+/// Callers may reach the server over HTTP, so a path they pass would let them read any local
+/// file through the engine; without a default form the config names no form at all.
+fn served_config(config: Option<&JsonValue>, defaults: &QaDefaults) -> JsonValue {
+    let mut served = config
+        .or(defaults.config.as_ref())
+        .cloned()
+        .unwrap_or_else(|| json!({}));
+    if let Some(object) = served.as_object_mut() {
+        object.remove(FORM_PATH_KEY);
+        if let Some(path) = defaults.form_path() {
+            object.insert(FORM_PATH_KEY.to_string(), path.clone());
+        }
+    }
+    served
+}
+
+fn merged_store_ctx(ctx: Option<&JsonValue>, config: &str) -> Result<String> {
+    let mut merged: JsonValue = serde_json::from_str(config).context("apply_store: config")?;
+    if let (Some(target), Some(JsonValue::Object(ctx))) = (merged.as_object_mut(), ctx) {
+        for (key, value) in ctx {
+            if key != FORM_PATH_KEY {
+                target.insert(key.clone(), value.clone());
+            }
+        }
+    }
+    Ok(merged.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup_form(dir: &Path) -> std::path::PathBuf {
+        let path = dir.join("setup.json");
+        let form = json!({
+            "id": "setup",
+            "title": "Setup",
+            "version": "0.1.0",
+            "questions": [
+                { "id": "name", "type": "string", "title": "Name", "required": true },
+                { "id": "port", "type": "integer", "title": "Port", "required": true }
+            ]
+        });
+        fs::write(&path, form.to_string()).unwrap();
+        path
+    }
+
+    #[test]
+    fn invoke_runs_the_default_form_through_component_qa() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let defaults = QaDefaults::for_form(&setup_form(temp.path())).unwrap();
+        assert_eq!(defaults.form_id.as_deref(), Some("setup"));
+
+        let described = invoke(QaOperation::Describe, &QaCall::default(), &defaults).unwrap();
+        assert_eq!(described["id"], "setup");
+
+        let call = QaCall {
+            answers: Some(json!({ "name": "demo" })),
+            question_id: Some("port".into()),
+            value: Some(json!(8080)),
+            ..QaCall::default()
+        };
+        let submitted = invoke(QaOperation::SubmitPatch, &call, &defaults).unwrap();
+        assert_eq!(submitted["status"], "complete");
+        assert_eq!(submitted["answers"]["port"], 8080);
+
+        let text = invoke(QaOperation::RenderText, &call, &defaults).unwrap();
+        assert!(text.as_str().is_some_and(|text| text.contains("Name")));
+
+        let unknown = QaCall {
+            form_id: Some("other".into()),
+            ..QaCall::default()
+        };
+        let error = invoke(QaOperation::Describe, &unknown, &defaults).unwrap();
+        assert!(is_engine_error(&error));
+    }
+
+    #[test]
+    fn invoke_requires_submit_patch_arguments() {
+        let defaults = QaDefaults {
+            form_id: Some("setup".into()),
+            ..QaDefaults::default()
+        };
+        let err = invoke(QaOperation::SubmitPatch, &QaCall::default(), &defaults).unwrap_err();
+        assert!(err.to_string().contains("missing question_id"));
+        assert_eq!(
+            QaOperation::from_name("render_json_ui"),
+            Some(QaOperation::RenderJsonUi)
+        );
+        assert_eq!(QaOperation::from_name("render"), None);
+    }
+}
@@ -0,0 +1,225 @@
+#![cfg(feature = "cli")]
+
+//! `qa rpc`: the `component_qa` operations as newline-delimited JSON-RPC 2.0 over stdin/stdout,
+//! so hosts in other languages can drive the wizard engine through a subprocess.
+//!
+//! Each input line is one request whose `method` is an operation name (`describe`,
+//! `submit_patch`, ...) and whose `params` object is a [`QaCall`]. Each request with an `id`
+//! gets exactly one response line; notifications (no `id`) are run without a reply.
+
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::Args;
+use serde::Deserialize;
+use serde_json::{Value as JsonValue, json};
+
+use super::qa_ops::{self, QaCall, QaDefaults, QaOperation};
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+/// Server-defined code for errors reported by the QA engine itself.
+const ENGINE_ERROR: i64 = -32000;
+
+#[derive(Args, Debug, Clone)]
+pub struct QaRpcArgs {
+    /// FormSpec (JSON or YAML) answered for every request
+    #[arg(long = "form", value_name = "PATH")]
+    pub form: PathBuf,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcRequest {
+    jsonrpc: String,
+    method: String,
+    #[serde(default)]
+    params: Option<JsonValue>,
+    /// `None` for notifications; a present `null` id still gets a reply.
+    #[serde(default, deserialize_with = "present")]
+    id: Option<JsonValue>,
+}
+
+fn present<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<JsonValue>, D::Error> {
+    JsonValue::deserialize(deserializer).map(Some)
+}
+
+pub fn run(args: &QaRpcArgs) -> Result<()> {
+    let defaults = QaDefaults::for_form(&args.form)?;
+    serve(io::stdin().lock(), io::stdout().lock(), &defaults)
+}
+
+/// Answer every request line from `input` on `output` until `input` ends.
+pub fn serve(input: impl BufRead, mut output: impl Write, defaults: &QaDefaults) -> Result<()> {
+    for line in input.lines() {
+        let line = line.context("failed to read request")?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(response) = handle_line(&line, defaults) {
+            serde_json::to_writer(&mut output, &response)?;
+            output.write_all(b"\n")?;
+            output.flush()?;
+        }
+    }
+    Ok(())
+}
+
+/// The response to one request line, or `None` for a notification.
+pub fn handle_line(line: &str, defaults: &QaDefaults) -> Option<JsonValue> {
+    let value: JsonValue = match serde_json::from_str(line) {
+        Ok(value) => value,
+        Err(err) => {
+            return Some(error_response(
+                JsonValue::Null,
+                PARSE_ERROR,
+                err.to_string(),
+            ));
+        }
+    };
+    let request: RpcRequest = match serde_json::from_value(value) {
+        Ok(request) => request,
+        Err(err) => {
+            return Some(error_response(
+                JsonValue::Null,
+                INVALID_REQUEST,
+                err.to_string(),
+            ));
+        }
+    };
+    let id = request.id.clone();
+    let outcome = dispatch(&request, defaults);
+    let id = id?;
+    Some(match outcome {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err((code, message)) => error_response(id, code, message),
+    })
+}
+
+fn dispatch(request: &RpcRequest, defaults: &QaDefaults) -> Result<JsonValue, (i64, String)> {
+    if request.jsonrpc != "2.0" {
+        return Err((INVALID_REQUEST, "jsonrpc must be \"2.0\"".to_string()));
+    }
+    let op = QaOperation::from_name(&request.method).ok_or_else(|| {
+        (
+            METHOD_NOT_FOUND,
+            format!("unknown method `{}`", request.method),
+        )
+    })?;
+    let call: QaCall = match &request.params {
+        Some(params) => serde_json::from_value(params.clone())
+            .map_err(|err| (INVALID_PARAMS, err.to_string()))?,
+        None => QaCall::default(),
+    };
+    let result =
+        qa_ops::invoke(op, &call, defaults).map_err(|err| (INVALID_PARAMS, format!("{err:#}")))?;
+    if qa_ops::is_engine_error(&result) {
+        let message = result["error"].as_str().unwrap_or_default().to_string();
+        return Err((ENGINE_ERROR, message));
+    }
+    Ok(result)
+}
+
+fn error_response(id: JsonValue, code: i64, message: String) -> JsonValue {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answers_requests_line_by_line() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let form = temp.path().join("setup.json");
+        std::fs::write(
+            &form,
+            json!({
+                "id": "setup",
+                "title": "Setup",
+                "version": "0.1.0",
+                "questions": [{ "id": "name", "type": "string", "title": "Name", "required": true }]
+            })
+            .to_string(),
+        )
+        .unwrap();
+        let defaults = QaDefaults::for_form(&form).unwrap();
+        let input = [
+            r#"{"jsonrpc":"2.0","id":1,"method":"next"}"#,
+            "",
+            r#"{"jsonrpc":"2.0","method":"describe"}"#,
+            r#"{"jsonrpc":"2.0","id":"b","method":"submit_patch","params":{"question_id":"name","value":"demo"}}"#,
+            r#"{"jsonrpc":"2.0","id":3,"method":"describe","params":{"form_id":"other"}}"#,
+            r#"{"jsonrpc":"2.0","id":4,"method":"launch"}"#,
+            r#"{"jsonrpc":"2.0","id":5,"method":"next","params":{"answer":{}}}"#,
+            "not json",
+        ]
+        .join("\n");
+        let mut output = Vec::new();
+        serve(input.as_bytes(), &mut output, &defaults).unwrap();
+
+        let responses = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str::<JsonValue>(line).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(responses.len(), 6, "the notification gets no reply");
+        assert_eq!(responses[0]["id"], 1);
+        assert_eq!(responses[0]["result"]["status"], "need_input");
+        assert_eq!(responses[1]["id"], "b");
+        assert_eq!(responses[1]["result"]["answers"]["name"], "demo");
+        assert_eq!(responses[2]["error"]["code"], ENGINE_ERROR);
+        assert_eq!(responses[3]["error"]["code"], METHOD_NOT_FOUND);
+        assert_eq!(responses[4]["error"]["code"], INVALID_PARAMS);
+        assert_eq!(responses[5]["error"]["code"], PARSE_ERROR);
+        assert_eq!(responses[5]["id"], JsonValue::Null);
+    }
+
+    #[test]
+    fn answers_yaml_forms_and_ignores_client_form_paths() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let form = temp.path().join("setup.yaml");
+        std::fs::write(
+            &form,
+            "id: setup\ntitle: Setup\nversion: 0.1.0\nquestions:\n  - id: name\n    type: string\n    title: Name\n    required: true\n",
+        )
+        .unwrap();
+        let secret = temp.path().join("secret.json");
+        std::fs::write(
+            &secret,
+            json!({ "id": "secret", "title": "Secret", "version": "0.1.0", "questions": [] })
+                .to_string(),
+        )
+        .unwrap();
+        let defaults = QaDefaults::for_form(&form).unwrap();
+        let path = json!({ "qa_form_asset_path": secret.display().to_string() });
+        let request = |id: i64, method: &str, params: JsonValue| {
+            json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }).to_string()
+        };
+
+        let described = handle_line(
+            &request(1, "describe", json!({ "config": path })),
+            &defaults,
+        )
+        .unwrap();
+        assert_eq!(described["result"]["id"], "setup");
+        let stored = handle_line(
+            &request(
+                2,
+                "apply_store",
+                json!({ "form_id": "secret", "ctx": path }),
+            ),
+            &defaults,
+        )
+        .unwrap();
+        assert_eq!(stored["error"]["code"], ENGINE_ERROR);
+    }
+}
@@ -0,0 +1,274 @@
+//! `qa serve`: the `component_qa` operations over local HTTP, for driving wizards from a web UI
+//! during development. Every route takes a [`QaCall`] JSON body and answers with the engine's
+//! own JSON response.
+
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use axum::extract::{Path, State};
+use axum::http::{HeaderValue, Method, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
+use axum::{Json, Router};
+use clap::Args;
+use serde_json::{Value as JsonValue, json};
+
+use super::qa_ops::{self, QaCall, QaDefaults, QaOperation};
+
+const DEFAULT_BIND: &str = "127.0.0.1:8787";
+
+#[derive(Args, Debug, Clone)]
+pub struct QaServeArgs {
+    /// Address to listen on
+    #[arg(long = "bind", value_name = "ADDR", default_value = DEFAULT_BIND)]
+    pub bind: SocketAddr,
+    /// FormSpec (JSON or YAML) served to every request
+    #[arg(long = "form", value_name = "PATH")]
+    pub form: PathBuf,
+    /// Origin allowed to call the server from a browser (CORS), e.g. http://localhost:5173
+    #[arg(long = "allow-origin", value_name = "ORIGIN")]
+    pub allow_origin: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct ServeState {
+    defaults: QaDefaults,
+    allow_origin: Option<HeaderValue>,
+}
+
+pub fn run(args: &QaServeArgs) -> Result<()> {
+    let defaults = QaDefaults::for_form(&args.form)?;
+    let allow_origin = args
+        .allow_origin
+        .as_deref()
+        .map(HeaderValue::from_str)
+        .transpose()
+        .context("invalid --allow-origin")?;
+    let app = router(ServeState {
+        defaults,
+        allow_origin,
+    });
+    let runtime = tokio::runtime::Runtime::new().context("failed to create async runtime")?;
+    runtime.block_on(async {
+        let listener = tokio::net::TcpListener::bind(args.bind)
+            .await
+            .with_context(|| format!("failed to bind {}", args.bind))?;
+        println!("qa serve: listening on http://{}", listener.local_addr()?);
+        axum::serve(listener, app).await.context("qa serve failed")
+    })
+}
+
+fn router(state: ServeState) -> Router {
+    Router::new()
+        .route("/describe", post(describe).options(preflight))
+        .route("/schema", post(schema).options(preflight))
+        .route("/render/{frontend}", post(render).options(preflight))
+        .route("/submit-patch", post(submit_patch).options(preflight))
+        .route("/submit-all", post(submit_all).options(preflight))
+        .with_state(Arc::new(state))
+}
+
+async fn describe(State(state): State<Arc<ServeState>>, Json(call): Json<QaCall>) -> Response {
+    respond(&state, QaOperation::Describe, &call)
+}
+
+async fn schema(State(state): State<Arc<ServeState>>, Json(call): Json<QaCall>) -> Response {
+    respond(&state, QaOperation::Schema, &call)
+}
+
+async fn render(
+    State(state): State<Arc<ServeState>>,
+    Path(frontend): Path<String>,
+    Json(call): Json<QaCall>,
+) -> Response {
+    let op = match frontend.as_str() {
+        "text" => QaOperation::RenderText,
+        "json-ui" => QaOperation::RenderJsonUi,
+        "card" => QaOperation::RenderCard,
+        other => {
+            let body = json!({
+                "error": format!("unknown frontend `{other}` (expected text, json-ui or card)")
+            });
+            return with_cors(&state, (StatusCode::NOT_FOUND, Json(body)).into_response());
+        }
+    };
+    respond(&state, op, &call)
+}
+
+async fn submit_patch(State(state): State<Arc<ServeState>>, Json(call): Json<QaCall>) -> Response {
+    respond(&state, QaOperation::SubmitPatch, &call)
+}
+
+async fn submit_all(State(state): State<Arc<ServeState>>, Json(call): Json<QaCall>) -> Response {
+    respond(&state, QaOperation::SubmitAll, &call)
+}
+
+async fn preflight(State(state): State<Arc<ServeState>>) -> Response {
+    let mut response = StatusCode::NO_CONTENT.into_response();
+    if state.allow_origin.is_some() {
+        let headers = response.headers_mut();
+        headers.insert(
+            header::ACCESS_CONTROL_ALLOW_METHODS,
+            HeaderValue::from_static(Method::POST.as_str()),
+        );
+        headers.insert(
+            header::ACCESS_CONTROL_ALLOW_HEADERS,
+            HeaderValue::from_static("content-type"),
+        );
+    }
+    with_cors(&state, response)
+}
+
+/// Engine errors keep the engine's `{"error": ...}` body and are sent as 400.
+fn respond(state: &ServeState, op: QaOperation, call: &QaCall) -> Response {
+    let (status, body) = match qa_ops::invoke(op, call, &state.defaults) {
+        Ok(body) if qa_ops::is_engine_error(&body) => (StatusCode::BAD_REQUEST, body),
+        Ok(body) => (StatusCode::OK, body),
+        Err(err) => (
+            StatusCode::BAD_REQUEST,
+            json!({ "error": format!("{err:#}") }),
+        ),
+    };
+    with_cors(state, (status, Json::<JsonValue>(body)).into_response())
+}
+
+fn with_cors(state: &ServeState, mut response: Response) -> Response {
+    if let Some(origin) = &state.allow_origin {
+        response
+            .headers_mut()
+            .insert(header::ACCESS_CONTROL_ALLOW_ORIGIN, origin.clone());
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn spawn(state: ServeState) -> SocketAddr {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router(state)).await });
+        addr
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn serves_component_qa_operations() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let form = temp.path().join("setup.json");
+        std::fs::write(
+            &form,
+            json!({
+                "id": "setup",
+                "title": "Setup",
+                "version": "0.1.0",
+                "questions": [{ "id": "name", "type": "string", "title": "Name", "required": true }]
+            })
+            .to_string(),
+        )
+        .unwrap();
+        let addr = spawn(ServeState {
+            defaults: QaDefaults::for_form(&form).unwrap(),
+            allow_origin: Some(HeaderValue::from_static("http://localhost:5173")),
+        })
+        .await;
+        let client = reqwest::Client::new();
+        let post = |path: &str, body: JsonValue| {
+            client
+                .post(format!("http://{addr}{path}"))
+                .json(&body)
+                .send()
+        };
+
+        let response = post("/describe", json!({})).await.unwrap();
+        assert_eq!(response.status(), 200);
+        assert_eq!(
+            response.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+            "http://localhost:5173"
+        );
+        let described: JsonValue = response.json().await.unwrap();
+        assert_eq!(described["id"], "setup");
+
+        let submitted: JsonValue = post(
+            "/submit-patch",
+            json!({ "question_id": "name", "value": "demo" }),
+        )
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+        assert_eq!(submitted["status"], "complete");
+
+        let card = post("/render/card", json!({})).await.unwrap();
+        assert_eq!(card.status(), 200);
+        assert_eq!(post("/render/html", json!({})).await.unwrap().status(), 404);
+
+        let missing = post("/describe", json!({ "form_id": "other" }))
+            .await
+            .unwrap();
+        assert_eq!(missing.status(), 400);
+        let body: JsonValue = missing.json().await.unwrap();
+        assert!(body["error"].as_str().unwrap().contains("other"));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn serves_yaml_forms_and_ignores_client_form_paths() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let forms = temp.path().join("forms");
+        let i18n = temp.path().join("i18n");
+        std::fs::create_dir_all(&forms).unwrap();
+        std::fs::create_dir_all(&i18n).unwrap();
+        std::fs::write(
+            forms.join("setup.yaml"),
+            "id: setup\ntitle: Setup\nversion: 0.1.0\nquestions:\n  - id: name\n    type: string\n    title: Name\n    title_i18n:\n      key: setup.name\n    required: true\n",
+        )
+        .unwrap();
+        std::fs::write(i18n.join("en.json"), r#"{"setup.name":"Name"}"#).unwrap();
+        let secret = temp.path().join("secret.json");
+        std::fs::write(
+            &secret,
+            json!({ "id": "secret", "title": "Secret", "version": "0.1.0", "questions": [] })
+                .to_string(),
+        )
+        .unwrap();
+        let addr = spawn(ServeState {
+            defaults: QaDefaults::for_form(&forms.join("setup.yaml")).unwrap(),
+            allow_origin: None,
+        })
+        .await;
+        let client = reqwest::Client::new();
+        let post = |path: &str, body: JsonValue| {
+            client
+                .post(format!("http://{addr}{path}"))
+                .json(&body)
+                .send()
+        };
+
+        let described: JsonValue = post("/describe", json!({}))
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(described["id"], "setup");
+
+        let config = json!({ "qa_form_asset_path": secret.display().to_string() });
+        let redirected: JsonValue = post("/describe", json!({ "config": config }))
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(redirected["id"], "setup");
+        let other = post(
+            "/describe",
+            json!({ "form_id": "secret", "config": config }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(other.status(), 400);
+    }
+}
@@ -0,0 +1,103 @@
+#![cfg(feature = "cli")]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use clap::{Args, Subcommand};
+use qa_spec::{AnswerSet, Expr, FormSpec, QAFlowSpec, QuestionSpec, StoreOp};
+use schemars::{Schema, schema_for};
+
+const DEFAULT_OUT_DIR: &str = "schemas/qa";
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum SchemaCommand {
+    /// Write JSON Schemas for the QA spec types (FormSpec, QuestionSpec, QAFlowSpec, ...)
+    Export(SchemaExportArgs),
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct SchemaExportArgs {
+    /// Directory that receives one `<type>.schema.json` per spec type
+    #[arg(long = "out-dir", value_name = "DIR", default_value = DEFAULT_OUT_DIR)]
+    pub out_dir: PathBuf,
+}
+
+pub fn run(command: SchemaCommand) -> Result<()> {
+    match command {
+        SchemaCommand::Export(args) => {
+            for path in export(&args.out_dir)? {
+                println!("wrote {}", path.display());
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Every exported schema, keyed by the file stem it is written under.
+pub fn spec_schemas() -> Vec<(&'static str, Schema)> {
+    vec![
+        ("form-spec", schema_for!(FormSpec)),
+        ("question-spec", schema_for!(QuestionSpec)),
+        ("qa-flow-spec", schema_for!(QAFlowSpec)),
+        ("store-op", schema_for!(StoreOp)),
+        ("expr", schema_for!(Expr)),
+        ("answer-set", schema_for!(AnswerSet)),
+    ]
+}
+
+/// Write the spec schema set into `out_dir` and return the written paths.
+pub fn export(out_dir: &Path) -> Result<Vec<PathBuf>> {
+    fs::create_dir_all(out_dir)
+        .with_context(|| format!("failed to create {}", out_dir.display()))?;
+    spec_schemas()
+        .into_iter()
+        .map(|(name, schema)| {
+            let path = out_dir.join(format!("{name}.schema.json"));
+            let rendered = serde_json::to_string_pretty(&schema)? + "\n";
+            fs::write(&path, rendered)
+                .with_context(|| format!("failed to write {}", path.display()))?;
+            Ok(path)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{Value as JsonValue, json};
+
+    #[test]
+    fn exported_form_spec_schema_validates_spec_files() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let written = export(temp.path()).unwrap();
+        assert_eq!(written.len(), spec_schemas().len());
+
+        let schema: JsonValue = serde_json::from_str(
+            &fs::read_to_string(temp.path().join("form-spec.schema.json")).unwrap(),
+        )
+        .unwrap();
+        let validator = jsonschema::validator_for(&schema).unwrap();
+
+        let form = json!({
+            "id": "setup",
+            "title": "Setup",
+            "version": "0.1.0",
+            "questions": [{
+                "id": "region",
+                "type": "enum",
+                "title": "Region",
+                "choices": ["eu", "us"],
+                "visible_if": { "op": "is_set", "path": "tenant" }
+            }]
+        });
+        assert!(validator.is_valid(&form));
+
+        let mut bad = form.clone();
+        bad["questions"][0]["type"] = json!("dropdown");
+        assert!(!validator.is_valid(&bad));
+        let mut bad = form;
+        bad["questions"][0]["visible_if"] = json!({ "op": "matches" });
+        assert!(!validator.is_valid(&bad));
+    }
+}
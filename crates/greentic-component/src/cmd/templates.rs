@@ -1,20 +1,44 @@
 #![cfg(feature = "cli")]
 
 use std::io::Write;
+use std::path::PathBuf;
 
-use anyhow::Result;
-use clap::Args;
+use anyhow::{Result, bail};
+use clap::{Args, Subcommand};
+use serde::Serialize;
 
 use crate::scaffold::engine::{ScaffoldEngine, TemplateDescriptor};
+use crate::wizard::{TemplatePackLint, lint_template_pack};
 
 #[derive(Args, Debug, Clone)]
 pub struct TemplatesArgs {
+    #[command(subcommand)]
+    pub command: Option<TemplatesCommand>,
     /// Emit JSON instead of a table
     #[arg(long = "json")]
     pub json: bool,
 }
 
+#[derive(Subcommand, Debug, Clone)]
+pub enum TemplatesCommand {
+    /// Render a wizard template pack with sample answers and check its output
+    Lint(TemplatesLintArgs),
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct TemplatesLintArgs {
+    /// Template pack directory (holding `wizard-template.json`)
+    #[arg(value_name = "dir")]
+    pub dir: PathBuf,
+    /// Emit JSON instead of human-readable output
+    #[arg(long = "json")]
+    pub json: bool,
+}
+
 pub fn run(args: TemplatesArgs, engine: &ScaffoldEngine) -> Result<()> {
+    if let Some(TemplatesCommand::Lint(lint_args)) = args.command {
+        return run_lint(&lint_args);
+    }
     let templates = engine.templates()?;
     if args.json {
         print_json(&templates)?;
@@ -24,9 +48,46 @@ pub fn run(args: TemplatesArgs, engine: &ScaffoldEngine) -> Result<()> {
     Ok(())
 }
 
-fn print_json(templates: &[TemplateDescriptor]) -> Result<()> {
+fn run_lint(args: &TemplatesLintArgs) -> Result<()> {
+    let lint = lint_template_pack(&args.dir)?;
+    if args.json {
+        print_json(&lint)?;
+    } else {
+        print_lint(&lint);
+    }
+    if !lint.is_ok() {
+        bail!(
+            "template pack `{}` has {} lint error(s)",
+            lint.pack,
+            lint.errors.len()
+        );
+    }
+    Ok(())
+}
+
+fn print_lint(lint: &TemplatePackLint) {
+    println!(
+        "template pack {}: {} file(s) rendered",
+        lint.pack,
+        lint.files.len()
+    );
+    for error in &lint.errors {
+        println!("error: {error}");
+    }
+    if !lint.unused_variables.is_empty() {
+        println!(
+            "unused answer variables: {}",
+            lint.unused_variables.join(", ")
+        );
+    }
+    if lint.is_ok() {
+        println!("ok");
+    }
+}
+
+fn print_json<T: Serialize + ?Sized>(value: &T) -> Result<()> {
     let mut handle = std::io::stdout();
-    serde_json::to_writer_pretty(&mut handle, templates)?;
+    serde_json::to_writer_pretty(&mut handle, value)?;
     handle.write_all(b"\n").ok();
     Ok(())
 }
@@ -123,9 +123,30 @@ pub struct TestArgs {
     /// Emit extra diagnostic output (e.g. generated session id).
     #[arg(long)]
     pub verbose: bool,
+    /// Fuzz apply-answers with answer sets generated from the component's qa-spec output.
+    #[cfg(feature = "fuzz")]
+    #[arg(long = "fuzz-qa", conflicts_with_all = ["op", "input", "input_json"])]
+    pub fuzz_qa: bool,
+    /// Answer sets to try per QA mode with --fuzz-qa.
+    #[cfg(feature = "fuzz")]
+    #[arg(
+        long = "fuzz-qa-cases",
+        default_value_t = 64,
+        value_name = "N",
+        requires = "fuzz_qa"
+    )]
+    pub fuzz_qa_cases: u32,
+    /// Seed for --fuzz-qa, to replay a run; random when omitted.
+    #[cfg(feature = "fuzz")]
+    #[arg(long = "fuzz-qa-seed", value_name = "SEED", requires = "fuzz_qa")]
+    pub fuzz_qa_seed: Option<u64>,
 }
 
 pub fn run(args: TestArgs) -> Result<()> {
+    #[cfg(feature = "fuzz")]
+    if args.fuzz_qa {
+        return run_fuzz_qa(&args);
+    }
     let trace_out = resolve_trace_out(&args)?;
     match run_inner(&args, trace_out.as_deref()) {
         Ok(()) => Ok(()),
@@ -330,6 +351,21 @@ fn run_inner(args: &TestArgs, trace_out: Option<&Path>) -> Result<()> {
     }
 }
 
+#[cfg(feature = "fuzz")]
+fn run_fuzz_qa(args: &TestArgs) -> Result<()> {
+    let manifest_path = resolve_manifest_path(&args.wasm, args.manifest.as_deref())?;
+    let manifest_raw = fs::read_to_string(&manifest_path)
+        .with_context(|| format!("read manifest {}", manifest_path.display()))?;
+    let manifest = parse_manifest(&manifest_raw).context("parse manifest")?;
+    super::test_qa_fuzz::run(
+        &args.wasm,
+        &manifest,
+        args.fuzz_qa_cases,
+        args.fuzz_qa_seed,
+        args.pretty,
+    )
+}
+
 fn resolve_manifest_path(wasm: &Path, manifest: Option<&Path>) -> Result<PathBuf> {
     if let Some(path) = manifest {
         return Ok(path.to_path_buf());
@@ -0,0 +1,251 @@
+//! `test --fuzz-qa`: property-based checks of a component's QA handling.
+//!
+//! For every mode, the component's own `qa-spec` output is turned into a proptest strategy
+//! that produces answer sets a user could submit: one value of the right kind per question,
+//! with optional questions sometimes left out. Each answer set is sent to `apply-answers`,
+//! which must return the base response shape instead of failing. A failing case is shrunk
+//! before it is reported.
+
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use greentic_types::schemas::component::v0_6_0::{ComponentQaSpec, Question, QuestionKind};
+use proptest::prelude::*;
+use proptest::test_runner::{Config, RngSeed, TestCaseError, TestError, TestRunner};
+use serde::Serialize;
+use serde_json::{Map, Number, Value, json};
+
+use crate::manifest::ComponentManifest;
+use crate::test_harness::TestHarness;
+
+const QA_MODES: [&str; 4] = ["default", "setup", "update", "remove"];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct QaFuzzReport {
+    pub status: &'static str,
+    pub modes: Vec<QaFuzzMode>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct QaFuzzMode {
+    pub mode: String,
+    pub questions: usize,
+    pub cases: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failure: Option<QaFuzzFailure>,
+}
+
+/// The smallest answer set found that breaks `apply-answers`, and why.
+#[derive(Debug, Clone, Serialize)]
+pub struct QaFuzzFailure {
+    pub reason: String,
+    pub answers: Value,
+}
+
+/// Fuzz `apply-answers` for every mode and print the report; fails when any mode fails.
+pub fn run(
+    wasm: &Path,
+    manifest: &ComponentManifest,
+    cases: u32,
+    seed: Option<u64>,
+    pretty: bool,
+) -> Result<()> {
+    let harness = super::doctor::new_doctor_harness(wasm, manifest).map_err(anyhow::Error::msg)?;
+    let report = fuzz(&harness, cases, seed)?;
+    let rendered = if pretty {
+        serde_json::to_string_pretty(&report)?
+    } else {
+        serde_json::to_string(&report)?
+    };
+    println!("{rendered}");
+    let failed = report
+        .modes
+        .iter()
+        .filter(|mode| mode.failure.is_some())
+        .count();
+    if failed > 0 {
+        bail!("qa fuzz: apply-answers failed in {failed} mode(s)");
+    }
+    Ok(())
+}
+
+pub fn fuzz(harness: &TestHarness, cases: u32, seed: Option<u64>) -> Result<QaFuzzReport> {
+    let mut modes = Vec::new();
+    for mode in QA_MODES {
+        let spec = super::doctor::invoke_json(harness, "qa-spec", &json!({ "mode": mode }))
+            .map_err(anyhow::Error::msg)
+            .with_context(|| format!("qa-spec({mode}) failed"))?;
+        let spec: ComponentQaSpec = serde_json::from_value(spec)
+            .with_context(|| format!("qa-spec({mode}) is not a valid QA spec"))?;
+        let mut runner = TestRunner::new(Config {
+            cases,
+            failure_persistence: None,
+            rng_seed: seed.map_or(RngSeed::Random, RngSeed::Fixed),
+            ..Config::default()
+        });
+        let outcome = runner.run(&answers_strategy(&spec), |answers| {
+            check_apply_answers(harness, mode, &answers).map_err(TestCaseError::fail)
+        });
+        let failure = match outcome {
+            Ok(()) => None,
+            Err(TestError::Fail(reason, answers)) => Some(QaFuzzFailure {
+                reason: reason.message().to_string(),
+                answers: Value::Object(answers),
+            }),
+            Err(TestError::Abort(reason)) => bail!("qa fuzz ({mode}) aborted: {reason}"),
+        };
+        modes.push(QaFuzzMode {
+            mode: mode.to_string(),
+            questions: spec.questions.len(),
+            cases,
+            failure,
+        });
+    }
+    let status = if modes.iter().any(|mode| mode.failure.is_some()) {
+        "failed"
+    } else {
+        "ok"
+    };
+    Ok(QaFuzzReport { status, modes })
+}
+
+fn check_apply_answers(
+    harness: &TestHarness,
+    mode: &str,
+    answers: &Map<String, Value>,
+) -> Result<(), String> {
+    let payload = json!({ "mode": mode, "answers": answers, "current_config": {} });
+    let output = super::doctor::invoke_json(harness, "apply-answers", &payload)?;
+    let Some(object) = output.as_object() else {
+        return Err("apply-answers returned non-object JSON".to_string());
+    };
+    if !object.get("ok").is_some_and(Value::is_boolean) {
+        return Err("apply-answers must include boolean `ok`".to_string());
+    }
+    for field in ["warnings", "errors"] {
+        if !object.get(field).is_some_and(Value::is_array) {
+            return Err(format!("apply-answers must include array `{field}`"));
+        }
+    }
+    Ok(())
+}
+
+/// Answer sets for `spec`: required questions always get a value, optional ones may not.
+pub fn answers_strategy(spec: &ComponentQaSpec) -> BoxedStrategy<Map<String, Value>> {
+    let fields = spec
+        .questions
+        .iter()
+        .map(|question| {
+            let id = question.id.clone();
+            let value = answer_strategy(question);
+            if question.required {
+                value
+                    .prop_map(move |value| Some((id.clone(), value)))
+                    .boxed()
+            } else {
+                proptest::option::of(value)
+                    .prop_map(move |value| value.map(|value| (id.clone(), value)))
+                    .boxed()
+            }
+        })
+        .collect::<Vec<_>>();
+    fields
+        .prop_map(|fields| fields.into_iter().flatten().collect())
+        .boxed()
+}
+
+fn answer_strategy(question: &Question) -> BoxedStrategy<Value> {
+    match &question.kind {
+        QuestionKind::Text => "\\PC{0,32}".prop_map(Value::String).boxed(),
+        QuestionKind::Choice { options } if !options.is_empty() => {
+            let values = options
+                .iter()
+                .map(|option| Value::String(option.value.clone()))
+                .collect::<Vec<_>>();
+            proptest::sample::select(values).boxed()
+        }
+        QuestionKind::Choice { .. } => "\\PC{0,16}".prop_map(Value::String).boxed(),
+        QuestionKind::Number => prop_oneof![
+            any::<i64>().prop_map(Value::from),
+            proptest::num::f64::NORMAL
+                .prop_map(|value| Number::from_f64(value).map_or(Value::Null, Value::Number)),
+        ]
+        .boxed(),
+        QuestionKind::Bool => any::<bool>().prop_map(Value::Bool).boxed(),
+        QuestionKind::InlineJson { .. } => inline_json_strategy(),
+        QuestionKind::AssetRef { file_types, .. } => {
+            let extensions = if file_types.is_empty() {
+                vec!["json".to_string()]
+            } else {
+                file_types
+                    .iter()
+                    .map(|ext| ext.trim_start_matches('.').to_string())
+                    .collect()
+            };
+            (
+                "[a-z0-9_]{1,12}(/[a-z0-9_]{1,12}){0,2}",
+                proptest::sample::select(extensions),
+            )
+                .prop_map(|(stem, ext)| Value::String(format!("{stem}.{ext}")))
+                .boxed()
+        }
+    }
+}
+
+fn inline_json_strategy() -> BoxedStrategy<Value> {
+    let leaf = prop_oneof![
+        Just(Value::Null),
+        any::<bool>().prop_map(Value::Bool),
+        any::<i64>().prop_map(Value::from),
+        "\\PC{0,16}".prop_map(Value::String),
+    ];
+    leaf.prop_recursive(2, 16, 4, |inner| {
+        prop_oneof![
+            prop::collection::vec(inner.clone(), 0..4).prop_map(Value::Array),
+            prop::collection::btree_map("[a-z_]{1,8}", inner, 0..4)
+                .prop_map(|map| Value::Object(map.into_iter().collect())),
+        ]
+    })
+    .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::strategy::ValueTree;
+
+    #[test]
+    fn answer_sets_follow_the_spec() {
+        let spec: ComponentQaSpec = serde_json::from_value(json!({
+            "mode": "setup",
+            "title": { "key": "qa.title" },
+            "questions": [
+                {
+                    "id": "region",
+                    "label": { "key": "qa.region" },
+                    "kind": { "type": "choice", "options": [
+                        { "value": "eu", "label": { "key": "qa.region.eu" } },
+                        { "value": "us", "label": { "key": "qa.region.us" } }
+                    ] },
+                    "required": true
+                },
+                {
+                    "id": "enabled",
+                    "label": { "key": "qa.enabled" },
+                    "kind": { "type": "bool" },
+                    "required": false
+                }
+            ],
+            "defaults": {}
+        }))
+        .unwrap();
+        let strategy = answers_strategy(&spec);
+        let mut runner = TestRunner::deterministic();
+        for _ in 0..64 {
+            let answers = strategy.new_tree(&mut runner).unwrap().current();
+            assert!(matches!(answers["region"].as_str(), Some("eu" | "us")));
+            assert!(answers.get("enabled").is_none_or(Value::is_boolean));
+            assert!(answers.len() <= 2);
+        }
+    }
+}
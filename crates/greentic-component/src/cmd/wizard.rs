@@ -6,23 +6,40 @@ use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use anyhow::{Context, Result, anyhow, bail};
-use clap::{Args, Subcommand, ValueEnum};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use directories::BaseDirs;
 use greentic_qa_lib::QaLibError;
+use jsonschema::{Validator, validator_for};
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use serde_json::{Map as JsonMap, Value as JsonValue, json};
 
 use crate::cmd::build::BuildArgs;
-use crate::cmd::doctor::{DoctorArgs, DoctorFormat};
+use crate::cmd::doctor::DoctorArgs;
 use crate::cmd::i18n;
+use crate::cmd::post;
 use crate::scaffold::config_schema::{ConfigSchemaInput, parse_config_field};
+use crate::scaffold::license::LicenseKind;
 use crate::scaffold::runtime_capabilities::{
     RuntimeCapabilitiesInput, parse_filesystem_mode, parse_filesystem_mount, parse_secret_format,
     parse_telemetry_attributes, parse_telemetry_scope,
 };
-use crate::scaffold::validate::{ComponentName, normalize_version};
-use crate::wizard::{self, AnswersPayload, WizardPlanEnvelope, WizardPlanMetadata, WizardStep};
+use crate::scaffold::validate::{ComponentName, OrgNamespace, normalize_version};
+use crate::wizard::{
+    self, AnswersPayload, OverwriteChange, OverwritePreview, PlanKeystore, StepFailure,
+    TemplatePack, TemplatePackSource, WizardCi, WizardPlanEnvelope, WizardPlanMetadata, WizardStep,
+    WizardTemplate,
+};
 
 const WIZARD_RUN_SCHEMA: &str = "component-wizard-run/v1";
+static RUN_ANSWERS_SCHEMA: &str = include_str!("../../schemas/v1/component-wizard-run.schema.json");
+static RUN_ANSWERS_VALIDATOR: Lazy<Validator> = Lazy::new(|| {
+    let value: JsonValue =
+        serde_json::from_str(RUN_ANSWERS_SCHEMA).expect("wizard answers schema must be valid JSON");
+    validator_for(&value).expect("wizard answers schema must compile")
+});
+const WIZARD_PREFILL_ENV_PREFIX: &str = "GREENTIC_WIZARD_";
+const WIZARD_DEFAULTS_PATH: &str = ".config/greentic-component/wizard-defaults.json";
 const ANSWER_DOC_WIZARD_ID: &str = "greentic-component.wizard.run";
 const ANSWER_DOC_SCHEMA_ID: &str = "greentic-component.wizard.run";
 const ANSWER_DOC_SCHEMA_VERSION: &str = "1.0.0";
@@ -42,6 +59,40 @@ pub enum WizardSubcommand {
     Apply(WizardArgs),
     #[command(hide = true)]
     New(WizardLegacyNewArgs),
+    /// Check an answers document against the `component-wizard-run/v1` schema.
+    ValidateAnswers(WizardValidateAnswersArgs),
+    /// Manage the ed25519 keys used to sign and verify wizard plans.
+    #[command(subcommand)]
+    Keys(WizardKeysCommand),
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum WizardKeysCommand {
+    /// Create a signing key in the keystore.
+    Generate {
+        #[arg(value_name = "KEY_ID")]
+        key_id: String,
+    },
+    /// List keys in the keystore.
+    List {
+        #[arg(long = "json", default_value_t = false)]
+        json: bool,
+    },
+    /// Trust a hex-encoded public key for plan verification.
+    Trust {
+        #[arg(value_name = "KEY_ID")]
+        key_id: String,
+        #[arg(value_name = "PUBLIC_KEY")]
+        public_key: String,
+    },
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct WizardValidateAnswersArgs {
+    #[arg(value_name = "answers.json")]
+    pub answers: PathBuf,
+    #[arg(long = "json", default_value_t = false)]
+    pub json: bool,
 }
 
 #[derive(Args, Debug, Clone)]
@@ -100,16 +151,56 @@ pub struct WizardArgs {
     pub migrate: bool,
     #[arg(long = "plan-out", value_name = "plan.json")]
     pub plan_out: Option<PathBuf>,
+    #[arg(
+        long = "plan",
+        value_name = "plan.json",
+        conflicts_with_all = ["answers", "qa_answers", "plan_out", "set"]
+    )]
+    pub plan: Option<PathBuf>,
+    /// Encoding of `--plan-out` and `--plan` files.
+    #[arg(long = "plan-format", value_enum, default_value = "json")]
+    pub plan_format: PlanFormat,
+    /// Sign plans written by `--plan-out`/dry-run with this keystore key.
+    #[arg(long = "sign-key", value_name = "KEY_ID", conflicts_with = "plan")]
+    pub sign_key: Option<String>,
+    /// Refuse `--plan` files that are not signed by a key in the keystore, even when it is empty.
+    #[arg(long = "require-signed", default_value_t = false, requires = "plan")]
+    pub require_signed: bool,
+    /// Apply a `--plan` with run_cli steps even when no trusted key signed it.
+    #[arg(
+        long = "allow-unsigned-commands",
+        default_value_t = false,
+        requires = "plan"
+    )]
+    pub allow_unsigned_commands: bool,
+    /// Answer field override, repeatable; applied over `--answers` and prefilled values.
+    #[arg(long = "set", value_name = "KEY=VALUE")]
+    pub set: Vec<String>,
     #[arg(long = "project-root", value_name = "PATH", default_value = ".")]
     pub project_root: PathBuf,
     #[arg(long = "template", value_name = "TEMPLATE_ID")]
     pub template: Option<String>,
+    /// Scaffold as a member of the enclosing Cargo workspace.
+    #[arg(long = "workspace", default_value_t = false)]
+    pub workspace: bool,
     #[arg(long = "full-tests")]
     pub full_tests: bool,
     #[arg(long = "json", default_value_t = false)]
     pub json: bool,
 }
 
+impl Default for WizardArgs {
+    /// The arguments of a bare `greentic-component wizard`, taken from the clap defaults.
+    fn default() -> Self {
+        #[derive(Parser)]
+        struct WizardCli {
+            #[command(flatten)]
+            args: WizardArgs,
+        }
+        WizardCli::parse_from(["wizard"]).args
+    }
+}
+
 #[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum RunMode {
@@ -124,6 +215,7 @@ pub enum RunMode {
     #[serde(alias = "build-test")]
     BuildTest,
     Doctor,
+    Remove,
 }
 
 #[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -134,6 +226,14 @@ pub enum ExecutionMode {
     Execute,
 }
 
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PlanFormat {
+    #[default]
+    Json,
+    /// Canonical CBOR, as consumed by operator-side tooling.
+    Cbor,
+}
+
 #[derive(Debug, Clone)]
 struct WizardLegacyNewCompat {
     name: Option<String>,
@@ -174,6 +274,9 @@ struct WizardRunOutput {
     plan: WizardPlanEnvelope,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     warnings: Vec<String>,
+    /// Existing files that `overwrite_output` replaces or deletes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    overwrite: Option<OverwritePreview>,
 }
 
 pub fn run_cli(cli: WizardCliArgs) -> Result<()> {
@@ -196,6 +299,10 @@ pub fn run_cli(cli: WizardCliArgs) -> Result<()> {
             });
             new_args.args
         }
+        Some(WizardSubcommand::ValidateAnswers(validate_args)) => {
+            return run_validate_answers(&validate_args);
+        }
+        Some(WizardSubcommand::Keys(command)) => return run_keys(command),
         None => cli.args,
     };
     run_with_context(args, execution_override, legacy_new)
@@ -238,8 +345,25 @@ fn run_with_context(
         execution = ExecutionMode::Execute;
     }
 
+    if let Some(path) = &args.plan {
+        return run_saved_plan(
+            path,
+            args.plan_format,
+            execution,
+            args.require_signed,
+            args.allow_unsigned_commands,
+            args.json,
+        );
+    }
+
     apply_legacy_wizard_new_compat(legacy_new, &mut args, &mut answers)?;
 
+    if !args.set.is_empty() {
+        let overrides = parse_set_overrides(&args.set)?;
+        let doc = answers.get_or_insert_with(|| default_answers_for(&args));
+        doc.fields.extend(overrides);
+    }
+
     if answers.is_none() && io::stdin().is_terminal() && io::stdout().is_terminal() {
         return run_interactive_loop(args, execution);
     }
@@ -260,7 +384,18 @@ fn run_with_context(
         }
     }
 
+    let prefill = load_wizard_prefill()?;
+    if !prefill.is_empty() {
+        let doc = answers.get_or_insert_with(|| default_answers_for(&args));
+        merge_prefill(&mut doc.fields, prefill);
+    }
+
     let output = build_run_output(&args, execution, answers.as_ref())?;
+    if !args.json
+        && let Some(preview) = &output.overwrite
+    {
+        print_overwrite_preview(preview);
+    }
 
     if let Some(path) = &args.qa_answers_out {
         let doc = answers
@@ -285,7 +420,12 @@ fn run_with_context(
     match execution {
         ExecutionMode::DryRun => {
             let plan_out = resolve_plan_out(&args)?;
-            write_plan_json(&output.plan, &plan_out)?;
+            write_plan_file(
+                &output.plan,
+                &plan_out,
+                args.plan_format,
+                args.sign_key.as_deref(),
+            )?;
             println!(
                 "{}",
                 trf(
@@ -295,7 +435,7 @@ fn run_with_context(
             );
         }
         ExecutionMode::Execute => {
-            execute_run_plan(&output.plan)?;
+            print_step_failures(&execute_run_output(&output)?);
             if args.mode == RunMode::Create {
                 println!(
                     "{}",
@@ -317,6 +457,48 @@ fn run_with_context(
     Ok(())
 }
 
+fn run_saved_plan(
+    path: &Path,
+    format: PlanFormat,
+    execution: ExecutionMode,
+    require_signed: bool,
+    allow_unsigned_commands: bool,
+    json: bool,
+) -> Result<()> {
+    let raw = fs::read(path).with_context(|| format!("failed to read plan {}", path.display()))?;
+    let plan: WizardPlanEnvelope = match format {
+        PlanFormat::Json => serde_json::from_slice(&raw)
+            .with_context(|| format!("plan {} must be a wizard plan envelope", path.display()))?,
+        PlanFormat::Cbor => greentic_types::cbor::canonical::from_cbor(&raw).map_err(|err| {
+            anyhow!(
+                "plan {} must be a CBOR wizard plan envelope: {err}",
+                path.display()
+            )
+        })?,
+    };
+    wizard::verify_plan(&plan)?;
+    PlanKeystore::open_default()?.verify_saved(&plan, require_signed, allow_unsigned_commands)?;
+
+    match execution {
+        ExecutionMode::DryRun => println!(
+            "{}",
+            trf(
+                "cli.wizard.result.plan_verified",
+                &[path.to_string_lossy().as_ref()]
+            )
+        ),
+        ExecutionMode::Execute => {
+            print_step_failures(&execute_run_plan(&plan)?);
+            println!("{}", tr("cli.wizard.result.execute_ok"));
+        }
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&plan)?);
+    }
+    Ok(())
+}
+
 fn run_interactive_loop(mut args: WizardArgs, execution: ExecutionMode) -> Result<()> {
     loop {
         let Some(mode) = prompt_main_menu_mode(args.mode)? else {
@@ -328,11 +510,34 @@ fn run_interactive_loop(mut args: WizardArgs, execution: ExecutionMode) -> Resul
             continue;
         };
         let output = build_run_output(&args, execution, Some(&answers))?;
+        if let Some(preview) = &output.overwrite {
+            print_overwrite_preview(preview);
+            if execution == ExecutionMode::Execute
+                && !matches!(
+                    prompt_yes_no(
+                        trf(
+                            "cli.wizard.prompt.confirm_overwrite",
+                            &[preview.target_root.to_string_lossy().as_ref()],
+                        ),
+                        false,
+                    )?,
+                    InteractiveAnswer::Value(JsonValue::Bool(true))
+                )
+            {
+                println!("{}", tr("cli.wizard.result.overwrite_cancelled"));
+                continue;
+            }
+        }
 
         match execution {
             ExecutionMode::DryRun => {
                 let plan_out = resolve_plan_out(&args)?;
-                write_plan_json(&output.plan, &plan_out)?;
+                write_plan_file(
+                    &output.plan,
+                    &plan_out,
+                    args.plan_format,
+                    args.sign_key.as_deref(),
+                )?;
                 println!(
                     "{}",
                     trf(
@@ -342,7 +547,7 @@ fn run_interactive_loop(mut args: WizardArgs, execution: ExecutionMode) -> Resul
                 );
             }
             ExecutionMode::Execute => {
-                execute_run_plan(&output.plan)?;
+                print_step_failures(&execute_run_output(&output)?);
                 if args.mode == RunMode::Create {
                     println!(
                         "{}",
@@ -400,12 +605,18 @@ fn build_run_output(
 ) -> Result<WizardRunOutput> {
     let mode = args.mode;
 
+    let mut overwrite = None;
     let (plan, warnings) = match mode {
-        RunMode::Create => build_create_plan(args, execution, answers)?,
+        RunMode::Create => {
+            let (plan, warnings, preview) = build_create_plan(args, answers)?;
+            overwrite = preview;
+            (plan, warnings)
+        }
         RunMode::AddOperation => build_add_operation_plan(args, answers)?,
         RunMode::UpdateOperation => build_update_operation_plan(args, answers)?,
-        RunMode::BuildTest => build_build_test_plan(args, answers),
-        RunMode::Doctor => build_doctor_plan(args, answers),
+        RunMode::BuildTest => build_build_test_plan(args, answers)?,
+        RunMode::Doctor => build_doctor_plan(args, answers)?,
+        RunMode::Remove => build_remove_plan(args, answers)?,
     };
 
     Ok(WizardRunOutput {
@@ -413,9 +624,56 @@ fn build_run_output(
         execution,
         plan,
         warnings,
+        overwrite,
     })
 }
 
+fn execute_run_output(output: &WizardRunOutput) -> Result<Vec<StepFailure>> {
+    if let Some(preview) = &output.overwrite {
+        fs::remove_dir_all(&preview.target_root).with_context(|| {
+            format!(
+                "failed to clear output directory before overwrite {}",
+                preview.target_root.display()
+            )
+        })?;
+    }
+    execute_run_plan(&output.plan)
+}
+
+/// Report post-steps that failed without stopping the plan, in the format `new` uses.
+fn print_step_failures(failures: &[StepFailure]) {
+    for failure in failures {
+        println!(
+            "{}",
+            i18n::tr_lit("{label}: {status} ({message})")
+                .replace("{label}", &failure.command)
+                .replace("{status}", "failed")
+                .replace("{message}", &failure.message)
+        );
+    }
+}
+
+fn print_overwrite_preview(preview: &OverwritePreview) {
+    println!(
+        "{}",
+        trf(
+            "cli.wizard.result.overwrite_preview",
+            &[
+                preview.target_root.to_string_lossy().as_ref(),
+                &preview.count(OverwriteChange::Modified).to_string(),
+                &preview.count(OverwriteChange::Removed).to_string(),
+            ],
+        )
+    );
+    for file in &preview.files {
+        match (file.change, &file.diff) {
+            (OverwriteChange::Modified, Some(diff)) => print!("{diff}"),
+            (OverwriteChange::Modified, None) => println!("~ {}", file.path),
+            (OverwriteChange::Removed, _) => println!("- {}", file.path),
+        }
+    }
+}
+
 fn resolve_plan_out(args: &WizardArgs) -> Result<PathBuf> {
     if let Some(path) = &args.plan_out {
         return Ok(path.clone());
@@ -432,8 +690,23 @@ fn resolve_plan_out(args: &WizardArgs) -> Result<PathBuf> {
     );
 }
 
-fn write_plan_json(plan: &WizardPlanEnvelope, path: &PathBuf) -> Result<()> {
-    let payload = serde_json::to_string_pretty(plan)?;
+fn write_plan_file(
+    plan: &WizardPlanEnvelope,
+    path: &PathBuf,
+    format: PlanFormat,
+    sign_key: Option<&str>,
+) -> Result<()> {
+    let mut plan = plan.clone();
+    if let Some(key_id) = sign_key {
+        PlanKeystore::open_default()?.sign(key_id, &mut plan)?;
+        println!("{}", trf("cli.wizard.result.plan_signed", &[key_id]));
+    }
+    let payload = match format {
+        PlanFormat::Json => serde_json::to_string_pretty(&plan)?.into_bytes(),
+        // Delegate answers are arbitrary JSON, so floats must be allowed.
+        PlanFormat::Cbor => greentic_types::cbor::canonical::to_canonical_cbor_allow_floats(&plan)
+            .map_err(|err| anyhow!("failed to encode plan as CBOR: {err}"))?,
+    };
     if let Some(parent) = path.parent()
         && !parent.as_os_str().is_empty()
     {
@@ -445,9 +718,8 @@ fn write_plan_json(plan: &WizardPlanEnvelope, path: &PathBuf) -> Result<()> {
 
 fn build_create_plan(
     args: &WizardArgs,
-    execution: ExecutionMode,
     answers: Option<&WizardRunAnswers>,
-) -> Result<(WizardPlanEnvelope, Vec<String>)> {
+) -> Result<(WizardPlanEnvelope, Vec<String>, Option<OverwritePreview>)> {
     let fields = answers.map(|doc| &doc.fields);
 
     let component_name = fields
@@ -462,27 +734,49 @@ fn build_create_plan(
         .unwrap_or("0.6.0");
     let abi_version = normalize_version(abi_version)?;
 
+    let output_root = fields
+        .and_then(|f| f.get("output_root"))
+        .and_then(JsonValue::as_str)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| args.project_root.clone());
     let output_dir = fields
         .and_then(|f| f.get("output_dir"))
         .and_then(JsonValue::as_str)
         .map(PathBuf::from)
-        .unwrap_or_else(|| args.project_root.join(&component_name));
+        .unwrap_or_else(|| output_root.join(&component_name));
+
+    let org = fields
+        .and_then(|f| f.get("org"))
+        .and_then(JsonValue::as_str)
+        .map(|org| OrgNamespace::parse(org).map(OrgNamespace::into_string))
+        .transpose()?;
+    let license = fields
+        .and_then(|f| f.get("license"))
+        .and_then(JsonValue::as_str)
+        .map(LicenseKind::parse)
+        .transpose()?
+        .unwrap_or_default();
+    let author = fields
+        .and_then(|f| f.get("author"))
+        .and_then(JsonValue::as_str)
+        .map(str::trim)
+        .filter(|author| !author.is_empty())
+        .map(ToOwned::to_owned);
 
     let overwrite_output = fields
         .and_then(|f| f.get("overwrite_output"))
         .and_then(JsonValue::as_bool)
         .unwrap_or(false);
 
-    if overwrite_output {
-        if execution == ExecutionMode::Execute && output_dir.exists() {
-            fs::remove_dir_all(&output_dir).with_context(|| {
-                format!(
-                    "failed to clear output directory before overwrite {}",
-                    output_dir.display()
-                )
-            })?;
-        }
-    } else {
+    // Regenerate in place through the wizard lock instead of requiring an empty directory.
+    let update_existing = fields
+        .and_then(|f| f.get("update_existing"))
+        .and_then(JsonValue::as_bool)
+        .unwrap_or(false);
+
+    // Update mode validates the lock file when the plan is built; an overwrite is previewed
+    // against the plan and the directory is only cleared when the plan executes.
+    if !update_existing && !overwrite_output {
         validate_output_path_available(&output_dir)?;
     }
 
@@ -496,8 +790,19 @@ fn build_create_plan(
                 .map(ToOwned::to_owned)
         })
         .unwrap_or_else(default_template_id);
+    let (template, template_pack) = resolve_template(&template_id)?;
+    let workspace = args.workspace
+        || fields
+            .and_then(|f| f.get("workspace"))
+            .and_then(JsonValue::as_bool)
+            .unwrap_or(false);
+    let ci = match fields.and_then(|f| f.get("ci")).and_then(JsonValue::as_str) {
+        Some(id) => WizardCi::from_id(id)
+            .ok_or_else(|| anyhow!("{}", trf("cli.wizard.error.unknown_ci", &[id])))?,
+        None => WizardCi::None,
+    };
 
-    let user_operations = parse_user_operations(fields)?;
+    let user_operations = parse_user_operations(fields, template)?;
     let default_operation = parse_default_operation(fields, &user_operations);
     let runtime_capabilities = parse_runtime_capabilities(fields)?;
 
@@ -522,11 +827,22 @@ fn build_create_plan(
 
     let request = wizard::WizardRequest {
         name: component_name,
+        org,
+        license,
+        author,
         abi_version,
-        mode: wizard::WizardMode::Default,
+        mode: if update_existing {
+            wizard::WizardMode::Update
+        } else {
+            wizard::WizardMode::Default
+        },
+        template,
+        template_pack,
         target: output_dir,
+        workspace,
+        ci,
         answers: prefill,
-        required_capabilities: Vec::new(),
+        required_capabilities: parse_string_array(fields, "required_capabilities"),
         provided_capabilities: Vec::new(),
         user_operations,
         default_operation,
@@ -534,10 +850,83 @@ fn build_create_plan(
         config_schema: parse_config_schema(fields)?,
     };
 
-    let result = wizard::apply_scaffold(request, true)?;
+    let post_steps = parse_post_steps(fields)?;
+    let mut result = wizard::apply_scaffold(request, true)?;
     let mut warnings = result.warnings;
+    append_post_steps(&mut result.plan, &post_steps, &template_id, &mut warnings);
+    wizard::seal_plan(&mut result.plan)?;
+    let overwrite = if overwrite_output && !update_existing {
+        wizard::preview_overwrite(&result.plan)?
+    } else {
+        None
+    };
     warnings.push(trf("cli.wizard.step.template_used", &[&template_id]));
-    Ok((result.plan, warnings))
+    Ok((result.plan, warnings, overwrite))
+}
+
+/// Post-generation steps a create plan can run once its files are written.
+const POST_STEPS: &[&str] = &["fmt", "git", "check"];
+
+/// Read `post_steps`, returned in execution order: `cargo fmt` runs before the initial
+/// commit so the commit holds formatted sources, and `cargo check` runs last.
+fn parse_post_steps(fields: Option<&JsonMap<String, JsonValue>>) -> Result<Vec<&'static str>> {
+    let requested = parse_string_array(fields, "post_steps");
+    if let Some(unknown) = requested
+        .iter()
+        .find(|step| !POST_STEPS.contains(&step.as_str()))
+    {
+        bail!(
+            "{}",
+            trf(
+                "cli.wizard.error.unknown_post_step",
+                &[unknown, &POST_STEPS.join(", ")]
+            )
+        );
+    }
+    Ok(POST_STEPS
+        .iter()
+        .copied()
+        .filter(|step| requested.iter().any(|value| value == step))
+        .collect())
+}
+
+/// Post-steps run after the files are written, so a failing one is reported rather than
+/// stopping the plan. `git` is left out when the target already belongs to a repository.
+fn append_post_steps(
+    plan: &mut WizardPlanEnvelope,
+    post_steps: &[&str],
+    template_id: &str,
+    warnings: &mut Vec<String>,
+) {
+    let run_cli = |command: &str, args: &[&str]| WizardStep::RunCli {
+        command: command.to_string(),
+        args: args.iter().map(|arg| arg.to_string()).collect(),
+        cwd: None,
+        timeout_secs: None,
+        continue_on_error: true,
+    };
+    for step in post_steps {
+        match *step {
+            "fmt" => plan.plan.steps.push(run_cli("cargo", &["fmt", "--all"])),
+            "git" => {
+                if let Some(reason) = post::existing_git_repo(&plan.target_root) {
+                    warnings.push(trf("cli.wizard.step.git_skipped", &[reason]));
+                    continue;
+                }
+                let message = format!("chore(init): scaffold component from {template_id}");
+                plan.plan.steps.extend([
+                    run_cli("git", &["init"]),
+                    run_cli("git", &["add", "--all"]),
+                    run_cli("git", &["commit", "-m", &message]),
+                ]);
+            }
+            "check" => plan
+                .plan
+                .steps
+                .push(run_cli("cargo", &["check", "--target", "wasm32-wasip2"])),
+            _ => {}
+        }
+    }
 }
 
 fn build_add_operation_plan(
@@ -575,7 +964,6 @@ fn build_add_operation_plan(
     Ok((
         write_files_plan(
             "greentic.component.add_operation",
-            "mode-add-operation",
             &project_root,
             vec![
                 (
@@ -584,7 +972,7 @@ fn build_add_operation_plan(
                 ),
                 ("src/lib.rs".to_string(), updated_lib),
             ],
-        ),
+        )?,
         Vec::new(),
     ))
 }
@@ -632,7 +1020,6 @@ fn build_update_operation_plan(
     Ok((
         write_files_plan(
             "greentic.component.update_operation",
-            "mode-update-operation",
             &project_root,
             vec![
                 (
@@ -641,7 +1028,7 @@ fn build_update_operation_plan(
                 ),
                 ("src/lib.rs".to_string(), updated_lib),
             ],
-        ),
+        )?,
         Vec::new(),
     ))
 }
@@ -672,7 +1059,10 @@ fn normalize_operation_name(value: &str) -> Result<String> {
     Ok(trimmed.to_string())
 }
 
-fn parse_user_operations(fields: Option<&JsonMap<String, JsonValue>>) -> Result<Vec<String>> {
+fn parse_user_operations(
+    fields: Option<&JsonMap<String, JsonValue>>,
+    template: WizardTemplate,
+) -> Result<Vec<String>> {
     if let Some(csv) = fields
         .and_then(|f| f.get("operation_names"))
         .and_then(JsonValue::as_str)
@@ -716,7 +1106,7 @@ fn parse_user_operations(fields: Option<&JsonMap<String, JsonValue>>) -> Result<
         return Ok(vec![normalize_operation_name(name)?]);
     }
 
-    Ok(vec!["handle_message".to_string()])
+    Ok(template.default_operations())
 }
 
 fn parse_operation_names_csv(value: &str) -> Result<Vec<String>> {
@@ -764,6 +1154,38 @@ fn parse_runtime_capabilities(
         .map(str::trim)
         .filter(|value| !value.is_empty())
         .map(ToOwned::to_owned);
+    // `telemetry_enabled` gates the top-level telemetry config (and the generated
+    // `src/telemetry.rs`); enabling it without a prefix falls back to the component name.
+    let (telemetry_span_prefix, telemetry_attributes) = match fields
+        .and_then(|f| f.get("telemetry_enabled"))
+        .and_then(JsonValue::as_bool)
+    {
+        Some(false) => (None, Default::default()),
+        Some(true) => (
+            telemetry_span_prefix.or_else(|| {
+                Some(
+                    fields
+                        .and_then(|f| f.get("component_name"))
+                        .and_then(JsonValue::as_str)
+                        .unwrap_or("component")
+                        .to_string(),
+                )
+            }),
+            telemetry_attributes,
+        ),
+        None => (telemetry_span_prefix, telemetry_attributes),
+    };
+
+    // An explicit `secrets_enabled: false` drops secret keys left over from other sources.
+    let secret_keys = if fields
+        .and_then(|f| f.get("secrets_enabled"))
+        .and_then(JsonValue::as_bool)
+        .unwrap_or(true)
+    {
+        parse_string_array(fields, "secret_keys")
+    } else {
+        Vec::new()
+    };
 
     Ok(RuntimeCapabilitiesInput {
         filesystem_mode: parse_filesystem_mode(filesystem_mode).map_err(anyhow::Error::from)?,
@@ -807,7 +1229,7 @@ fn parse_runtime_capabilities(
         telemetry_scope: parse_telemetry_scope(telemetry_scope).map_err(anyhow::Error::from)?,
         telemetry_span_prefix,
         telemetry_attributes,
-        secret_keys: parse_string_array(fields, "secret_keys"),
+        secret_keys,
         secret_env: fields
             .and_then(|f| f.get("secret_env"))
             .and_then(JsonValue::as_str)
@@ -963,31 +1385,45 @@ fn collect_user_operation_names(manifest: &JsonValue) -> Result<Vec<String>> {
 
 fn write_files_plan(
     id: &str,
-    digest: &str,
     project_root: &Path,
     files: Vec<(String, String)>,
-) -> WizardPlanEnvelope {
+) -> Result<WizardPlanEnvelope> {
     let file_map = files
         .into_iter()
         .collect::<std::collections::BTreeMap<_, _>>();
-    WizardPlanEnvelope {
+    runner_plan(
+        id,
+        project_root.to_path_buf(),
+        vec![WizardStep::WriteFiles { files: file_map }],
+    )
+}
+
+fn runner_plan(
+    id: &str,
+    project_root: PathBuf,
+    steps: Vec<WizardStep>,
+) -> Result<WizardPlanEnvelope> {
+    let mut plan = WizardPlanEnvelope {
         plan_version: wizard::PLAN_VERSION,
         metadata: WizardPlanMetadata {
-            generator: "greentic-component/wizard-runner".to_string(),
+            generator: wizard::RUNNER_GENERATOR_ID.to_string(),
             template_version: "component-wizard-run/v1".to_string(),
-            template_digest_blake3: digest.to_string(),
+            template_digest_blake3: String::new(),
             requested_abi_version: "0.6.0".to_string(),
         },
-        target_root: project_root.to_path_buf(),
+        target_root: project_root,
         plan: wizard::WizardPlan {
             meta: wizard::WizardPlanMeta {
                 id: id.to_string(),
                 target: wizard::WizardTarget::Component,
                 mode: wizard::WizardPlanMode::Scaffold,
             },
-            steps: vec![WizardStep::WriteFiles { files: file_map }],
+            steps,
         },
-    }
+        signature: None,
+    };
+    wizard::seal_plan(&mut plan)?;
+    Ok(plan)
 }
 
 fn rewrite_lib_user_ops(source: &str, user_operations: &[String]) -> Result<String> {
@@ -1076,7 +1512,7 @@ fn rewrite_lib_user_ops(source: &str, user_operations: &[String]) -> Result<Stri
 fn build_build_test_plan(
     args: &WizardArgs,
     answers: Option<&WizardRunAnswers>,
-) -> (WizardPlanEnvelope, Vec<String>) {
+) -> Result<(WizardPlanEnvelope, Vec<String>)> {
     let fields = answers.map(|doc| &doc.fields);
     let project_root = fields
         .and_then(|f| f.get("project_root"))
@@ -1100,33 +1536,16 @@ fn build_build_test_plan(
         });
     }
 
-    (
-        WizardPlanEnvelope {
-            plan_version: wizard::PLAN_VERSION,
-            metadata: WizardPlanMetadata {
-                generator: "greentic-component/wizard-runner".to_string(),
-                template_version: "component-wizard-run/v1".to_string(),
-                template_digest_blake3: "mode-build-test".to_string(),
-                requested_abi_version: "0.6.0".to_string(),
-            },
-            target_root: project_root,
-            plan: wizard::WizardPlan {
-                meta: wizard::WizardPlanMeta {
-                    id: "greentic.component.build_test".to_string(),
-                    target: wizard::WizardTarget::Component,
-                    mode: wizard::WizardPlanMode::Scaffold,
-                },
-                steps,
-            },
-        },
+    Ok((
+        runner_plan("greentic.component.build_test", project_root, steps)?,
         Vec::new(),
-    )
+    ))
 }
 
 fn build_doctor_plan(
     args: &WizardArgs,
     answers: Option<&WizardRunAnswers>,
-) -> (WizardPlanEnvelope, Vec<String>) {
+) -> Result<(WizardPlanEnvelope, Vec<String>)> {
     let fields = answers.map(|doc| &doc.fields);
     let project_root = fields
         .and_then(|f| f.get("project_root"))
@@ -1134,36 +1553,44 @@ fn build_doctor_plan(
         .map(PathBuf::from)
         .unwrap_or_else(|| args.project_root.clone());
 
-    (
-        WizardPlanEnvelope {
-            plan_version: wizard::PLAN_VERSION,
-            metadata: WizardPlanMetadata {
-                generator: "greentic-component/wizard-runner".to_string(),
-                template_version: "component-wizard-run/v1".to_string(),
-                template_digest_blake3: "mode-doctor".to_string(),
-                requested_abi_version: "0.6.0".to_string(),
-            },
-            target_root: project_root.clone(),
-            plan: wizard::WizardPlan {
-                meta: wizard::WizardPlanMeta {
-                    id: "greentic.component.doctor".to_string(),
-                    target: wizard::WizardTarget::Component,
-                    mode: wizard::WizardPlanMode::Scaffold,
-                },
-                steps: vec![WizardStep::Doctor {
-                    project_root: project_root.display().to_string(),
-                }],
-            },
-        },
+    let steps = vec![WizardStep::Doctor {
+        project_root: project_root.display().to_string(),
+    }];
+    Ok((
+        runner_plan("greentic.component.doctor", project_root, steps)?,
         Vec::new(),
-    )
+    ))
 }
 
-fn execute_run_plan(plan: &WizardPlanEnvelope) -> Result<()> {
+fn build_remove_plan(
+    args: &WizardArgs,
+    answers: Option<&WizardRunAnswers>,
+) -> Result<(WizardPlanEnvelope, Vec<String>)> {
+    let fields = answers.map(|doc| &doc.fields);
+    let project_root = resolve_project_root(args, fields);
+    let clean_dist = fields
+        .and_then(|f| f.get("clean_dist"))
+        .and_then(JsonValue::as_bool)
+        .unwrap_or(false);
+    let result =
+        wizard::remove_scaffold(&project_root, wizard::RemoveOptions { clean_dist }, true)?;
+    Ok((result.plan, result.warnings))
+}
+
+fn execute_run_plan(plan: &WizardPlanEnvelope) -> Result<Vec<StepFailure>> {
+    // Steps reach the provider one at a time, so the plan is verified as a whole first.
+    wizard::verify_plan(plan)?;
+    let mut failures = Vec::new();
     for step in &plan.plan.steps {
         match step {
-            WizardStep::EnsureDir { .. } | WizardStep::WriteFiles { .. } => {
-                let single = WizardPlanEnvelope {
+            WizardStep::EnsureDir { .. }
+            | WizardStep::WriteFiles { .. }
+            | WizardStep::RemoveFiles { .. }
+            | WizardStep::UpdateWorkspaceMembers { .. }
+            | WizardStep::WriteWorkspaceFiles { .. }
+            | WizardStep::RunCli { .. }
+            | WizardStep::Delegate { .. } => {
+                let mut single = WizardPlanEnvelope {
                     plan_version: plan.plan_version,
                     metadata: plan.metadata.clone(),
                     target_root: plan.target_root.clone(),
@@ -1171,29 +1598,23 @@ fn execute_run_plan(plan: &WizardPlanEnvelope) -> Result<()> {
                         meta: plan.plan.meta.clone(),
                         steps: vec![step.clone()],
                     },
+                    signature: None,
                 };
-                wizard::execute_plan(&single)?;
+                wizard::seal_plan(&mut single)?;
+                failures.extend(wizard::execute_plan(&single)?);
             }
             WizardStep::BuildComponent { project_root } => {
                 let manifest = PathBuf::from(project_root).join("component.manifest.json");
                 crate::cmd::build::run(BuildArgs {
                     manifest,
-                    cargo_bin: None,
-                    no_flow: false,
-                    no_infer_config: false,
-                    no_write_schema: false,
-                    force_write_schema: false,
-                    no_validate: false,
-                    json: false,
-                    permissive: false,
+                    ..BuildArgs::default()
                 })?;
             }
             WizardStep::Doctor { project_root } => {
                 let manifest = PathBuf::from(project_root).join("component.manifest.json");
                 crate::cmd::doctor::run(DoctorArgs {
-                    target: project_root.clone(),
                     manifest: Some(manifest),
-                    format: DoctorFormat::Human,
+                    ..DoctorArgs::for_target(project_root)
                 })
                 .map_err(|err| anyhow!(err.to_string()))?;
             }
@@ -1212,21 +1633,9 @@ fn execute_run_plan(plan: &WizardPlanEnvelope) -> Result<()> {
                     }
                 }
             }
-            WizardStep::RunCli { command } => {
-                bail!(
-                    "{}",
-                    trf("cli.wizard.error.unsupported_run_cli", &[command])
-                );
-            }
-            WizardStep::Delegate { id } => {
-                bail!(
-                    "{}",
-                    trf("cli.wizard.error.unsupported_delegate", &[id.as_str()])
-                );
-            }
         }
     }
-    Ok(())
+    Ok(failures)
 }
 
 fn parse_string_array(fields: Option<&JsonMap<String, JsonValue>>, key: &str) -> Vec<String> {
@@ -1254,30 +1663,224 @@ fn load_run_answers(path: &PathBuf, args: &WizardArgs) -> Result<LoadedRunAnswer
 
     if let Some(doc) = parse_answer_document(&value)? {
         let migrated = maybe_migrate_document(doc, args)?;
-        let run_answers = run_answers_from_answer_document(&migrated, args)?;
+        let run_answers = run_answers_from_answer_document(&migrated, args.mode)?;
+        ensure_valid_run_answers(&serde_json::to_value(&run_answers)?, path)?;
         return Ok(LoadedRunAnswers {
             run_answers,
             source_document: Some(migrated),
         });
     }
 
-    let answers: WizardRunAnswers = serde_json::from_value(value)
-        .with_context(|| format!("qa answers {} must be valid JSON", path.display()))?;
-    if answers.schema != WIZARD_RUN_SCHEMA {
+    let schema = value
+        .get("schema")
+        .and_then(JsonValue::as_str)
+        .unwrap_or_default();
+    if schema != WIZARD_RUN_SCHEMA {
         bail!(
             "{}",
             trf(
                 "cli.wizard.result.invalid_schema",
-                &[&answers.schema, WIZARD_RUN_SCHEMA],
+                &[schema, WIZARD_RUN_SCHEMA],
             )
         );
     }
+    ensure_valid_run_answers(&value, path)?;
+    let answers: WizardRunAnswers = serde_json::from_value(value)
+        .with_context(|| format!("qa answers {} must be valid JSON", path.display()))?;
     Ok(LoadedRunAnswers {
         run_answers: answers,
         source_document: None,
     })
 }
 
+/// Answers teams set once instead of re-typing: `~/.config/greentic-component/wizard-defaults.json`
+/// overlaid by `GREENTIC_WIZARD_<FIELD>` environment variables.
+fn load_wizard_prefill() -> Result<JsonMap<String, JsonValue>> {
+    let config = match BaseDirs::new().map(|dirs| dirs.home_dir().join(WIZARD_DEFAULTS_PATH)) {
+        Some(path) if path.is_file() => {
+            let raw = fs::read_to_string(&path)
+                .with_context(|| format!("failed to read wizard defaults {}", path.display()))?;
+            match serde_json::from_str(&raw) {
+                Ok(JsonValue::Object(fields)) => fields,
+                Ok(_) => bail!(
+                    "{}",
+                    trf(
+                        "cli.wizard.error.defaults_not_object",
+                        &[path.to_string_lossy().as_ref()]
+                    )
+                ),
+                Err(err) => {
+                    return Err(err).with_context(|| {
+                        format!("wizard defaults {} must be valid JSON", path.display())
+                    });
+                }
+            }
+        }
+        _ => JsonMap::new(),
+    };
+    Ok(wizard_prefill(config, std::env::vars()))
+}
+
+fn wizard_prefill(
+    mut fields: JsonMap<String, JsonValue>,
+    vars: impl IntoIterator<Item = (String, String)>,
+) -> JsonMap<String, JsonValue> {
+    for (name, value) in vars {
+        let Some(field) = name.strip_prefix(WIZARD_PREFILL_ENV_PREFIX) else {
+            continue;
+        };
+        if field.is_empty() {
+            continue;
+        }
+        fields.insert(field.to_ascii_lowercase(), answer_value_from_str(value));
+    }
+    fields
+}
+
+fn parse_set_overrides(values: &[String]) -> Result<JsonMap<String, JsonValue>> {
+    let mut fields = JsonMap::new();
+    for entry in values {
+        let Some((key, value)) = entry.split_once('=') else {
+            bail!("{}", trf("cli.wizard.error.invalid_set", &[entry]));
+        };
+        let key = key.trim();
+        if key.is_empty() {
+            bail!("{}", trf("cli.wizard.error.invalid_set", &[entry]));
+        }
+        fields.insert(key.to_string(), answer_value_from_str(value.to_string()));
+    }
+    Ok(fields)
+}
+
+/// Text answers from flags and env vars; `true`/`false` become booleans for yes/no fields.
+fn answer_value_from_str(value: String) -> JsonValue {
+    match value.as_str() {
+        "true" => JsonValue::Bool(true),
+        "false" => JsonValue::Bool(false),
+        _ => JsonValue::String(value),
+    }
+}
+
+/// Explicit answers always win over prefilled values.
+fn merge_prefill(fields: &mut JsonMap<String, JsonValue>, prefill: JsonMap<String, JsonValue>) {
+    for (key, value) in prefill {
+        fields.entry(key).or_insert(value);
+    }
+}
+
+/// Schema violations as `<json pointer>: <message>`, in document order.
+fn run_answers_schema_errors(value: &JsonValue) -> Vec<String> {
+    RUN_ANSWERS_VALIDATOR
+        .iter_errors(value)
+        .map(|err| {
+            let pointer = err.instance_path().to_string();
+            let pointer = if pointer.is_empty() {
+                "/".to_string()
+            } else {
+                pointer
+            };
+            format!("{pointer}: {err}")
+        })
+        .collect()
+}
+
+fn ensure_valid_run_answers(value: &JsonValue, path: &Path) -> Result<()> {
+    let errors = run_answers_schema_errors(value);
+    if errors.is_empty() {
+        return Ok(());
+    }
+    bail!(
+        "{}",
+        trf(
+            "cli.wizard.result.answers_schema_violations",
+            &[path.to_string_lossy().as_ref(), &errors.join("\n  ")],
+        )
+    )
+}
+
+fn run_validate_answers(args: &WizardValidateAnswersArgs) -> Result<()> {
+    let path = &args.answers;
+    let raw = fs::read_to_string(path)
+        .with_context(|| format!("failed to read qa answers {}", path.display()))?;
+    let value: JsonValue = serde_json::from_str(&raw)
+        .with_context(|| format!("qa answers {} must be valid JSON", path.display()))?;
+    let value = match parse_answer_document(&value)? {
+        Some(doc) => {
+            serde_json::to_value(run_answers_from_answer_document(&doc, RunMode::Create)?)?
+        }
+        None => value,
+    };
+    let errors = run_answers_schema_errors(&value);
+
+    if args.json {
+        let report = json!({
+            "path": path.display().to_string(),
+            "valid": errors.is_empty(),
+            "errors": errors,
+        });
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        if !errors.is_empty() {
+            bail!("{}", tr("cli.wizard.result.answers_invalid"));
+        }
+        return Ok(());
+    }
+    if !errors.is_empty() {
+        bail!(
+            "{}",
+            trf(
+                "cli.wizard.result.answers_schema_violations",
+                &[path.to_string_lossy().as_ref(), &errors.join("\n  ")],
+            )
+        );
+    }
+    println!(
+        "{}",
+        trf(
+            "cli.wizard.result.answers_valid",
+            &[path.to_string_lossy().as_ref()]
+        )
+    );
+    Ok(())
+}
+
+fn run_keys(command: WizardKeysCommand) -> Result<()> {
+    let keystore = PlanKeystore::open_default()?;
+    let root = keystore.root().to_string_lossy().into_owned();
+    match command {
+        WizardKeysCommand::Generate { key_id } => {
+            let public_key = keystore.generate(&key_id)?;
+            println!(
+                "{}",
+                trf(
+                    "cli.wizard.result.key_generated",
+                    &[&key_id, &public_key, &root]
+                )
+            );
+        }
+        WizardKeysCommand::List { json } => {
+            let keys = keystore.list()?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&keys)?);
+            } else if keys.is_empty() {
+                println!("{}", trf("cli.wizard.result.keys_empty", &[&root]));
+            } else {
+                for key in keys {
+                    let role = if key.can_sign { "sign" } else { "verify" };
+                    println!("{}\t{}\t{role}", key.key_id, key.public_key);
+                }
+            }
+        }
+        WizardKeysCommand::Trust { key_id, public_key } => {
+            keystore.trust(&key_id, &public_key)?;
+            println!(
+                "{}",
+                trf("cli.wizard.result.key_trusted", &[&key_id, &root])
+            );
+        }
+    }
+    Ok(())
+}
+
 fn parse_answer_document(value: &JsonValue) -> Result<Option<AnswerDocument>> {
     let JsonValue::Object(map) = value else {
         return Ok(None);
@@ -1324,7 +1927,7 @@ fn maybe_migrate_document(doc: AnswerDocument, args: &WizardArgs) -> Result<Answ
 
 fn run_answers_from_answer_document(
     doc: &AnswerDocument,
-    args: &WizardArgs,
+    default_mode: RunMode,
 ) -> Result<WizardRunAnswers> {
     let mode = doc
         .answers
@@ -1332,7 +1935,7 @@ fn run_answers_from_answer_document(
         .and_then(JsonValue::as_str)
         .map(parse_run_mode)
         .transpose()?
-        .unwrap_or(args.mode);
+        .unwrap_or(default_mode);
     let fields = match doc.answers.get("fields") {
         Some(JsonValue::Object(fields)) => fields.clone(),
         _ => doc.answers.clone(),
@@ -1351,6 +1954,7 @@ fn parse_run_mode(value: &str) -> Result<RunMode> {
         "update-operation" | "update_operation" => Ok(RunMode::UpdateOperation),
         "build-test" | "build_test" => Ok(RunMode::BuildTest),
         "doctor" => Ok(RunMode::Doctor),
+        "remove" => Ok(RunMode::Remove),
         _ => bail!(
             "{}",
             trf("cli.wizard.result.answer_mode_unsupported", &[value])
@@ -1591,6 +2195,24 @@ fn interactive_questions(args: &WizardArgs) -> Vec<JsonValue> {
             "required": true,
             "default": args.project_root.display().to_string()
         })],
+        RunMode::Remove => vec![
+            json!({
+                "id": "project_root",
+                "type": "string",
+                "title": tr("cli.wizard.prompt.project_root"),
+                "title_i18n": {"key":"cli.wizard.prompt.project_root"},
+                "required": true,
+                "default": args.project_root.display().to_string()
+            }),
+            json!({
+                "id": "clean_dist",
+                "type": "boolean",
+                "title": tr("cli.wizard.prompt.clean_dist"),
+                "title_i18n": {"key":"cli.wizard.prompt.clean_dist"},
+                "required": false,
+                "default": false
+            }),
+        ],
     }
 }
 
@@ -1626,6 +2248,22 @@ fn create_questions(args: &WizardArgs, include_advanced: bool) -> Vec<JsonValue>
         return questions;
     }
 
+    // Asked first so operation defaults can follow the selected template.
+    if args.template.is_none() && templates.len() > 1 {
+        let template_choices = templates
+            .into_iter()
+            .map(JsonValue::String)
+            .collect::<Vec<_>>();
+        questions.push(json!({
+            "id": "template_id",
+            "type": "enum",
+            "title": tr("cli.wizard.prompt.template_id"),
+            "title_i18n": {"key":"cli.wizard.prompt.template_id"},
+            "required": true,
+            "default": default_template_id(),
+            "choices": template_choices
+        }));
+    }
     questions.extend([
         json!({
             "id": "abi_version",
@@ -1635,13 +2273,37 @@ fn create_questions(args: &WizardArgs, include_advanced: bool) -> Vec<JsonValue>
             "required": true,
             "default": "0.6.0"
         }),
+        json!({
+            "id": "org",
+            "type": "string",
+            "title": tr("cli.wizard.prompt.org"),
+            "title_i18n": {"key":"cli.wizard.prompt.org"},
+            "required": true,
+            "default": wizard::DEFAULT_COMPONENT_ORG
+        }),
+        json!({
+            "id": "license",
+            "type": "enum",
+            "title": tr("cli.wizard.prompt.license"),
+            "title_i18n": {"key":"cli.wizard.prompt.license"},
+            "required": true,
+            "default": LicenseKind::default().id(),
+            "choices": LicenseKind::ALL.into_iter().map(LicenseKind::id).collect::<Vec<_>>()
+        }),
+        json!({
+            "id": "author",
+            "type": "string",
+            "title": tr("cli.wizard.prompt.author"),
+            "title_i18n": {"key":"cli.wizard.prompt.author"},
+            "required": false,
+            "default": ""
+        }),
         json!({
             "id": "operation_names",
             "type": "string",
             "title": tr("cli.wizard.prompt.operation_names"),
             "title_i18n": {"key":"cli.wizard.prompt.operation_names"},
-            "required": true,
-            "default": "handle_message"
+            "required": true
         }),
         json!({
             "id": "filesystem_mode",
@@ -1741,6 +2403,14 @@ fn create_questions(args: &WizardArgs, include_advanced: bool) -> Vec<JsonValue>
             "default": "node",
             "choices": ["tenant", "pack", "node"]
         }),
+        json!({
+            "id": "telemetry_enabled",
+            "type": "boolean",
+            "title": tr("cli.wizard.prompt.telemetry_enabled"),
+            "title_i18n": {"key":"cli.wizard.prompt.telemetry_enabled"},
+            "required": false,
+            "default": false
+        }),
         json!({
             "id": "telemetry_span_prefix",
             "type": "string",
@@ -1806,34 +2476,54 @@ fn create_questions(args: &WizardArgs, include_advanced: bool) -> Vec<JsonValue>
             "required": false,
             "default": ""
         }),
-    ]);
-    if args.template.is_none() && templates.len() > 1 {
-        let template_choices = templates
-            .into_iter()
-            .map(JsonValue::String)
-            .collect::<Vec<_>>();
-        questions.push(json!({
-            "id": "template_id",
+        json!({
+            "id": "ci",
             "type": "enum",
-            "title": tr("cli.wizard.prompt.template_id"),
-            "title_i18n": {"key":"cli.wizard.prompt.template_id"},
-            "required": true,
-            "default": "component-v0_6",
-            "choices": template_choices
-        }));
-    }
+            "title": tr("cli.wizard.prompt.ci"),
+            "title_i18n": {"key":"cli.wizard.prompt.ci"},
+            "required": false,
+            "default": WizardCi::None.id(),
+            "choices": WizardCi::ALL.into_iter().map(WizardCi::id).collect::<Vec<_>>()
+        }),
+    ]);
     questions
 }
 
 fn available_template_ids() -> Vec<String> {
-    vec!["component-v0_6".to_string()]
+    WizardTemplate::ALL
+        .into_iter()
+        .map(|template| template.id().to_string())
+        .collect()
+}
+
+/// Resolve a built-in template id, or load an external pack from a directory or `git+` URL.
+fn resolve_template(template_id: &str) -> Result<(WizardTemplate, Option<TemplatePack>)> {
+    if let Some(template) = WizardTemplate::from_id(template_id) {
+        return Ok((template, None));
+    }
+    let source = TemplatePackSource::parse(template_id).ok_or_else(|| {
+        anyhow!(
+            "{}",
+            trf(
+                "cli.wizard.error.unknown_template",
+                &[template_id, &available_template_ids().join(", ")]
+            )
+        )
+    })?;
+    let pack = TemplatePack::load(&source)?;
+    Ok((pack.base, Some(pack)))
 }
 
 fn default_template_id() -> String {
-    available_template_ids()
-        .into_iter()
-        .next()
-        .unwrap_or_else(|| "component-v0_6".to_string())
+    WizardTemplate::Component.id().to_string()
+}
+
+fn selected_template(args: &WizardArgs, answered: &JsonMap<String, JsonValue>) -> WizardTemplate {
+    args.template
+        .as_deref()
+        .or_else(|| answered.get("template_id").and_then(JsonValue::as_str))
+        .and_then(WizardTemplate::from_id)
+        .unwrap_or(WizardTemplate::Component)
 }
 
 fn mode_name(mode: RunMode) -> &'static str {
@@ -1843,6 +2533,7 @@ fn mode_name(mode: RunMode) -> &'static str {
         RunMode::UpdateOperation => "update_operation",
         RunMode::BuildTest => "build_test",
         RunMode::Doctor => "doctor",
+        RunMode::Remove => "remove",
     }
 }
 
@@ -2001,6 +2692,7 @@ fn prompt_main_menu_mode(default: RunMode) -> Result<Option<RunMode>> {
     println!("3) {}", tr("cli.wizard.menu.update_operation"));
     println!("4) {}", tr("cli.wizard.menu.build_and_test_component"));
     println!("5) {}", tr("cli.wizard.menu.doctor_component"));
+    println!("6) {}", tr("cli.wizard.menu.remove_component"));
     println!("0) exit");
     let default_label = match default {
         RunMode::Create => "1",
@@ -2008,6 +2700,7 @@ fn prompt_main_menu_mode(default: RunMode) -> Result<Option<RunMode>> {
         RunMode::UpdateOperation => "3",
         RunMode::BuildTest => "4",
         RunMode::Doctor => "5",
+        RunMode::Remove => "6",
     };
     loop {
         print!(
@@ -2046,6 +2739,7 @@ fn parse_main_menu_selection(value: &str) -> Option<RunMode> {
         "3" | "update-operation" | "update_operation" => Some(RunMode::UpdateOperation),
         "4" | "build" | "build-test" | "build_test" => Some(RunMode::BuildTest),
         "5" | "doctor" => Some(RunMode::Doctor),
+        "6" | "remove" => Some(RunMode::Remove),
         _ => None,
     }
 }
@@ -2068,24 +2762,63 @@ fn fallback_default_for_question(
         }
         (RunMode::Create, "advanced_setup") => Some(JsonValue::Bool(false)),
         (RunMode::Create, "secrets_enabled") => Some(JsonValue::Bool(false)),
+        (RunMode::Create, "telemetry_enabled") => Some(JsonValue::Bool(false)),
         (RunMode::Create, "abi_version") => Some(JsonValue::String("0.6.0".to_string())),
-        (RunMode::Create, "operation_names") | (RunMode::Create, "primary_operation_name") => {
-            Some(JsonValue::String("handle_message".to_string()))
+        (RunMode::Create, "org") => {
+            Some(JsonValue::String(wizard::DEFAULT_COMPONENT_ORG.to_string()))
+        }
+        (RunMode::Create, "license") => {
+            Some(JsonValue::String(LicenseKind::default().id().to_string()))
         }
+        (RunMode::Create, "operation_names") => Some(JsonValue::String(
+            selected_template(args, answered)
+                .default_operations()
+                .join(","),
+        )),
+        (RunMode::Create, "primary_operation_name") => selected_template(args, answered)
+            .default_operations()
+            .into_iter()
+            .next()
+            .map(JsonValue::String),
         (RunMode::Create, "template_id") => Some(JsonValue::String(default_template_id())),
+        (RunMode::Create, "ci") => Some(JsonValue::String(WizardCi::None.id().to_string())),
         (RunMode::AddOperation, "project_root")
         | (RunMode::UpdateOperation, "project_root")
         | (RunMode::BuildTest, "project_root")
-        | (RunMode::Doctor, "project_root") => {
+        | (RunMode::Doctor, "project_root")
+        | (RunMode::Remove, "project_root") => {
             Some(JsonValue::String(args.project_root.display().to_string()))
         }
         (RunMode::AddOperation, "set_default_operation")
         | (RunMode::UpdateOperation, "set_default_operation") => Some(JsonValue::Bool(false)),
         (RunMode::BuildTest, "full_tests") => Some(JsonValue::Bool(args.full_tests)),
+        (RunMode::Remove, "clean_dist") => Some(JsonValue::Bool(false)),
         _ => None,
     }
 }
 
+/// Prefilled answers become the prompt default rather than skipping the question.
+fn prefilled_default(
+    question_id: &str,
+    prefill: &JsonMap<String, JsonValue>,
+    answered: &JsonMap<String, JsonValue>,
+) -> Option<JsonValue> {
+    if let Some(value) = prefill.get(question_id) {
+        return Some(value.clone());
+    }
+    let root = prefill.get("output_root").and_then(JsonValue::as_str)?;
+    if question_id != "output_dir" {
+        return None;
+    }
+    let name = answered
+        .get("component_name")
+        .and_then(JsonValue::as_str)
+        .unwrap_or("component");
+    Some(JsonValue::String(
+        Path::new(root).join(name).display().to_string(),
+    ))
+}
+
 fn is_secret_question(question_id: &str) -> bool {
     matches!(
         question_id,
@@ -2108,6 +2841,16 @@ fn should_skip_create_advanced_question(
     {
         return true;
     }
+    if matches!(
+        question_id,
+        "telemetry_span_prefix" | "telemetry_attributes"
+    ) && !answered
+        .get("telemetry_enabled")
+        .and_then(JsonValue::as_bool)
+        .unwrap_or(false)
+    {
+        return true;
+    }
     is_secret_question(question_id)
         && !answered
             .get("secrets_enabled")
@@ -2321,6 +3064,7 @@ fn collect_interactive_question_map_with_skip(
     mut answered: JsonMap<String, JsonValue>,
     should_skip: fn(&str, &JsonMap<String, JsonValue>) -> bool,
 ) -> Result<Option<JsonMap<String, JsonValue>>> {
+    let prefill = load_wizard_prefill()?;
     let mut index = 0usize;
     while index < questions.len() {
         let question = &questions[index];
@@ -2335,9 +3079,13 @@ fn collect_interactive_question_map_with_skip(
             continue;
         }
 
+        let mut question = question.clone();
+        if let Some(default) = prefilled_default(&question_id, &prefill, &answered) {
+            question["default"] = default;
+        }
         match prompt_for_wizard_answer(
             &question_id,
-            question,
+            &question,
             fallback_default_for_question(args, &question_id, &answered),
         )
         .map_err(|err| anyhow!("{err}"))?
@@ -2451,11 +3199,13 @@ fn trf(key: &str, args: &[&str]) -> String {
 
 #[cfg(test)]
 mod tests {
-    use serde_json::{Map as JsonMap, Value as JsonValue};
+    use serde_json::{Map as JsonMap, Value as JsonValue, json};
 
     use super::{
-        RunMode, WizardArgs, create_questions, fallback_default_for_question,
-        parse_main_menu_selection, should_skip_create_advanced_question,
+        RunMode, WizardArgs, WizardStep, append_post_steps, create_questions,
+        fallback_default_for_question, merge_prefill, parse_main_menu_selection, parse_post_steps,
+        parse_runtime_capabilities, parse_set_overrides, prefilled_default,
+        should_skip_create_advanced_question, wizard_prefill,
     };
 
     #[test]
@@ -2468,6 +3218,7 @@ mod tests {
         );
         assert_eq!(parse_main_menu_selection("4"), Some(RunMode::BuildTest));
         assert_eq!(parse_main_menu_selection("5"), Some(RunMode::Doctor));
+        assert_eq!(parse_main_menu_selection("6"), Some(RunMode::Remove));
     }
 
     #[test]
@@ -2490,34 +3241,23 @@ mod tests {
             Some(RunMode::BuildTest)
         );
         assert_eq!(parse_main_menu_selection("doctor"), Some(RunMode::Doctor));
+        assert_eq!(parse_main_menu_selection("remove"), Some(RunMode::Remove));
     }
 
     #[test]
     fn parse_main_menu_selection_rejects_unknown_values() {
         assert_eq!(parse_main_menu_selection(""), None);
-        assert_eq!(parse_main_menu_selection("6"), None);
+        assert_eq!(parse_main_menu_selection("7"), None);
         assert_eq!(parse_main_menu_selection("unknown"), None);
     }
 
     #[test]
     fn create_questions_minimal_flow_only_asks_core_fields() {
         let args = WizardArgs {
-            mode: RunMode::Create,
             execution: super::ExecutionMode::Execute,
-            dry_run: false,
-            validate: false,
-            apply: false,
-            qa_answers: None,
-            answers: None,
-            qa_answers_out: None,
-            emit_answers: None,
-            schema_version: None,
-            migrate: false,
-            plan_out: None,
+            plan_format: super::PlanFormat::Json,
             project_root: std::path::PathBuf::from("."),
-            template: None,
-            full_tests: false,
-            json: false,
+            ..WizardArgs::default()
         };
 
         let questions = create_questions(&args, false);
@@ -2531,22 +3271,10 @@ mod tests {
     #[test]
     fn create_flow_defaults_advanced_setup_to_false() {
         let args = WizardArgs {
-            mode: RunMode::Create,
             execution: super::ExecutionMode::Execute,
-            dry_run: false,
-            validate: false,
-            apply: false,
-            qa_answers: None,
-            answers: None,
-            qa_answers_out: None,
-            emit_answers: None,
-            schema_version: None,
-            migrate: false,
-            plan_out: None,
+            plan_format: super::PlanFormat::Json,
             project_root: std::path::PathBuf::from("/tmp/demo"),
-            template: None,
-            full_tests: false,
-            json: false,
+            ..WizardArgs::default()
         };
 
         assert_eq!(
@@ -2562,22 +3290,10 @@ mod tests {
     #[test]
     fn create_questions_advanced_flow_includes_secret_gate_before_secret_fields() {
         let args = WizardArgs {
-            mode: RunMode::Create,
             execution: super::ExecutionMode::Execute,
-            dry_run: false,
-            validate: false,
-            apply: false,
-            qa_answers: None,
-            answers: None,
-            qa_answers_out: None,
-            emit_answers: None,
-            schema_version: None,
-            migrate: false,
-            plan_out: None,
+            plan_format: super::PlanFormat::Json,
             project_root: std::path::PathBuf::from("."),
-            template: None,
-            full_tests: false,
-            json: false,
+            ..WizardArgs::default()
         };
 
         let questions = create_questions(&args, true);
@@ -2593,22 +3309,10 @@ mod tests {
     #[test]
     fn create_questions_advanced_flow_includes_messaging_and_events_fields() {
         let args = WizardArgs {
-            mode: RunMode::Create,
             execution: super::ExecutionMode::Execute,
-            dry_run: false,
-            validate: false,
-            apply: false,
-            qa_answers: None,
-            answers: None,
-            qa_answers_out: None,
-            emit_answers: None,
-            schema_version: None,
-            migrate: false,
-            plan_out: None,
+            plan_format: super::PlanFormat::Json,
             project_root: std::path::PathBuf::from("."),
-            template: None,
-            full_tests: false,
-            json: false,
+            ..WizardArgs::default()
         };
 
         let questions = create_questions(&args, true);
@@ -2622,6 +3326,41 @@ mod tests {
         assert!(ids.contains(&"events_outbound"));
     }
 
+    #[test]
+    fn operation_defaults_follow_selected_template() {
+        let args = WizardArgs {
+            execution: super::ExecutionMode::Execute,
+            plan_format: super::PlanFormat::Json,
+            project_root: std::path::PathBuf::from("."),
+            ..WizardArgs::default()
+        };
+
+        let ids = create_questions(&args, true)
+            .iter()
+            .filter_map(|question| question.get("id").and_then(JsonValue::as_str))
+            .map(ToOwned::to_owned)
+            .collect::<Vec<_>>();
+        let template_index = ids.iter().position(|id| id == "template_id").unwrap();
+        let ops_index = ids.iter().position(|id| id == "operation_names").unwrap();
+        assert!(template_index < ops_index);
+
+        let mut answered = JsonMap::new();
+        answered.insert(
+            "template_id".to_string(),
+            JsonValue::String("channel-v0_6".to_string()),
+        );
+        assert_eq!(
+            fallback_default_for_question(&args, "operation_names", &answered),
+            Some(JsonValue::String(
+                "send_message,receive_message".to_string()
+            ))
+        );
+        assert_eq!(
+            fallback_default_for_question(&args, "operation_names", &JsonMap::new()),
+            Some(JsonValue::String("handle_message".to_string()))
+        );
+    }
+
     #[test]
     fn advanced_create_flow_skips_questions_answered_in_minimal_pass() {
         let mut answered = JsonMap::new();
@@ -2676,4 +3415,138 @@ mod tests {
             &answered
         ));
     }
+
+    #[test]
+    fn secrets_gate_controls_declared_secret_keys() {
+        let mut fields = JsonMap::new();
+        fields.insert("secret_keys".to_string(), JsonValue::from("API_TOKEN"));
+        let capabilities = parse_runtime_capabilities(Some(&fields)).unwrap();
+        assert_eq!(capabilities.secret_keys, vec!["API_TOKEN"]);
+
+        fields.insert("secrets_enabled".to_string(), JsonValue::Bool(false));
+        let capabilities = parse_runtime_capabilities(Some(&fields)).unwrap();
+        assert!(capabilities.secret_keys.is_empty());
+        assert!(
+            capabilities.manifest_capabilities()["host"]
+                .get("secrets")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn post_steps_run_in_fixed_order_and_reject_unknown_ids() {
+        let mut fields = JsonMap::new();
+        fields.insert("post_steps".to_string(), json!(["check", "git", "fmt"]));
+        assert_eq!(
+            parse_post_steps(Some(&fields)).unwrap(),
+            vec!["fmt", "git", "check"]
+        );
+        assert!(parse_post_steps(None).unwrap().is_empty());
+
+        fields.insert("post_steps".to_string(), json!(["editor"]));
+        let err = parse_post_steps(Some(&fields)).unwrap_err();
+        assert!(err.to_string().contains("editor"));
+    }
+
+    #[test]
+    fn post_steps_continue_on_error_and_skip_git_inside_a_repository() {
+        let repo = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(repo.path().join(".git")).unwrap();
+        let mut plan = super::write_files_plan("test", repo.path(), Vec::new()).unwrap();
+        let mut warnings = Vec::new();
+        append_post_steps(&mut plan, &["fmt", "git"], "component", &mut warnings);
+
+        assert_eq!(plan.plan.steps.len(), 2, "only fmt is added");
+        assert!(matches!(
+            &plan.plan.steps[1],
+            WizardStep::RunCli { command, continue_on_error: true, .. } if command == "cargo"
+        ));
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains(".git"), "{warnings:?}");
+    }
+
+    #[test]
+    fn telemetry_gate_controls_span_prefix_and_questions() {
+        let mut fields = JsonMap::new();
+        fields.insert("component_name".to_string(), JsonValue::from("demo"));
+        fields.insert("telemetry_enabled".to_string(), JsonValue::Bool(true));
+        let capabilities = parse_runtime_capabilities(Some(&fields)).unwrap();
+        assert_eq!(capabilities.telemetry_span_prefix.as_deref(), Some("demo"));
+        assert!(!should_skip_create_advanced_question(
+            "telemetry_span_prefix",
+            &fields
+        ));
+
+        fields.insert("telemetry_enabled".to_string(), JsonValue::Bool(false));
+        fields.insert(
+            "telemetry_span_prefix".to_string(),
+            JsonValue::from("component.demo"),
+        );
+        let capabilities = parse_runtime_capabilities(Some(&fields)).unwrap();
+        assert_eq!(capabilities.telemetry_span_prefix, None);
+        assert_eq!(capabilities.manifest_telemetry(), None);
+        let mut answered = JsonMap::new();
+        answered.insert("telemetry_enabled".to_string(), JsonValue::Bool(false));
+        assert!(should_skip_create_advanced_question(
+            "telemetry_attributes",
+            &answered
+        ));
+    }
+
+    #[test]
+    fn prefill_layers_env_over_config_and_under_explicit_answers() {
+        let mut config = JsonMap::new();
+        config.insert("abi_version".to_string(), JsonValue::from("0.5.0"));
+        config.insert("org".to_string(), JsonValue::from("ai.acme"));
+        let prefill = wizard_prefill(
+            config,
+            [
+                (
+                    "GREENTIC_WIZARD_ABI_VERSION".to_string(),
+                    "0.6.0".to_string(),
+                ),
+                ("GREENTIC_WIZARD_WORKSPACE".to_string(), "true".to_string()),
+                (
+                    "GREENTIC_WIZARD_OUTPUT_ROOT".to_string(),
+                    "/work".to_string(),
+                ),
+                ("HOME".to_string(), "/home/dev".to_string()),
+            ],
+        );
+        assert_eq!(prefill["abi_version"], "0.6.0");
+        assert_eq!(prefill["org"], "ai.acme");
+        assert_eq!(prefill["workspace"], true);
+        assert!(!prefill.contains_key("home"));
+
+        let answered =
+            JsonMap::from_iter([("component_name".to_string(), JsonValue::from("demo"))]);
+        assert_eq!(
+            prefilled_default("output_dir", &prefill, &answered),
+            Some(JsonValue::from("/work/demo"))
+        );
+        assert_eq!(prefilled_default("template_id", &prefill, &answered), None);
+
+        let mut fields = JsonMap::new();
+        fields.insert("org".to_string(), JsonValue::from("ai.explicit"));
+        merge_prefill(&mut fields, prefill);
+        assert_eq!(fields["org"], "ai.explicit");
+        assert_eq!(fields["abi_version"], "0.6.0");
+    }
+
+    #[test]
+    fn set_overrides_parse_key_value_pairs() {
+        let fields = parse_set_overrides(&[
+            "component_name=foo".to_string(),
+            "abi_version=0.6.0".to_string(),
+            "operation_names=a,b=c".to_string(),
+            "advanced_setup=true".to_string(),
+        ])
+        .expect("valid overrides");
+        assert_eq!(fields["component_name"], "foo");
+        assert_eq!(fields["operation_names"], "a,b=c");
+        assert_eq!(fields["advanced_setup"], true);
+
+        assert!(parse_set_overrides(&["component_name".to_string()]).is_err());
+        assert!(parse_set_overrides(&["=foo".to_string()]).is_err());
+    }
 }
@@ -0,0 +1,468 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use thiserror::Error;
+
+/// Locale every other bundle is compared against.
+pub const SOURCE_LOCALE: &str = "en";
+
+/// `locales.json` lists target locales; it is metadata, not a translation map.
+const LOCALES_METADATA_STEM: &str = "locales";
+
+/// Translation key -> message for a single locale.
+pub type LocaleMessages = BTreeMap<String, String>;
+/// Locale -> messages, keyed by file stem (`assets/i18n/<locale>.json` or `<locale>.ftl`).
+pub type LocaleBundle = BTreeMap<String, LocaleMessages>;
+
+#[derive(Debug, Error)]
+pub enum I18nCheckError {
+    #[error("failed to read locale directory {path}: {source}")]
+    ReadDir {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("failed to read locale file {path}: {source}")]
+    ReadFile {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("locale file {path} must be a flat JSON object of strings: {source}")]
+    Parse {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error("locale file {path} is not a valid Fluent bundle: {message}")]
+    ParseFtl { path: PathBuf, message: String },
+    #[error("source locale `en.json` or `en.ftl` not found in {0}")]
+    MissingSource(PathBuf),
+}
+
+/// Comparison of one locale against the source locale.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct LocaleCheck {
+    pub locale: String,
+    pub missing_keys: Vec<String>,
+    pub extra_keys: Vec<String>,
+    pub empty_values: Vec<String>,
+}
+
+impl LocaleCheck {
+    pub fn is_clean(&self) -> bool {
+        self.missing_keys.is_empty() && self.extra_keys.is_empty() && self.empty_values.is_empty()
+    }
+}
+
+/// Result of comparing every locale in a bundle against `en`.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct I18nCheckReport {
+    pub source_locale: String,
+    pub source_keys: usize,
+    pub ok: bool,
+    pub locales: Vec<LocaleCheck>,
+}
+
+impl I18nCheckReport {
+    pub fn failing_locales(&self) -> impl Iterator<Item = &LocaleCheck> {
+        self.locales.iter().filter(|locale| !locale.is_clean())
+    }
+}
+
+/// Translation progress of one locale relative to the source locale.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct LocaleCoverage {
+    pub locale: String,
+    pub total_keys: usize,
+    pub translated_keys: usize,
+    pub coverage_percent: f64,
+    /// Source keys that are missing or empty in this locale.
+    pub untranslated_keys: Vec<String>,
+    /// Keys this locale still carries but the source locale no longer defines.
+    pub stale_keys: Vec<String>,
+}
+
+/// Coverage of every target locale against `en`.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct I18nCoverageReport {
+    pub source_locale: String,
+    pub source_keys: usize,
+    pub locales: Vec<LocaleCoverage>,
+}
+
+/// Read every `*.json` and `*.ftl` locale map in `dir` (skipping `locales.json`).
+///
+/// Same merge order as the scaffolded `i18n_bundle.rs`: JSON maps load first and a locale's
+/// `.ftl` file overrides them key by key.
+pub fn load_locale_dir(dir: &Path) -> Result<LocaleBundle, I18nCheckError> {
+    let entries = fs::read_dir(dir).map_err(|source| I18nCheckError::ReadDir {
+        path: dir.to_path_buf(),
+        source,
+    })?;
+    let mut paths = Vec::new();
+    for entry in entries {
+        let entry = entry.map_err(|source| I18nCheckError::ReadDir {
+            path: dir.to_path_buf(),
+            source,
+        })?;
+        let path = entry.path();
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => paths.push((false, path)),
+            Some("ftl") => paths.push((true, path)),
+            _ => {}
+        }
+    }
+    paths.sort();
+
+    let mut bundle = LocaleBundle::new();
+    for (is_ftl, path) in paths {
+        let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        if stem == LOCALES_METADATA_STEM {
+            continue;
+        }
+        let raw = fs::read_to_string(&path).map_err(|source| I18nCheckError::ReadFile {
+            path: path.clone(),
+            source,
+        })?;
+        let messages: LocaleMessages = if is_ftl {
+            parse_ftl(&raw).map_err(|message| I18nCheckError::ParseFtl {
+                path: path.clone(),
+                message,
+            })?
+        } else {
+            serde_json::from_str(&raw).map_err(|source| I18nCheckError::Parse {
+                path: path.clone(),
+                source,
+            })?
+        };
+        bundle.entry(stem.to_string()).or_default().extend(messages);
+    }
+    Ok(bundle)
+}
+
+/// Parse a flat Fluent bundle the way scaffolded components do at build time: `key = value`
+/// lines, indented continuation lines, and `.attribute = value` lines stored as
+/// `key.attribute`. Comments are skipped; placeables and selectors are kept verbatim.
+pub fn parse_ftl(source: &str) -> Result<LocaleMessages, String> {
+    let mut messages = LocaleMessages::new();
+    let mut with_attributes = BTreeSet::new();
+    let mut message_id: Option<String> = None;
+    let mut current: Option<String> = None;
+
+    for (idx, line) in source.lines().enumerate() {
+        let line_no = idx + 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if line.starts_with('#') {
+            message_id = None;
+            current = None;
+            continue;
+        }
+        if line.starts_with(char::is_whitespace) {
+            let trimmed = line.trim();
+            if let Some(attribute) = trimmed.strip_prefix('.') {
+                let Some(id) = message_id.as_ref() else {
+                    return Err(format!("line {line_no}: attribute outside of a message"));
+                };
+                let (name, value) = split_ftl_entry(attribute, line_no)?;
+                let key = format!("{id}.{name}");
+                with_attributes.insert(id.clone());
+                messages.insert(key.clone(), value);
+                current = Some(key);
+                continue;
+            }
+            let Some(key) = current.as_ref() else {
+                return Err(format!(
+                    "line {line_no}: indented line outside of a message"
+                ));
+            };
+            let value = messages.entry(key.clone()).or_default();
+            if !value.is_empty() {
+                value.push('\n');
+            }
+            value.push_str(trimmed);
+            continue;
+        }
+        let (id, value) = split_ftl_entry(line, line_no)?;
+        messages.insert(id.clone(), value);
+        message_id = Some(id.clone());
+        current = Some(id);
+    }
+
+    // `key =` followed only by attributes is a container, not a message.
+    for id in with_attributes {
+        if messages.get(&id).is_some_and(|value| value.is_empty()) {
+            messages.remove(&id);
+        }
+    }
+    Ok(messages)
+}
+
+fn split_ftl_entry(line: &str, line_no: usize) -> Result<(String, String), String> {
+    let Some((id, value)) = line.split_once('=') else {
+        return Err(format!("line {line_no}: expected `key = value`"));
+    };
+    let id = id.trim();
+    if id.is_empty() || id.contains(char::is_whitespace) {
+        return Err(format!("line {line_no}: invalid message id `{id}`"));
+    }
+    Ok((id.to_string(), value.trim().to_string()))
+}
+
+/// Compare every locale in `bundle` against `SOURCE_LOCALE`.
+///
+/// The source locale itself is only checked for empty values.
+pub fn check_bundle(bundle: &LocaleBundle) -> Option<I18nCheckReport> {
+    let source = bundle.get(SOURCE_LOCALE)?;
+    let locales = bundle
+        .iter()
+        .map(|(locale, messages)| check_locale(locale, source, messages))
+        .collect::<Vec<_>>();
+    Some(I18nCheckReport {
+        source_locale: SOURCE_LOCALE.to_string(),
+        source_keys: source.len(),
+        ok: locales.iter().all(LocaleCheck::is_clean),
+        locales,
+    })
+}
+
+/// Load `dir` and compare every locale against `en`.
+pub fn check_locale_dir(dir: &Path) -> Result<I18nCheckReport, I18nCheckError> {
+    let bundle = load_locale_dir(dir)?;
+    check_bundle(&bundle).ok_or_else(|| I18nCheckError::MissingSource(dir.to_path_buf()))
+}
+
+/// Compute per-locale coverage for every non-source locale in `bundle`.
+///
+/// Locales listed in `expected` but absent from the bundle are reported with 0% coverage.
+pub fn coverage_bundle(bundle: &LocaleBundle, expected: &[String]) -> Option<I18nCoverageReport> {
+    let source = bundle.get(SOURCE_LOCALE)?;
+    let empty = LocaleMessages::new();
+    let mut locales = bundle
+        .keys()
+        .map(String::as_str)
+        .chain(expected.iter().map(String::as_str))
+        .filter(|locale| *locale != SOURCE_LOCALE)
+        .collect::<Vec<_>>();
+    locales.sort_unstable();
+    locales.dedup();
+    let locales = locales
+        .into_iter()
+        .map(|locale| {
+            let messages = bundle.get(locale).unwrap_or(&empty);
+            locale_coverage(locale, source, messages)
+        })
+        .collect();
+    Some(I18nCoverageReport {
+        source_locale: SOURCE_LOCALE.to_string(),
+        source_keys: source.len(),
+        locales,
+    })
+}
+
+/// Load `dir` and compute coverage for every locale found there or listed in `locales.json`.
+pub fn coverage_locale_dir(dir: &Path) -> Result<I18nCoverageReport, I18nCheckError> {
+    let bundle = load_locale_dir(dir)?;
+    let expected = load_expected_locales(dir)?;
+    coverage_bundle(&bundle, &expected)
+        .ok_or_else(|| I18nCheckError::MissingSource(dir.to_path_buf()))
+}
+
+fn load_expected_locales(dir: &Path) -> Result<Vec<String>, I18nCheckError> {
+    let path = dir.join(format!("{LOCALES_METADATA_STEM}.json"));
+    if !path.is_file() {
+        return Ok(Vec::new());
+    }
+    let raw = fs::read_to_string(&path).map_err(|source| I18nCheckError::ReadFile {
+        path: path.clone(),
+        source,
+    })?;
+    serde_json::from_str(&raw).map_err(|source| I18nCheckError::Parse { path, source })
+}
+
+fn locale_coverage(
+    locale: &str,
+    source: &LocaleMessages,
+    messages: &LocaleMessages,
+) -> LocaleCoverage {
+    let untranslated_keys = source
+        .keys()
+        .filter(|key| {
+            messages
+                .get(*key)
+                .is_none_or(|value| value.trim().is_empty())
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+    let stale_keys = messages
+        .keys()
+        .filter(|key| !source.contains_key(*key))
+        .cloned()
+        .collect();
+    let total_keys = source.len();
+    let translated_keys = total_keys - untranslated_keys.len();
+    let coverage_percent = if total_keys == 0 {
+        100.0
+    } else {
+        (translated_keys as f64 * 10_000.0 / total_keys as f64).round() / 100.0
+    };
+    LocaleCoverage {
+        locale: locale.to_string(),
+        total_keys,
+        translated_keys,
+        coverage_percent,
+        untranslated_keys,
+        stale_keys,
+    }
+}
+
+fn check_locale(locale: &str, source: &LocaleMessages, messages: &LocaleMessages) -> LocaleCheck {
+    let missing_keys = source
+        .keys()
+        .filter(|key| !messages.contains_key(*key))
+        .cloned()
+        .collect();
+    let extra_keys = messages
+        .keys()
+        .filter(|key| !source.contains_key(*key))
+        .cloned()
+        .collect();
+    let empty_values = messages
+        .iter()
+        .filter(|(_, value)| value.trim().is_empty())
+        .map(|(key, _)| key.clone())
+        .collect();
+    LocaleCheck {
+        locale: locale.to_string(),
+        missing_keys,
+        extra_keys,
+        empty_values,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(dir: &Path, name: &str, contents: &str) {
+        fs::write(dir.join(name), contents).unwrap();
+    }
+
+    #[test]
+    fn reports_missing_extra_and_empty_keys() {
+        let temp = tempfile::TempDir::new().unwrap();
+        write(temp.path(), "en.json", r#"{"a": "A", "b": "B", "c": ""}"#);
+        write(
+            temp.path(),
+            "de.json",
+            r#"{"a": "A-de", "c": "C", "z": "Z"}"#,
+        );
+        write(
+            temp.path(),
+            "fr.json",
+            r#"{"a": "A-fr", "b": " ", "c": "C"}"#,
+        );
+        write(temp.path(), "locales.json", r#"["de", "fr"]"#);
+
+        let report = check_locale_dir(temp.path()).unwrap();
+        assert!(!report.ok);
+        assert_eq!(report.source_keys, 3);
+        let by_locale = report
+            .locales
+            .iter()
+            .map(|check| (check.locale.as_str(), check))
+            .collect::<BTreeMap<_, _>>();
+        assert_eq!(by_locale.len(), 3);
+        assert_eq!(by_locale["en"].empty_values, vec!["c"]);
+        assert_eq!(by_locale["de"].missing_keys, vec!["b"]);
+        assert_eq!(by_locale["de"].extra_keys, vec!["z"]);
+        assert_eq!(by_locale["fr"].empty_values, vec!["b"]);
+        assert!(by_locale["fr"].missing_keys.is_empty());
+    }
+
+    #[test]
+    fn clean_bundle_is_ok() {
+        let temp = tempfile::TempDir::new().unwrap();
+        write(temp.path(), "en.json", r#"{"a": "A"}"#);
+        write(temp.path(), "nl.json", r#"{"a": "A-nl"}"#);
+
+        let report = check_locale_dir(temp.path()).unwrap();
+        assert!(report.ok);
+        assert_eq!(report.failing_locales().count(), 0);
+    }
+
+    #[test]
+    fn coverage_reports_percent_untranslated_and_stale_keys() {
+        let temp = tempfile::TempDir::new().unwrap();
+        write(temp.path(), "en.json", r#"{"a": "A", "b": "B", "c": "C"}"#);
+        write(
+            temp.path(),
+            "de.json",
+            r#"{"a": "A-de", "b": "", "old": "Alt"}"#,
+        );
+        write(temp.path(), "locales.json", r#"["en", "de", "fr"]"#);
+
+        let report = coverage_locale_dir(temp.path()).unwrap();
+        assert_eq!(report.source_keys, 3);
+        let locales = report
+            .locales
+            .iter()
+            .map(|coverage| coverage.locale.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(locales, vec!["de", "fr"]);
+
+        let de = &report.locales[0];
+        assert_eq!(de.translated_keys, 1);
+        assert_eq!(de.coverage_percent, 33.33);
+        assert_eq!(de.untranslated_keys, vec!["b", "c"]);
+        assert_eq!(de.stale_keys, vec!["old"]);
+
+        let fr = &report.locales[1];
+        assert_eq!(fr.coverage_percent, 0.0);
+        assert_eq!(fr.untranslated_keys.len(), 3);
+    }
+
+    #[test]
+    fn ftl_bundles_count_and_override_json() {
+        let temp = tempfile::TempDir::new().unwrap();
+        write(temp.path(), "en.json", r#"{"a": "A", "b": "B"}"#);
+        write(temp.path(), "en.ftl", "# Fields\nc =\n    .label = C\n");
+        write(
+            temp.path(),
+            "de.ftl",
+            "a = A-de\nb = B-de\nc =\n    .label = C-de\n",
+        );
+        write(
+            temp.path(),
+            "fr.json",
+            r#"{"a": "A-fr", "b": "", "c.label": "C-fr"}"#,
+        );
+        write(temp.path(), "fr.ftl", "b = B-fr\n");
+
+        let bundle = load_locale_dir(temp.path()).unwrap();
+        assert_eq!(bundle["en"].len(), 3);
+        assert_eq!(bundle["fr"]["b"], "B-fr");
+        let report = check_bundle(&bundle).unwrap();
+        assert!(report.ok, "{report:?}");
+
+        write(temp.path(), "nl.ftl", "  .label = orphan\n");
+        let err = load_locale_dir(temp.path()).unwrap_err();
+        assert!(matches!(err, I18nCheckError::ParseFtl { .. }), "{err}");
+    }
+
+    #[test]
+    fn missing_source_locale_is_an_error() {
+        let temp = tempfile::TempDir::new().unwrap();
+        write(temp.path(), "nl.json", r#"{"a": "A-nl"}"#);
+
+        let err = check_locale_dir(temp.path()).unwrap_err();
+        assert!(matches!(err, I18nCheckError::MissingSource(_)));
+    }
+}
@@ -2,6 +2,8 @@
 
 #[cfg(feature = "abi")]
 pub mod abi;
+#[cfg(any(feature = "cli", feature = "abi", feature = "prepare"))]
+pub mod build_metadata;
 pub mod capabilities;
 #[cfg(feature = "cli")]
 pub mod config;
@@ -12,6 +14,7 @@ pub mod embedded_compare;
 #[cfg(any(feature = "cli", feature = "abi", feature = "prepare"))]
 pub mod embedded_descriptor;
 pub mod error;
+pub mod i18n_check;
 pub mod lifecycle;
 pub mod limits;
 #[cfg(feature = "loader")]
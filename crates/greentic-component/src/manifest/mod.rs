@@ -49,6 +49,9 @@ pub struct ComponentManifest {
     pub limits: Option<Limits>,
     #[serde(default)]
     pub telemetry: Option<TelemetrySpec>,
+    /// JSON Schema of the record a stateful component keeps in the host state store.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_schema: Option<Value>,
     pub describe_export: DescribeExport,
     pub operations: Vec<ComponentOperation>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -288,6 +291,8 @@ pub enum ManifestError {
     Limits(String),
     #[error("provenance invalid: {0}")]
     Provenance(String),
+    #[error("state_schema invalid: {0}")]
+    InvalidStateSchema(String),
 }
 
 #[derive(Debug, serde::Deserialize)]
@@ -310,6 +315,8 @@ struct RawManifest {
     limits: Option<Limits>,
     #[serde(default)]
     telemetry: Option<TelemetrySpec>,
+    #[serde(default)]
+    state_schema: Option<Value>,
     describe_export: String,
     operations: Vec<ComponentOperation>,
     #[serde(default)]
@@ -370,6 +377,10 @@ impl TryFrom<RawManifest> for ComponentManifest {
                 .map_err(|err| ManifestError::Provenance(err.to_string()))?;
         }
 
+        if let Some(state_schema) = &raw.state_schema {
+            validate_state_schema(state_schema, &raw.capabilities)?;
+        }
+
         if raw.operations.is_empty() {
             return Err(ManifestError::MissingOperations);
         }
@@ -407,6 +418,7 @@ impl TryFrom<RawManifest> for ComponentManifest {
             configurators: raw.configurators,
             limits: raw.limits,
             telemetry: raw.telemetry,
+            state_schema: raw.state_schema,
             describe_export,
             operations: raw.operations,
             default_operation: raw.default_operation,
@@ -417,6 +429,27 @@ impl TryFrom<RawManifest> for ComponentManifest {
     }
 }
 
+fn validate_state_schema(schema: &Value, capabilities: &Capabilities) -> Result<(), ManifestError> {
+    if !schema.is_object() {
+        return Err(ManifestError::InvalidStateSchema(
+            "must be a JSON Schema object".into(),
+        ));
+    }
+    validator_for(schema)
+        .map_err(|err| ManifestError::InvalidStateSchema(format!("does not compile: {err}")))?;
+    let state_access = capabilities
+        .host
+        .state
+        .as_ref()
+        .is_some_and(|state| state.read || state.write);
+    if !state_access {
+        return Err(ManifestError::InvalidStateSchema(
+            "requires capabilities.host.state with read or write access".into(),
+        ));
+    }
+    Ok(())
+}
+
 fn normalize_secret_requirements(
     capabilities: &mut Capabilities,
     top_level: &[SecretRequirement],
@@ -14,11 +14,11 @@ use handlebars::{Handlebars, no_escape};
 use include_dir::{Dir, DirEntry, include_dir};
 use serde::{Deserialize, Serialize, Serializer};
 use thiserror::Error;
-use time::OffsetDateTime;
 use walkdir::WalkDir;
 
 use super::config_schema::ConfigSchemaInput;
 use super::deps::{self, DependencyMode};
+use super::license::{LicenseKind, copyright_year};
 use super::runtime_capabilities::RuntimeCapabilitiesInput;
 use super::validate::{self, ValidationError};
 use super::write::{GeneratedFile, WriteError, Writer};
@@ -30,7 +30,6 @@ pub const DEFAULT_WIT_WORLD: &str = "greentic:component/component@0.6.0";
 
 const METADATA_FILE: &str = "template.json";
 const TEMPLATE_HOME_ENV: &str = "GREENTIC_TEMPLATE_ROOT";
-const TEMPLATE_YEAR_ENV: &str = "GREENTIC_TEMPLATE_YEAR";
 
 #[derive(Debug, Clone, Default)]
 pub struct ScaffoldEngine;
@@ -58,7 +57,8 @@ impl ScaffoldEngine {
         let descriptor = self.resolve_template(&request.template_id)?;
         validate::ensure_path_available(&request.path)?;
         let package = self.load_template(&descriptor)?;
-        let context = TemplateContext::from_request(&request);
+        let license = LicenseKind::parse(&request.license)?;
+        let context = TemplateContext::from_request(&request, license);
         let rendered = self.render_files(&package, &context)?;
         let created = Writer::new().write_all(&request.path, &rendered)?;
 
@@ -277,7 +277,10 @@ pub struct ScaffoldRequest {
     pub template_id: String,
     pub org: String,
     pub version: String,
+    /// One of the [`LicenseKind`] ids; selects the `LICENSE` text and `Cargo.toml` license.
     pub license: String,
+    /// Cargo `authors` entry and copyright holder; detected from git/env vars when `None`.
+    pub author: Option<String>,
     pub wit_world: String,
     pub user_operations: Vec<String>,
     pub default_operation: String,
@@ -603,6 +606,7 @@ struct TemplateContext {
     org: String,
     version: String,
     license: String,
+    license_text: String,
     wit_world: String,
     user_operations: Vec<TemplateOperation>,
     default_operation: String,
@@ -629,13 +633,21 @@ struct TemplateOperation {
 }
 
 impl TemplateContext {
-    fn from_request(request: &ScaffoldRequest) -> Self {
+    fn from_request(request: &ScaffoldRequest, license: LicenseKind) -> Self {
         let name_snake = request.name.replace('-', "_");
         let name_kebab = request.name.replace('_', "-");
         let package_id = format!("{}.{}", request.org, name_snake);
         let namespace_wit = sanitize_namespace(&request.org);
-        let year = request.year_override.unwrap_or_else(template_year);
+        let year = request.year_override.unwrap_or_else(copyright_year);
         let deps = deps::resolve_dependency_templates(request.dependency_mode, &request.path);
+        let author = request
+            .author
+            .as_deref()
+            .map(str::trim)
+            .filter(|author| !author.is_empty())
+            .map(ToOwned::to_owned)
+            .or_else(detect_author);
+        let holder = author.as_deref().unwrap_or(&request.org);
         Self {
             name: request.name.clone(),
             name_snake,
@@ -644,7 +656,8 @@ impl TemplateContext {
             namespace_wit,
             org: request.org.clone(),
             version: request.version.clone(),
-            license: request.license.clone(),
+            license: license.spdx().to_string(),
+            license_text: license.render(year, holder),
             wit_world: request.wit_world.clone(),
             user_operations: request
                 .user_operations
@@ -680,7 +693,7 @@ impl TemplateContext {
                 .map(|value| indent_json_block(&value)),
             year,
             repo: request.name.clone(),
-            author: detect_author(),
+            author,
             dependency_mode: request.dependency_mode.as_str(),
             greentic_interfaces_dep: deps.greentic_interfaces,
             greentic_interfaces_guest_dep: deps.greentic_interfaces_guest,
@@ -699,15 +712,6 @@ fn indent_json_block(value: &serde_json::Value) -> String {
     indented.join("\n")
 }
 
-fn template_year() -> i32 {
-    if let Ok(value) = env::var(TEMPLATE_YEAR_ENV)
-        && let Ok(parsed) = value.parse()
-    {
-        return parsed;
-    }
-    OffsetDateTime::now_utc().year()
-}
-
 fn sanitize_namespace(value: &str) -> String {
     value
         .chars()
@@ -840,6 +844,7 @@ mod tests {
             org: "ai.greentic".into(),
             version: "0.1.0".into(),
             license: "MIT".into(),
+            author: None,
             wit_world: DEFAULT_WIT_WORLD.into(),
             user_operations: vec!["handle_message".into()],
             default_operation: "handle_message".into(),
@@ -859,6 +864,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn scaffolds_selected_license_and_author() {
+        let temp = TempDir::new().unwrap();
+        let target = temp.path().join("demo-licensed");
+        let engine = ScaffoldEngine::new();
+        let request = ScaffoldRequest {
+            name: "demo-licensed".into(),
+            path: target.clone(),
+            template_id: "rust-wasi-p2-min".into(),
+            org: "ai.greentic".into(),
+            version: "0.1.0".into(),
+            license: "Apache-2.0".into(),
+            author: Some("Acme Corp".into()),
+            wit_world: DEFAULT_WIT_WORLD.into(),
+            user_operations: vec!["handle_message".into()],
+            default_operation: "handle_message".into(),
+            runtime_capabilities: RuntimeCapabilitiesInput::default(),
+            config_schema: ConfigSchemaInput::default(),
+            non_interactive: true,
+            year_override: Some(2030),
+            dependency_mode: DependencyMode::Local,
+        };
+        engine.scaffold(request).unwrap();
+        let license = fs::read_to_string(target.join("LICENSE")).unwrap();
+        assert!(license.starts_with("                              Apache License"));
+        assert!(license.contains("Copyright (c) 2030 Acme Corp"));
+        let cargo = fs::read_to_string(target.join("Cargo.toml")).unwrap();
+        assert!(cargo.contains("license = \"Apache-2.0\""));
+        assert!(cargo.contains("authors = [\"Acme Corp\"]"));
+    }
+
     #[test]
     fn refuses_non_empty_directory() {
         let temp = TempDir::new().unwrap();
@@ -873,6 +909,7 @@ mod tests {
             org: "ai.greentic".into(),
             version: "0.1.0".into(),
             license: "MIT".into(),
+            author: None,
             wit_world: DEFAULT_WIT_WORLD.into(),
             user_operations: vec!["handle_message".into()],
             default_operation: "handle_message".into(),
@@ -0,0 +1,162 @@
+#![cfg(feature = "cli")]
+
+use std::env;
+
+use time::OffsetDateTime;
+
+use super::validate::ValidationError;
+
+const APACHE_2_0_TERMS: &str = include_str!("../../assets/licenses/Apache-2.0.txt");
+const TEMPLATE_YEAR_ENV: &str = "GREENTIC_TEMPLATE_YEAR";
+
+/// Year on generated copyright lines: `GREENTIC_TEMPLATE_YEAR` when it parses, otherwise the
+/// current year.
+pub fn copyright_year() -> i32 {
+    if let Ok(value) = env::var(TEMPLATE_YEAR_ENV)
+        && let Ok(parsed) = value.parse()
+    {
+        return parsed;
+    }
+    OffsetDateTime::now_utc().year()
+}
+
+/// Licenses a scaffold can be generated under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LicenseKind {
+    #[default]
+    Mit,
+    Apache2,
+    Proprietary,
+}
+
+impl LicenseKind {
+    pub const ALL: [LicenseKind; 3] = [
+        LicenseKind::Mit,
+        LicenseKind::Apache2,
+        LicenseKind::Proprietary,
+    ];
+
+    /// Accepts the ids from [`LicenseKind::id`], case-insensitively, plus `apache`.
+    pub fn parse(value: &str) -> Result<Self, ValidationError> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "mit" => Ok(LicenseKind::Mit),
+            "apache-2.0" | "apache2" | "apache" => Ok(LicenseKind::Apache2),
+            "proprietary" => Ok(LicenseKind::Proprietary),
+            _ => Err(ValidationError::InvalidLicense(value.trim().to_string())),
+        }
+    }
+
+    pub fn id(self) -> &'static str {
+        match self {
+            LicenseKind::Mit => "MIT",
+            LicenseKind::Apache2 => "Apache-2.0",
+            LicenseKind::Proprietary => "proprietary",
+        }
+    }
+
+    /// SPDX expression for the `license` field of `Cargo.toml`.
+    pub fn spdx(self) -> &'static str {
+        match self {
+            LicenseKind::Mit => "MIT",
+            LicenseKind::Apache2 => "Apache-2.0",
+            LicenseKind::Proprietary => "LicenseRef-Proprietary",
+        }
+    }
+
+    /// `LICENSE` for `holder` as every scaffold path writes it: "Copyright (c) {year} {holder}".
+    pub fn render(self, year: i32, holder: &str) -> String {
+        self.license_file(&format!("{year} {holder}"))
+    }
+
+    /// Contents of the generated `LICENSE` file; `copyright` follows "Copyright (c)",
+    /// e.g. `2030 Greentic Labs`.
+    pub fn license_file(self, copyright: &str) -> String {
+        match self {
+            LicenseKind::Mit => format!(
+                r#"MIT License
+
+Copyright (c) {copyright}
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+"#
+            ),
+            LicenseKind::Apache2 => format!(
+                r#"{APACHE_2_0_TERMS}
+   Copyright (c) {copyright}
+
+   Licensed under the Apache License, Version 2.0 (the "License");
+   you may not use this file except in compliance with the License.
+   You may obtain a copy of the License at
+
+       http://www.apache.org/licenses/LICENSE-2.0
+
+   Unless required by applicable law or agreed to in writing, software
+   distributed under the License is distributed on an "AS IS" BASIS,
+   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+   See the License for the specific language governing permissions and
+   limitations under the License.
+"#
+            ),
+            LicenseKind::Proprietary => format!(
+                r#"Copyright (c) {copyright}. All rights reserved.
+
+This software is proprietary and confidential. No part of it may be copied,
+modified, distributed, or used without the prior written permission of the
+copyright holder.
+"#
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_supported_licenses() {
+        assert_eq!(LicenseKind::parse("mit").unwrap(), LicenseKind::Mit);
+        assert_eq!(
+            LicenseKind::parse("Apache-2.0").unwrap(),
+            LicenseKind::Apache2
+        );
+        assert_eq!(
+            LicenseKind::parse(" proprietary ").unwrap(),
+            LicenseKind::Proprietary
+        );
+        assert!(matches!(
+            LicenseKind::parse("GPL-3.0"),
+            Err(ValidationError::InvalidLicense(_))
+        ));
+    }
+
+    #[test]
+    fn license_files_carry_the_copyright_holder() {
+        for license in LicenseKind::ALL {
+            let text = license.license_file("2030 Acme Corp");
+            assert!(text.contains("Copyright (c) 2030 Acme Corp"), "{license:?}");
+            assert_eq!(license.render(2030, "Acme Corp"), text);
+        }
+        assert!(
+            LicenseKind::Apache2
+                .license_file("x")
+                .starts_with(APACHE_2_0_TERMS)
+        );
+    }
+}
@@ -1,6 +1,7 @@
 pub mod config_schema;
 pub mod deps;
 pub mod engine;
+pub mod license;
 pub mod runtime_capabilities;
 pub mod validate;
 pub mod write;
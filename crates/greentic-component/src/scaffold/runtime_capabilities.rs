@@ -6,6 +6,8 @@ use serde_json::{Value as JsonValue, json};
 
 use super::validate::ValidationError;
 
+const DEFAULT_TELEMETRY_SCOPE: &str = "node";
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RuntimeCapabilitiesInput {
     pub filesystem_mode: String,
@@ -49,7 +51,7 @@ impl Default for RuntimeCapabilitiesInput {
             state_read: false,
             state_write: false,
             state_delete: false,
-            telemetry_scope: "node".to_string(),
+            telemetry_scope: DEFAULT_TELEMETRY_SCOPE.to_string(),
             telemetry_span_prefix: None,
             telemetry_attributes: BTreeMap::new(),
             secret_keys: Vec::new(),
@@ -125,18 +127,23 @@ impl RuntimeCapabilitiesInput {
                 }),
             );
         }
-        host.insert(
-            "telemetry".to_string(),
-            json!({
-                "scope": self.telemetry_scope
-            }),
-        );
-        host.insert(
-            "secrets".to_string(),
-            json!({
-                "required": self.manifest_secret_requirements()
-            }),
-        );
+        // Telemetry is granted only when enabled or given a non-default scope.
+        if self.telemetry_span_prefix.is_some() || self.telemetry_scope != DEFAULT_TELEMETRY_SCOPE {
+            host.insert(
+                "telemetry".to_string(),
+                json!({
+                    "scope": self.telemetry_scope
+                }),
+            );
+        }
+        if !self.secret_keys.is_empty() {
+            host.insert(
+                "secrets".to_string(),
+                json!({
+                    "required": self.manifest_secret_requirements()
+                }),
+            );
+        }
         if self.http_client || self.http_server {
             host.insert(
                 "http".to_string(),
@@ -92,6 +92,12 @@ pub enum ValidationError {
         help = "Use one of `bytes`, `text`, or `json`."
     )]
     InvalidSecretFormat(String),
+    #[error("license must be one of MIT, Apache-2.0, proprietary (got `{0}`)")]
+    #[diagnostic(
+        code = "greentic.cli.license_invalid",
+        help = "Use one of `MIT`, `Apache-2.0`, or `proprietary`."
+    )]
+    InvalidLicense(String),
     #[error("telemetry attribute must be `key=value` (got `{0}`)")]
     #[diagnostic(
         code = "greentic.cli.telemetry_attribute_invalid",
@@ -150,6 +156,7 @@ impl ValidationError {
             ValidationError::InvalidFilesystemMount(_) => "greentic.cli.filesystem_mount_invalid",
             ValidationError::InvalidTelemetryScope(_) => "greentic.cli.telemetry_scope_invalid",
             ValidationError::InvalidSecretFormat(_) => "greentic.cli.secret_format_invalid",
+            ValidationError::InvalidLicense(_) => "greentic.cli.license_invalid",
             ValidationError::InvalidTelemetryAttribute(_) => {
                 "greentic.cli.telemetry_attribute_invalid"
             }
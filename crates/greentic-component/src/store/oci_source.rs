@@ -16,6 +16,8 @@ pub async fn fetch(reference: &str) -> Result<Bytes> {
         "application/octet-stream",
         "application/vnd.module.wasm.content.layer.v1+wasm",
         "application/vnd.module.wasm.content.layer.v1+tar",
+        // SBOM layer of artifacts packaged by `greentic-component build --oci`.
+        "application/vnd.cyclonedx+json",
     ];
     let image = client.pull(&reference, &auth, accepted_media_types).await?;
 
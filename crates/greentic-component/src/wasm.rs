@@ -1,5 +1,7 @@
+use std::collections::BTreeMap;
+
 use anyhow::{Result, anyhow};
-use wasmparser::Parser;
+use wasmparser::{Parser, Payload};
 use wit_component::{DecodedWasm, metadata};
 use wit_parser::{Resolve, WorldId};
 
@@ -19,6 +21,20 @@ pub struct DecodedWorld {
     pub source: WorldSource,
 }
 
+/// Total size of each custom section name, nested modules included, largest first.
+pub fn custom_section_sizes(bytes: &[u8]) -> Result<Vec<(String, usize)>> {
+    let mut sizes = BTreeMap::<String, usize>::new();
+    for payload in Parser::new(0).parse_all(bytes) {
+        let payload = payload.map_err(|err| anyhow!("failed to parse wasm: {err}"))?;
+        if let Payload::CustomSection(section) = payload {
+            *sizes.entry(section.name().to_string()).or_default() += section.data().len();
+        }
+    }
+    let mut sizes = sizes.into_iter().collect::<Vec<_>>();
+    sizes.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    Ok(sizes)
+}
+
 /// Decode a wasm module or component into its WIT world description.
 pub fn decode_world(bytes: &[u8]) -> Result<DecodedWorld> {
     if Parser::is_component(bytes) {
@@ -0,0 +1,87 @@
+//! ABI template matrix: the parts of a scaffold that change with the requested component ABI.
+
+use semver::Version;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AbiTemplate {
+    /// Canonical ABI version; any patch release of the same `major.minor` selects this entry.
+    pub abi_version: &'static str,
+    pub template_version: &'static str,
+    pub world: &'static str,
+    /// `greentic-interfaces-guest` feature enabling the guest bindings.
+    pub guest_feature: &'static str,
+    /// Module under `greentic_interfaces_guest` that holds the `node` bindings.
+    pub guest_module: &'static str,
+    pub export_macro: &'static str,
+    /// Module under `greentic_interfaces_host` with the wasmtime bindings for `world`,
+    /// used by the generated integration test.
+    pub host_module: &'static str,
+    pub host_world: &'static str,
+}
+
+pub const ABI_TEMPLATES: &[AbiTemplate] = &[AbiTemplate {
+    abi_version: "0.6.0",
+    template_version: super::TEMPLATE_VERSION,
+    world: "greentic:component/component@0.6.0",
+    guest_feature: "component-v0-6",
+    guest_module: "component_v0_6",
+    export_macro: "export_component_v060",
+    host_module: "component_v0_6",
+    host_world: "ComponentV0V6V0",
+}];
+
+impl AbiTemplate {
+    /// Template for `abi_version`, matched on `major.minor`. Versions without a template fall
+    /// back to the first (default) entry and return a warning saying so.
+    pub fn for_version(abi_version: &str) -> (&'static AbiTemplate, Option<String>) {
+        let requested = Version::parse(abi_version).ok();
+        let found = ABI_TEMPLATES.iter().find(|template| {
+            let canonical = Version::parse(template.abi_version).expect("matrix versions parse");
+            requested.as_ref().is_some_and(|requested| {
+                requested.major == canonical.major && requested.minor == canonical.minor
+            })
+        });
+        match found {
+            Some(template) => (template, None),
+            None => {
+                let fallback = &ABI_TEMPLATES[0];
+                let warning = format!(
+                    "wizard: no component template for ABI {abi_version} (supported: {}); using {}",
+                    ABI_TEMPLATES
+                        .iter()
+                        .map(|template| template.abi_version)
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    fallback.abi_version
+                );
+                (fallback, Some(warning))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selects_template_by_major_minor() {
+        let (template, warning) = AbiTemplate::for_version("0.6.3");
+        assert_eq!(template.world, "greentic:component/component@0.6.0");
+        assert!(warning.is_none());
+    }
+
+    #[test]
+    fn unknown_versions_fall_back_with_a_warning() {
+        for version in ["0.5.0", "0.7.0", "latest"] {
+            let (template, warning) = AbiTemplate::for_version(version);
+            assert_eq!(template.abi_version, "0.6.0");
+            let warning = warning.expect("fallback warning");
+            assert!(
+                warning.contains(&format!("no component template for ABI {version}")),
+                "{warning}"
+            );
+            assert!(warning.contains("using 0.6.0"), "{warning}");
+        }
+    }
+}
@@ -0,0 +1,886 @@
+//! Example modules generated next to `src/lib.rs` for the host capabilities a scaffold
+//! declares. Each example wraps its host interface behind a trait so the generated unit
+//! tests run natively against a fake host.
+
+use crate::scaffold::runtime_capabilities::RuntimeCapabilitiesInput;
+
+use super::{GeneratedFile, text_file};
+
+/// Capability id (as used in `required_capabilities`) for the host HTTP client.
+pub const HOST_HTTP_CLIENT: &str = "host.http.client";
+
+struct CapabilityExample {
+    module: &'static str,
+    summary: &'static str,
+    /// `greentic-interfaces-guest` feature providing the host import the example calls.
+    guest_feature: &'static str,
+    render: fn(&RuntimeCapabilitiesInput) -> String,
+}
+
+const HTTP_EXAMPLE: CapabilityExample = CapabilityExample {
+    module: "http",
+    summary: "host HTTP client example (capabilities.host.http.client).",
+    guest_feature: "http-client",
+    render: render_http_rs,
+};
+
+const SECRETS_EXAMPLE: CapabilityExample = CapabilityExample {
+    module: "secrets",
+    summary: "host secrets example reading the declared secret_requirements.",
+    guest_feature: "secrets",
+    render: render_secrets_rs,
+};
+
+const TELEMETRY_EXAMPLE: CapabilityExample = CapabilityExample {
+    module: "telemetry",
+    summary: "span/counter helpers for the host telemetry interface.",
+    guest_feature: "telemetry",
+    render: render_telemetry_rs,
+};
+
+const STATE_EXAMPLE: CapabilityExample = CapabilityExample {
+    module: "state",
+    summary: "host state-store example persisting records as canonical CBOR.",
+    guest_feature: "state-store",
+    render: render_state_rs,
+};
+
+/// Turn `required_capabilities` entries into runtime capabilities. Unknown ids are
+/// returned so the caller can warn about them.
+pub(super) fn apply_required_capabilities(
+    required: &[String],
+    capabilities: &mut RuntimeCapabilitiesInput,
+) -> Vec<String> {
+    let mut unknown = Vec::new();
+    for capability in required {
+        match capability.trim() {
+            HOST_HTTP_CLIENT => capabilities.http_client = true,
+            other => unknown.push(other.to_string()),
+        }
+    }
+    unknown
+}
+
+fn enabled_examples(capabilities: &RuntimeCapabilitiesInput) -> Vec<&'static CapabilityExample> {
+    let mut examples = Vec::new();
+    if capabilities.http_client {
+        examples.push(&HTTP_EXAMPLE);
+    }
+    if !capabilities.secret_keys.is_empty() {
+        examples.push(&SECRETS_EXAMPLE);
+    }
+    if capabilities.telemetry_span_prefix.is_some() {
+        examples.push(&TELEMETRY_EXAMPLE);
+    }
+    if capabilities.state_read || capabilities.state_write || capabilities.state_delete {
+        examples.push(&STATE_EXAMPLE);
+    }
+    examples
+}
+
+pub(super) fn example_files(capabilities: &RuntimeCapabilitiesInput) -> Vec<GeneratedFile> {
+    enabled_examples(capabilities)
+        .into_iter()
+        .map(|example| {
+            text_file(
+                &format!("src/{}.rs", example.module),
+                (example.render)(capabilities),
+            )
+        })
+        .collect()
+}
+
+/// `greentic-interfaces-guest` features the generated examples need on top of the ABI's.
+pub(super) fn guest_features(capabilities: &RuntimeCapabilitiesInput) -> Vec<&'static str> {
+    enabled_examples(capabilities)
+        .into_iter()
+        .map(|example| example.guest_feature)
+        .collect()
+}
+
+/// Body of the generated `invoke` export up to the `output` binding. With telemetry
+/// enabled every operation runs inside a span and bumps the call counter.
+pub(super) fn invoke_output(capabilities: &RuntimeCapabilitiesInput) -> &'static str {
+    if capabilities.telemetry_span_prefix.is_some() {
+        r#"let output = telemetry::instrument(&mut telemetry::RunnerTelemetry, &operation, || {
+            run_component_cbor(&operation, envelope.payload_cbor)
+        });"#
+    } else {
+        "let output = run_component_cbor(&operation, envelope.payload_cbor);"
+    }
+}
+
+/// `mod` declarations for `src/lib.rs`; empty when no example is generated.
+pub(super) fn example_mods(capabilities: &RuntimeCapabilitiesInput) -> String {
+    enabled_examples(capabilities)
+        .into_iter()
+        .map(|example| {
+            format!(
+                "\n// {module}: {summary}\npub mod {module};",
+                module = example.module,
+                summary = example.summary
+            )
+        })
+        .collect()
+}
+
+fn render_http_rs(_capabilities: &RuntimeCapabilitiesInput) -> String {
+    r#"// Host HTTP client example.
+// Requests go through `HttpHost` so the request builder and error mapping can be tested
+// against a fake host; `RunnerHttp` forwards to the runner host import inside wasm32.
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpRequest {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<Vec<u8>>,
+}
+
+impl HttpRequest {
+    pub fn new(method: &str, url: &str) -> Self {
+        Self {
+            method: method.to_ascii_uppercase(),
+            url: url.trim().to_string(),
+            headers: Vec::new(),
+            body: None,
+        }
+    }
+
+    pub fn get(url: &str) -> Self {
+        Self::new("GET", url)
+    }
+
+    pub fn post(url: &str) -> Self {
+        Self::new("POST", url)
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn json_body(self, value: &serde_json::Value) -> Self {
+        let mut request = self.header("content-type", "application/json");
+        request.body = Some(value.to_string().into_bytes());
+        request
+    }
+
+    pub fn validate(&self) -> Result<(), HttpError> {
+        if self.url.starts_with("https://") || self.url.starts_with("http://") {
+            Ok(())
+        } else {
+            Err(HttpError::InvalidUrl(self.url.clone()))
+        }
+    }
+
+    // The host takes headers as `name: value` lines.
+    fn host_headers(&self) -> Vec<String> {
+        self.headers
+            .iter()
+            .map(|(name, value)| format!("{name}: {value}"))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HttpError {
+    InvalidUrl(String),
+    /// The host policy does not grant this component outbound HTTP.
+    Denied(String),
+    Status(u16),
+    Transport(String),
+}
+
+impl HttpError {
+    // Map the host's error string onto a typed error.
+    pub fn from_host(message: &str) -> Self {
+        if message.contains("denied") {
+            return HttpError::Denied(message.to_string());
+        }
+        let status = message
+            .split("status ")
+            .nth(1)
+            .and_then(|rest| rest.split_whitespace().next())
+            .and_then(|code| code.parse().ok());
+        match status {
+            Some(status) => HttpError::Status(status),
+            None => HttpError::Transport(message.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for HttpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HttpError::InvalidUrl(url) => write!(f, "expected an http(s) URL, got `{url}`"),
+            HttpError::Denied(message) => write!(f, "http access denied: {message}"),
+            HttpError::Status(status) => write!(f, "http request failed with status {status}"),
+            HttpError::Transport(message) => write!(f, "http request failed: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for HttpError {}
+
+pub trait HttpHost {
+    fn request(
+        &mut self,
+        method: &str,
+        url: &str,
+        headers: &[String],
+        body: Option<&[u8]>,
+    ) -> Result<Vec<u8>, String>;
+}
+
+pub fn send(host: &mut impl HttpHost, request: &HttpRequest) -> Result<Vec<u8>, HttpError> {
+    request.validate()?;
+    host.request(
+        &request.method,
+        &request.url,
+        &request.host_headers(),
+        request.body.as_deref(),
+    )
+    .map_err(|message| HttpError::from_host(&message))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub struct RunnerHttp;
+
+#[cfg(target_arch = "wasm32")]
+impl HttpHost for RunnerHttp {
+    fn request(
+        &mut self,
+        method: &str,
+        url: &str,
+        headers: &[String],
+        body: Option<&[u8]>,
+    ) -> Result<Vec<u8>, String> {
+        use greentic_interfaces_guest::http_client;
+
+        let request = http_client::Request {
+            method: method.to_string(),
+            url: url.to_string(),
+            headers: headers
+                .iter()
+                .filter_map(|line| line.split_once(':'))
+                .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+                .collect(),
+            body: body.map(<[u8]>::to_vec),
+        };
+        let response = http_client::send(&request, None)
+            .map_err(|err| format!("{}: {}", err.code, err.message))?;
+        if response.status >= 400 {
+            return Err(format!("status {}", response.status));
+        }
+        Ok(response.body.unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // method, url, headers, body
+    type Call = (String, String, Vec<String>, Option<Vec<u8>>);
+
+    #[derive(Default)]
+    struct FakeHost {
+        calls: Vec<Call>,
+        response: Option<Result<Vec<u8>, String>>,
+    }
+
+    impl HttpHost for FakeHost {
+        fn request(
+            &mut self,
+            method: &str,
+            url: &str,
+            headers: &[String],
+            body: Option<&[u8]>,
+        ) -> Result<Vec<u8>, String> {
+            self.calls.push((
+                method.to_string(),
+                url.to_string(),
+                headers.to_vec(),
+                body.map(<[u8]>::to_vec),
+            ));
+            self.response.clone().unwrap_or_else(|| Ok(Vec::new()))
+        }
+    }
+
+    #[test]
+    fn sends_built_request_to_host() {
+        let mut host = FakeHost {
+            response: Some(Ok(b"{\"ok\":true}".to_vec())),
+            ..FakeHost::default()
+        };
+        let request = HttpRequest::post("https://example.com/items")
+            .json_body(&serde_json::json!({ "name": "demo" }));
+        let body = send(&mut host, &request).expect("fake host responds");
+        assert_eq!(body, b"{\"ok\":true}");
+        let (method, url, headers, sent) = &host.calls[0];
+        assert_eq!(method, "POST");
+        assert_eq!(url, "https://example.com/items");
+        assert_eq!(headers, &["content-type: application/json".to_string()]);
+        assert_eq!(sent.as_deref(), Some(&b"{\"name\":\"demo\"}"[..]));
+    }
+
+    #[test]
+    fn maps_host_errors() {
+        for (message, expected) in [
+            (
+                "http fetch denied by policy",
+                HttpError::Denied("http fetch denied by policy".to_string()),
+            ),
+            (
+                "http request failed with status 404 Not Found",
+                HttpError::Status(404),
+            ),
+            (
+                "connection reset",
+                HttpError::Transport("connection reset".to_string()),
+            ),
+        ] {
+            let mut host = FakeHost {
+                response: Some(Err(message.to_string())),
+                ..FakeHost::default()
+            };
+            let err = send(&mut host, &HttpRequest::get("https://example.com")).unwrap_err();
+            assert_eq!(err, expected);
+        }
+    }
+
+    #[test]
+    fn rejects_non_http_urls_without_calling_host() {
+        let mut host = FakeHost::default();
+        let err = send(&mut host, &HttpRequest::get("ftp://example.com")).unwrap_err();
+        assert_eq!(err, HttpError::InvalidUrl("ftp://example.com".to_string()));
+        assert!(host.calls.is_empty());
+    }
+}
+"#
+    .to_string()
+}
+
+fn render_secrets_rs(capabilities: &RuntimeCapabilitiesInput) -> String {
+    let declared = capabilities
+        .secret_keys
+        .iter()
+        .map(|key| format!("{key:?}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        r#"// Host secrets example.
+// Reads the secrets declared in `secret_requirements` (component.manifest.json) through
+// `SecretsHost` so policy failures can be tested against a fake host; `RunnerSecrets`
+// forwards to the host secrets-store import inside wasm32.
+use std::fmt;
+
+/// Keys listed in the manifest's `secret_requirements`; the host only grants these.
+pub const DECLARED_SECRETS: &[&str] = &[{declared}];
+/// Declared `format` of the secrets (`bytes`, `text`, or `json`).
+pub const SECRET_FORMAT: &str = "{format}";
+
+/// Errors reported by the host secrets store.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HostSecretError {{
+    Denied,
+    InvalidKey,
+    NotFound,
+    Other(String),
+}}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecretError {{
+    /// The key is not in `secret_requirements`, so the host would refuse it anyway.
+    Undeclared(String),
+    /// Host policy does not grant this component secrets access.
+    Denied(String),
+    /// The key is declared but not granted for the current env/tenant scope.
+    NotGranted(String),
+    Missing(String),
+    InvalidFormat(String),
+    Host(String),
+}}
+
+impl fmt::Display for SecretError {{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {{
+        match self {{
+            SecretError::Undeclared(key) => {{
+                write!(f, "secret `{{key}}` is not declared in secret_requirements")
+            }}
+            SecretError::Denied(key) => write!(
+                f,
+                "secret `{{key}}`: host policy denies secrets access (check capabilities.host.secrets)"
+            ),
+            SecretError::NotGranted(key) => {{
+                write!(f, "secret `{{key}}` is not granted for this env/tenant")
+            }}
+            SecretError::Missing(key) => write!(f, "secret `{{key}}` has no value"),
+            SecretError::InvalidFormat(key) => {{
+                write!(f, "secret `{{key}}` is not valid {{SECRET_FORMAT}}")
+            }}
+            SecretError::Host(message) => write!(f, "secrets host error: {{message}}"),
+        }}
+    }}
+}}
+
+impl std::error::Error for SecretError {{}}
+
+pub trait SecretsHost {{
+    fn get(&mut self, key: &str) -> Result<Option<Vec<u8>>, HostSecretError>;
+}}
+
+pub fn read_secret(host: &mut impl SecretsHost, key: &str) -> Result<Vec<u8>, SecretError> {{
+    if !DECLARED_SECRETS.contains(&key) {{
+        return Err(SecretError::Undeclared(key.to_string()));
+    }}
+    match host.get(key) {{
+        Ok(Some(bytes)) => Ok(bytes),
+        Ok(None) | Err(HostSecretError::NotFound) => Err(SecretError::Missing(key.to_string())),
+        Err(HostSecretError::Denied) => Err(SecretError::Denied(key.to_string())),
+        Err(HostSecretError::InvalidKey) => Err(SecretError::NotGranted(key.to_string())),
+        Err(HostSecretError::Other(message)) => Err(SecretError::Host(message)),
+    }}
+}}
+
+pub fn read_text(host: &mut impl SecretsHost, key: &str) -> Result<String, SecretError> {{
+    String::from_utf8(read_secret(host, key)?)
+        .map_err(|_| SecretError::InvalidFormat(key.to_string()))
+}}
+
+pub fn read_json(host: &mut impl SecretsHost, key: &str) -> Result<serde_json::Value, SecretError> {{
+    serde_json::from_slice(&read_secret(host, key)?)
+        .map_err(|_| SecretError::InvalidFormat(key.to_string()))
+}}
+
+/// Read every declared secret, stopping at the first one the host does not provide.
+pub fn read_declared(host: &mut impl SecretsHost) -> Result<Vec<(String, Vec<u8>)>, SecretError> {{
+    DECLARED_SECRETS
+        .iter()
+        .map(|key| Ok((key.to_string(), read_secret(host, key)?)))
+        .collect()
+}}
+
+#[cfg(target_arch = "wasm32")]
+pub struct RunnerSecrets;
+
+#[cfg(target_arch = "wasm32")]
+impl SecretsHost for RunnerSecrets {{
+    fn get(&mut self, key: &str) -> Result<Option<Vec<u8>>, HostSecretError> {{
+        use greentic_interfaces_guest::secrets_store::{{self, SecretsError}};
+        #[allow(unreachable_patterns)]
+        secrets_store::get(key).map_err(|err| match err {{
+            SecretsError::Denied => HostSecretError::Denied,
+            SecretsError::InvalidKey => HostSecretError::InvalidKey,
+            SecretsError::NotFound => HostSecretError::NotFound,
+            other => HostSecretError::Other(format!("{{other:?}}")),
+        }})
+    }}
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+    use std::collections::HashMap;
+
+    #[derive(Default)]
+    struct FakeSecrets {{
+        deny: bool,
+        values: HashMap<String, Vec<u8>>,
+        calls: usize,
+    }}
+
+    impl SecretsHost for FakeSecrets {{
+        fn get(&mut self, key: &str) -> Result<Option<Vec<u8>>, HostSecretError> {{
+            self.calls += 1;
+            if self.deny {{
+                return Err(HostSecretError::Denied);
+            }}
+            Ok(self.values.get(key).cloned())
+        }}
+    }}
+
+    fn granted() -> FakeSecrets {{
+        FakeSecrets {{
+            values: DECLARED_SECRETS
+                .iter()
+                .map(|key| (key.to_string(), format!("{{key}}-value").into_bytes()))
+                .collect(),
+            ..FakeSecrets::default()
+        }}
+    }}
+
+    #[test]
+    fn reads_declared_secrets() {{
+        let mut host = granted();
+        let key = DECLARED_SECRETS[0];
+        assert_eq!(read_text(&mut host, key).unwrap(), format!("{{key}}-value"));
+        assert_eq!(
+            read_declared(&mut host).unwrap().len(),
+            DECLARED_SECRETS.len()
+        );
+    }}
+
+    #[test]
+    fn maps_policy_failures() {{
+        let key = DECLARED_SECRETS[0];
+        let mut host = FakeSecrets {{
+            deny: true,
+            ..FakeSecrets::default()
+        }};
+        assert_eq!(
+            read_secret(&mut host, key).unwrap_err(),
+            SecretError::Denied(key.to_string())
+        );
+        let mut host = FakeSecrets::default();
+        assert_eq!(
+            read_secret(&mut host, key).unwrap_err(),
+            SecretError::Missing(key.to_string())
+        );
+    }}
+
+    #[test]
+    fn rejects_undeclared_keys_without_calling_host() {{
+        let mut host = granted();
+        assert_eq!(
+            read_secret(&mut host, "NOT_DECLARED").unwrap_err(),
+            SecretError::Undeclared("NOT_DECLARED".to_string())
+        );
+        assert_eq!(host.calls, 0);
+    }}
+}}
+"#,
+        format = capabilities.secret_format
+    )
+}
+
+fn render_telemetry_rs(capabilities: &RuntimeCapabilitiesInput) -> String {
+    let attributes = capabilities
+        .telemetry_attributes
+        .iter()
+        .map(|(key, value)| format!("({key:?}, {value:?})"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        r#"// Telemetry example.
+// Spans and counters are emitted as events through `TelemetryHost` so instrumentation can be
+// tested against a fake host; `RunnerTelemetry` forwards them to the host telemetry import
+// inside wasm32. Names are prefixed with the manifest's `telemetry.span_prefix`.
+use std::time::Instant;
+
+pub const SPAN_PREFIX: &str = {prefix:?};
+/// `telemetry.attributes` from the manifest, attached to every event.
+pub const ATTRIBUTES: &[(&str, &str)] = &[{attributes}];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TelemetryEvent {{
+    SpanStart {{
+        name: String,
+    }},
+    SpanEnd {{
+        name: String,
+        ok: bool,
+        duration_ms: u64,
+    }},
+    Counter {{
+        name: String,
+        value: u64,
+    }},
+}}
+
+impl TelemetryEvent {{
+    pub fn to_json(&self) -> serde_json::Value {{
+        let attributes = ATTRIBUTES
+            .iter()
+            .map(|(key, value)| (key.to_string(), serde_json::Value::from(*value)))
+            .collect::<serde_json::Map<_, _>>();
+        match self {{
+            TelemetryEvent::SpanStart {{ name }} => {{
+                serde_json::json!({{ "kind": "span_start", "name": name, "attributes": attributes }})
+            }}
+            TelemetryEvent::SpanEnd {{
+                name,
+                ok,
+                duration_ms,
+            }} => serde_json::json!({{
+                "kind": "span_end",
+                "name": name,
+                "ok": ok,
+                "duration_ms": duration_ms,
+                "attributes": attributes,
+            }}),
+            TelemetryEvent::Counter {{ name, value }} => serde_json::json!({{
+                "kind": "counter",
+                "name": name,
+                "value": value,
+                "attributes": attributes,
+            }}),
+        }}
+    }}
+}}
+
+pub trait TelemetryHost {{
+    fn emit(&mut self, event: TelemetryEvent);
+}}
+
+fn prefixed(name: &str) -> String {{
+    format!("{{SPAN_PREFIX}}.{{name}}")
+}}
+
+pub struct Span {{
+    name: String,
+    started: Instant,
+}}
+
+pub fn start_span(host: &mut impl TelemetryHost, name: &str) -> Span {{
+    let name = prefixed(name);
+    host.emit(TelemetryEvent::SpanStart {{ name: name.clone() }});
+    Span {{
+        name,
+        started: Instant::now(),
+    }}
+}}
+
+impl Span {{
+    pub fn finish(self, host: &mut impl TelemetryHost, ok: bool) {{
+        host.emit(TelemetryEvent::SpanEnd {{
+            name: self.name,
+            ok,
+            duration_ms: self.started.elapsed().as_millis() as u64,
+        }});
+    }}
+}}
+
+pub fn increment(host: &mut impl TelemetryHost, name: &str, value: u64) {{
+    host.emit(TelemetryEvent::Counter {{
+        name: prefixed(name),
+        value,
+    }});
+}}
+
+/// Run `operation` inside a span and count the call; used by the `invoke` dispatcher.
+pub fn instrument<T>(host: &mut impl TelemetryHost, operation: &str, run: impl FnOnce() -> T) -> T {{
+    let span = start_span(host, operation);
+    let output = run();
+    span.finish(host, true);
+    increment(host, &format!("{{operation}}.calls"), 1);
+    output
+}}
+
+#[cfg(target_arch = "wasm32")]
+pub struct RunnerTelemetry;
+
+#[cfg(target_arch = "wasm32")]
+impl TelemetryHost for RunnerTelemetry {{
+    fn emit(&mut self, event: TelemetryEvent) {{
+        use greentic_interfaces_guest::telemetry_logger;
+
+        let span = telemetry_logger::SpanContext {{
+            tenant: String::new(),
+            session_id: None,
+            flow_id: String::new(),
+            node_id: None,
+            provider: SPAN_PREFIX.to_string(),
+            start_ms: None,
+            end_ms: None,
+        }};
+        let fields = [("event".to_string(), event.to_json().to_string())];
+        // Telemetry is best effort; a rejected log must not fail the operation.
+        let _ = telemetry_logger::log(&span, &fields, None);
+    }}
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    #[derive(Default)]
+    struct FakeTelemetry {{
+        events: Vec<TelemetryEvent>,
+    }}
+
+    impl TelemetryHost for FakeTelemetry {{
+        fn emit(&mut self, event: TelemetryEvent) {{
+            self.events.push(event);
+        }}
+    }}
+
+    #[test]
+    fn instrument_wraps_operation_in_span_and_counter() {{
+        let mut host = FakeTelemetry::default();
+        let output = instrument(&mut host, "handle_message", || 42);
+        assert_eq!(output, 42);
+        assert_eq!(host.events.len(), 3);
+        assert_eq!(
+            host.events[0],
+            TelemetryEvent::SpanStart {{
+                name: prefixed("handle_message")
+            }}
+        );
+        assert!(matches!(
+            &host.events[1],
+            TelemetryEvent::SpanEnd {{ ok: true, .. }}
+        ));
+        assert_eq!(
+            host.events[2],
+            TelemetryEvent::Counter {{
+                name: prefixed("handle_message.calls"),
+                value: 1
+            }}
+        );
+    }}
+
+    #[test]
+    fn events_carry_manifest_attributes() {{
+        let event = TelemetryEvent::Counter {{
+            name: prefixed("errors"),
+            value: 2,
+        }};
+        let json = event.to_json();
+        assert_eq!(json["kind"], "counter");
+        for (key, value) in ATTRIBUTES {{
+            assert_eq!(json["attributes"][*key], *value);
+        }}
+    }}
+}}
+"#,
+        prefix = capabilities
+            .telemetry_span_prefix
+            .as_deref()
+            .unwrap_or_default()
+    )
+}
+
+fn render_state_rs(_capabilities: &RuntimeCapabilitiesInput) -> String {
+    r#"// Host state-store example.
+// Records are stored as canonical CBOR under a key through `StateHost`, so load/save can be
+// tested against a fake host; `RunnerState` forwards to the state-store import inside wasm32.
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+/// Key the component's state record is stored under; the host namespaces it per component.
+pub const STATE_KEY: &str = "state";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateError(pub String);
+
+impl std::fmt::Display for StateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "state store failed: {}", self.0)
+    }
+}
+
+impl std::error::Error for StateError {}
+
+pub trait StateHost {
+    /// Stored bytes, or an empty buffer when nothing was written yet.
+    fn read(&mut self, key: &str) -> Result<Vec<u8>, StateError>;
+    fn write(&mut self, key: &str, bytes: &[u8]) -> Result<(), StateError>;
+}
+
+/// Load the record under `key`; missing or unreadable state starts over from the default.
+pub fn load<T: DeserializeOwned + Default>(host: &mut impl StateHost, key: &str) -> T {
+    match host.read(key) {
+        Ok(bytes) if !bytes.is_empty() => {
+            greentic_types::cbor::canonical::from_cbor(&bytes).unwrap_or_default()
+        }
+        _ => T::default(),
+    }
+}
+
+pub fn save<T: Serialize>(host: &mut impl StateHost, key: &str, value: &T) -> Result<(), StateError> {
+    let bytes = greentic_types::cbor::canonical::to_canonical_cbor_allow_floats(value)
+        .map_err(|err| StateError(err.to_string()))?;
+    host.write(key, &bytes)
+}
+
+#[cfg(target_arch = "wasm32")]
+pub struct RunnerState;
+
+#[cfg(target_arch = "wasm32")]
+impl StateHost for RunnerState {
+    fn read(&mut self, key: &str) -> Result<Vec<u8>, StateError> {
+        use greentic_interfaces_guest::state_store;
+        match state_store::read(key, None) {
+            Ok(bytes) => Ok(bytes),
+            Err(err) if err.code == "not_found" => Ok(Vec::new()),
+            Err(err) => Err(StateError(format!("{}: {}", err.code, err.message))),
+        }
+    }
+
+    fn write(&mut self, key: &str, bytes: &[u8]) -> Result<(), StateError> {
+        use greentic_interfaces_guest::state_store;
+        state_store::write(key, bytes, None)
+            .map(|_| ())
+            .map_err(|err| StateError(format!("{}: {}", err.code, err.message)))
+    }
+}
+
+/// In-memory `StateHost` for native builds, where there is no runner host.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Default)]
+pub struct MemoryState {
+    slots: std::collections::BTreeMap<String, Vec<u8>>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl StateHost for MemoryState {
+    fn read(&mut self, key: &str) -> Result<Vec<u8>, StateError> {
+        Ok(self.slots.get(key).cloned().unwrap_or_default())
+    }
+
+    fn write(&mut self, key: &str, bytes: &[u8]) -> Result<(), StateError> {
+        self.slots.insert(key.to_string(), bytes.to_vec());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    struct DeniedState;
+
+    impl StateHost for DeniedState {
+        fn read(&mut self, _key: &str) -> Result<Vec<u8>, StateError> {
+            Err(StateError("denied".to_string()))
+        }
+
+        fn write(&mut self, _key: &str, _bytes: &[u8]) -> Result<(), StateError> {
+            Err(StateError("denied".to_string()))
+        }
+    }
+
+    #[test]
+    fn save_then_load_round_trips_through_cbor() {
+        let mut host = MemoryState::default();
+        let record = BTreeMap::from([("greeting".to_string(), "hello".to_string())]);
+        save(&mut host, STATE_KEY, &record).unwrap();
+        let loaded: BTreeMap<String, String> = load(&mut host, STATE_KEY);
+        assert_eq!(loaded, record);
+    }
+
+    #[test]
+    fn missing_or_unreadable_state_loads_the_default() {
+        let mut host = MemoryState::default();
+        let empty: BTreeMap<String, String> = load(&mut host, STATE_KEY);
+        assert!(empty.is_empty());
+        host.write(STATE_KEY, &[0xff, 0x00]).unwrap();
+        let corrupt: BTreeMap<String, String> = load(&mut host, STATE_KEY);
+        assert!(corrupt.is_empty());
+        let denied: BTreeMap<String, String> = load(&mut DeniedState, STATE_KEY);
+        assert!(denied.is_empty());
+    }
+
+    #[test]
+    fn write_failures_are_reported() {
+        let err = save(&mut DeniedState, STATE_KEY, &1u8).unwrap_err();
+        assert_eq!(err.to_string(), "state store failed: denied");
+    }
+}
+"#
+    .to_string()
+}
@@ -0,0 +1,211 @@
+//! Execution of `run_cli` and `delegate` plan steps.
+//!
+//! `run_cli` only spawns the `(program, subcommand)` pairs in [`RUN_CLI_ALLOWED`], refuses
+//! options that redirect a tool's configuration or working tree, runs inside the plan's
+//! target root, and kills commands that exceed their timeout. Plans holding `run_cli` steps
+//! must pass [`super::verify_plan`] before anything runs. `delegate` hands the step's answers to a
+//! sub-generator registered with [`register_delegate`] and executes the plan it returns.
+
+use std::collections::BTreeMap;
+use std::path::{Component, Path};
+use std::process::Command;
+use std::sync::{Arc, OnceLock, RwLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result, anyhow, bail};
+use serde_json::Value as JsonValue;
+
+use super::WizardPlanEnvelope;
+
+/// `(program, subcommand)` pairs a plan may invoke through `run_cli`; the subcommand must be
+/// the first argument.
+pub const RUN_CLI_ALLOWED: &[(&str, &str)] = &[
+    ("cargo", "build"),
+    ("cargo", "check"),
+    ("cargo", "fmt"),
+    ("cargo", "test"),
+    ("cargo-component", "build"),
+    ("git", "add"),
+    ("git", "commit"),
+    ("git", "init"),
+    ("greentic-component", "build"),
+    ("greentic-component", "doctor"),
+];
+/// Options that point a tool at other configuration, directories or helper programs.
+const RUN_CLI_DENIED_OPTIONS: &[&str] = &[
+    "--config",
+    "--config-path",
+    "-c",
+    "-C",
+    "-Z",
+    "--manifest-path",
+    "--exec-path",
+    "--git-dir",
+    "--work-tree",
+    "--template",
+    "--upload-pack",
+    "--receive-pack",
+];
+const RUN_CLI_DEFAULT_TIMEOUT_SECS: u64 = 600;
+const RUN_CLI_POLL: Duration = Duration::from_millis(50);
+
+/// A sub-generator that `delegate` steps dispatch to by id.
+pub trait WizardDelegate: Send + Sync {
+    /// Build the plan to execute for `answers`, rooted at the parent plan's target.
+    fn plan(&self, target_root: &Path, answers: &JsonValue) -> Result<WizardPlanEnvelope>;
+}
+
+type DelegateRegistry = RwLock<BTreeMap<String, Arc<dyn WizardDelegate>>>;
+
+fn delegates() -> &'static DelegateRegistry {
+    static DELEGATES: OnceLock<DelegateRegistry> = OnceLock::new();
+    DELEGATES.get_or_init(|| RwLock::new(BTreeMap::new()))
+}
+
+/// Register (or replace) the sub-generator behind `delegate` steps with this `id`.
+pub fn register_delegate(id: impl Into<String>, delegate: Arc<dyn WizardDelegate>) {
+    delegates()
+        .write()
+        .expect("wizard delegate registry poisoned")
+        .insert(id.into(), delegate);
+}
+
+pub(super) fn delegate_plan(
+    id: &str,
+    target_root: &Path,
+    answers: &JsonValue,
+) -> Result<WizardPlanEnvelope> {
+    let delegate = delegates()
+        .read()
+        .expect("wizard delegate registry poisoned")
+        .get(id)
+        .cloned()
+        .ok_or_else(|| anyhow!("wizard: no sub-generator registered for delegate `{id}`"))?;
+    delegate
+        .plan(target_root, answers)
+        .with_context(|| format!("wizard: delegate `{id}` failed to plan"))
+}
+
+pub(super) fn run_cli(
+    target_root: &Path,
+    command: &str,
+    args: &[String],
+    cwd: Option<&str>,
+    timeout_secs: Option<u64>,
+) -> Result<()> {
+    let subcommand = args.first().map(String::as_str).unwrap_or_default();
+    if !RUN_CLI_ALLOWED.contains(&(command, subcommand)) {
+        let allowed = RUN_CLI_ALLOWED
+            .iter()
+            .map(|(program, subcommand)| format!("{program} {subcommand}"))
+            .collect::<Vec<_>>();
+        bail!(
+            "wizard: run_cli `{command} {subcommand}` is not allowed (allowed: {})",
+            allowed.join(", ")
+        );
+    }
+    if let Some(option) = args.iter().find(|arg| is_denied_option(arg)) {
+        bail!("wizard: run_cli option `{option}` is not allowed for `{command} {subcommand}`");
+    }
+    let dir = match cwd {
+        Some(cwd) => {
+            let relative = Path::new(cwd);
+            if relative
+                .components()
+                .any(|component| !matches!(component, Component::Normal(_) | Component::CurDir))
+            {
+                bail!("wizard: run_cli cwd `{cwd}` must stay inside the target root");
+            }
+            target_root.join(relative)
+        }
+        None => target_root.to_path_buf(),
+    };
+
+    let mut child = Command::new(command)
+        .args(args)
+        .current_dir(&dir)
+        .spawn()
+        .with_context(|| format!("wizard: failed to spawn `{command}` in {}", dir.display()))?;
+    let timeout = Duration::from_secs(timeout_secs.unwrap_or(RUN_CLI_DEFAULT_TIMEOUT_SECS));
+    let started = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if started.elapsed() >= timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            bail!("wizard: `{command}` timed out after {}s", timeout.as_secs());
+        }
+        thread::sleep(RUN_CLI_POLL);
+    };
+    if !status.success() {
+        bail!(
+            "wizard: `{command} {}` failed with {status}",
+            args.join(" ")
+        );
+    }
+    Ok(())
+}
+
+fn is_denied_option(arg: &str) -> bool {
+    RUN_CLI_DENIED_OPTIONS.iter().any(|option| {
+        let attached = if option.starts_with("--") {
+            arg.strip_prefix(option)
+                .is_some_and(|rest| rest.starts_with('='))
+        } else {
+            // Short options also take their value attached, as in `-Cpath`.
+            arg.starts_with(option) && !arg.starts_with("--")
+        };
+        arg == *option || attached
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_cli_rejects_unlisted_commands_and_escaping_cwd() {
+        let root = Path::new(".");
+        let args = |values: &[&str]| values.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+        let err = run_cli(root, "rm", &args(&["-rf"]), None, None).unwrap_err();
+        assert!(err.to_string().contains("not allowed"));
+        let err = run_cli(root, "cargo", &args(&["install", "x"]), None, None).unwrap_err();
+        assert!(err.to_string().contains("not allowed"), "{err}");
+        let err = run_cli(root, "git", &args(&["-C", "/", "init"]), None, None).unwrap_err();
+        assert!(err.to_string().contains("not allowed"), "{err}");
+        let err =
+            run_cli(root, "cargo", &args(&["check"]), Some("../elsewhere"), None).unwrap_err();
+        assert!(err.to_string().contains("inside the target root"));
+    }
+
+    #[test]
+    fn run_cli_rejects_config_and_directory_options() {
+        let root = Path::new(".");
+        for denied in [
+            &["check", "--config", "build.rustc-wrapper='sh'"][..],
+            &["check", "--config=build.rustc-wrapper='sh'"],
+            &["commit", "-c", "core.hooksPath=/tmp"],
+            &["add", "-C/elsewhere", "--all"],
+            &["build", "-Zunstable-options"],
+            &["check", "--manifest-path", "/elsewhere/Cargo.toml"],
+            &["init", "--template=/tmp/hooks"],
+            &["fmt", "--all", "--", "--config-path", "/tmp/rustfmt.toml"],
+            &["fmt", "--", "--config-path=/tmp/rustfmt.toml"],
+        ] {
+            let (command, args) = if ["commit", "add", "init"].contains(&denied[0]) {
+                ("git", denied)
+            } else {
+                ("cargo", denied)
+            };
+            let args = args.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+            let err = run_cli(root, command, &args, None, None).unwrap_err();
+            assert!(err.to_string().contains("option"), "{args:?}: {err}");
+        }
+        assert!(!is_denied_option("--config-only-looks-similar"));
+        assert!(!is_denied_option("--all"));
+        assert!(!is_denied_option("-m"));
+    }
+}
@@ -7,18 +7,49 @@ use ciborium::Value as CborValue;
 use greentic_types::cbor::canonical;
 use greentic_types::i18n_text::I18nText;
 use greentic_types::schemas::component::v0_6_0::{ChoiceOption, ComponentQaSpec, QaMode, Question};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Map as JsonMap;
 use serde_json::Value as JsonValue;
 use serde_json::json;
 
 use crate::scaffold::config_schema::ConfigSchemaInput;
 use crate::scaffold::deps::{DependencyMode, DependencyTemplates, resolve_dependency_templates};
+use crate::scaffold::license::{LicenseKind, copyright_year};
 use crate::scaffold::runtime_capabilities::RuntimeCapabilitiesInput;
 
+mod abi;
+mod examples;
+mod exec;
+mod multi;
+mod overwrite;
+mod plan_signing;
+mod remove;
+mod template_lint;
+mod template_pack;
+mod update;
+mod workspace;
+
+pub use abi::{ABI_TEMPLATES, AbiTemplate};
+pub use examples::HOST_HTTP_CLIENT;
+pub use exec::{RUN_CLI_ALLOWED, WizardDelegate, register_delegate};
+pub use multi::{MultiComponent, MultiSpec, apply_multi_scaffold};
+pub use overwrite::{OverwriteChange, OverwritePreview, OverwrittenFile, preview_overwrite};
+pub use plan_signing::{
+    PLAN_KEYSTORE_ENV, PLAN_SIGNATURE_ALGORITHM, PlanKeyEntry, PlanKeystore, PlanSignature,
+};
+pub use remove::{RemoveOptions, remove_scaffold};
+pub use template_lint::{TemplatePackLint, lint_template_pack};
+pub use template_pack::{TEMPLATE_PACK_MANIFEST, TemplatePack, TemplatePackSource};
+pub use update::{WIZARD_LOCK_PATH, WizardLock};
+use workspace::CargoWorkspace;
+
 pub const PLAN_VERSION: u32 = 1;
 pub const TEMPLATE_VERSION: &str = "component-scaffold-v0.6.0";
 pub const GENERATOR_ID: &str = "greentic-component/wizard-provider";
+/// Generator of the `wizard run` operation, build/test and doctor plans.
+pub const RUNNER_GENERATOR_ID: &str = "greentic-component/wizard-runner";
+pub const DEFAULT_COMPONENT_ORG: &str = "com.example";
+const MAX_DELEGATE_DEPTH: usize = 8;
 
 fn question(id: &str, label_key: &str, help_key: &str, required: bool) -> Question {
     question_json(json!({
@@ -77,6 +108,150 @@ pub enum WizardMode {
     Remove,
 }
 
+/// Built-in project templates the wizard can scaffold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WizardTemplate {
+    /// Generic message-handling tool component.
+    Component,
+    /// Ingress component that turns inbound messages/events into flow payloads.
+    Source,
+    /// Messaging channel component with inbound and outbound operations.
+    Channel,
+    /// Tool that calls external HTTP APIs through the host http client.
+    HttpTool,
+    /// Tool that keeps per-tenant data in the host state store.
+    StatefulTool,
+}
+
+impl WizardTemplate {
+    pub const ALL: [WizardTemplate; 5] = [
+        WizardTemplate::Component,
+        WizardTemplate::Source,
+        WizardTemplate::Channel,
+        WizardTemplate::HttpTool,
+        WizardTemplate::StatefulTool,
+    ];
+
+    pub fn id(self) -> &'static str {
+        match self {
+            WizardTemplate::Component => "component-v0_6",
+            WizardTemplate::Source => "source-v0_6",
+            WizardTemplate::Channel => "channel-v0_6",
+            WizardTemplate::HttpTool => "http-tool-v0_6",
+            WizardTemplate::StatefulTool => "stateful-tool-v0_6",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|template| template.id() == id.trim())
+    }
+
+    /// Operations scaffolded when the caller does not name any.
+    pub fn default_operations(self) -> Vec<String> {
+        let names: &[&str] = match self {
+            WizardTemplate::Component => &["handle_message"],
+            WizardTemplate::Source => &["ingest"],
+            WizardTemplate::Channel => &["send_message", "receive_message"],
+            WizardTemplate::HttpTool => &["fetch"],
+            WizardTemplate::StatefulTool => &["get_value", "put_value"],
+        };
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    fn role(self) -> &'static str {
+        match self {
+            WizardTemplate::Component | WizardTemplate::HttpTool | WizardTemplate::StatefulTool => {
+                "tool"
+            }
+            WizardTemplate::Source => "source",
+            WizardTemplate::Channel => "channel",
+        }
+    }
+
+    fn supports(self) -> Vec<&'static str> {
+        match self {
+            WizardTemplate::Source => vec!["messaging", "event"],
+            WizardTemplate::HttpTool => vec!["messaging", "http"],
+            _ => vec!["messaging"],
+        }
+    }
+
+    fn profile(self) -> &'static str {
+        match self {
+            WizardTemplate::StatefulTool => "stateful",
+            _ => "stateless",
+        }
+    }
+
+    /// Manifest `state_schema` for the state record the example code persists.
+    fn state_schema(self) -> Option<JsonValue> {
+        match self {
+            WizardTemplate::StatefulTool => Some(json!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "type": "object",
+                "properties": {
+                    "values": {
+                        "type": "object",
+                        "additionalProperties": { "type": "string" }
+                    }
+                },
+                "required": ["values"],
+                "additionalProperties": false
+            })),
+            _ => None,
+        }
+    }
+
+    /// Enable the host capabilities the template's example code relies on.
+    fn apply_capabilities(self, capabilities: &mut RuntimeCapabilitiesInput) {
+        match self {
+            WizardTemplate::Component => {}
+            WizardTemplate::Source => {
+                capabilities.messaging_inbound = true;
+                capabilities.events_inbound = true;
+            }
+            WizardTemplate::Channel => {
+                capabilities.messaging_inbound = true;
+                capabilities.messaging_outbound = true;
+            }
+            WizardTemplate::HttpTool => capabilities.http_client = true,
+            WizardTemplate::StatefulTool => {
+                capabilities.state_read = true;
+                capabilities.state_write = true;
+            }
+        }
+    }
+}
+
+/// CI system the scaffold ships a workflow for (`component.ci`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WizardCi {
+    #[default]
+    None,
+    Github,
+    Gitlab,
+}
+
+impl WizardCi {
+    pub const ALL: [WizardCi; 3] = [WizardCi::None, WizardCi::Github, WizardCi::Gitlab];
+
+    pub fn id(self) -> &'static str {
+        match self {
+            WizardCi::None => "none",
+            WizardCi::Github => "github",
+            WizardCi::Gitlab => "gitlab",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|ci| ci.id() == id.trim())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct AnswersPayload {
     pub json: String,
@@ -86,9 +261,21 @@ pub struct AnswersPayload {
 #[derive(Debug, Clone)]
 pub struct WizardRequest {
     pub name: String,
+    /// Reverse-DNS namespace for the manifest id; defaults to [`DEFAULT_COMPONENT_ORG`].
+    pub org: Option<String>,
+    /// Selects the generated `LICENSE` and the `Cargo.toml` license field.
+    pub license: LicenseKind,
+    /// `Cargo.toml` `authors` entry and copyright holder (the org when `None`).
+    pub author: Option<String>,
     pub abi_version: String,
     pub mode: WizardMode,
+    pub template: WizardTemplate,
+    /// External pack rendered over `template`'s files.
+    pub template_pack: Option<TemplatePack>,
     pub target: PathBuf,
+    /// Add the component as a member of the enclosing Cargo workspace.
+    pub workspace: bool,
+    pub ci: WizardCi,
     pub answers: Option<AnswersPayload>,
     pub required_capabilities: Vec<String>,
     pub provided_capabilities: Vec<String>,
@@ -104,15 +291,17 @@ pub struct ApplyResult {
     pub warnings: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WizardPlanEnvelope {
     pub plan_version: u32,
     pub metadata: WizardPlanMetadata,
     pub target_root: PathBuf,
     pub plan: WizardPlan,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<PlanSignature>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WizardPlanMetadata {
     pub generator: String,
     pub template_version: String,
@@ -122,41 +311,97 @@ pub struct WizardPlanMetadata {
 
 // Compat shim: keep deterministic plan JSON stable without requiring newer
 // greentic-types exports during cargo package verification.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WizardPlan {
     pub meta: WizardPlanMeta,
     pub steps: Vec<WizardStep>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+impl WizardPlan {
+    /// Whether applying the plan runs external commands (`run_cli` steps).
+    pub fn runs_commands(&self) -> bool {
+        self.steps
+            .iter()
+            .any(|step| matches!(step, WizardStep::RunCli { .. }))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WizardPlanMeta {
     pub id: String,
     pub target: WizardTarget,
     pub mode: WizardPlanMode,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum WizardTarget {
     Component,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum WizardPlanMode {
     Scaffold,
+    Update,
+    Remove,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum WizardStep {
-    EnsureDir { paths: Vec<String> },
-    WriteFiles { files: BTreeMap<String, String> },
-    RunCli { command: String },
-    Delegate { id: String },
-    BuildComponent { project_root: String },
-    TestComponent { project_root: String, full: bool },
-    Doctor { project_root: String },
+    EnsureDir {
+        paths: Vec<String>,
+    },
+    WriteFiles {
+        files: BTreeMap<String, String>,
+    },
+    RemoveFiles {
+        paths: Vec<String>,
+    },
+    /// Add `member` to the `[workspace]` members of the ancestor `Cargo.toml` at `manifest`,
+    /// both relative to the target root. Applied to the manifest as it is at execution time.
+    UpdateWorkspaceMembers {
+        manifest: String,
+        member: String,
+    },
+    /// Write `files` relative to the workspace root that `manifest` and `member` lead back to,
+    /// as for `update_workspace_members`. Used for files that only work at the repository
+    /// root, such as CI workflows of a workspace member.
+    WriteWorkspaceFiles {
+        manifest: String,
+        member: String,
+        files: BTreeMap<String, String>,
+    },
+    RunCli {
+        command: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        args: Vec<String>,
+        /// Working directory relative to the plan's target root.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cwd: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        timeout_secs: Option<u64>,
+        /// Report a failure and go on with the plan instead of stopping it, as for
+        /// post-generation steps that run once the files are already written.
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        continue_on_error: bool,
+    },
+    Delegate {
+        id: String,
+        #[serde(default, skip_serializing_if = "JsonValue::is_null")]
+        answers: JsonValue,
+    },
+    BuildComponent {
+        project_root: String,
+    },
+    TestComponent {
+        project_root: String,
+        full: bool,
+    },
+    Doctor {
+        project_root: String,
+    },
 }
 
 pub fn spec_scaffold(mode: WizardMode) -> ComponentQaSpec {
@@ -205,6 +450,22 @@ pub fn spec_scaffold(mode: WizardMode) -> ComponentQaSpec {
                 "wizard.component.features.enabled.help",
                 false,
             ),
+            question_choice(
+                "component.ci",
+                "wizard.component.ci.label",
+                "wizard.component.ci.help",
+                false,
+                WizardCi::ALL
+                    .into_iter()
+                    .map(|ci| ChoiceOption {
+                        value: ci.id().to_string(),
+                        label: I18nText::new(
+                            format!("wizard.component.ci.option.{}", ci.id()),
+                            None,
+                        ),
+                    })
+                    .collect(),
+            ),
         ],
         defaults: BTreeMap::from([(
             "component.features.enabled".to_string(),
@@ -214,13 +475,108 @@ pub fn spec_scaffold(mode: WizardMode) -> ComponentQaSpec {
 }
 
 pub fn apply_scaffold(request: WizardRequest, dry_run: bool) -> Result<ApplyResult> {
-    let warnings = abi_warnings(&request.abi_version);
-    let (prefill_answers_json, prefill_answers_cbor, mut mapping_warnings) =
+    if request.mode == WizardMode::Remove {
+        return remove_scaffold(&request.target, RemoveOptions::default(), dry_run);
+    }
+    let workspace = if request.workspace {
+        let target = std::path::absolute(&request.target)
+            .with_context(|| format!("wizard: failed to resolve {}", request.target.display()))?;
+        let workspace = CargoWorkspace::find_enclosing(&target)?.ok_or_else(|| {
+            anyhow!(
+                "wizard: --workspace requires a Cargo workspace above {}",
+                target.display()
+            )
+        })?;
+        let member = workspace.member_path(&target)?;
+        Some((workspace, member))
+    } else {
+        None
+    };
+    let target = request.target.clone();
+    let abi_version = request.abi_version.clone();
+    let updating = request.mode == WizardMode::Update;
+    let RenderedScaffold {
+        mut files,
+        workspace_files,
+        template_version,
+        warnings: mut all_warnings,
+    } = render_scaffold(
+        request,
+        workspace.as_ref().map(|(workspace, _)| workspace.clone()),
+    )?;
+    if updating {
+        let (writes, mut update_warnings) = update::plan_update(&target, files, &template_version)?;
+        files = writes;
+        all_warnings.append(&mut update_warnings);
+    }
+    let mut plan = build_plan(target, &abi_version, files)?;
+    plan.metadata.template_version = template_version;
+    // The workspace manifest belongs to the user, so it is edited in place but never locked;
+    // neither are the files written at the workspace root.
+    if let Some((workspace, member)) = &workspace {
+        let depth = Path::new(member).components().count();
+        let manifest = format!("{}Cargo.toml", "../".repeat(depth));
+        if workspace.manifest_with_member(member)?.is_some() {
+            plan.plan.steps.push(WizardStep::UpdateWorkspaceMembers {
+                manifest: manifest.clone(),
+                member: member.clone(),
+            });
+        }
+        if !workspace_files.is_empty() {
+            plan.plan.steps.push(WizardStep::WriteWorkspaceFiles {
+                manifest,
+                member: member.clone(),
+                files: workspace_files
+                    .iter()
+                    .map(|file| {
+                        (
+                            file.path.to_string_lossy().into_owned(),
+                            encode_step_content(&file.path, &file.contents),
+                        )
+                    })
+                    .collect(),
+            });
+        }
+        seal_plan(&mut plan)?;
+    }
+    if updating {
+        plan.plan.meta.mode = WizardPlanMode::Update;
+    }
+    if !dry_run {
+        execute_plan(&plan)?;
+    }
+
+    Ok(ApplyResult {
+        plan,
+        warnings: all_warnings,
+    })
+}
+
+/// One component's generated files, lock file included, relative to its target.
+struct RenderedScaffold {
+    files: Vec<GeneratedFile>,
+    /// Files relative to the enclosing workspace root (workspace mode only).
+    workspace_files: Vec<GeneratedFile>,
+    template_version: String,
+    warnings: Vec<String>,
+}
+
+fn render_scaffold(
+    request: WizardRequest,
+    workspace: Option<CargoWorkspace>,
+) -> Result<RenderedScaffold> {
+    let (abi, abi_warning) = AbiTemplate::for_version(&request.abi_version);
+    // A fallback template renders its own ABI version, not the unsupported request.
+    let abi_version = match abi_warning {
+        Some(_) => abi.abi_version.to_string(),
+        None => request.abi_version.clone(),
+    };
+    let (prefill_answers_json, prefill_answers_cbor, mapping_warnings) =
         normalize_answers(request.answers, request.mode)?;
-    let mut all_warnings = warnings;
-    all_warnings.append(&mut mapping_warnings);
+    let mut warnings = mapping_warnings;
+    warnings.extend(abi_warning);
     let user_operations = if request.user_operations.is_empty() {
-        vec!["handle_message".to_string()]
+        request.template.default_operations()
     } else {
         request.user_operations.clone()
     };
@@ -229,76 +585,170 @@ pub fn apply_scaffold(request: WizardRequest, dry_run: bool) -> Result<ApplyResu
         .clone()
         .or_else(|| user_operations.first().cloned())
         .unwrap_or_else(|| "handle_message".to_string());
+    let mut runtime_capabilities = request.runtime_capabilities;
+    request
+        .template
+        .apply_capabilities(&mut runtime_capabilities);
+    for capability in examples::apply_required_capabilities(
+        &request.required_capabilities,
+        &mut runtime_capabilities,
+    ) {
+        warnings.push(format!(
+            "wizard: required capability `{capability}` has no scaffold support; declare it in component.manifest.json by hand"
+        ));
+    }
     let context = WizardContext {
         name: request.name,
-        abi_version: request.abi_version.clone(),
+        org: request
+            .org
+            .unwrap_or_else(|| DEFAULT_COMPONENT_ORG.to_string()),
+        license: request.license,
+        author: request.author,
+        abi_version,
+        abi,
+        template: request.template,
         prefill_mode: request.mode,
         prefill_answers_cbor,
         prefill_answers_json,
         user_operations,
         default_operation,
-        runtime_capabilities: request.runtime_capabilities,
+        runtime_capabilities,
         config_schema: request.config_schema,
         dependency_templates: resolve_dependency_templates(
             DependencyMode::from_env(),
             &request.target,
         ),
+        workspace,
+        ci: request.ci,
     };
 
-    let files = build_files(&context)?;
-    let plan = build_plan(request.target, &request.abi_version, files);
-    if !dry_run {
-        execute_plan(&plan)?;
+    let mut files = build_files(&context)?;
+    // A workspace member's CI runs from the repository root, so it is written there.
+    let workspace_files = match &context.workspace {
+        Some(workspace) if context.ci != WizardCi::None => {
+            let target = std::path::absolute(&request.target).with_context(|| {
+                format!("wizard: failed to resolve {}", request.target.display())
+            })?;
+            let member = workspace.member_path(&target)?;
+            if context.ci == WizardCi::Gitlab {
+                warnings.push(format!(
+                    "wizard: wrote the GitLab job to .gitlab/ci/{}.yml at the workspace root; include it from .gitlab-ci.yml",
+                    context.name
+                ));
+            }
+            ci_files(&context, Some(&member))
+        }
+        _ => Vec::new(),
+    };
+    if let Some(pack) = &request.template_pack {
+        pack.render(&context, &mut files)?;
     }
-
-    Ok(ApplyResult {
-        plan,
-        warnings: all_warnings,
+    let template_version = request
+        .template_pack
+        .as_ref()
+        .map(TemplatePack::template_version)
+        .unwrap_or_else(|| abi.template_version.to_string());
+    files.push(update::lock_file(
+        context.template.id(),
+        &template_version,
+        &files,
+    )?);
+    Ok(RenderedScaffold {
+        files,
+        workspace_files,
+        template_version,
+        warnings,
     })
 }
 
-pub fn execute_plan(envelope: &WizardPlanEnvelope) -> Result<()> {
+/// A `continue_on_error` step that failed while the rest of the plan ran on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StepFailure {
+    /// The command line, e.g. `git commit -m ...`.
+    pub command: String,
+    pub message: String,
+}
+
+/// Apply every step of `envelope`. Failing `continue_on_error` steps do not stop the plan;
+/// they are returned instead.
+pub fn execute_plan(envelope: &WizardPlanEnvelope) -> Result<Vec<StepFailure>> {
+    plan_signing::verify_signature(envelope)?;
+    let mut failures = Vec::new();
+    execute_plan_nested(envelope, 0, &mut failures)?;
+    Ok(failures)
+}
+
+fn execute_plan_nested(
+    envelope: &WizardPlanEnvelope,
+    depth: usize,
+    failures: &mut Vec<StepFailure>,
+) -> Result<()> {
+    // Reject every escaping path up front so a bad step cannot leave a half-written project.
+    check_plan_paths(envelope)?;
+    if envelope.plan.runs_commands() {
+        verify_plan(envelope).context("wizard: plans with run_cli steps must verify")?;
+    }
     for step in &envelope.plan.steps {
         match step {
             WizardStep::EnsureDir { paths } => {
                 for path in paths {
-                    let dir = envelope.target_root.join(path);
+                    let dir = contained_path(&envelope.target_root, path, "create")?;
                     fs::create_dir_all(&dir).with_context(|| {
                         format!("wizard: failed to create directory {}", dir.display())
                     })?;
                 }
             }
-            WizardStep::WriteFiles { files } => {
-                for (relative_path, content) in files {
-                    let target = envelope.target_root.join(relative_path);
-                    if let Some(parent) = target.parent() {
-                        fs::create_dir_all(parent).with_context(|| {
-                            format!("wizard: failed to create directory {}", parent.display())
-                        })?;
-                    }
-                    let bytes = decode_step_content(relative_path, content)?;
-                    fs::write(&target, bytes)
-                        .with_context(|| format!("wizard: failed to write {}", target.display()))?;
-                    #[cfg(unix)]
-                    if is_executable_heuristic(Path::new(relative_path)) {
-                        use std::os::unix::fs::PermissionsExt;
-                        let mut permissions = fs::metadata(&target)
-                            .with_context(|| {
-                                format!("wizard: failed to stat {}", target.display())
-                            })?
-                            .permissions();
-                        permissions.set_mode(0o755);
-                        fs::set_permissions(&target, permissions).with_context(|| {
-                            format!("wizard: failed to set executable bit {}", target.display())
-                        })?;
-                    }
+            WizardStep::WriteFiles { files } => write_step_files(&envelope.target_root, files)?,
+            WizardStep::RemoveFiles { paths } => {
+                for relative_path in paths {
+                    remove_file_and_prune(&envelope.target_root, relative_path)?;
                 }
             }
-            WizardStep::RunCli { command, .. } => {
-                bail!("wizard: unsupported plan step run_cli ({command})")
+            WizardStep::UpdateWorkspaceMembers { manifest, member } => {
+                workspace::update_members(&envelope.target_root, manifest, member)?
             }
-            WizardStep::Delegate { id, .. } => {
-                bail!("wizard: unsupported plan step delegate ({})", id.as_str())
+            WizardStep::WriteWorkspaceFiles {
+                manifest,
+                member,
+                files,
+            } => {
+                let root =
+                    workspace::member_workspace_root(&envelope.target_root, manifest, member)?;
+                write_step_files(&root, files)?;
+            }
+            WizardStep::RunCli {
+                command,
+                args,
+                cwd,
+                timeout_secs,
+                continue_on_error,
+            } => {
+                let result = exec::run_cli(
+                    &envelope.target_root,
+                    command,
+                    args,
+                    cwd.as_deref(),
+                    *timeout_secs,
+                );
+                match result {
+                    Err(err) if *continue_on_error => failures.push(StepFailure {
+                        command: std::iter::once(command.as_str())
+                            .chain(args.iter().map(String::as_str))
+                            .collect::<Vec<_>>()
+                            .join(" "),
+                        message: format!("{err:#}"),
+                    }),
+                    result => result?,
+                }
+            }
+            WizardStep::Delegate { id, answers } => {
+                if depth >= MAX_DELEGATE_DEPTH {
+                    bail!(
+                        "wizard: delegate `{id}` exceeds the nesting limit of {MAX_DELEGATE_DEPTH}"
+                    );
+                }
+                let plan = exec::delegate_plan(id, &envelope.target_root, answers)?;
+                execute_plan_nested(&plan, depth + 1, failures)?;
             }
             WizardStep::BuildComponent { project_root } => {
                 bail!("wizard: unsupported plan step build_component ({project_root})")
@@ -314,6 +764,82 @@ pub fn execute_plan(envelope: &WizardPlanEnvelope) -> Result<()> {
     Ok(())
 }
 
+fn write_step_files(root: &Path, files: &BTreeMap<String, String>) -> Result<()> {
+    for (relative_path, content) in files {
+        let target = contained_path(root, relative_path, "write")?;
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).with_context(|| {
+                format!("wizard: failed to create directory {}", parent.display())
+            })?;
+        }
+        let bytes = decode_step_content(relative_path, content)?;
+        fs::write(&target, bytes)
+            .with_context(|| format!("wizard: failed to write {}", target.display()))?;
+        #[cfg(unix)]
+        if is_executable_heuristic(Path::new(relative_path)) {
+            use std::os::unix::fs::PermissionsExt;
+            let mut permissions = fs::metadata(&target)
+                .with_context(|| format!("wizard: failed to stat {}", target.display()))?
+                .permissions();
+            permissions.set_mode(0o755);
+            fs::set_permissions(&target, permissions).with_context(|| {
+                format!("wizard: failed to set executable bit {}", target.display())
+            })?;
+        }
+    }
+    Ok(())
+}
+
+fn check_plan_paths(envelope: &WizardPlanEnvelope) -> Result<()> {
+    let root = &envelope.target_root;
+    for step in &envelope.plan.steps {
+        match step {
+            WizardStep::EnsureDir { paths } => {
+                for path in paths {
+                    contained_path(root, path, "create")?;
+                }
+            }
+            WizardStep::WriteFiles { files } => {
+                for path in files.keys() {
+                    contained_path(root, path, "write")?;
+                }
+            }
+            WizardStep::RemoveFiles { paths } => {
+                for path in paths {
+                    contained_path(root, path, "remove")?;
+                }
+            }
+            WizardStep::UpdateWorkspaceMembers { manifest, member } => {
+                workspace::member_workspace_root(root, manifest, member)?;
+            }
+            WizardStep::WriteWorkspaceFiles {
+                manifest,
+                member,
+                files,
+            } => {
+                let workspace_root = workspace::member_workspace_root(root, manifest, member)?;
+                for path in files.keys() {
+                    contained_path(&workspace_root, path, "write")?;
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Join a plan path onto `root`, refusing absolute paths and `..` segments.
+fn contained_path(root: &Path, relative_path: &str, action: &str) -> Result<PathBuf> {
+    let relative = Path::new(relative_path);
+    if relative
+        .components()
+        .any(|component| !matches!(component, std::path::Component::Normal(_)))
+    {
+        bail!("wizard: refusing to {action} `{relative_path}` outside the project root");
+    }
+    Ok(root.join(relative))
+}
+
 fn is_executable_heuristic(path: &Path) -> bool {
     matches!(
         path.extension().and_then(|ext| ext.to_str()),
@@ -337,7 +863,12 @@ pub fn load_answers_payload(path: &Path) -> Result<AnswersPayload> {
 
 struct WizardContext {
     name: String,
+    org: String,
+    license: LicenseKind,
+    author: Option<String>,
     abi_version: String,
+    abi: &'static AbiTemplate,
+    template: WizardTemplate,
     prefill_mode: WizardMode,
     prefill_answers_cbor: Option<Vec<u8>>,
     prefill_answers_json: Option<String>,
@@ -346,6 +877,8 @@ struct WizardContext {
     runtime_capabilities: RuntimeCapabilitiesInput,
     config_schema: ConfigSchemaInput,
     dependency_templates: DependencyTemplates,
+    workspace: Option<CargoWorkspace>,
+    ci: WizardCi,
 }
 
 type NormalizedAnswers = (Option<String>, Option<Vec<u8>>, Vec<String>);
@@ -421,8 +954,14 @@ struct GeneratedFile {
 fn build_files(context: &WizardContext) -> Result<Vec<GeneratedFile>> {
     let mut files = vec![
         text_file("Cargo.toml", render_cargo_toml(context)),
-        text_file("rust-toolchain.toml", render_rust_toolchain_toml()),
         text_file("README.md", render_readme(context)),
+        text_file(
+            "LICENSE",
+            context.license.render(
+                copyright_year(),
+                context.author.as_deref().unwrap_or(&context.org),
+            ),
+        ),
         text_file("component.manifest.json", render_manifest_json(context)),
         text_file(
             "schemas/component.schema.json",
@@ -437,7 +976,20 @@ fn build_files(context: &WizardContext) -> Result<Vec<GeneratedFile>> {
         text_file("assets/i18n/en.json", render_i18n_bundle()),
         text_file("assets/i18n/locales.json", render_i18n_locales_json()),
         text_file("tools/i18n.sh", render_i18n_sh()),
+        text_file("tests/component_it.rs", render_component_it_rs(context)),
     ];
+    files.extend(examples::example_files(&context.runtime_capabilities));
+    // Workspace members use the workspace's toolchain file.
+    if context.workspace.is_none() {
+        files.push(text_file(
+            "rust-toolchain.toml",
+            render_rust_toolchain_toml(),
+        ));
+    }
+    // Workspace members get their CI at the workspace root instead (`render_scaffold`).
+    if context.workspace.is_none() {
+        files.extend(ci_files(context, None));
+    }
 
     if let (Some(json), Some(cbor)) = (
         context.prefill_answers_json.as_ref(),
@@ -462,7 +1014,11 @@ fn build_files(context: &WizardContext) -> Result<Vec<GeneratedFile>> {
     Ok(files)
 }
 
-fn build_plan(target: PathBuf, abi_version: &str, files: Vec<GeneratedFile>) -> WizardPlanEnvelope {
+fn build_plan(
+    target: PathBuf,
+    abi_version: &str,
+    files: Vec<GeneratedFile>,
+) -> Result<WizardPlanEnvelope> {
     let mut dirs = BTreeSet::new();
     for file in &files {
         if let Some(parent) = file.path.parent()
@@ -500,15 +1056,18 @@ fn build_plan(target: PathBuf, abi_version: &str, files: Vec<GeneratedFile>) ->
     let metadata = WizardPlanMetadata {
         generator: GENERATOR_ID.to_string(),
         template_version: TEMPLATE_VERSION.to_string(),
-        template_digest_blake3: template_digest_hex(&files),
+        template_digest_blake3: String::new(),
         requested_abi_version: abi_version.to_string(),
     };
-    WizardPlanEnvelope {
+    let mut envelope = WizardPlanEnvelope {
         plan_version: PLAN_VERSION,
         metadata,
         target_root: target,
         plan,
-    }
+        signature: None,
+    };
+    seal_plan(&mut envelope)?;
+    Ok(envelope)
 }
 
 const STEP_BASE64_PREFIX: &str = "base64:";
@@ -528,6 +1087,27 @@ fn encode_step_content(path: &Path, bytes: &[u8]) -> String {
     }
 }
 
+fn remove_file_and_prune(root: &Path, relative_path: &str) -> Result<()> {
+    let target = contained_path(root, relative_path, "remove")?;
+    match fs::remove_file(&target) {
+        Ok(()) => {}
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => {
+            return Err(err)
+                .with_context(|| format!("wizard: failed to remove {}", target.display()));
+        }
+    }
+    // Drop directories the removal left empty, stopping at the project root.
+    let mut dir = target.parent();
+    while let Some(current) = dir {
+        if current == root || fs::remove_dir(current).is_err() {
+            break;
+        }
+        dir = current.parent();
+    }
+    Ok(())
+}
+
 fn decode_step_content(relative_path: &str, content: &str) -> Result<Vec<u8>> {
     if relative_path.ends_with(".cbor") && content.starts_with(STEP_BASE64_PREFIX) {
         let raw = content.trim_start_matches(STEP_BASE64_PREFIX);
@@ -538,25 +1118,42 @@ fn decode_step_content(relative_path: &str, content: &str) -> Result<Vec<u8>> {
     Ok(content.as_bytes().to_vec())
 }
 
-fn template_digest_hex(files: &[GeneratedFile]) -> String {
-    let mut hasher = blake3::Hasher::new();
-    for file in files {
-        hasher.update(file.path.to_string_lossy().as_bytes());
-        hasher.update(&[0]);
-        hasher.update(&file.contents);
-        hasher.update(&[0xff]);
+/// Check a previously written plan before it is applied: the envelope version must match,
+/// the plan must come from a known generator and its steps must still hash to the recorded
+/// digest. A signed plan must also carry a signature that matches its contents.
+pub fn verify_plan(envelope: &WizardPlanEnvelope) -> Result<()> {
+    if envelope.plan_version != PLAN_VERSION {
+        bail!(
+            "wizard: unsupported plan_version {} (expected {PLAN_VERSION})",
+            envelope.plan_version
+        );
+    }
+    plan_signing::verify_signature(envelope)?;
+    let generator = envelope.metadata.generator.as_str();
+    if generator != GENERATOR_ID && generator != RUNNER_GENERATOR_ID {
+        bail!("wizard: plan generator `{generator}` is not recognised; refusing to apply it");
+    }
+    let digest = plan_digest_hex(&envelope.plan.steps)?;
+    if digest != envelope.metadata.template_digest_blake3 {
+        bail!(
+            "wizard: plan digest mismatch (recorded {}, steps hash to {digest}); the plan was modified after it was generated",
+            envelope.metadata.template_digest_blake3
+        );
     }
-    hasher.finalize().to_hex().to_string()
+    Ok(())
 }
 
-fn abi_warnings(abi_version: &str) -> Vec<String> {
-    if abi_version == "0.6.0" {
-        Vec::new()
-    } else {
-        vec![format!(
-            "wizard: warning: only component@0.6.0 template is generated (requested {abi_version})"
-        )]
-    }
+/// Record the digest of the plan's steps; call again after adding steps to a built plan.
+pub fn seal_plan(envelope: &mut WizardPlanEnvelope) -> Result<()> {
+    envelope.metadata.template_digest_blake3 = plan_digest_hex(&envelope.plan.steps)?;
+    Ok(())
+}
+
+fn plan_digest_hex(steps: &[WizardStep]) -> Result<String> {
+    // Canonical CBOR hashes the same for JSON and CBOR plan files.
+    let bytes = canonical::to_canonical_cbor_allow_floats(&steps)
+        .map_err(|err| anyhow!("wizard: failed to encode plan steps: {err}"))?;
+    Ok(blake3::hash(&bytes).to_hex().to_string())
 }
 
 fn qa_mode(mode: WizardMode) -> QaMode {
@@ -593,14 +1190,38 @@ fn binary_file(path: &str, contents: Vec<u8>) -> GeneratedFile {
 }
 
 fn render_cargo_toml(context: &WizardContext) -> String {
+    let workspace = context.workspace.as_ref();
+    let package_field = |key: &str, value: &str| match workspace {
+        Some(workspace) if workspace.package_fields.contains(key) => {
+            format!("{key}.workspace = true")
+        }
+        _ => format!("{key} = \"{value}\""),
+    };
+    // `extra` keeps per-crate settings (features) when the version comes from the workspace.
+    let dependency = |name: &str, spec: &str, extra: &str| match workspace {
+        Some(workspace) if workspace.dependencies.contains(name) => {
+            format!("{name} = {{ workspace = true{extra} }}")
+        }
+        _ => format!("{name} = {{ {spec}{extra} }}"),
+    };
+    let greentic_types = dependency(
+        "greentic-types",
+        &context.dependency_templates.greentic_types,
+        "",
+    );
+    let guest_features = std::iter::once(context.abi.guest_feature)
+        .chain(examples::guest_features(&context.runtime_capabilities))
+        .map(|feature| format!("\"{feature}\""))
+        .collect::<Vec<_>>()
+        .join(", ");
     format!(
         r#"[package]
 name = "{name}"
-version = "0.1.0"
-edition = "2024"
-license = "MIT"
-rust-version = "1.91"
-description = "Greentic component {name}"
+{version}
+{edition}
+{license}
+{rust_version}
+description = "Greentic component {name}"{authors}
 build = "build.rs"
 
 [lib]
@@ -608,27 +1229,68 @@ crate-type = ["cdylib", "rlib"]
 
 [package.metadata.greentic]
 abi_version = "{abi_version}"
+template = "{template}"
 
 [package.metadata.component]
 package = "greentic:component"
 
 [package.metadata.component.target]
-world = "greentic:component/component@0.6.0"
+world = "{world}"
 
 [dependencies]
-greentic-types = {{ {greentic_types} }}
-greentic-interfaces-guest = {{ {greentic_interfaces_guest}, default-features = false, features = ["component-v0-6"] }}
-serde = {{ version = "1", features = ["derive"] }}
-serde_json = "1"
+{greentic_types}
+{greentic_interfaces_guest}
+{serde}
+{serde_json}
 
 [build-dependencies]
-greentic-types = {{ {greentic_types} }}
-serde_json = "1"
+{greentic_types}
+{serde_json}
+
+# Used by tests/component_it.rs to run the built component natively.
+[target.'cfg(not(target_arch = "wasm32"))'.dev-dependencies]
+{greentic_interfaces_host}
+{wasmtime}
+{wasmtime_wasi}
 "#,
         name = context.name,
+        version = package_field("version", "0.1.0"),
+        edition = package_field("edition", "2024"),
+        license = package_field("license", context.license.spdx()),
+        authors = match (workspace, &context.author) {
+            (Some(workspace), _) if workspace.package_fields.contains("authors") => {
+                "\nauthors.workspace = true".to_string()
+            }
+            (_, Some(author)) => format!("\nauthors = [{}]", JsonValue::from(author.as_str())),
+            (_, None) => String::new(),
+        },
+        rust_version = package_field("rust-version", "1.91"),
         abi_version = context.abi_version,
-        greentic_types = context.dependency_templates.greentic_types,
-        greentic_interfaces_guest = context.dependency_templates.greentic_interfaces_guest
+        template = context.template.id(),
+        world = context.abi.world,
+        greentic_interfaces_guest = dependency(
+            "greentic-interfaces-guest",
+            &context.dependency_templates.greentic_interfaces_guest,
+            &if workspace.is_some_and(|ws| ws.dependencies.contains("greentic-interfaces-guest")) {
+                format!(", features = [{guest_features}]")
+            } else {
+                format!(", default-features = false, features = [{guest_features}]")
+            },
+        ),
+        serde = dependency("serde", r#"version = "1""#, r#", features = ["derive"]"#),
+        greentic_interfaces_host = dependency("greentic-interfaces-host", r#"version = "0.4""#, ""),
+        wasmtime = dependency(
+            "wasmtime",
+            r#"version = "43", default-features = false"#,
+            r#", features = ["component-model", "cranelift", "runtime", "std"]"#,
+        ),
+        wasmtime_wasi = dependency("wasmtime-wasi", r#"version = "43""#, ""),
+        serde_json = match workspace {
+            Some(workspace) if workspace.dependencies.contains("serde_json") => {
+                "serde_json = { workspace = true }".to_string()
+            }
+            _ => r#"serde_json = "1""#.to_string(),
+        },
     )
 }
 
@@ -636,12 +1298,15 @@ fn render_readme(context: &WizardContext) -> String {
     format!(
         r#"# {name}
 
-Generated by `greentic-component wizard` for component@0.6.0.
+Generated by `greentic-component wizard` for component@{abi_version} from the `{template}` template.
 
 ## Next steps
 - Extend QA flows in `src/qa.rs` and i18n keys in `src/i18n.rs`.
-- Generate/update locales via `./tools/i18n.sh`.
+- Generate/update locales via `greentic-component i18n translate` (or the `./tools/i18n.sh` wrapper).
 - Rebuild to embed translations: `cargo build`.
+- `cargo test` includes `tests/component_it.rs`, which runs the built wasm under wasmtime
+  (`COMPONENT_WASM=path/to.wasm` tests a prebuilt artifact; otherwise `dist/` or a fresh
+  `cargo component build` is used).
 
 ## QA ops
 - `qa-spec`: emits setup/update/remove semantics and accepts `default|setup|install|update|upgrade|remove`.
@@ -649,12 +1314,13 @@ Generated by `greentic-component wizard` for component@0.6.0.
 - `i18n-keys`: returns i18n keys used by QA/setup messaging.
 
 ## ABI version
-Requested ABI version: {abi_version}
-
-Note: the wizard currently emits a fixed 0.6.0 template.
+Built against `{world}`. The ABI is recorded in `[package.metadata.greentic]` and selects the
+guest bindings enabled in `Cargo.toml`.
 "#,
         name = context.name,
-        abi_version = context.abi_version
+        template = context.template.id(),
+        abi_version = context.abi_version,
+        world = context.abi.world
     )
 }
 
@@ -685,7 +1351,7 @@ clippy:
 
 wasm:
 	if ! cargo component --version >/dev/null 2>&1; then \
-		echo "cargo-component is required to produce a valid component@0.6.0 wasm"; \
+		echo "cargo-component is required to produce a valid component@$(ABI_VERSION) wasm"; \
 		echo "install with: cargo install cargo-component --locked"; \
 		exit 1; \
 	fi
@@ -712,6 +1378,111 @@ doctor:
     .to_string()
 }
 
+fn wasm_dist_path(context: &WizardContext) -> String {
+    format!(
+        "dist/{}__{}.wasm",
+        context.name,
+        context.abi_version.replace('.', "_")
+    )
+}
+
+/// CI files for `context.ci`. With `member` (the member's path from the workspace root) they
+/// are laid out for the workspace root and run inside the member directory.
+fn ci_files(context: &WizardContext, member: Option<&str>) -> Vec<GeneratedFile> {
+    match (context.ci, member) {
+        (WizardCi::None, _) => Vec::new(),
+        (WizardCi::Github, None) => vec![text_file(
+            ".github/workflows/ci.yml",
+            render_github_workflow(context, None),
+        )],
+        (WizardCi::Github, Some(member)) => vec![text_file(
+            &format!(".github/workflows/{}.yml", context.name),
+            render_github_workflow(context, Some(member)),
+        )],
+        (WizardCi::Gitlab, None) => {
+            vec![text_file(".gitlab-ci.yml", render_gitlab_ci(context, None))]
+        }
+        (WizardCi::Gitlab, Some(member)) => vec![text_file(
+            &format!(".gitlab/ci/{}.yml", context.name),
+            render_gitlab_ci(context, Some(member)),
+        )],
+    }
+}
+
+fn render_github_workflow(context: &WizardContext, member: Option<&str>) -> String {
+    let (defaults, dist) = match member {
+        Some(member) => (
+            format!("    defaults:\n      run:\n        working-directory: {member}\n"),
+            format!("{member}/dist/"),
+        ),
+        None => (String::new(), "dist/".to_string()),
+    };
+    format!(
+        r#"name: {name}
+
+on:
+  push:
+    branches: [main]
+  pull_request:
+
+jobs:
+  component:
+    runs-on: ubuntu-latest
+{defaults}    steps:
+      - uses: actions/checkout@v4
+      - uses: dtolnay/rust-toolchain@master
+        with:
+          toolchain: "1.91.0"
+          targets: wasm32-wasip2
+      - uses: Swatinem/rust-cache@v2
+      - name: Install cargo-component and greentic-component
+        run: cargo install cargo-component greentic-component --locked
+      - name: Build wasm with cargo-component
+        run: make wasm
+      - name: greentic-component doctor
+        run: greentic-component doctor {wasm} --manifest ./component.manifest.json
+      - uses: actions/upload-artifact@v4
+        with:
+          name: {name}-dist
+          path: {dist}
+"#,
+        name = context.name,
+        wasm = wasm_dist_path(context)
+    )
+}
+
+fn render_gitlab_ci(context: &WizardContext, member: Option<&str>) -> String {
+    let (job, enter, dist) = match member {
+        Some(member) => (
+            context.name.clone(),
+            format!("    - cd {member}\n"),
+            format!("{member}/dist/"),
+        ),
+        None => ("component".to_string(), String::new(), "dist/".to_string()),
+    };
+    format!(
+        r#"stages:
+  - build
+
+{job}:
+  stage: build
+  image: rust:1.91
+  before_script:
+    - rustup target add wasm32-wasip2
+    - cargo install cargo-component greentic-component --locked
+{enter}  script:
+    - make wasm
+    - greentic-component doctor {wasm} --manifest ./component.manifest.json
+  artifacts:
+    name: {name}-dist
+    paths:
+      - {dist}
+"#,
+        name = context.name,
+        wasm = wasm_dist_path(context)
+    )
+}
+
 fn render_manifest_json(context: &WizardContext) -> String {
     let name_snake = context.name.replace('-', "_");
     let mut operations = context
@@ -826,18 +1597,18 @@ fn render_manifest_json(context: &WizardContext) -> String {
 
     let mut manifest = json!({
         "$schema": "https://greenticai.github.io/greentic-component/schemas/v1/component.manifest.schema.json",
-        "id": format!("com.example.{}", context.name),
+        "id": format!("{}.{}", context.org, context.name),
         "name": context.name,
         "version": "0.1.0",
-        "world": "greentic:component/component@0.6.0",
+        "world": context.abi.world,
         "describe_export": "describe",
         "operations": operations,
         "default_operation": context.default_operation,
         "config_schema": context.config_schema.manifest_schema(),
-        "supports": ["messaging"],
+        "supports": context.template.supports(),
         "profiles": {
-            "default": "stateless",
-            "supported": ["stateless"]
+            "default": context.template.profile(),
+            "supported": [context.template.profile()]
         },
         "secret_requirements": context.runtime_capabilities.manifest_secret_requirements(),
         "capabilities": context.runtime_capabilities.manifest_capabilities(),
@@ -869,6 +1640,9 @@ fn render_manifest_json(context: &WizardContext) -> String {
     if let Some(telemetry) = context.runtime_capabilities.manifest_telemetry() {
         manifest["telemetry"] = telemetry;
     }
+    if let Some(state_schema) = context.template.state_schema() {
+        manifest["state_schema"] = state_schema;
+    }
     serde_json::to_string_pretty(&manifest).expect("wizard manifest should serialize")
 }
 
@@ -885,7 +1659,7 @@ fn render_lib_rs(context: &WizardContext) -> String {
 use std::collections::BTreeMap;
 
 #[cfg(target_arch = "wasm32")]
-use greentic_interfaces_guest::component_v0_6::node;
+use greentic_interfaces_guest::{guest_module}::node;
 #[cfg(target_arch = "wasm32")]
 use greentic_types::cbor::canonical;
 #[cfg(target_arch = "wasm32")]
@@ -897,11 +1671,11 @@ use greentic_types::schemas::component::v0_6_0::{{ComponentInfo, I18nText}};
 pub mod i18n;
 pub mod i18n_bundle;
 // qa: mode normalization, QA spec generation, apply-answers validation.
-pub mod qa;
+pub mod qa;{example_mods}
 
 const COMPONENT_NAME: &str = "{name}";
 #[cfg(target_arch = "wasm32")]
-const COMPONENT_ORG: &str = "com.example";
+const COMPONENT_ORG: &str = "{org}";
 #[cfg(target_arch = "wasm32")]
 const COMPONENT_VERSION: &str = "0.1.0";
 
@@ -987,7 +1761,7 @@ impl node::Guest for Component {{
         operation: String,
         envelope: node::InvocationEnvelope,
     ) -> Result<node::InvocationResult, node::NodeError> {{
-        let output = run_component_cbor(&operation, envelope.payload_cbor);
+        {invoke_output}
         Ok(node::InvocationResult {{
             ok: true,
             output_cbor: output,
@@ -997,13 +1771,9 @@ impl node::Guest for Component {{
 }}
 
 #[cfg(target_arch = "wasm32")]
-greentic_interfaces_guest::export_component_v060!(Component);
+greentic_interfaces_guest::{export_macro}!(Component);
 
-// Default user-operation implementation.
-// Replace this with domain logic for your component.
-pub fn handle_message(operation: &str, input: &str) -> String {{
-    format!("{{COMPONENT_NAME}}::{{operation}} => {{}}", input.trim())
-}}
+{handle_message}
 
 #[cfg(target_arch = "wasm32")]
 fn encode_cbor<T: serde::Serialize>(value: &T) -> Vec<u8> {{
@@ -1078,7 +1848,7 @@ fn component_info() -> ComponentInfo {{
     ComponentInfo {{
         id: format!("{{COMPONENT_ORG}}.{{COMPONENT_NAME}}"),
         version: COMPONENT_VERSION.to_string(),
-        role: "tool".to_string(),
+        role: "{role}".to_string(),
         display_name: Some(I18nText::new("component.display_name", Some(COMPONENT_NAME.to_string()))),
     }}
 }}
@@ -1132,10 +1902,130 @@ fn run_component_cbor(operation: &str, input: Vec<u8>) -> Vec<u8> {{
 }}
 "#,
         name = context.name,
-        user_describe_ops = user_describe_ops
+        org = context.org,
+        role = context.template.role(),
+        handle_message = render_lib_handle_message(context.template),
+        user_describe_ops = user_describe_ops,
+        example_mods = examples::example_mods(&context.runtime_capabilities),
+        invoke_output = examples::invoke_output(&context.runtime_capabilities),
+        guest_module = context.abi.guest_module,
+        export_macro = context.abi.export_macro
     )
 }
 
+// Example user-operation body per template. Every variant keeps the
+// `handle_message(operation, input) -> String` signature used by the dispatcher.
+fn render_lib_handle_message(template: WizardTemplate) -> &'static str {
+    match template {
+        WizardTemplate::Component => {
+            r#"// Default user-operation implementation.
+// Replace this with domain logic for your component.
+pub fn handle_message(operation: &str, input: &str) -> String {
+    format!("{COMPONENT_NAME}::{operation} => {}", input.trim())
+}"#
+        }
+        WizardTemplate::Source => {
+            r#"// Ingress example: wrap inbound text/events into a flow payload.
+// Replace this with parsing for your upstream system (webhook body, queue message, ...).
+pub fn handle_message(operation: &str, input: &str) -> String {
+    serde_json::json!({
+        "source": COMPONENT_NAME,
+        "operation": operation,
+        "kind": "event",
+        "payload": input.trim(),
+    })
+    .to_string()
+}"#
+        }
+        WizardTemplate::Channel => {
+            r#"// Channel example: normalize inbound/outbound messages.
+// `receive_message` maps provider payloads into flow messages; every other operation
+// prepares an outbound message for the host messaging capability.
+pub fn handle_message(operation: &str, input: &str) -> String {
+    let direction = if operation == "receive_message" {
+        "inbound"
+    } else {
+        "outbound"
+    };
+    serde_json::json!({
+        "channel": COMPONENT_NAME,
+        "direction": direction,
+        "text": input.trim(),
+    })
+    .to_string()
+}"#
+        }
+        WizardTemplate::HttpTool => {
+            r#"// HTTP tool example: fetch the input URL through the host http client.
+// `http.rs` holds the request builder, error mapping, and the `HttpHost` trait its unit
+// tests fake. Outside wasm32 there is no host, so the request is only described.
+pub fn handle_message(operation: &str, input: &str) -> String {
+    let request = http::HttpRequest::get(input).header("accept", "application/json");
+    if let Err(err) = request.validate() {
+        return format!("{COMPONENT_NAME}::{operation} => {err}");
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        match http::send(&mut http::RunnerHttp, &request) {
+            Ok(body) => String::from_utf8_lossy(&body).into_owned(),
+            Err(err) => format!("{COMPONENT_NAME}::{operation} => {err}"),
+        }
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    serde_json::json!({
+        "operation": operation,
+        "request": { "method": request.method, "url": request.url },
+    })
+    .to_string()
+}"#
+        }
+        WizardTemplate::StatefulTool => {
+            r#"// Stateful tool example: `put_value` stores `key=value`, `get_value` reads `key`.
+// The state record matches `state_schema` in component.manifest.json and is persisted as
+// canonical CBOR through the host state store (`state.rs`). Outside wasm32 there is no
+// host, so each call starts from an empty in-memory store.
+#[derive(Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ComponentState {
+    pub values: std::collections::BTreeMap<String, String>,
+}
+
+pub fn handle_message(operation: &str, input: &str) -> String {
+    #[cfg(target_arch = "wasm32")]
+    let mut host = state::RunnerState;
+    #[cfg(not(target_arch = "wasm32"))]
+    let mut host = state::MemoryState::default();
+    run_stateful(&mut host, operation, input)
+}
+
+pub fn run_stateful(host: &mut impl state::StateHost, operation: &str, input: &str) -> String {
+    let mut current: ComponentState = state::load(host, state::STATE_KEY);
+    match operation {
+        "put_value" => match input.trim().split_once('=') {
+            Some((key, value)) => {
+                current
+                    .values
+                    .insert(key.trim().to_string(), value.trim().to_string());
+                match state::save(host, state::STATE_KEY, &current) {
+                    Ok(()) => format!("{COMPONENT_NAME}::{operation} => stored {}", key.trim()),
+                    Err(err) => format!("{COMPONENT_NAME}::{operation} => {err}"),
+                }
+            }
+            None => format!("{COMPONENT_NAME}::{operation} => expected key=value"),
+        },
+        _ => {
+            let key = input.trim();
+            match current.values.get(key) {
+                Some(value) => value.clone(),
+                None => format!("{COMPONENT_NAME}::{operation} => no value for {key}"),
+            }
+        }
+    }
+}
+"#
+        }
+    }
+}
+
 fn render_lib_user_describe_ops(context: &WizardContext) -> String {
     context
         .user_operations
@@ -1164,6 +2054,172 @@ fn render_lib_user_describe_ops(context: &WizardContext) -> String {
         .join(",\n")
 }
 
+fn render_component_it_rs(context: &WizardContext) -> String {
+    // Invoking goes through the host telemetry import, which the test's linker only traps.
+    let invoke_ignore = if context.runtime_capabilities.telemetry_span_prefix.is_some() {
+        "#[ignore = \"invoke emits through the host telemetry import, which this test does not provide\"]\n"
+    } else {
+        ""
+    };
+    format!(
+        r#"//! Integration test: instantiate the built component with wasmtime and call its exports
+//! through the component model.
+//!
+//! The wasm comes from `$COMPONENT_WASM`, the `make wasm` artifact in `dist/`, or a
+//! `cargo component build` into `target/component-it` when neither exists.
+
+use std::path::PathBuf;
+use std::process::Command;
+
+use greentic_interfaces_host::{host_module}::{host_world};
+use greentic_interfaces_host::{host_module}::exports::greentic::component::node::{{
+    InvocationEnvelope, SchemaSource, TenantCtx,
+}};
+use greentic_types::cbor::canonical;
+use greentic_types::schemas::common::schema_ir::SchemaIr;
+use wasmtime::component::{{Component, Linker, ResourceTable}};
+use wasmtime::{{Config, Engine, Store}};
+use wasmtime_wasi::{{WasiCtx, WasiCtxBuilder, WasiCtxView, WasiView}};
+
+struct Host {{
+    wasi: WasiCtx,
+    table: ResourceTable,
+}}
+
+impl WasiView for Host {{
+    fn ctx(&mut self) -> WasiCtxView<'_> {{
+        WasiCtxView {{
+            ctx: &mut self.wasi,
+            table: &mut self.table,
+        }}
+    }}
+}}
+
+fn component_wasm() -> PathBuf {{
+    if let Ok(path) = std::env::var("COMPONENT_WASM") {{
+        return PathBuf::from(path);
+    }}
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let dist = root.join("{dist_path}");
+    if dist.exists() {{
+        return dist;
+    }}
+    // A separate target dir keeps the nested build off the lock held by `cargo test`.
+    let target_dir = root.join("target/component-it");
+    let status = Command::new(env!("CARGO"))
+        .args([
+            "component",
+            "build",
+            "--release",
+            "--target",
+            "wasm32-wasip2",
+        ])
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .current_dir(&root)
+        .status()
+        .expect(
+            "run cargo component build (install with `cargo install cargo-component --locked`)",
+        );
+    assert!(status.success(), "cargo component build failed");
+    target_dir.join("wasm32-wasip2/release/{name_snake}.wasm")
+}}
+
+fn instantiate() -> (Store<Host>, {host_world}) {{
+    let mut config = Config::new();
+    config.wasm_component_model(true);
+    let engine = Engine::new(&config).expect("engine");
+    let component = Component::from_file(&engine, component_wasm()).expect("load component");
+    let mut linker = Linker::new(&engine);
+    wasmtime_wasi::p2::add_to_linker_sync(&mut linker).expect("add wasi");
+    // Greentic host imports (http, secrets, telemetry, ...) trap if the component calls them.
+    linker
+        .define_unknown_imports_as_traps(&component)
+        .expect("stub host imports");
+    let host = Host {{
+        wasi: WasiCtxBuilder::new().inherit_stderr().build(),
+        table: ResourceTable::new(),
+    }};
+    let mut store = Store::new(&engine, host);
+    let exports =
+        {host_world}::instantiate(&mut store, &component, &linker).expect("instantiate");
+    (store, exports)
+}}
+
+fn envelope(operation: &str, payload: &serde_json::Value) -> InvocationEnvelope {{
+    InvocationEnvelope {{
+        ctx: TenantCtx {{
+            tenant_id: "tenant-it".to_string(),
+            team_id: None,
+            user_id: None,
+            env_id: "dev".to_string(),
+            trace_id: "trace-it".to_string(),
+            correlation_id: "corr-it".to_string(),
+            deadline_ms: 0,
+            attempt: 0,
+            idempotency_key: None,
+            i18n_id: "en".to_string(),
+        }},
+        flow_id: "flow-it".to_string(),
+        step_id: operation.to_string(),
+        component_id: "{name}".to_string(),
+        attempt: 0,
+        payload_cbor: canonical::to_canonical_cbor_allow_floats(payload).expect("encode payload"),
+        metadata_cbor: None,
+    }}
+}}
+
+#[test]
+fn describe_reports_operations_with_round_tripping_schemas() {{
+    let (mut store, exports) = instantiate();
+    let descriptor = exports
+        .greentic_component_node()
+        .call_describe(&mut store)
+        .expect("describe");
+    assert_eq!(descriptor.name, "{name}");
+    assert!(descriptor.ops.iter().any(|op| op.name == "{default_operation}"));
+    for op in &descriptor.ops {{
+        for io in [&op.input, &op.output] {{
+            let SchemaSource::InlineCbor(bytes) = &io.schema else {{
+                panic!("{{}} schema is not inline CBOR", op.name);
+            }};
+            let schema: SchemaIr = canonical::from_cbor(bytes).expect("schema decodes");
+            let encoded =
+                canonical::to_canonical_cbor_allow_floats(&schema).expect("schema encodes");
+            assert_eq!(
+                &encoded, bytes,
+                "{{}} schema must round-trip as canonical CBOR",
+                op.name
+            );
+        }}
+    }}
+}}
+
+#[test]
+{invoke_ignore}fn invoke_runs_default_operation() {{
+    let (mut store, exports) = instantiate();
+    let payload = serde_json::json!({{ "input": "hello" }});
+    let result = exports
+        .greentic_component_node()
+        .call_invoke(&mut store, "{default_operation}", &envelope("{default_operation}", &payload))
+        .expect("invoke call")
+        .expect("invoke result");
+    assert!(result.ok);
+    let output: serde_json::Value =
+        canonical::from_cbor(&result.output_cbor).expect("output decodes");
+    assert!(output["message"].is_string(), "unexpected output: {{output}}");
+}}
+"#,
+        name = context.name,
+        name_snake = context.name.replace('-', "_"),
+        dist_path = wasm_dist_path(context),
+        default_operation = context.default_operation,
+        host_module = context.abi.host_module,
+        host_world = context.abi.host_world,
+        invoke_ignore = invoke_ignore,
+    )
+}
+
 fn render_qa_rs() -> String {
     r#"use greentic_types::i18n_text::I18nText;
 use greentic_types::schemas::component::v0_6_0::{QaMode, Question};
@@ -1579,7 +2635,7 @@ fn render_i18n_locales_json() -> String {
 }
 
 fn render_i18n_bundle_rs() -> String {
-    r#"use std::collections::BTreeMap;
+    r#"use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
 use std::path::Path;
 
@@ -1588,19 +2644,28 @@ use greentic_types::cbor::canonical;
 // Locale -> (key -> translated message)
 pub type LocaleBundle = BTreeMap<String, BTreeMap<String, String>>;
 
-// Reads `assets/i18n/*.json` locale maps and returns stable BTreeMap ordering.
+// Reads `assets/i18n/*.json` and `assets/i18n/*.ftl` locale maps and returns stable BTreeMap ordering.
+// When a locale ships both formats the maps are merged and `.ftl` entries win.
 // Extend here if you need stricter file validation rules.
 pub fn load_locale_files(dir: &Path) -> Result<LocaleBundle, String> {
     let mut locales = LocaleBundle::new();
     if !dir.exists() {
         return Ok(locales);
     }
+
+    let mut paths = Vec::new();
     for entry in fs::read_dir(dir).map_err(|err| err.to_string())? {
-        let entry = entry.map_err(|err| err.to_string())?;
-        let path = entry.path();
-        if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
-            continue;
+        let path = entry.map_err(|err| err.to_string())?.path();
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => paths.push((false, path)),
+            Some("ftl") => paths.push((true, path)),
+            _ => {}
         }
+    }
+    // JSON first so Fluent files can override individual keys.
+    paths.sort();
+
+    for (is_ftl, path) in paths {
         let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) else {
             continue;
         };
@@ -1609,12 +2674,91 @@ pub fn load_locale_files(dir: &Path) -> Result<LocaleBundle, String> {
             continue;
         }
         let raw = fs::read_to_string(&path).map_err(|err| err.to_string())?;
-        let map: BTreeMap<String, String> = serde_json::from_str(&raw).map_err(|err| err.to_string())?;
-        locales.insert(stem.to_string(), map);
+        let map: BTreeMap<String, String> = if is_ftl {
+            parse_ftl(&raw).map_err(|err| format!("{}: {err}", path.display()))?
+        } else {
+            serde_json::from_str(&raw).map_err(|err| err.to_string())?
+        };
+        locales.entry(stem.to_string()).or_default().extend(map);
     }
+
     Ok(locales)
 }
 
+// Minimal Fluent reader for flat message bundles:
+//   key = value
+//   key =
+//       continued value
+//       .attribute = value   (stored as `key.attribute`)
+// Comments and blank lines are skipped; placeables and selectors are kept verbatim.
+pub fn parse_ftl(source: &str) -> Result<BTreeMap<String, String>, String> {
+    let mut messages = BTreeMap::new();
+    let mut with_attributes = BTreeSet::new();
+    let mut message_id: Option<String> = None;
+    let mut current: Option<String> = None;
+
+    for (idx, line) in source.lines().enumerate() {
+        let line_no = idx + 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if line.starts_with('#') {
+            message_id = None;
+            current = None;
+            continue;
+        }
+        if line.starts_with(char::is_whitespace) {
+            let trimmed = line.trim();
+            if let Some(attribute) = trimmed.strip_prefix('.') {
+                let Some(id) = message_id.as_ref() else {
+                    return Err(format!("line {line_no}: attribute outside of a message"));
+                };
+                let (name, value) = split_ftl_entry(attribute, line_no)?;
+                let key = format!("{id}.{name}");
+                with_attributes.insert(id.clone());
+                messages.insert(key.clone(), value);
+                current = Some(key);
+                continue;
+            }
+            let Some(key) = current.as_ref() else {
+                return Err(format!(
+                    "line {line_no}: indented line outside of a message"
+                ));
+            };
+            let value = messages.entry(key.clone()).or_insert_with(String::new);
+            if !value.is_empty() {
+                value.push('\n');
+            }
+            value.push_str(trimmed);
+            continue;
+        }
+        let (id, value) = split_ftl_entry(line, line_no)?;
+        messages.insert(id.clone(), value);
+        message_id = Some(id.clone());
+        current = Some(id);
+    }
+
+    // `key =` followed only by attributes is a container, not a message.
+    for id in with_attributes {
+        if messages.get(&id).is_some_and(|value| value.is_empty()) {
+            messages.remove(&id);
+        }
+    }
+    Ok(messages)
+}
+
+fn split_ftl_entry(line: &str, line_no: usize) -> Result<(String, String), String> {
+    let Some((id, value)) = line.split_once('=') else {
+        return Err(format!("line {line_no}: expected `key = value`"));
+    };
+    let id = id.trim();
+    if id.is_empty() || id.contains(char::is_whitespace) {
+        return Err(format!("line {line_no}: invalid message id `{id}`"));
+    }
+    Ok((id.to_string(), value.trim().to_string()))
+}
+
+// Produces canonical CBOR bytes for reproducible build embedding.
 pub fn pack_locales_to_cbor(locales: &LocaleBundle) -> Result<Vec<u8>, String> {
     canonical::to_canonical_cbor_allow_floats(locales).map_err(|err| err.to_string())
 }
@@ -1638,8 +2782,23 @@ mod tests {
 
         let cbor = pack_locales_to_cbor(&locales).expect("pack locales");
         let decoded = unpack_locales_from_cbor(&cbor).expect("decode locales");
+
         assert!(decoded.contains_key("en"));
     }
+
+    #[test]
+    fn parses_ftl_messages_and_attributes() {
+        let source = "qa.install.title = Install\n## Fields\nqa-field-region =\n    .label = Region\n    .help = Region identifier\n        for the account.\n";
+        let messages = parse_ftl(source).expect("parse ftl");
+
+        assert_eq!(messages["qa.install.title"], "Install");
+        assert_eq!(messages["qa-field-region.label"], "Region");
+        assert_eq!(
+            messages["qa-field-region.help"],
+            "Region identifier\nfor the account."
+        );
+        assert!(!messages.contains_key("qa-field-region"));
+    }
 }
 "#
     .to_string()
@@ -1654,7 +2813,7 @@ use std::fs;
 use std::path::Path;
 
 // Build-time embedding pipeline:
-// 1) Read assets/i18n/*.json
+// 1) Read assets/i18n/*.json and *.ftl
 // 2) Pack canonical CBOR bundle
 // 3) Emit OUT_DIR constants included by src/i18n.rs
 fn main() {
@@ -1685,139 +2844,16 @@ fn render_i18n_sh() -> String {
     r#"#!/usr/bin/env bash
 set -euo pipefail
 
+# Thin wrapper kept for existing workflows; the translation logic lives in
+# `greentic-component i18n translate` so Windows and CI runs need no bash/python.
 ROOT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")/.." && pwd)"
-LOCALES_FILE="$ROOT_DIR/assets/i18n/locales.json"
-SOURCE_FILE="$ROOT_DIR/assets/i18n/en.json"
-
-log() {
-  printf '[i18n] %s\n' "$*"
-}
 
-fail() {
-  printf '[i18n] error: %s\n' "$*" >&2
+if ! command -v greentic-component >/dev/null 2>&1; then
+  printf '[i18n] error: %s\n' "greentic-component not found. Install it with: cargo install greentic-component" >&2
   exit 1
-}
+fi
 
-ensure_codex() {
-  if command -v codex >/dev/null 2>&1; then
-    return
-  fi
-  if command -v npm >/dev/null 2>&1; then
-    log "installing Codex CLI via npm"
-    npm i -g @openai/codex || fail "failed to install Codex CLI via npm"
-  elif command -v brew >/dev/null 2>&1; then
-    log "installing Codex CLI via brew"
-    brew install codex || fail "failed to install Codex CLI via brew"
-  else
-    fail "Codex CLI not found and no supported installer available (npm or brew)"
-  fi
-}
-
-ensure_codex_login() {
-  if codex login status >/dev/null 2>&1; then
-    return
-  fi
-  log "Codex login status unavailable or not logged in; starting login flow"
-  codex login || fail "Codex login failed"
-}
-
-probe_translator() {
-  command -v greentic-i18n-translator >/dev/null 2>&1 || fail "greentic-i18n-translator not found. Install it and rerun this script."
-  local help_output
-  help_output="$(greentic-i18n-translator --help 2>&1 || true)"
-  [[ -n "$help_output" ]] || fail "unable to inspect greentic-i18n-translator --help"
-  if ! greentic-i18n-translator translate --help >/dev/null 2>&1; then
-    fail "translator subcommand 'translate' is required but unavailable"
-  fi
-}
-
-run_translate() {
-  while IFS= read -r locale; do
-    [[ -n "$locale" ]] || continue
-    log "translating locale: $locale"
-    greentic-i18n-translator translate \
-      --langs "$locale" \
-      --en "$SOURCE_FILE" || fail "translate failed for locale $locale"
-  done < <(python3 - "$LOCALES_FILE" <<'PY'
-import json
-import sys
-with open(sys.argv[1], 'r', encoding='utf-8') as f:
-    data = json.load(f)
-for locale in data:
-    if locale != "en":
-        print(locale)
-PY
-)
-}
-
-run_validate_per_locale() {
-  local failed=0
-  while IFS= read -r locale; do
-    [[ -n "$locale" ]] || continue
-    if ! greentic-i18n-translator validate --langs "$locale" --en "$SOURCE_FILE"; then
-      log "validate failed for locale: $locale"
-      failed=1
-    fi
-  done < <(python3 - "$LOCALES_FILE" <<'PY'
-import json
-import sys
-with open(sys.argv[1], 'r', encoding='utf-8') as f:
-    data = json.load(f)
-for locale in data:
-    if locale != "en":
-        print(locale)
-PY
-)
-  return "$failed"
-}
-
-run_status_per_locale() {
-  local failed=0
-  while IFS= read -r locale; do
-    [[ -n "$locale" ]] || continue
-    if ! greentic-i18n-translator status --langs "$locale" --en "$SOURCE_FILE"; then
-      log "status failed for locale: $locale"
-      failed=1
-    fi
-  done < <(python3 - "$LOCALES_FILE" <<'PY'
-import json
-import sys
-with open(sys.argv[1], 'r', encoding='utf-8') as f:
-    data = json.load(f)
-for locale in data:
-    if locale != "en":
-        print(locale)
-PY
-)
-  return "$failed"
-}
-
-run_optional_checks() {
-  if greentic-i18n-translator validate --help >/dev/null 2>&1; then
-    log "running translator validate"
-    if ! run_validate_per_locale; then
-      fail "translator validate failed"
-    fi
-  else
-    log "warning: translator validate command not available; skipping"
-  fi
-  if greentic-i18n-translator status --help >/dev/null 2>&1; then
-    log "running translator status"
-    run_status_per_locale || fail "translator status failed"
-  else
-    log "warning: translator status command not available; skipping"
-  fi
-}
-
-[[ -f "$LOCALES_FILE" ]] || fail "missing locales file: $LOCALES_FILE"
-[[ -f "$SOURCE_FILE" ]] || fail "missing source locale file: $SOURCE_FILE"
-
-ensure_codex
-ensure_codex_login
-probe_translator
-run_translate
-run_optional_checks
-log "translations updated. Run cargo build to embed translations into WASM"
+exec greentic-component i18n translate --project-root "$ROOT_DIR" "$@"
 "#
     .to_string()
 }
@@ -0,0 +1,367 @@
+//! Monorepo scaffolding (`greentic-component new --multi spec.yaml`).
+//!
+//! A spec lists several components; they are rendered as members of a fresh Cargo
+//! workspace under `components/<name>`, next to a shared `crates/common` library every
+//! component depends on. Everything lands in one plan, so the whole monorepo is written
+//! (or previewed) in a single step.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, anyhow, bail};
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+
+use super::workspace::CargoWorkspace;
+use super::{
+    ApplyResult, WizardCi, WizardMode, WizardRequest, WizardTemplate, build_plan, execute_plan,
+    render_rust_toolchain_toml, render_scaffold, text_file,
+};
+use crate::scaffold::config_schema::ConfigSchemaInput;
+use crate::scaffold::license::{LicenseKind, copyright_year};
+use crate::scaffold::runtime_capabilities::RuntimeCapabilitiesInput;
+use crate::scaffold::validate::{self, ComponentName, OrgNamespace};
+
+const COMMON_CRATE: &str = "crates/common";
+const COMPONENTS_DIR: &str = "components";
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MultiSpec {
+    /// Workspace directory name when no path is given on the command line.
+    pub workspace: String,
+    #[serde(default)]
+    pub org: Option<String>,
+    #[serde(default)]
+    pub license: Option<String>,
+    #[serde(default)]
+    pub author: Option<String>,
+    #[serde(default = "default_abi_version")]
+    pub abi_version: String,
+    /// Required capabilities added to every component.
+    #[serde(default)]
+    pub shared_capabilities: Vec<String>,
+    pub components: Vec<MultiComponent>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MultiComponent {
+    pub name: String,
+    /// Built-in template id, with or without the `-v0_6` suffix (`http-tool`, `source`, ...).
+    #[serde(default = "default_kind")]
+    pub kind: String,
+    #[serde(default)]
+    pub capabilities: Vec<String>,
+    #[serde(default)]
+    pub operations: Vec<String>,
+    #[serde(default)]
+    pub default_operation: Option<String>,
+}
+
+fn default_abi_version() -> String {
+    "0.6.0".to_string()
+}
+
+fn default_kind() -> String {
+    "component".to_string()
+}
+
+impl MultiSpec {
+    pub fn load(path: &Path) -> Result<Self> {
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("wizard: failed to read {}", path.display()))?;
+        serde_norway::from_str(&raw)
+            .with_context(|| format!("wizard: invalid multi-component spec {}", path.display()))
+    }
+}
+
+fn template_for_kind(kind: &str) -> Result<WizardTemplate> {
+    let kind = kind.trim();
+    WizardTemplate::ALL
+        .into_iter()
+        .find(|template| template.id() == kind || template.id().strip_suffix("-v0_6") == Some(kind))
+        .ok_or_else(|| {
+            let kinds = WizardTemplate::ALL
+                .iter()
+                .map(|template| template.id().trim_end_matches("-v0_6"))
+                .collect::<Vec<_>>();
+            anyhow!(
+                "wizard: unknown component kind `{kind}` (expected one of: {})",
+                kinds.join(", ")
+            )
+        })
+}
+
+/// Scaffold every component in `spec` into a new workspace at `target`.
+pub fn apply_multi_scaffold(
+    spec: MultiSpec,
+    target: PathBuf,
+    dry_run: bool,
+) -> Result<ApplyResult> {
+    if spec.components.is_empty() {
+        bail!("wizard: multi-component spec lists no components");
+    }
+    let org = spec
+        .org
+        .as_deref()
+        .map(OrgNamespace::parse)
+        .transpose()?
+        .map(OrgNamespace::into_string);
+    let license = spec
+        .license
+        .as_deref()
+        .map(LicenseKind::parse)
+        .transpose()?
+        .unwrap_or_default();
+    let copyright_org = org
+        .clone()
+        .unwrap_or_else(|| super::DEFAULT_COMPONENT_ORG.to_string());
+    let mut names = BTreeSet::new();
+    let mut components = Vec::new();
+    for component in &spec.components {
+        let name = ComponentName::parse(&component.name)?.into_string();
+        if !names.insert(name.clone()) {
+            bail!("wizard: component `{name}` is listed more than once");
+        }
+        components.push((name, template_for_kind(&component.kind)?, component));
+    }
+
+    let members = std::iter::once(COMMON_CRATE.to_string())
+        .chain(
+            components
+                .iter()
+                .map(|(name, _, _)| format!("{COMPONENTS_DIR}/{name}")),
+        )
+        .collect::<Vec<_>>();
+    let root_manifest = render_workspace_toml(&members, license, spec.author.as_deref());
+    let workspace = CargoWorkspace::from_manifest(&target, root_manifest.clone())?
+        .expect("generated manifest declares a workspace");
+
+    let mut files = vec![
+        text_file("Cargo.toml", root_manifest),
+        text_file("rust-toolchain.toml", render_rust_toolchain_toml()),
+        text_file(
+            "LICENSE",
+            license.render(
+                copyright_year(),
+                spec.author.as_deref().unwrap_or(&copyright_org),
+            ),
+        ),
+        text_file("README.md", render_workspace_readme(&spec, &components)),
+        text_file(&format!("{COMMON_CRATE}/Cargo.toml"), render_common_toml()),
+        text_file(
+            &format!("{COMMON_CRATE}/src/lib.rs"),
+            render_common_lib_rs(&spec, org.as_deref()),
+        ),
+    ];
+    let mut warnings = Vec::new();
+    let mut template_version = None;
+    for (name, template, component) in components {
+        let user_operations = component
+            .operations
+            .iter()
+            .map(|operation| validate::normalize_operation_name(operation))
+            .collect::<Result<Vec<_>, _>>()?;
+        let member = PathBuf::from(COMPONENTS_DIR).join(&name);
+        let request = WizardRequest {
+            name: name.clone(),
+            org: org.clone(),
+            license,
+            author: spec.author.clone(),
+            abi_version: spec.abi_version.clone(),
+            mode: WizardMode::Default,
+            template,
+            template_pack: None,
+            target: target.join(&member),
+            workspace: true,
+            ci: WizardCi::None,
+            answers: None,
+            required_capabilities: spec
+                .shared_capabilities
+                .iter()
+                .chain(&component.capabilities)
+                .cloned()
+                .collect(),
+            provided_capabilities: Vec::new(),
+            user_operations,
+            default_operation: component.default_operation.clone(),
+            runtime_capabilities: RuntimeCapabilitiesInput::default(),
+            config_schema: ConfigSchemaInput::default(),
+        };
+        let rendered = render_scaffold(request, Some(workspace.clone()))
+            .with_context(|| format!("wizard: failed to render component `{name}`"))?;
+        warnings.extend(
+            rendered
+                .warnings
+                .into_iter()
+                .map(|warning| format!("{name}: {warning}")),
+        );
+        // The lock keeps the template's Cargo.toml, so updates treat `common` as a user edit.
+        for mut file in rendered.files {
+            if file.path == Path::new("Cargo.toml") {
+                file.contents = with_common_dependency(&file.contents)?;
+            }
+            file.path = member.join(&file.path);
+            files.push(file);
+        }
+        template_version.get_or_insert(rendered.template_version);
+    }
+
+    let mut plan = build_plan(target, &spec.abi_version, files)?;
+    if let Some(template_version) = template_version {
+        plan.metadata.template_version = template_version;
+    }
+    if !dry_run {
+        execute_plan(&plan)?;
+    }
+    Ok(ApplyResult { plan, warnings })
+}
+
+fn with_common_dependency(manifest: &[u8]) -> Result<Vec<u8>> {
+    let manifest = std::str::from_utf8(manifest).context("wizard: Cargo.toml is not UTF-8")?;
+    let updated = manifest.replacen(
+        "[dependencies]\n",
+        "[dependencies]\ncommon = { workspace = true }\n",
+        1,
+    );
+    if updated == manifest {
+        bail!("wizard: component Cargo.toml has no [dependencies] table");
+    }
+    Ok(updated.into_bytes())
+}
+
+fn render_workspace_toml(members: &[String], license: LicenseKind, author: Option<&str>) -> String {
+    let members = members
+        .iter()
+        .map(|member| format!("    \"{member}\",\n"))
+        .collect::<String>();
+    let authors = author
+        .map(|author| format!("authors = [{}]\n", JsonValue::from(author)))
+        .unwrap_or_default();
+    format!(
+        r#"[workspace]
+resolver = "3"
+members = [
+{members}]
+
+[workspace.package]
+version = "0.1.0"
+edition = "2024"
+license = "{license}"
+rust-version = "1.91"
+{authors}
+[workspace.dependencies]
+common = {{ path = "{COMMON_CRATE}" }}
+serde = {{ version = "1", features = ["derive"] }}
+serde_json = "1"
+"#,
+        license = license.spdx(),
+    )
+}
+
+fn render_common_toml() -> String {
+    r#"[package]
+name = "common"
+version.workspace = true
+edition.workspace = true
+license.workspace = true
+rust-version.workspace = true
+publish = false
+
+[dependencies]
+"#
+    .to_string()
+}
+
+fn render_common_lib_rs(spec: &MultiSpec, org: Option<&str>) -> String {
+    let capabilities = spec
+        .shared_capabilities
+        .iter()
+        .map(|capability| format!("{:?}", capability.trim()))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        r#"//! Code shared by the components of the `{workspace}` workspace.
+
+/// Reverse-DNS namespace of every component id in the workspace.
+pub const ORG: &str = "{org}";
+
+/// Capabilities every component in the workspace requires.
+pub const SHARED_CAPABILITIES: &[&str] = &[{capabilities}];
+
+/// Manifest id of the workspace component called `name`.
+pub fn component_id(name: &str) -> String {{
+    format!("{{ORG}}.{{name}}")
+}}
+"#,
+        workspace = spec.workspace,
+        org = org.unwrap_or(super::DEFAULT_COMPONENT_ORG),
+    )
+}
+
+fn render_workspace_readme(
+    spec: &MultiSpec,
+    components: &[(String, WizardTemplate, &MultiComponent)],
+) -> String {
+    let list = components
+        .iter()
+        .map(|(name, template, _)| {
+            format!(
+                "- `{COMPONENTS_DIR}/{name}` ({template})\n",
+                template = template.id()
+            )
+        })
+        .collect::<String>();
+    format!(
+        r#"# {workspace}
+
+Generated by `greentic-component new --multi` for component@{abi_version}.
+
+## Components
+{list}
+Shared code lives in `{COMMON_CRATE}`; every component depends on it as `common`.
+
+## Next steps
+- `cargo check --workspace --target wasm32-wasip2`
+- `greentic-component build --manifest components/<name>/component.manifest.json`
+"#,
+        workspace = spec.workspace,
+        abi_version = spec.abi_version,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_yaml_spec_and_resolves_kinds() {
+        let spec: MultiSpec = serde_norway::from_str(
+            r#"
+workspace: acme
+org: com.acme
+shared_capabilities: [host.http.client]
+components:
+  - name: fetcher
+    kind: http-tool
+  - name: inbox
+    kind: source-v0_6
+    operations: [ingest, replay]
+"#,
+        )
+        .expect("spec parses");
+        assert_eq!(spec.abi_version, "0.6.0");
+        assert_eq!(spec.components.len(), 2);
+        assert_eq!(spec.components[1].operations, vec!["ingest", "replay"]);
+        assert_eq!(
+            template_for_kind(&spec.components[0].kind).unwrap(),
+            WizardTemplate::HttpTool
+        );
+        assert_eq!(
+            template_for_kind(&spec.components[1].kind).unwrap(),
+            WizardTemplate::Source
+        );
+        assert!(template_for_kind("gadget").is_err());
+    }
+}
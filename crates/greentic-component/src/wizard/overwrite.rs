@@ -0,0 +1,135 @@
+//! Preview of what `overwrite_output` destroys.
+//!
+//! Overwriting clears the target directory before a scaffold plan runs. The preview compares
+//! the directory against the plan's `write_files` step: files the plan rewrites with other
+//! contents get a unified diff, and anything the plan does not recreate is reported as removed.
+//! Directories holding no planned paths are reported once instead of file by file.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use similar::TextDiff;
+use walkdir::WalkDir;
+
+use super::update::path_key;
+use super::{WizardPlanEnvelope, WizardStep, decode_step_content};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OverwritePreview {
+    pub target_root: PathBuf,
+    pub files: Vec<OverwrittenFile>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OverwrittenFile {
+    /// Path relative to the target root; directories end with `/`.
+    pub path: String,
+    pub change: OverwriteChange,
+    /// Unified diff from the existing file to the planned one (text files only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diff: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverwriteChange {
+    Modified,
+    Removed,
+}
+
+impl OverwritePreview {
+    pub fn count(&self, change: OverwriteChange) -> usize {
+        self.files
+            .iter()
+            .filter(|file| file.change == change)
+            .count()
+    }
+}
+
+/// Compare the plan's target directory with the files it would write.
+/// Returns `None` when the target does not exist yet.
+pub fn preview_overwrite(envelope: &WizardPlanEnvelope) -> Result<Option<OverwritePreview>> {
+    let root = &envelope.target_root;
+    if !root.is_dir() {
+        return Ok(None);
+    }
+    let mut planned = BTreeMap::new();
+    let mut planned_dirs = Vec::new();
+    for step in &envelope.plan.steps {
+        match step {
+            WizardStep::WriteFiles { files } => {
+                for (relative_path, content) in files {
+                    planned.insert(
+                        relative_path.clone(),
+                        decode_step_content(relative_path, content)?,
+                    );
+                }
+            }
+            WizardStep::EnsureDir { paths } => planned_dirs.extend(paths.iter().cloned()),
+            _ => {}
+        }
+    }
+    let holds_planned = |dir: &str| {
+        let prefix = format!("{dir}/");
+        planned_dirs.iter().any(|path| path == dir)
+            || planned.keys().any(|path| path.starts_with(&prefix))
+    };
+
+    let mut files = Vec::new();
+    let mut walker = WalkDir::new(root)
+        .min_depth(1)
+        .sort_by_file_name()
+        .into_iter();
+    while let Some(entry) = walker.next() {
+        let entry = entry.with_context(|| format!("wizard: failed to scan {}", root.display()))?;
+        let relative = path_key(entry.path().strip_prefix(root).unwrap_or(entry.path()));
+        if entry.file_type().is_dir() {
+            if !holds_planned(&relative) {
+                files.push(OverwrittenFile {
+                    path: format!("{relative}/"),
+                    change: OverwriteChange::Removed,
+                    diff: None,
+                });
+                walker.skip_current_dir();
+            }
+            continue;
+        }
+        let Some(new_contents) = planned.get(&relative) else {
+            files.push(OverwrittenFile {
+                path: relative,
+                change: OverwriteChange::Removed,
+                diff: None,
+            });
+            continue;
+        };
+        let old_contents = fs::read(entry.path())
+            .with_context(|| format!("wizard: failed to read {}", entry.path().display()))?;
+        if &old_contents == new_contents {
+            continue;
+        }
+        let diff = match (
+            std::str::from_utf8(&old_contents),
+            std::str::from_utf8(new_contents),
+        ) {
+            (Ok(old), Ok(new)) => Some(
+                TextDiff::from_lines(old, new)
+                    .unified_diff()
+                    .header(&format!("a/{relative}"), &format!("b/{relative}"))
+                    .to_string(),
+            ),
+            _ => None,
+        };
+        files.push(OverwrittenFile {
+            path: relative,
+            change: OverwriteChange::Modified,
+            diff,
+        });
+    }
+    Ok(Some(OverwritePreview {
+        target_root: root.clone(),
+        files,
+    }))
+}
@@ -0,0 +1,279 @@
+//! Ed25519 signatures over wizard plans.
+//!
+//! A signature covers the plan envelope encoded as canonical CBOR with its `signature` field
+//! removed. Keys live in a small keystore directory managed by `wizard keys`: each key id
+//! has a `<id>.pub` file (hex public key) and, for keys generated locally, a `<id>.key`
+//! file (hex secret seed, created owner-only). Every public key in the keystore is trusted
+//! for verification, and once the keystore holds a key, saved plans must be signed.
+
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, anyhow, bail};
+use directories::BaseDirs;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use greentic_types::cbor::canonical;
+use serde::{Deserialize, Serialize};
+
+use super::WizardPlanEnvelope;
+
+pub const PLAN_SIGNATURE_ALGORITHM: &str = "ed25519";
+/// Overrides the default keystore location (`~/.greentic/keys/wizard`).
+pub const PLAN_KEYSTORE_ENV: &str = "GREENTIC_COMPONENT_KEYSTORE";
+const PUBLIC_KEY_EXT: &str = "pub";
+const SECRET_KEY_EXT: &str = "key";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlanSignature {
+    pub algorithm: String,
+    pub key_id: String,
+    /// Hex-encoded ed25519 public key.
+    pub public_key: String,
+    /// Hex-encoded ed25519 signature.
+    pub signature: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PlanKeyEntry {
+    pub key_id: String,
+    pub public_key: String,
+    /// Whether the secret key is available for signing.
+    pub can_sign: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct PlanKeystore {
+    root: PathBuf,
+}
+
+impl PlanKeystore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// The keystore named by `GREENTIC_COMPONENT_KEYSTORE`, or `~/.greentic/keys/wizard`.
+    pub fn open_default() -> Result<Self> {
+        if let Some(root) = env::var_os(PLAN_KEYSTORE_ENV) {
+            return Ok(Self::new(root));
+        }
+        let dirs = BaseDirs::new()
+            .ok_or_else(|| anyhow!("wizard: cannot locate the home directory for the keystore"))?;
+        Ok(Self::new(
+            dirs.home_dir()
+                .join(".greentic")
+                .join("keys")
+                .join("wizard"),
+        ))
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Create a new signing key; returns its hex public key.
+    pub fn generate(&self, key_id: &str) -> Result<String> {
+        validate_key_id(key_id)?;
+        if self.key_path(key_id, PUBLIC_KEY_EXT).exists() {
+            bail!(
+                "wizard: key `{key_id}` already exists in {}",
+                self.root.display()
+            );
+        }
+        let mut seed = [0u8; 32];
+        getrandom::getrandom(&mut seed)
+            .map_err(|err| anyhow!("wizard: failed to gather randomness: {err}"))?;
+        let signing_key = SigningKey::from_bytes(&seed);
+        let public_key = hex::encode(signing_key.verifying_key().as_bytes());
+
+        self.write_key(key_id, SECRET_KEY_EXT, &hex::encode(seed), true)?;
+        self.write_key(key_id, PUBLIC_KEY_EXT, &public_key, false)?;
+        Ok(public_key)
+    }
+
+    /// Trust a public key generated elsewhere (for example a reviewer's key).
+    pub fn trust(&self, key_id: &str, public_key: &str) -> Result<()> {
+        validate_key_id(key_id)?;
+        parse_public_key(public_key)?;
+        self.write_key(key_id, PUBLIC_KEY_EXT, public_key.trim(), false)
+    }
+
+    pub fn list(&self) -> Result<Vec<PlanKeyEntry>> {
+        if !self.root.is_dir() {
+            return Ok(Vec::new());
+        }
+        let mut entries = Vec::new();
+        for entry in fs::read_dir(&self.root)
+            .with_context(|| format!("wizard: failed to read keystore {}", self.root.display()))?
+        {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(PUBLIC_KEY_EXT) {
+                continue;
+            }
+            let Some(key_id) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            entries.push(PlanKeyEntry {
+                key_id: key_id.to_string(),
+                public_key: self.read_key(key_id, PUBLIC_KEY_EXT)?,
+                can_sign: self.key_path(key_id, SECRET_KEY_EXT).is_file(),
+            });
+        }
+        entries.sort_by(|a, b| a.key_id.cmp(&b.key_id));
+        Ok(entries)
+    }
+
+    /// Sign `envelope` in place, replacing any previous signature.
+    pub fn sign(&self, key_id: &str, envelope: &mut WizardPlanEnvelope) -> Result<()> {
+        validate_key_id(key_id)?;
+        let seed = hex::decode(self.read_key(key_id, SECRET_KEY_EXT)?)
+            .ok()
+            .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+            .ok_or_else(|| anyhow!("wizard: secret key `{key_id}` is malformed"))?;
+        let signing_key = SigningKey::from_bytes(&seed);
+        let signature = signing_key.sign(&signing_payload(envelope)?);
+        envelope.signature = Some(PlanSignature {
+            algorithm: PLAN_SIGNATURE_ALGORITHM.to_string(),
+            key_id: key_id.to_string(),
+            public_key: hex::encode(signing_key.verifying_key().as_bytes()),
+            signature: hex::encode(signature.to_bytes()),
+        });
+        Ok(())
+    }
+
+    /// Require a valid signature from a key in this keystore.
+    pub fn verify_trusted(&self, envelope: &WizardPlanEnvelope) -> Result<()> {
+        let signature = envelope
+            .signature
+            .as_ref()
+            .ok_or_else(|| anyhow!("wizard: plan is not signed"))?;
+        validate_key_id(&signature.key_id)?;
+        let trusted = self
+            .read_key(&signature.key_id, PUBLIC_KEY_EXT)
+            .with_context(|| {
+                format!(
+                    "wizard: plan signer `{}` is not in the keystore",
+                    signature.key_id
+                )
+            })?;
+        if !trusted.eq_ignore_ascii_case(signature.public_key.trim()) {
+            bail!(
+                "wizard: plan was signed with a different key than trusted `{}`",
+                signature.key_id
+            );
+        }
+        verify_signature(envelope)
+    }
+
+    /// Check a saved plan before it is applied: it must carry a trusted signature when
+    /// `require_signed` is set or when the keystore trusts any key. A plan that runs commands
+    /// needs one too, because anyone can recompute the steps digest, unless the caller opted
+    /// in with `allow_unsigned_commands`.
+    pub fn verify_saved(
+        &self,
+        envelope: &WizardPlanEnvelope,
+        require_signed: bool,
+        allow_unsigned_commands: bool,
+    ) -> Result<()> {
+        if require_signed || !self.list()?.is_empty() {
+            return self.verify_trusted(envelope);
+        }
+        if envelope.plan.runs_commands() && !allow_unsigned_commands {
+            return self.verify_trusted(envelope).context(
+                "wizard: plan runs commands (run_cli steps) and must be signed by a trusted key",
+            );
+        }
+        Ok(())
+    }
+
+    fn key_path(&self, key_id: &str, ext: &str) -> PathBuf {
+        self.root.join(format!("{key_id}.{ext}"))
+    }
+
+    fn read_key(&self, key_id: &str, ext: &str) -> Result<String> {
+        let path = self.key_path(key_id, ext);
+        fs::read_to_string(&path)
+            .map(|raw| raw.trim().to_string())
+            .with_context(|| format!("wizard: failed to read key {}", path.display()))
+    }
+
+    fn write_key(&self, key_id: &str, ext: &str, contents: &str, secret: bool) -> Result<()> {
+        fs::create_dir_all(&self.root).with_context(|| {
+            format!("wizard: failed to create keystore {}", self.root.display())
+        })?;
+        let path = self.key_path(key_id, ext);
+        if !secret {
+            return fs::write(&path, format!("{contents}\n"))
+                .with_context(|| format!("wizard: failed to write key {}", path.display()));
+        }
+        // Secret keys are never replaced and are owner-only from the moment they exist.
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options
+            .open(&path)
+            .with_context(|| format!("wizard: failed to create key {}", path.display()))?;
+        writeln!(file, "{contents}")
+            .with_context(|| format!("wizard: failed to write key {}", path.display()))
+    }
+}
+
+/// Check that an attached signature matches the plan. Unsigned plans pass.
+pub(super) fn verify_signature(envelope: &WizardPlanEnvelope) -> Result<()> {
+    let Some(signature) = &envelope.signature else {
+        return Ok(());
+    };
+    if signature.algorithm != PLAN_SIGNATURE_ALGORITHM {
+        bail!(
+            "wizard: unsupported plan signature algorithm `{}`",
+            signature.algorithm
+        );
+    }
+    let public_key = parse_public_key(&signature.public_key)?;
+    let bytes = hex::decode(signature.signature.trim())
+        .ok()
+        .and_then(|bytes| <[u8; 64]>::try_from(bytes).ok())
+        .ok_or_else(|| anyhow!("wizard: plan signature is malformed"))?;
+    public_key
+        .verify(&signing_payload(envelope)?, &Signature::from_bytes(&bytes))
+        .map_err(|_| {
+            anyhow!(
+                "wizard: plan signature from `{}` does not match the plan contents",
+                signature.key_id
+            )
+        })
+}
+
+/// The envelope without its `signature`, as canonical CBOR so the payload does not depend on
+/// how the plan file was formatted.
+fn signing_payload(envelope: &WizardPlanEnvelope) -> Result<Vec<u8>> {
+    let mut unsigned = envelope.clone();
+    unsigned.signature = None;
+    canonical::to_canonical_cbor_allow_floats(&unsigned)
+        .map_err(|err| anyhow!("wizard: failed to encode plan for signing: {err}"))
+}
+
+fn parse_public_key(value: &str) -> Result<VerifyingKey> {
+    hex::decode(value.trim())
+        .ok()
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        .and_then(|bytes| VerifyingKey::from_bytes(&bytes).ok())
+        .ok_or_else(|| anyhow!("wizard: `{value}` is not a hex-encoded ed25519 public key"))
+}
+
+fn validate_key_id(key_id: &str) -> Result<()> {
+    let valid = !key_id.is_empty()
+        && key_id
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.'))
+        && !key_id.starts_with('.');
+    if !valid {
+        bail!("wizard: invalid key id `{key_id}` (use letters, digits, `-`, `_`, `.`)");
+    }
+    Ok(())
+}
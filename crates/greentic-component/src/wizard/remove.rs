@@ -0,0 +1,89 @@
+//! `WizardMode::Remove`: uninstall what the wizard generated.
+//!
+//! Only files recorded in `.greentic/wizard.lock.json` whose contents still match the
+//! recorded digest are removed; edited or user-authored files stay in place.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use walkdir::WalkDir;
+
+use super::update::{WIZARD_LOCK_PATH, WizardLock, digest_hex};
+use super::{
+    ApplyResult, GENERATOR_ID, PLAN_VERSION, WizardPlan, WizardPlanEnvelope, WizardPlanMeta,
+    WizardPlanMetadata, WizardPlanMode, WizardStep, WizardTarget, execute_plan, seal_plan,
+};
+
+const DIST_DIR: &str = "dist";
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RemoveOptions {
+    /// Also delete build artifacts under `dist/`.
+    pub clean_dist: bool,
+}
+
+pub fn remove_scaffold(
+    project_root: &Path,
+    options: RemoveOptions,
+    dry_run: bool,
+) -> Result<ApplyResult> {
+    let lock = WizardLock::load(project_root)?;
+    let mut paths = Vec::new();
+    let mut warnings = Vec::new();
+
+    for (relative, recorded) in &lock.files {
+        let path = project_root.join(relative);
+        if !path.is_file() {
+            continue;
+        }
+        let contents = std::fs::read(&path)
+            .with_context(|| format!("wizard: failed to read {}", path.display()))?;
+        if digest_hex(&contents) == *recorded {
+            paths.push(relative.clone());
+        } else {
+            warnings.push(format!(
+                "wizard: keeping {relative} (modified since generation)"
+            ));
+        }
+    }
+
+    if options.clean_dist {
+        let dist = project_root.join(DIST_DIR);
+        if dist.is_dir() {
+            for entry in WalkDir::new(&dist).sort_by_file_name() {
+                let entry = entry.context("wizard: failed to walk dist/")?;
+                if entry.file_type().is_file()
+                    && let Ok(relative) = entry.path().strip_prefix(project_root)
+                {
+                    paths.push(relative.to_string_lossy().replace('\\', "/"));
+                }
+            }
+        }
+    }
+    paths.push(WIZARD_LOCK_PATH.to_string());
+
+    let mut plan = WizardPlanEnvelope {
+        plan_version: PLAN_VERSION,
+        metadata: WizardPlanMetadata {
+            generator: GENERATOR_ID.to_string(),
+            template_version: lock.template_version.clone(),
+            template_digest_blake3: String::new(),
+            requested_abi_version: String::new(),
+        },
+        target_root: project_root.to_path_buf(),
+        plan: WizardPlan {
+            meta: WizardPlanMeta {
+                id: "greentic.component.remove".to_string(),
+                target: WizardTarget::Component,
+                mode: WizardPlanMode::Remove,
+            },
+            steps: vec![WizardStep::RemoveFiles { paths }],
+        },
+        signature: None,
+    };
+    seal_plan(&mut plan)?;
+    if !dry_run {
+        execute_plan(&plan)?;
+    }
+    Ok(ApplyResult { plan, warnings })
+}
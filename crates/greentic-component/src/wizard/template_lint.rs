@@ -0,0 +1,156 @@
+//! Lint for external template packs (`greentic-component templates lint <dir>`).
+//!
+//! The pack is rendered over its base template with a sample context, the same way
+//! `wizard --template <dir>` would, and the output is checked: `Cargo.toml` must parse,
+//! `component.manifest.json` must pass the manifest schema, and i18n bundles must be valid
+//! JSON. Render-context variables that none of the pack's templates reference are listed too.
+
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+
+use super::template_pack::ANSWER_VARIABLES;
+use super::{
+    TemplatePack, WizardCi, WizardMode, WizardRequest, WizardStep, apply_scaffold,
+    decode_step_content,
+};
+use crate::scaffold::config_schema::ConfigSchemaInput;
+use crate::scaffold::license::LicenseKind;
+use crate::scaffold::runtime_capabilities::RuntimeCapabilitiesInput;
+
+const SAMPLE_NAME: &str = "lint-sample";
+
+static MUSTACHE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\{\{(.*?)\}\}").expect("valid mustache regex"));
+static IDENTIFIER: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"[A-Za-z_][A-Za-z0-9_]*").expect("valid identifier regex"));
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TemplatePackLint {
+    pub pack: String,
+    /// Files the sample scaffold would write, base template included.
+    pub files: Vec<String>,
+    pub errors: Vec<String>,
+    /// Answer-derived variables (`name`, `org`, `user_operations`, `default_operation`) that no
+    /// pack template references.
+    pub unused_variables: Vec<String>,
+}
+
+impl TemplatePackLint {
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Load the pack in `root` and check what it renders. Errors loading the pack itself are
+/// returned; problems in the rendered output are collected in [`TemplatePackLint::errors`].
+pub fn lint_template_pack(root: &Path) -> Result<TemplatePackLint> {
+    let pack = TemplatePack::from_dir(root)?;
+    let mut lint = TemplatePackLint {
+        pack: pack.template_version(),
+        files: Vec::new(),
+        errors: Vec::new(),
+        unused_variables: unused_variables(&pack),
+    };
+
+    let target = tempfile::tempdir().context("wizard: failed to create lint directory")?;
+    let request = WizardRequest {
+        name: SAMPLE_NAME.to_string(),
+        org: None,
+        license: LicenseKind::default(),
+        author: None,
+        abi_version: "0.6.0".to_string(),
+        mode: WizardMode::Default,
+        template: pack.base,
+        template_pack: Some(pack),
+        target: target.path().join(SAMPLE_NAME),
+        workspace: false,
+        ci: WizardCi::None,
+        answers: None,
+        required_capabilities: Vec::new(),
+        provided_capabilities: Vec::new(),
+        user_operations: Vec::new(),
+        default_operation: None,
+        runtime_capabilities: RuntimeCapabilitiesInput::default(),
+        config_schema: ConfigSchemaInput::default(),
+    };
+    let plan = match apply_scaffold(request, true) {
+        Ok(result) => result.plan,
+        Err(err) => {
+            lint.errors.push(format!("{err:#}"));
+            return Ok(lint);
+        }
+    };
+
+    for step in &plan.plan.steps {
+        let WizardStep::WriteFiles { files } = step else {
+            continue;
+        };
+        for (path, content) in files {
+            lint.files.push(path.clone());
+            let bytes = decode_step_content(path, content)?;
+            if let Some(error) = check_file(path, &bytes) {
+                lint.errors.push(format!("{path}: {error}"));
+            }
+        }
+    }
+    Ok(lint)
+}
+
+fn check_file(path: &str, bytes: &[u8]) -> Option<String> {
+    let is_i18n_bundle = path.ends_with(".json") && path.split('/').any(|part| part == "i18n");
+    if path != "Cargo.toml" && path != "component.manifest.json" && !is_i18n_bundle {
+        return None;
+    }
+    let text = match std::str::from_utf8(bytes) {
+        Ok(text) => text,
+        Err(err) => return Some(format!("not valid UTF-8: {err}")),
+    };
+    match path {
+        "Cargo.toml" => toml::from_str::<toml::Table>(text)
+            .err()
+            .map(|err| format!("invalid TOML: {err}")),
+        "component.manifest.json" => crate::manifest::validate_manifest(text)
+            .err()
+            .map(|err| format!("invalid manifest: {err}")),
+        _ => serde_json::from_str::<JsonValue>(text)
+            .err()
+            .map(|err| format!("invalid JSON: {err}")),
+    }
+}
+
+fn unused_variables(pack: &TemplatePack) -> Vec<String> {
+    let mut referenced = BTreeSet::new();
+    for source in pack.template_sources() {
+        for expression in MUSTACHE.captures_iter(source) {
+            referenced.extend(
+                IDENTIFIER
+                    .find_iter(&expression[1])
+                    .map(|identifier| identifier.as_str().to_string()),
+            );
+        }
+    }
+    ANSWER_VARIABLES
+        .iter()
+        .filter(|variable| !referenced.contains(**variable))
+        .map(|variable| variable.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_checks_manifests_and_i18n_bundles() {
+        assert!(check_file("Cargo.toml", b"[package\n").is_some());
+        assert!(check_file("assets/i18n/en.json", b"{").is_some());
+        assert!(check_file("assets/i18n/en.json", b"{}").is_none());
+        assert!(check_file("README.md", b"{").is_none());
+    }
+}
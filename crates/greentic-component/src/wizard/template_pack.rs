@@ -0,0 +1,348 @@
+//! External wizard template packs.
+//!
+//! A pack is a directory (local, or cloned from a `git+https://` reference) holding a
+//! `wizard-template.json` manifest plus the files it lists. Each listed file is rendered
+//! with Handlebars against the wizard context and layered over the files generated by the
+//! pack's built-in base template, so organizations can ship their own scaffolds without
+//! forking this module.
+
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result, anyhow, bail};
+use handlebars::{Handlebars, no_escape};
+use serde::Deserialize;
+use serde_json::{Value as JsonValue, json};
+
+use super::{GeneratedFile, WizardContext, WizardTemplate};
+
+pub const TEMPLATE_PACK_MANIFEST: &str = "wizard-template.json";
+/// Variables of the context built by [`TemplatePack::render`] that come from the user's
+/// answers. The rest (`world`, `role`, `pack`, ...) follow from the base template, so a pack
+/// that ignores them is not missing anything.
+pub(super) const ANSWER_VARIABLES: &[&str] =
+    &["name", "org", "user_operations", "default_operation"];
+const GIT_PREFIX: &str = "git+";
+
+/// Where a template pack is loaded from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplatePackSource {
+    Dir(PathBuf),
+    /// `git+<url>[#<ref>]`, cloned shallowly before loading.
+    Git {
+        url: String,
+        reference: Option<String>,
+    },
+}
+
+impl TemplatePackSource {
+    /// Interpret a `--template` value as a pack reference. Returns `None` for plain ids.
+    pub fn parse(spec: &str) -> Option<Self> {
+        let spec = spec.trim();
+        if let Some(rest) = spec.strip_prefix(GIT_PREFIX) {
+            let (url, reference) = match rest.split_once('#') {
+                Some((url, reference)) if !reference.is_empty() => {
+                    (url, Some(reference.to_string()))
+                }
+                Some((url, _)) => (url, None),
+                None => (rest, None),
+            };
+            return Some(Self::Git {
+                url: url.to_string(),
+                reference,
+            });
+        }
+        let path = Path::new(spec);
+        path.join(TEMPLATE_PACK_MANIFEST)
+            .is_file()
+            .then(|| Self::Dir(path.to_path_buf()))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TemplatePackManifest {
+    id: String,
+    #[serde(default)]
+    version: Option<String>,
+    /// Built-in template whose files the pack starts from.
+    #[serde(default)]
+    base: Option<String>,
+    files: Vec<TemplatePackManifestFile>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TemplatePackManifestFile {
+    /// Output path relative to the component root; may contain Handlebars expressions.
+    path: String,
+    /// Source file inside the pack. Files ending in `.hbs` are rendered, others copied.
+    source: String,
+}
+
+#[derive(Debug, Clone)]
+struct TemplatePackEntry {
+    path_template: String,
+    contents: Vec<u8>,
+    templated: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct TemplatePack {
+    pub id: String,
+    pub version: Option<String>,
+    pub base: WizardTemplate,
+    entries: Vec<TemplatePackEntry>,
+}
+
+impl TemplatePack {
+    pub fn load(source: &TemplatePackSource) -> Result<Self> {
+        match source {
+            TemplatePackSource::Dir(path) => Self::from_dir(path),
+            TemplatePackSource::Git { url, reference } => {
+                // git would read a leading `-` as an option such as `--upload-pack=<cmd>`.
+                if url.starts_with('-') {
+                    bail!("wizard: template pack url `{url}` must not start with `-`");
+                }
+                if let Some(reference) = reference.as_deref().filter(|r| r.starts_with('-')) {
+                    bail!("wizard: template pack ref `{reference}` must not start with `-`");
+                }
+                let checkout = tempfile::tempdir()
+                    .context("wizard: failed to create template pack checkout directory")?;
+                let mut command = Command::new("git");
+                command.args(["clone", "--quiet", "--depth", "1"]);
+                if let Some(reference) = reference {
+                    command.args(["--branch", reference]);
+                }
+                let status = command
+                    .arg("--")
+                    .arg(url)
+                    .arg(checkout.path())
+                    .status()
+                    .with_context(|| format!("wizard: failed to run git clone for {url}"))?;
+                if !status.success() {
+                    bail!("wizard: git clone of template pack {url} failed");
+                }
+                // Entries are read into memory, so the checkout can be dropped afterwards.
+                Self::from_dir(checkout.path())
+            }
+        }
+    }
+
+    pub fn from_dir(root: &Path) -> Result<Self> {
+        let manifest_path = root.join(TEMPLATE_PACK_MANIFEST);
+        let raw = fs::read_to_string(&manifest_path)
+            .with_context(|| format!("wizard: failed to read {}", manifest_path.display()))?;
+        let manifest: TemplatePackManifest = serde_json::from_str(&raw).with_context(|| {
+            format!("wizard: invalid template pack {}", manifest_path.display())
+        })?;
+
+        let base = match manifest.base.as_deref() {
+            Some(id) => WizardTemplate::from_id(id).ok_or_else(|| {
+                anyhow!(
+                    "wizard: template pack `{}` has unknown base template `{id}`",
+                    manifest.id
+                )
+            })?,
+            None => WizardTemplate::Component,
+        };
+
+        let pack_root = root
+            .canonicalize()
+            .with_context(|| format!("wizard: failed to resolve {}", root.display()))?;
+        let mut entries = Vec::with_capacity(manifest.files.len());
+        for file in manifest.files {
+            let source = relative_path(&file.source)?;
+            let source_path = root.join(&source);
+            let resolved = source_path.canonicalize().with_context(|| {
+                format!(
+                    "wizard: template pack `{}` is missing {}",
+                    manifest.id,
+                    source_path.display()
+                )
+            })?;
+            // Packs may come from untrusted remotes; a symlink must not pull in files from
+            // elsewhere on this machine.
+            if !resolved.starts_with(&pack_root) {
+                bail!(
+                    "wizard: template pack `{}` source `{}` resolves outside the pack",
+                    manifest.id,
+                    file.source
+                );
+            }
+            let contents = fs::read(&resolved).with_context(|| {
+                format!(
+                    "wizard: template pack `{}` failed to read {}",
+                    manifest.id,
+                    source_path.display()
+                )
+            })?;
+            entries.push(TemplatePackEntry {
+                path_template: file.path,
+                contents,
+                templated: file.source.ends_with(".hbs"),
+            });
+        }
+
+        Ok(Self {
+            id: manifest.id,
+            version: manifest.version,
+            base,
+            entries,
+        })
+    }
+
+    /// Value recorded as `template_version` in plan metadata.
+    pub fn template_version(&self) -> String {
+        match &self.version {
+            Some(version) => format!("{}@{version}", self.id),
+            None => self.id.clone(),
+        }
+    }
+
+    /// Handlebars sources in the pack: every output path plus the contents of `.hbs` files.
+    pub(super) fn template_sources(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().flat_map(|entry| {
+            let contents = entry
+                .templated
+                .then(|| std::str::from_utf8(&entry.contents).ok())
+                .flatten();
+            std::iter::once(entry.path_template.as_str()).chain(contents)
+        })
+    }
+
+    /// Render the pack over `files`, replacing base files that share an output path.
+    pub(super) fn render(
+        &self,
+        context: &WizardContext,
+        files: &mut Vec<GeneratedFile>,
+    ) -> Result<()> {
+        let mut handlebars = Handlebars::new();
+        handlebars.set_strict_mode(true);
+        handlebars.register_escape_fn(no_escape);
+        let data = self.render_context(context);
+
+        for entry in &self.entries {
+            let rendered_path = handlebars
+                .render_template(&entry.path_template, &data)
+                .with_context(|| {
+                    format!(
+                        "wizard: failed to render pack path `{}`",
+                        entry.path_template
+                    )
+                })?;
+            let path = relative_path(&rendered_path)?;
+            let contents = if entry.templated {
+                let template = std::str::from_utf8(&entry.contents).with_context(|| {
+                    format!("wizard: pack template for {rendered_path} must be UTF-8")
+                })?;
+                handlebars
+                    .render_template(template, &data)
+                    .with_context(|| format!("wizard: failed to render pack file {rendered_path}"))?
+                    .into_bytes()
+            } else {
+                entry.contents.clone()
+            };
+            files.retain(|file| file.path != path);
+            files.push(GeneratedFile { path, contents });
+        }
+        Ok(())
+    }
+
+    fn render_context(&self, context: &WizardContext) -> JsonValue {
+        json!({
+            "name": context.name,
+            "org": context.org,
+            "abi_version": context.abi_version,
+            "world": context.abi.world,
+            "template": context.template.id(),
+            "role": context.template.role(),
+            "supports": context.template.supports(),
+            "profile": context.template.profile(),
+            "user_operations": context.user_operations,
+            "default_operation": context.default_operation,
+            "pack": {
+                "id": self.id,
+                "version": self.version,
+            },
+        })
+    }
+}
+
+fn relative_path(value: &str) -> Result<PathBuf> {
+    let path = PathBuf::from(value);
+    let escapes = path
+        .components()
+        .any(|component| !matches!(component, Component::Normal(_)));
+    if value.trim().is_empty() || escapes {
+        bail!("wizard: template pack path `{value}` must be relative and must not contain `..`");
+    }
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_git_references_and_ignores_plain_ids() {
+        assert_eq!(
+            TemplatePackSource::parse("git+https://example.com/packs.git#v1"),
+            Some(TemplatePackSource::Git {
+                url: "https://example.com/packs.git".to_string(),
+                reference: Some("v1".to_string()),
+            })
+        );
+        assert_eq!(TemplatePackSource::parse("component-v0_6"), None);
+    }
+
+    #[test]
+    fn rejects_git_url_and_ref_that_look_like_options() {
+        for source in [
+            "git+--upload-pack=touch /tmp/pwned",
+            "git+https://example.com/packs.git#--upload-pack=touch /tmp/pwned",
+        ] {
+            let source = TemplatePackSource::parse(source).expect("git source");
+            let err = TemplatePack::load(&source).expect_err("option-like value must fail");
+            assert!(err.to_string().contains("must not start with `-`"), "{err}");
+        }
+    }
+
+    #[test]
+    fn rejects_paths_outside_the_pack() {
+        assert!(relative_path("../escape.rs").is_err());
+        assert!(relative_path("/etc/passwd").is_err());
+        assert!(relative_path("src/lib.rs").is_ok());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn rejects_sources_symlinked_outside_the_pack() {
+        let outside = tempfile::tempdir().expect("tempdir");
+        let secret = outside.path().join("id_ed25519");
+        fs::write(&secret, "private key").expect("write secret");
+        let pack = tempfile::tempdir().expect("tempdir");
+        fs::write(
+            pack.path().join(TEMPLATE_PACK_MANIFEST),
+            r#"{ "id": "acme", "files": [
+                { "path": "README.md", "source": "README.md" },
+                { "path": "key.txt", "source": "key.txt" }
+            ] }"#,
+        )
+        .expect("write manifest");
+        fs::write(pack.path().join("README.md"), "# demo").expect("write readme");
+        std::os::unix::fs::symlink(&secret, pack.path().join("key.txt")).expect("symlink");
+
+        let err = TemplatePack::from_dir(pack.path()).expect_err("symlink must be rejected");
+        assert!(
+            err.to_string().contains("resolves outside the pack"),
+            "{err}"
+        );
+
+        fs::remove_file(pack.path().join("key.txt")).expect("remove symlink");
+        std::os::unix::fs::symlink("README.md", pack.path().join("key.txt")).expect("symlink");
+        let loaded = TemplatePack::from_dir(pack.path()).expect("in-pack symlink loads");
+        assert_eq!(loaded.entries[1].contents, b"# demo");
+    }
+}
@@ -0,0 +1,208 @@
+//! Non-destructive regeneration for `WizardMode::Update`.
+//!
+//! Every scaffold records `.greentic/wizard.lock.json` with the blake3 digest of each file
+//! it generated. An update regenerates the template and compares three states per file:
+//! the recorded digest, the file on disk, and the fresh template output. Files the user
+//! never touched are rewritten, files the template did not change are left alone, and
+//! files changed on both sides get a sibling `<path>.new` with conflict markers instead of
+//! being overwritten.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+
+use super::GeneratedFile;
+
+pub const WIZARD_LOCK_PATH: &str = ".greentic/wizard.lock.json";
+const WIZARD_LOCK_SCHEMA: &str = "greentic.component.wizard-lock/v1";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WizardLock {
+    pub schema: String,
+    pub template: String,
+    pub template_version: String,
+    /// Relative path -> blake3 of the generated contents.
+    pub files: BTreeMap<String, String>,
+}
+
+impl WizardLock {
+    pub fn load(project_root: &Path) -> Result<Self> {
+        let path = project_root.join(WIZARD_LOCK_PATH);
+        let raw = fs::read_to_string(&path).with_context(|| {
+            format!(
+                "wizard: update requires {} (scaffold the component with the wizard first)",
+                path.display()
+            )
+        })?;
+        let lock: Self = serde_json::from_str(&raw)
+            .with_context(|| format!("wizard: invalid lock file {}", path.display()))?;
+        if lock.schema != WIZARD_LOCK_SCHEMA {
+            bail!(
+                "wizard: unsupported lock schema `{}` in {} (expected `{WIZARD_LOCK_SCHEMA}`)",
+                lock.schema,
+                path.display()
+            );
+        }
+        Ok(lock)
+    }
+}
+
+pub(super) fn lock_file(
+    template: &str,
+    template_version: &str,
+    files: &[GeneratedFile],
+) -> Result<GeneratedFile> {
+    let lock = WizardLock {
+        schema: WIZARD_LOCK_SCHEMA.to_string(),
+        template: template.to_string(),
+        template_version: template_version.to_string(),
+        files: files
+            .iter()
+            .map(|file| (path_key(&file.path), digest_hex(&file.contents)))
+            .collect(),
+    };
+    let mut contents = serde_json::to_vec_pretty(&lock)?;
+    contents.push(b'\n');
+    Ok(GeneratedFile {
+        path: PathBuf::from(WIZARD_LOCK_PATH),
+        contents,
+    })
+}
+
+/// Reduce a full regeneration to the writes an update needs, plus warnings for the caller.
+pub(super) fn plan_update(
+    project_root: &Path,
+    files: Vec<GeneratedFile>,
+    template_version: &str,
+) -> Result<(Vec<GeneratedFile>, Vec<String>)> {
+    let previous = WizardLock::load(project_root)?;
+    let mut writes = Vec::new();
+    let mut warnings = Vec::new();
+    if previous.template_version != template_version {
+        warnings.push(format!(
+            "wizard: updating from {} to {template_version}",
+            previous.template_version
+        ));
+    }
+
+    for file in files {
+        let key = path_key(&file.path);
+        if key == WIZARD_LOCK_PATH {
+            writes.push(file);
+            continue;
+        }
+        let on_disk = project_root.join(&file.path);
+        let current = match fs::read(&on_disk) {
+            Ok(bytes) => Some(bytes),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+            Err(err) => {
+                return Err(err)
+                    .with_context(|| format!("wizard: failed to read {}", on_disk.display()));
+            }
+        };
+        let recorded = previous.files.get(&key);
+
+        match (current, recorded) {
+            (None, None) => writes.push(file),
+            (None, Some(_)) => warnings.push(format!(
+                "wizard: {key} was deleted from the project; leaving it deleted"
+            )),
+            (Some(current), _) if current == file.contents => {}
+            (Some(current), Some(recorded)) if digest_hex(&current) == *recorded => {
+                writes.push(file);
+            }
+            (Some(_), Some(recorded)) if digest_hex(&file.contents) == *recorded => {}
+            (Some(current), _) => {
+                let conflict = conflict_file(&file, &current, template_version);
+                warnings.push(format!(
+                    "wizard: {key} has local changes; wrote template version to {}",
+                    path_key(&conflict.path)
+                ));
+                writes.push(conflict);
+            }
+        }
+    }
+    Ok((writes, warnings))
+}
+
+fn conflict_file(file: &GeneratedFile, current: &[u8], template_version: &str) -> GeneratedFile {
+    let mut path = file.path.clone().into_os_string();
+    path.push(".new");
+    let contents = match (
+        std::str::from_utf8(current),
+        std::str::from_utf8(&file.contents),
+    ) {
+        (Ok(current), Ok(generated)) => {
+            merge_with_markers(current, generated, template_version).into_bytes()
+        }
+        // Binary assets cannot carry markers; ship the regenerated bytes for manual review.
+        _ => file.contents.clone(),
+    };
+    GeneratedFile {
+        path: PathBuf::from(path),
+        contents,
+    }
+}
+
+/// Keep the lines both sides share at the start and end, and wrap the differing middle
+/// in git-style conflict markers.
+fn merge_with_markers(current: &str, generated: &str, template_version: &str) -> String {
+    let ours = current.split_inclusive('\n').collect::<Vec<_>>();
+    let theirs = generated.split_inclusive('\n').collect::<Vec<_>>();
+    let prefix = ours.iter().zip(&theirs).take_while(|(a, b)| a == b).count();
+    let max_suffix = ours.len().min(theirs.len()) - prefix;
+    let suffix = ours
+        .iter()
+        .rev()
+        .zip(theirs.iter().rev())
+        .take(max_suffix)
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut merged = String::new();
+    ours[..prefix].iter().for_each(|line| merged.push_str(line));
+    merged.push_str("<<<<<<< current\n");
+    push_lines(&mut merged, &ours[prefix..ours.len() - suffix]);
+    merged.push_str("=======\n");
+    push_lines(&mut merged, &theirs[prefix..theirs.len() - suffix]);
+    merged.push_str(&format!(">>>>>>> {template_version}\n"));
+    ours[ours.len() - suffix..]
+        .iter()
+        .for_each(|line| merged.push_str(line));
+    merged
+}
+
+fn push_lines(out: &mut String, lines: &[&str]) {
+    for line in lines {
+        out.push_str(line);
+        if !line.ends_with('\n') {
+            out.push('\n');
+        }
+    }
+}
+
+pub(super) fn path_key(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+pub(super) fn digest_hex(bytes: &[u8]) -> String {
+    blake3::hash(bytes).to_hex().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn markers_wrap_only_the_differing_lines() {
+        let merged = merge_with_markers("a\nmine\nz\n", "a\ntheirs\nz\n", "v2");
+        assert_eq!(
+            merged,
+            "a\n<<<<<<< current\nmine\n=======\ntheirs\n>>>>>>> v2\nz\n"
+        );
+    }
+}
@@ -0,0 +1,281 @@
+//! Scaffolding into an existing Cargo workspace.
+//!
+//! In workspace mode the wizard looks for the nearest ancestor `Cargo.toml` with a
+//! `[workspace]` table, registers the component as a member, and inherits any package
+//! fields and dependencies the workspace already declares.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use toml::{Table as TomlTable, Value as TomlValue};
+
+#[derive(Debug, Clone)]
+pub(super) struct CargoWorkspace {
+    pub root: PathBuf,
+    manifest: String,
+    members: Vec<String>,
+    /// Keys of `[workspace.package]`.
+    pub package_fields: BTreeSet<String>,
+    /// Keys of `[workspace.dependencies]`.
+    pub dependencies: BTreeSet<String>,
+}
+
+impl CargoWorkspace {
+    /// Find the closest workspace above `target`, which does not need to exist yet.
+    pub fn find_enclosing(target: &Path) -> Result<Option<Self>> {
+        for dir in target.ancestors().skip(1) {
+            let manifest_path = dir.join("Cargo.toml");
+            if !manifest_path.is_file() {
+                continue;
+            }
+            let manifest = fs::read_to_string(&manifest_path)
+                .with_context(|| format!("wizard: failed to read {}", manifest_path.display()))?;
+            let workspace = Self::from_manifest(dir, manifest)
+                .with_context(|| format!("wizard: invalid {}", manifest_path.display()))?;
+            if workspace.is_some() {
+                return Ok(workspace);
+            }
+        }
+        Ok(None)
+    }
+
+    /// Workspace declared by `manifest` at `root`, or `None` when it has no `[workspace]` table.
+    pub fn from_manifest(root: &Path, manifest: String) -> Result<Option<Self>> {
+        let parsed: TomlTable = toml::from_str(&manifest)?;
+        let Some(TomlValue::Table(workspace)) = parsed.get("workspace") else {
+            return Ok(None);
+        };
+        Ok(Some(Self {
+            root: root.to_path_buf(),
+            members: string_array(workspace.get("members")),
+            package_fields: table_keys(workspace.get("package")),
+            dependencies: table_keys(workspace.get("dependencies")),
+            manifest,
+        }))
+    }
+
+    /// Member path of `target` relative to the workspace root, with `/` separators.
+    pub fn member_path(&self, target: &Path) -> Result<String> {
+        let relative = target.strip_prefix(&self.root).with_context(|| {
+            format!(
+                "wizard: {} is not inside workspace {}",
+                target.display(),
+                self.root.display()
+            )
+        })?;
+        Ok(relative.to_string_lossy().replace('\\', "/"))
+    }
+
+    /// The workspace manifest with `member` added, or `None` when it is already covered.
+    pub fn manifest_with_member(&self, member: &str) -> Result<Option<String>> {
+        if self
+            .members
+            .iter()
+            .any(|pattern| member_matches(pattern, member))
+        {
+            return Ok(None);
+        }
+        let updated = insert_member(&self.manifest, member)?;
+        let parsed: TomlTable = toml::from_str(&updated)
+            .context("wizard: failed to update workspace members in Cargo.toml")?;
+        let members = parsed
+            .get("workspace")
+            .and_then(|workspace| workspace.get("members"));
+        if !string_array(members).iter().any(|entry| entry == member) {
+            bail!("wizard: failed to add `{member}` to workspace members in Cargo.toml");
+        }
+        Ok(Some(updated))
+    }
+}
+
+/// Resolve the workspace root of an `update_workspace_members` step: `manifest` must be the
+/// `Cargo.toml` of the ancestor that `member` leads back to the target root from.
+pub(super) fn member_workspace_root(
+    target_root: &Path,
+    manifest: &str,
+    member: &str,
+) -> Result<PathBuf> {
+    let member_path = Path::new(member);
+    let depth = member_path.components().count();
+    let manifest_path = Path::new(manifest);
+    let points_at_member_root = depth > 0
+        && member_path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+        && manifest_path
+            .file_name()
+            .is_some_and(|name| name == "Cargo.toml")
+        && manifest_path.parent().is_some_and(|parent| {
+            parent.components().count() == depth
+                && parent
+                    .components()
+                    .all(|component| component == Component::ParentDir)
+        });
+    let target_root = std::path::absolute(target_root)
+        .with_context(|| format!("wizard: failed to resolve {}", target_root.display()))?;
+    let workspace_root = target_root
+        .ancestors()
+        .nth(depth)
+        .filter(|root| points_at_member_root && root.join(member_path) == target_root);
+    let Some(workspace_root) = workspace_root else {
+        bail!(
+            "wizard: update_workspace_members `{manifest}` / `{member}` does not lead back to {}",
+            target_root.display()
+        );
+    };
+    Ok(workspace_root.to_path_buf())
+}
+
+/// Execute an `update_workspace_members` step against the manifest as it is now.
+pub(super) fn update_members(target_root: &Path, manifest: &str, member: &str) -> Result<()> {
+    let workspace_root = member_workspace_root(target_root, manifest, member)?;
+    let manifest_path = workspace_root.join("Cargo.toml");
+    let contents = fs::read_to_string(&manifest_path)
+        .with_context(|| format!("wizard: failed to read {}", manifest_path.display()))?;
+    let workspace = CargoWorkspace::from_manifest(&workspace_root, contents)
+        .with_context(|| format!("wizard: invalid {}", manifest_path.display()))?
+        .with_context(|| {
+            format!(
+                "wizard: {} has no [workspace] table",
+                manifest_path.display()
+            )
+        })?;
+    if let Some(updated) = workspace.manifest_with_member(member)? {
+        fs::write(&manifest_path, updated)
+            .with_context(|| format!("wizard: failed to write {}", manifest_path.display()))?;
+    }
+    Ok(())
+}
+
+fn member_matches(pattern: &str, member: &str) -> bool {
+    let pattern = pattern.trim_end_matches('/');
+    match pattern.strip_suffix("/*") {
+        Some(parent) => member
+            .rsplit_once('/')
+            .is_some_and(|(member_parent, _)| member_parent == parent),
+        None => pattern == member,
+    }
+}
+
+/// Textual edit so the rest of the user's manifest (comments, ordering) is untouched.
+fn insert_member(manifest: &str, member: &str) -> Result<String> {
+    let lines = manifest.split_inclusive('\n').collect::<Vec<_>>();
+    let Some(header) = lines.iter().position(|line| line.trim() == "[workspace]") else {
+        bail!("wizard: workspace Cargo.toml must use a `[workspace]` table to add members");
+    };
+    let section_end = lines[header + 1..]
+        .iter()
+        .position(|line| line.trim_start().starts_with('['))
+        .map_or(lines.len(), |offset| header + 1 + offset);
+    let entry = format!("\"{member}\"");
+
+    let members_line = (header + 1..section_end).find(|idx| {
+        let line = lines[*idx].trim_start();
+        line.strip_prefix("members")
+            .is_some_and(|rest| rest.trim_start().starts_with('='))
+    });
+    let Some(start) = members_line else {
+        let mut out = lines[..=header].concat();
+        if !out.ends_with('\n') {
+            out.push('\n');
+        }
+        out.push_str(&format!("members = [{entry}]\n"));
+        out.push_str(&lines[header + 1..].concat());
+        return Ok(out);
+    };
+    let Some(close) = (start..section_end).find(|idx| lines[*idx].contains(']')) else {
+        bail!("wizard: could not find the end of `workspace.members` in Cargo.toml");
+    };
+
+    let mut out = lines[..close].concat();
+    if close == start {
+        // Single-line array: `members = ["a", "b"]`.
+        let line = lines[close];
+        let bracket = line.rfind(']').expect("line contains `]`");
+        let before = line[..bracket].trim_end();
+        let separator = if before.ends_with('[') || before.ends_with(',') {
+            ""
+        } else {
+            ", "
+        };
+        out.push_str(before);
+        out.push_str(separator);
+        out.push_str(&entry);
+        out.push_str(&line[bracket..]);
+    } else {
+        // Multi-line array: add an entry on its own line before the closing bracket.
+        let previous = out.trim_end_matches(['\n', ' ', '\t']);
+        let needs_comma = !previous.ends_with(',') && !previous.ends_with('[');
+        if needs_comma {
+            let trimmed_len = previous.len();
+            let tail = out[trimmed_len..].to_string();
+            out.truncate(trimmed_len);
+            out.push(',');
+            out.push_str(&tail);
+        }
+        let indent = lines[start + 1..close]
+            .iter()
+            .find(|line| !line.trim().is_empty())
+            .map(|line| &line[..line.len() - line.trim_start().len()])
+            .unwrap_or("    ");
+        out.push_str(&format!("{indent}{entry},\n"));
+        out.push_str(lines[close]);
+    }
+    out.push_str(&lines[close + 1..].concat());
+    Ok(out)
+}
+
+fn string_array(value: Option<&TomlValue>) -> Vec<String> {
+    value
+        .and_then(TomlValue::as_array)
+        .map(|values| {
+            values
+                .iter()
+                .filter_map(TomlValue::as_str)
+                .map(ToOwned::to_owned)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn table_keys(value: Option<&TomlValue>) -> BTreeSet<String> {
+    value
+        .and_then(TomlValue::as_table)
+        .map(|table| table.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inserts_members_into_single_and_multi_line_arrays() {
+        let single = "[workspace]\nmembers = [\"crates/a\"]\n";
+        assert_eq!(
+            insert_member(single, "components/demo").unwrap(),
+            "[workspace]\nmembers = [\"crates/a\", \"components/demo\"]\n"
+        );
+
+        let multi = "[workspace]\nmembers = [\n  \"crates/a\"\n]\nresolver = \"2\"\n";
+        assert_eq!(
+            insert_member(multi, "components/demo").unwrap(),
+            "[workspace]\nmembers = [\n  \"crates/a\",\n  \"components/demo\",\n]\nresolver = \"2\"\n"
+        );
+
+        let missing = "[workspace]\nresolver = \"2\"\n";
+        assert_eq!(
+            insert_member(missing, "demo").unwrap(),
+            "[workspace]\nmembers = [\"demo\"]\nresolver = \"2\"\n"
+        );
+    }
+
+    #[test]
+    fn glob_members_cover_new_components() {
+        assert!(member_matches("components/*", "components/demo"));
+        assert!(!member_matches("components/*", "crates/demo"));
+        assert!(member_matches("demo", "demo"));
+    }
+}
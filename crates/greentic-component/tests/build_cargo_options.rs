@@ -0,0 +1,155 @@
+#![cfg(all(feature = "cli", unix))]
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use serde_json::Value as JsonValue;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use tempfile::TempDir;
+use wasm_encoder::{Component, Module, ModuleSection};
+
+/// Records its arguments and drops `component.wasm` into the debug and release dirs of `out/`.
+/// `cargo metadata` fails, so no SBOM is written.
+const FAKE_CARGO: &str = r#"#!/bin/sh
+set -e
+if [ "$1" = "metadata" ]; then
+  exit 1
+fi
+echo "$@" >> cargo-args.log
+for profile in debug release; do
+  mkdir -p "out/wasm32-wasip2/$profile"
+  cp component.wasm "out/wasm32-wasip2/$profile/example.wasm"
+done
+"#;
+
+fn write_project(dir: &Path) {
+    let manifest = serde_json::json!({
+        "id": "ai.greentic.example",
+        "name": "example",
+        "version": "0.1.0",
+        "world": "greentic:component/node@0.5.0",
+        "describe_export": "get-manifest",
+        "operations": [
+            {
+                "name": "handle_message",
+                "input_schema": {
+                    "type": "object",
+                    "properties": { "payload": { "type": "string", "default": "ping" } },
+                    "required": ["payload"]
+                },
+                "output_schema": {
+                    "type": "object",
+                    "properties": { "result": { "type": "string", "default": "ok" } },
+                    "required": ["result"]
+                }
+            }
+        ],
+        "default_operation": "handle_message",
+        "config_schema": {
+            "type": "object",
+            "properties": {},
+            "required": [],
+            "additionalProperties": false
+        },
+        "supports": ["messaging"],
+        "profiles": { "default": "stateless", "supported": ["stateless"] },
+        "secret_requirements": [],
+        "capabilities": {
+            "wasi": {
+                "filesystem": { "mode": "none", "mounts": [] },
+                "random": true,
+                "clocks": true
+            },
+            "host": {
+                "messaging": { "inbound": true, "outbound": true },
+                "telemetry": { "scope": "node" },
+                "secrets": { "required": [] }
+            }
+        },
+        "limits": { "memory_mb": 64, "wall_time_ms": 1000 },
+        "artifacts": { "component_wasm": "target/wasm32-wasip2/release/example.wasm" },
+        "hashes": { "component_wasm": "blake3:0000000000000000000000000000000000000000000000000000000000000000" }
+    });
+    fs::write(
+        dir.join("component.manifest.json"),
+        serde_json::to_string_pretty(&manifest).unwrap(),
+    )
+    .expect("write manifest");
+
+    let mut component = Component::new();
+    component.section(&ModuleSection(&Module::new()));
+    fs::write(dir.join("component.wasm"), component.finish()).expect("write wasm");
+
+    let path = dir.join("fake_cargo.sh");
+    fs::write(&path, FAKE_CARGO).expect("write fake cargo");
+    let mut perms = fs::metadata(&path).expect("metadata").permissions();
+    perms.set_mode(0o755);
+    fs::set_permissions(&path, perms).expect("chmod");
+}
+
+fn build_cmd(dir: &Path) -> assert_cmd::Command {
+    let mut cmd = cargo_bin_cmd!("greentic-component");
+    cmd.current_dir(dir)
+        .env("CARGO", dir.join("fake_cargo.sh"))
+        .env("GREENTIC_SKIP_NODE_EXPORT_CHECK", "1")
+        .env_remove("CARGO_TARGET_DIR")
+        .arg("build")
+        .arg("--no-flow")
+        .arg("--target-dir")
+        .arg("out")
+        .arg("--oci")
+        .arg("ghcr.io/acme/example:0.1.0");
+    cmd
+}
+
+fn read_manifest(dir: &Path) -> JsonValue {
+    serde_json::from_str(&fs::read_to_string(dir.join("component.manifest.json")).unwrap()).unwrap()
+}
+
+#[test]
+fn build_forwards_profile_and_features_to_cargo() {
+    let temp = TempDir::new().expect("tempdir");
+    write_project(temp.path());
+
+    build_cmd(temp.path())
+        .arg("--profile")
+        .arg("dev")
+        .arg("--features")
+        .arg("telemetry,extra")
+        .arg("--no-default-features")
+        .assert()
+        .success();
+
+    let args = fs::read_to_string(temp.path().join("cargo-args.log")).expect("cargo args");
+    let target_dir = temp.path().canonicalize().unwrap().join("out");
+    assert_eq!(
+        args.trim(),
+        format!(
+            "build --target wasm32-wasip2 --profile dev --features telemetry,extra --no-default-features --target-dir {}",
+            target_dir.display()
+        )
+    );
+    assert_eq!(
+        read_manifest(temp.path())["artifacts"]["component_wasm"],
+        "out/wasm32-wasip2/debug/example.wasm"
+    );
+    assert!(temp.path().join("dist/example__0_6_0__dev.oci").is_dir());
+    assert!(!temp.path().join("dist/example__0_6_0.oci").exists());
+}
+
+#[test]
+fn build_defaults_to_release_profile() {
+    let temp = TempDir::new().expect("tempdir");
+    write_project(temp.path());
+
+    build_cmd(temp.path()).assert().success();
+
+    let args = fs::read_to_string(temp.path().join("cargo-args.log")).expect("cargo args");
+    assert!(args.contains("--release"));
+    assert!(!args.contains("--profile"));
+    assert_eq!(
+        read_manifest(temp.path())["artifacts"]["component_wasm"],
+        "out/wasm32-wasip2/release/example.wasm"
+    );
+    assert!(temp.path().join("dist/example__0_6_0.oci").is_dir());
+}
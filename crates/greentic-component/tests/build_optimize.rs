@@ -0,0 +1,160 @@
+#![cfg(all(feature = "cli", unix))]
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use predicates::prelude::*;
+use serde_json::Value as JsonValue;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use tempfile::TempDir;
+use wasm_encoder::{Component, CustomSection, Module, ModuleSection};
+
+fn write_script(dir: &Path, name: &str, script: &str) -> std::path::PathBuf {
+    let path = dir.join(name);
+    fs::write(&path, script).expect("write script");
+    let mut perms = fs::metadata(&path).expect("metadata").permissions();
+    perms.set_mode(0o755);
+    fs::set_permissions(&path, perms).expect("chmod");
+    path
+}
+
+/// Records its arguments and "optimizes" by keeping only the 8-byte module header.
+const FAKE_WASM_OPT: &str = r#"#!/bin/sh
+set -e
+echo "$@" >> "$(dirname "$0")/wasm-opt.args"
+out=""
+input=""
+while [ $# -gt 0 ]; do
+  case "$1" in
+    -o) out="$2"; shift ;;
+    -*) ;;
+    *) input="$1" ;;
+  esac
+  shift
+done
+head -c 8 "$input" > "$out"
+"#;
+
+/// A component wrapping one core module that carries a large custom section.
+fn write_component_with_module(dir: &Path) {
+    let mut module = Module::new();
+    module.section(&CustomSection {
+        name: "filler".into(),
+        data: vec![0u8; 4096].into(),
+    });
+    let mut component = Component::new();
+    component.section(&ModuleSection(&module));
+    fs::write(dir.join("component.wasm"), component.finish()).expect("write wasm");
+}
+
+fn manifest() -> JsonValue {
+    serde_json::json!({
+        "id": "ai.greentic.example",
+        "name": "example",
+        "version": "0.1.0",
+        "world": "greentic:component/node@0.5.0",
+        "describe_export": "get-manifest",
+        "operations": [
+            {
+                "name": "handle_message",
+                "input_schema": {
+                    "type": "object",
+                    "properties": { "payload": { "type": "string", "default": "ping" } },
+                    "required": ["payload"]
+                },
+                "output_schema": {
+                    "type": "object",
+                    "properties": { "result": { "type": "string", "default": "ok" } },
+                    "required": ["result"]
+                }
+            }
+        ],
+        "default_operation": "handle_message",
+        "config_schema": {
+            "type": "object",
+            "properties": {},
+            "required": [],
+            "additionalProperties": false
+        },
+        "supports": ["messaging"],
+        "profiles": { "default": "stateless", "supported": ["stateless"] },
+        "secret_requirements": [],
+        "capabilities": {
+            "wasi": {
+                "filesystem": { "mode": "none", "mounts": [] },
+                "random": true,
+                "clocks": true
+            },
+            "host": {
+                "messaging": { "inbound": true, "outbound": true },
+                "telemetry": { "scope": "node" },
+                "secrets": { "required": [] }
+            }
+        },
+        "limits": { "memory_mb": 64, "wall_time_ms": 1000 },
+        "artifacts": { "component_wasm": "component.wasm" },
+        "hashes": { "component_wasm": "blake3:0000000000000000000000000000000000000000000000000000000000000000" }
+    })
+}
+
+#[test]
+fn build_optimize_runs_wasm_opt_on_nested_core_modules() {
+    let temp = TempDir::new().expect("tempdir");
+    fs::write(
+        temp.path().join("component.manifest.json"),
+        serde_json::to_string_pretty(&manifest()).unwrap(),
+    )
+    .expect("write manifest");
+    write_component_with_module(temp.path());
+    let fake_cargo = write_script(temp.path(), "fake_cargo.sh", "#!/bin/sh\nexit 0\n");
+    let fake_wasm_opt = write_script(temp.path(), "fake_wasm_opt.sh", FAKE_WASM_OPT);
+
+    let mut cmd = cargo_bin_cmd!("greentic-component");
+    cmd.current_dir(temp.path())
+        .env("CARGO", &fake_cargo)
+        .env("GREENTIC_SKIP_NODE_EXPORT_CHECK", "1")
+        .arg("build")
+        .arg("--no-flow")
+        .arg("--optimize")
+        .arg("size")
+        .arg("--wasm-opt")
+        .arg(&fake_wasm_opt);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Optimized wasm with"))
+        .stdout(predicate::str::contains("1 module(s), preset size"));
+
+    let args = fs::read_to_string(temp.path().join("wasm-opt.args")).expect("wasm-opt args");
+    assert!(args.starts_with("-Oz --strip-debug --strip-producers "));
+
+    let wasm = fs::read(temp.path().join("component.wasm")).expect("read wasm");
+    let sections = greentic_component::wasm::custom_section_sizes(&wasm).expect("parse wasm");
+    assert!(sections.iter().all(|(name, _)| name != "filler"));
+    assert!(wasm.len() < 4096);
+}
+
+#[test]
+fn build_optimize_reports_missing_wasm_opt() {
+    let temp = TempDir::new().expect("tempdir");
+    fs::write(
+        temp.path().join("component.manifest.json"),
+        serde_json::to_string_pretty(&manifest()).unwrap(),
+    )
+    .expect("write manifest");
+    write_component_with_module(temp.path());
+    let fake_cargo = write_script(temp.path(), "fake_cargo.sh", "#!/bin/sh\nexit 0\n");
+
+    let mut cmd = cargo_bin_cmd!("greentic-component");
+    cmd.current_dir(temp.path())
+        .env("CARGO", &fake_cargo)
+        .env("GREENTIC_SKIP_NODE_EXPORT_CHECK", "1")
+        .arg("build")
+        .arg("--no-flow")
+        .arg("--optimize")
+        .arg("speed")
+        .arg("--wasm-opt")
+        .arg(temp.path().join("missing-wasm-opt"));
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("wasm-opt post-processing failed"));
+}
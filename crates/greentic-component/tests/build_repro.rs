@@ -0,0 +1,176 @@
+#![cfg(all(feature = "cli", unix))]
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use predicates::prelude::*;
+use serde_json::Value as JsonValue;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use tempfile::TempDir;
+use wasm_encoder::{Component, CustomSection, Module, ModuleSection};
+
+const WASM_ARTIFACT: &str = "target/wasm32-wasip2/release/example.wasm";
+
+/// Copies `first.wasm` into the target dir, or `second.wasm` (when present) for the rebuild in
+/// a separate `CARGO_TARGET_DIR`, and records the encoded rustflags it was given, one line per
+/// build. `cargo metadata` fails, so no SBOM is written.
+const FAKE_CARGO: &str = r#"#!/bin/sh
+set -e
+if [ "$1" = "metadata" ]; then
+  exit 1
+fi
+printf '%s\n' "$CARGO_ENCODED_RUSTFLAGS" | tr '\037' ' ' >> rustflags.log
+out="${CARGO_TARGET_DIR:-target}/wasm32-wasip2/release"
+mkdir -p "$out"
+fixture=first.wasm
+if [ -n "$CARGO_TARGET_DIR" ] && [ -f second.wasm ]; then
+  fixture=second.wasm
+fi
+cp "$fixture" "$out/example.wasm"
+"#;
+
+fn write_fake_cargo(dir: &Path) -> std::path::PathBuf {
+    let path = dir.join("fake_cargo.sh");
+    fs::write(&path, FAKE_CARGO).expect("write fake cargo");
+    let mut perms = fs::metadata(&path).expect("metadata").permissions();
+    perms.set_mode(0o755);
+    fs::set_permissions(&path, perms).expect("chmod");
+    path
+}
+
+fn component_with_producers(producers: &str) -> Vec<u8> {
+    let mut module = Module::new();
+    module.section(&CustomSection {
+        name: "producers".into(),
+        data: producers.as_bytes().into(),
+    });
+    let mut component = Component::new();
+    component.section(&ModuleSection(&module));
+    component.finish()
+}
+
+fn write_project(dir: &Path) {
+    let manifest = serde_json::json!({
+        "id": "ai.greentic.example",
+        "name": "example",
+        "version": "0.1.0",
+        "world": "greentic:component/node@0.5.0",
+        "describe_export": "get-manifest",
+        "operations": [
+            {
+                "name": "handle_message",
+                "input_schema": {
+                    "type": "object",
+                    "properties": { "payload": { "type": "string", "default": "ping" } },
+                    "required": ["payload"]
+                },
+                "output_schema": {
+                    "type": "object",
+                    "properties": { "result": { "type": "string", "default": "ok" } },
+                    "required": ["result"]
+                }
+            }
+        ],
+        "default_operation": "handle_message",
+        "config_schema": {
+            "type": "object",
+            "properties": {},
+            "required": [],
+            "additionalProperties": false
+        },
+        "supports": ["messaging"],
+        "profiles": { "default": "stateless", "supported": ["stateless"] },
+        "secret_requirements": [],
+        "capabilities": {
+            "wasi": {
+                "filesystem": { "mode": "none", "mounts": [] },
+                "random": true,
+                "clocks": true
+            },
+            "host": {
+                "messaging": { "inbound": true, "outbound": true },
+                "telemetry": { "scope": "node" },
+                "secrets": { "required": [] }
+            }
+        },
+        "limits": { "memory_mb": 64, "wall_time_ms": 1000 },
+        "artifacts": { "component_wasm": WASM_ARTIFACT },
+        "hashes": { "component_wasm": "blake3:0000000000000000000000000000000000000000000000000000000000000000" }
+    });
+    fs::write(
+        dir.join("component.manifest.json"),
+        serde_json::to_string_pretty(&manifest).unwrap(),
+    )
+    .expect("write manifest");
+    fs::write(
+        dir.join("first.wasm"),
+        component_with_producers("rustc 1.0"),
+    )
+    .expect("write wasm");
+}
+
+fn build_cmd(dir: &Path) -> assert_cmd::Command {
+    let fake_cargo = write_fake_cargo(dir);
+    let mut cmd = cargo_bin_cmd!("greentic-component");
+    cmd.current_dir(dir)
+        .env("CARGO", &fake_cargo)
+        .env("GREENTIC_SKIP_NODE_EXPORT_CHECK", "1")
+        .env("SOURCE_DATE_EPOCH", "1700000000")
+        .env_remove("CARGO_TARGET_DIR")
+        .env_remove("RUSTFLAGS")
+        .env_remove("CARGO_ENCODED_RUSTFLAGS")
+        .env_remove("WASM_RUSTFLAGS")
+        .arg("build")
+        .arg("--no-flow")
+        .arg("--verify-repro");
+    cmd
+}
+
+#[test]
+fn verify_repro_accepts_identical_rebuilds() {
+    let temp = TempDir::new().expect("tempdir");
+    write_project(temp.path());
+
+    build_cmd(temp.path())
+        .arg("--json")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Verifying reproducibility"));
+
+    let rustflags = fs::read_to_string(temp.path().join("rustflags.log")).expect("rustflags");
+    let lines = rustflags.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0], lines[1]);
+    let root = temp.path().canonicalize().unwrap();
+    assert!(lines[0].contains(&format!("--remap-path-prefix={}=/build", root.display())));
+    assert!(
+        temp.path()
+            .join("target/greentic-repro/wasm32-wasip2/release/example.wasm")
+            .exists()
+    );
+
+    let manifest: JsonValue = serde_json::from_str(
+        &fs::read_to_string(temp.path().join("component.manifest.json")).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(manifest["artifacts"]["component_wasm"], WASM_ARTIFACT);
+}
+
+#[test]
+fn verify_repro_reports_differing_sections() {
+    let temp = TempDir::new().expect("tempdir");
+    write_project(temp.path());
+    fs::write(
+        temp.path().join("second.wasm"),
+        component_with_producers("rustc 1.1"),
+    )
+    .expect("write wasm");
+
+    build_cmd(temp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("build is not reproducible"))
+        .stderr(predicate::str::contains(
+            "core module #1: custom section `producers` differs",
+        ));
+}
@@ -0,0 +1,246 @@
+#![cfg(all(feature = "cli", unix))]
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use predicates::prelude::*;
+use serde_json::Value as JsonValue;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use tempfile::TempDir;
+use wasm_encoder::{Component, Module, ModuleSection};
+
+/// Answers `cargo metadata` with `metadata.json`; every other invocation is a no-op build.
+const FAKE_CARGO: &str = r#"#!/bin/sh
+set -e
+if [ "$1" = "metadata" ]; then
+  cat metadata.json
+fi
+"#;
+
+fn write_project(dir: &Path) {
+    let manifest = serde_json::json!({
+        "id": "ai.greentic.example",
+        "name": "example",
+        "version": "0.1.0",
+        "world": "greentic:component/node@0.5.0",
+        "describe_export": "get-manifest",
+        "operations": [
+            {
+                "name": "handle_message",
+                "input_schema": {
+                    "type": "object",
+                    "properties": { "payload": { "type": "string", "default": "ping" } },
+                    "required": ["payload"]
+                },
+                "output_schema": {
+                    "type": "object",
+                    "properties": { "result": { "type": "string", "default": "ok" } },
+                    "required": ["result"]
+                }
+            }
+        ],
+        "default_operation": "handle_message",
+        "config_schema": {
+            "type": "object",
+            "properties": {},
+            "required": [],
+            "additionalProperties": false
+        },
+        "supports": ["messaging"],
+        "profiles": { "default": "stateless", "supported": ["stateless"] },
+        "secret_requirements": [],
+        "capabilities": {
+            "wasi": {
+                "filesystem": { "mode": "none", "mounts": [] },
+                "random": true,
+                "clocks": true
+            },
+            "host": {
+                "messaging": { "inbound": true, "outbound": true },
+                "telemetry": { "scope": "node" },
+                "secrets": { "required": [] }
+            }
+        },
+        "limits": { "memory_mb": 64, "wall_time_ms": 1000 },
+        "artifacts": { "component_wasm": "component.wasm" },
+        "hashes": { "component_wasm": "blake3:0000000000000000000000000000000000000000000000000000000000000000" }
+    });
+    fs::write(
+        dir.join("component.manifest.json"),
+        serde_json::to_string_pretty(&manifest).unwrap(),
+    )
+    .expect("write manifest");
+
+    let metadata = serde_json::json!({
+        "packages": [
+            { "id": "example 0.1.0", "name": "example", "version": "0.1.0", "license": "MIT" },
+            { "id": "serde 1.0.0", "name": "serde", "version": "1.0.0", "license": "MIT OR Apache-2.0" },
+            { "id": "insta 1.0.0", "name": "insta", "version": "1.0.0", "license": "Apache-2.0" }
+        ],
+        "resolve": {
+            "root": "example 0.1.0",
+            "nodes": [
+                {
+                    "id": "example 0.1.0",
+                    "deps": [
+                        { "pkg": "serde 1.0.0", "dep_kinds": [{ "kind": null, "target": null }] },
+                        { "pkg": "insta 1.0.0", "dep_kinds": [{ "kind": "dev", "target": null }] }
+                    ]
+                },
+                { "id": "serde 1.0.0", "deps": [] },
+                { "id": "insta 1.0.0", "deps": [] }
+            ]
+        }
+    });
+    fs::write(dir.join("metadata.json"), metadata.to_string()).expect("write metadata");
+
+    let mut component = Component::new();
+    component.section(&ModuleSection(&Module::new()));
+    fs::write(dir.join("component.wasm"), component.finish()).expect("write wasm");
+
+    let path = dir.join("fake_cargo.sh");
+    fs::write(&path, FAKE_CARGO).expect("write fake cargo");
+    let mut perms = fs::metadata(&path).expect("metadata").permissions();
+    perms.set_mode(0o755);
+    fs::set_permissions(&path, perms).expect("chmod");
+}
+
+fn read_json(path: &Path) -> JsonValue {
+    serde_json::from_str(&fs::read_to_string(path).expect("read json")).expect("parse json")
+}
+
+#[test]
+fn build_writes_cyclonedx_sbom_referenced_from_manifest() {
+    let temp = TempDir::new().expect("tempdir");
+    write_project(temp.path());
+
+    let mut cmd = cargo_bin_cmd!("greentic-component");
+    cmd.current_dir(temp.path())
+        .env("CARGO", temp.path().join("fake_cargo.sh"))
+        .env("GREENTIC_SKIP_NODE_EXPORT_CHECK", "1")
+        .arg("build")
+        .arg("--no-flow");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Wrote CycloneDX SBOM"));
+
+    let manifest = read_json(&temp.path().join("component.manifest.json"));
+    assert_eq!(
+        manifest["artifacts"]["sbom"],
+        "dist/example__0_6_0.cdx.json"
+    );
+    let sbom_path = temp.path().join("dist/example__0_6_0.cdx.json");
+    assert_eq!(
+        manifest["hashes"]["sbom"],
+        format!(
+            "blake3:{}",
+            blake3::hash(&fs::read(&sbom_path).unwrap()).to_hex()
+        )
+    );
+
+    let sbom = read_json(&sbom_path);
+    assert_eq!(sbom["bomFormat"], "CycloneDX");
+    let wasm = fs::read(temp.path().join("component.wasm")).expect("read wasm");
+    assert_eq!(
+        sbom["metadata"]["component"]["hashes"][0],
+        serde_json::json!({ "alg": "BLAKE3", "content": blake3::hash(&wasm).to_hex().to_string() })
+    );
+    assert_eq!(
+        format!(
+            "blake3:{}",
+            sbom["metadata"]["component"]["hashes"][0]["content"]
+                .as_str()
+                .unwrap()
+        ),
+        manifest["hashes"]["component_wasm"].as_str().unwrap()
+    );
+    let names = sbom["components"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|component| component["name"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["serde"]);
+}
+
+#[test]
+fn build_no_sbom_drops_stale_reference() {
+    let temp = TempDir::new().expect("tempdir");
+    write_project(temp.path());
+    let manifest_path = temp.path().join("component.manifest.json");
+    let mut manifest = read_json(&manifest_path);
+    manifest["artifacts"]["sbom"] = "dist/old.cdx.json".into();
+    fs::write(&manifest_path, manifest.to_string()).expect("write manifest");
+
+    let mut cmd = cargo_bin_cmd!("greentic-component");
+    cmd.current_dir(temp.path())
+        .env("CARGO", temp.path().join("fake_cargo.sh"))
+        .env("GREENTIC_SKIP_NODE_EXPORT_CHECK", "1")
+        .arg("build")
+        .arg("--no-flow")
+        .arg("--no-sbom");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("SBOM").not());
+
+    let manifest = read_json(&manifest_path);
+    assert!(manifest["artifacts"].get("sbom").is_none());
+    assert!(!temp.path().join("dist/example__0_6_0.cdx.json").exists());
+}
+
+#[test]
+fn build_oci_writes_image_layout_with_wasm_and_sbom_layers() {
+    let temp = TempDir::new().expect("tempdir");
+    write_project(temp.path());
+
+    let mut cmd = cargo_bin_cmd!("greentic-component");
+    cmd.current_dir(temp.path())
+        .env("CARGO", temp.path().join("fake_cargo.sh"))
+        .env("GREENTIC_SKIP_NODE_EXPORT_CHECK", "1")
+        .arg("build")
+        .arg("--no-flow")
+        .arg("--oci")
+        .arg("ghcr.io/acme/example:0.1.0");
+    cmd.assert().success().stdout(predicate::str::contains(
+        "Wrote OCI artifact ghcr.io/acme/example:0.1.0",
+    ));
+
+    let layout = temp.path().join("dist/example__0_6_0.oci");
+    let blob = |digest: &JsonValue| {
+        fs::read(
+            layout
+                .join("blobs/sha256")
+                .join(digest.as_str().unwrap().trim_start_matches("sha256:")),
+        )
+        .expect("read blob")
+    };
+    let index = read_json(&layout.join("index.json"));
+    let entry = &index["manifests"][0];
+    assert_eq!(
+        entry["annotations"]["org.opencontainers.image.ref.name"],
+        "ghcr.io/acme/example:0.1.0"
+    );
+    let oci_manifest: JsonValue = serde_json::from_slice(&blob(&entry["digest"])).unwrap();
+    assert_eq!(
+        oci_manifest["annotations"]["org.opencontainers.image.version"],
+        "0.1.0"
+    );
+
+    let config = blob(&oci_manifest["config"]["digest"]);
+    assert_eq!(
+        config,
+        fs::read(temp.path().join("component.manifest.json")).unwrap()
+    );
+    let layers = oci_manifest["layers"].as_array().unwrap();
+    assert_eq!(layers.len(), 2);
+    assert_eq!(layers[0]["mediaType"], "application/wasm");
+    assert_eq!(
+        blob(&layers[0]["digest"]),
+        fs::read(temp.path().join("component.wasm")).unwrap()
+    );
+    assert_eq!(layers[1]["mediaType"], "application/vnd.cyclonedx+json");
+    assert_eq!(
+        layers[1]["annotations"]["org.opencontainers.image.title"],
+        "example__0_6_0.cdx.json"
+    );
+}
@@ -167,6 +167,108 @@ fn inspect_human_output_includes_manifest_and_describe_sections_for_embedded_was
         ));
 }
 
+#[test]
+fn inspect_live_decodes_node_inline_schemas() {
+    let (_temp, wasm_path, manifest_path) = copy_component_v060_fixture();
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("component-inspect");
+    cmd.arg(wasm_path)
+        .arg("--manifest")
+        .arg(manifest_path)
+        .arg("--live")
+        .arg("--rust-types")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("source: node"))
+        .stdout(predicate::str::contains("- handle_message [in manifest]"))
+        .stdout(predicate::str::contains("\"type\": \"object\""))
+        .stdout(predicate::str::contains("pub struct HandleMessageInput"))
+        .stdout(predicate::str::contains("matched: handle_message"))
+        .stdout(predicate::str::contains("missing in describe: <none>"));
+}
+
+#[test]
+fn inspect_diff_reports_breaking_changes_between_manifests() {
+    let (temp, _wasm_path, manifest_path) = copy_component_v060_fixture();
+    let mut manifest: Value =
+        serde_json::from_str(&fs::read_to_string(&manifest_path).unwrap()).unwrap();
+    manifest["limits"]["memory_mb"] = json!(256);
+    manifest["operations"][0]["name"] = json!("handle_event");
+    manifest["default_operation"] = json!("handle_event");
+    let next_dir = temp.path().join("next");
+    fs::create_dir_all(&next_dir).unwrap();
+    let next_manifest = next_dir.join("component.manifest.json");
+    fs::write(
+        &next_manifest,
+        serde_json::to_string_pretty(&manifest).unwrap(),
+    )
+    .unwrap();
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("component-inspect");
+    cmd.arg("diff")
+        .arg(&manifest_path)
+        .arg(&next_dir)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("breaking (3):"))
+        .stdout(predicate::str::contains(
+            "[operations] operations.handle_message: operation `handle_message` was removed",
+        ))
+        .stdout(predicate::str::contains(
+            "[limits] limits.memory_mb: raised from 128 to 256",
+        ))
+        .stdout(predicate::str::contains("added (1):"));
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("component-inspect");
+    cmd.arg("diff")
+        .arg(&manifest_path)
+        .arg(&next_manifest)
+        .arg("--fail-on-breaking")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("3 breaking change(s)"));
+}
+
+#[test]
+fn inspect_sections_emit_selected_metadata_as_yaml() {
+    let (_temp, wasm_path, _manifest_path) = copy_component_v060_fixture();
+    let workdir = wasm_path.parent().unwrap();
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("component-inspect");
+    cmd.arg(workdir)
+        .arg("--section")
+        .arg("ops")
+        .arg("--section")
+        .arg("i18n")
+        .arg("--output")
+        .arg("yaml")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "id: com.greentic.contract.fixture.v0_6_0",
+        ))
+        .stdout(predicate::str::contains(
+            "default_operation: handle_message",
+        ))
+        .stdout(predicate::str::contains("name: handle_message"))
+        .stdout(predicate::str::contains("keys: null"))
+        .stdout(predicate::str::contains("input_schema").not())
+        .stdout(predicate::str::contains("capabilities").not());
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("component-inspect");
+    let output = cmd
+        .arg(workdir)
+        .arg("--section")
+        .arg("capabilities")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let report: Value = serde_json::from_slice(&output).expect("section json");
+    assert!(report["capabilities"]["host"]["messaging"].is_object());
+    assert!(report.get("operations").is_none());
+}
+
 #[test]
 fn doctor_detects_scaffold_directory() {
     let temp = tempfile::TempDir::new().unwrap();
@@ -179,6 +281,7 @@ fn doctor_detects_scaffold_directory() {
         org: "ai.greentic".into(),
         version: "0.1.0".into(),
         license: "MIT".into(),
+        author: None,
         wit_world: DEFAULT_WIT_WORLD.into(),
         user_operations: vec!["handle_message".into()],
         default_operation: "handle_message".into(),
@@ -220,6 +323,142 @@ fn doctor_no_longer_reports_missing_embedded_when_section_is_present() {
         .stdout(predicate::str::contains("doctor.embedded.missing").not());
 }
 
+#[test]
+fn doctor_checks_exports_and_imports_against_manifest_world() {
+    let (_temp, wasm_path, manifest_path) = copy_component_v060_fixture();
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("component-doctor");
+    cmd.arg(&wasm_path)
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("doctor.world.export_mismatch").not())
+        .stdout(predicate::str::contains("doctor.world.undeclared_import").not());
+
+    let manifest_raw = fs::read_to_string(&manifest_path).unwrap();
+    fs::write(
+        &manifest_path,
+        manifest_raw.replace(
+            "greentic:component/component@0.6.0",
+            "greentic:component/component@0.5.0",
+        ),
+    )
+    .unwrap();
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("component-doctor");
+    cmd.arg(&wasm_path)
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "export `greentic:component/node@0.6.0` is not part of `greentic:component/component@0.5.0`",
+        ));
+}
+
+#[test]
+fn doctor_reports_and_fixes_stale_manifest_hash() {
+    let (_temp, wasm_path, manifest_path) = copy_component_v060_fixture();
+    let manifest_raw = fs::read_to_string(&manifest_path).unwrap();
+    let actual = greentic_component::compute_wasm_hash(&wasm_path).unwrap();
+    let stale = format!("blake3:{}", "0".repeat(64));
+    fs::write(&manifest_path, manifest_raw.replace(&actual, &stale)).unwrap();
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("component-doctor");
+    cmd.arg(&wasm_path)
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("doctor.manifest.hash_stale"))
+        .stdout(predicate::str::contains("greentic-component hash"));
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("component-doctor");
+    cmd.arg(&wasm_path)
+        .arg("--fix")
+        .assert()
+        .stdout(predicate::str::contains("doctor.manifest.hash_fixed"));
+    let fixed = fs::read_to_string(&manifest_path).unwrap();
+    assert!(fixed.contains(&actual));
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("component-doctor");
+    cmd.arg(&wasm_path)
+        .assert()
+        .stdout(predicate::str::contains("doctor.manifest.").not());
+}
+
+#[test]
+fn doctor_applies_workspace_rules_file() {
+    let (_temp, wasm_path, manifest_path) = copy_component_v060_fixture();
+    fs::write(
+        manifest_path.parent().unwrap().join("doctor.rules.json"),
+        r#"{"rules": [{"id": "acme.naming", "pointer": "/id", "pattern": "^com\\.acme\\."}]}"#,
+    )
+    .unwrap();
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("component-doctor");
+    cmd.arg(&wasm_path)
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("error[acme.naming] id:"));
+}
+
+#[test]
+fn doctor_baseline_only_fails_on_new_findings() {
+    let (_temp, wasm_path, manifest_path) = copy_component_v060_fixture();
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("component-doctor");
+    cmd.arg(&wasm_path)
+        .arg("--update-baseline")
+        .assert()
+        .success();
+    let baseline = manifest_path
+        .parent()
+        .unwrap()
+        .join(".greentic-doctor-baseline.json");
+    assert!(
+        fs::read_to_string(&baseline)
+            .unwrap()
+            .contains("doctor.embedded.missing")
+    );
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("component-doctor");
+    cmd.arg(&wasm_path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[baselined]"));
+
+    let manifest_raw = fs::read_to_string(&manifest_path).unwrap();
+    let actual = greentic_component::compute_wasm_hash(&wasm_path).unwrap();
+    fs::write(
+        &manifest_path,
+        manifest_raw.replace(&actual, &format!("blake3:{}", "0".repeat(64))),
+    )
+    .unwrap();
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("component-doctor");
+    cmd.arg(&wasm_path)
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("doctor.manifest.hash_stale"));
+}
+
+#[test]
+fn doctor_emits_sarif_findings_with_locations() {
+    let (_temp, wasm_path, _manifest_path) = copy_component_v060_fixture();
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("component-doctor");
+    let output = cmd
+        .arg(&wasm_path)
+        .args(["--format", "sarif"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let sarif: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(sarif["version"], "2.1.0");
+    let results = sarif["runs"][0]["results"].as_array().unwrap();
+    let missing = results
+        .iter()
+        .find(|result| result["ruleId"] == "doctor.embedded.missing")
+        .expect("embedded metadata finding");
+    assert_eq!(missing["level"], "error");
+    assert!(
+        missing["locations"][0]["physicalLocation"]["artifactLocation"]["uri"]
+            .as_str()
+            .unwrap()
+            .ends_with(".wasm")
+    );
+}
+
 #[test]
 fn scaffold_makefile_uses_greentic_dev_commands() {
     let temp = tempfile::TempDir::new().unwrap();
@@ -232,6 +471,7 @@ fn scaffold_makefile_uses_greentic_dev_commands() {
         org: "ai.greentic".into(),
         version: "0.1.0".into(),
         license: "MIT".into(),
+        author: None,
         wit_world: DEFAULT_WIT_WORLD.into(),
         user_operations: vec!["handle_message".into()],
         default_operation: "handle_message".into(),
@@ -263,6 +503,7 @@ fn build_logs_resolved_component_world_version() {
         org: "ai.greentic".into(),
         version: "0.1.0".into(),
         license: "MIT".into(),
+        author: None,
         wit_world: DEFAULT_WIT_WORLD.into(),
         user_operations: vec!["handle_message".into()],
         default_operation: "handle_message".into(),
@@ -511,10 +752,8 @@ fn build_fails_on_empty_operation_schemas() {
         no_flow: true,
         no_infer_config: true,
         no_write_schema: true,
-        force_write_schema: false,
         no_validate: true,
-        json: false,
-        permissive: false,
+        ..BuildArgs::default()
     };
 
     let err = build::run(args).expect_err("build should fail when schemas are empty");
@@ -535,10 +774,9 @@ fn build_permissive_allows_empty_operation_schemas() {
         no_flow: true,
         no_infer_config: true,
         no_write_schema: true,
-        force_write_schema: false,
         no_validate: true,
-        json: false,
         permissive: true,
+        ..BuildArgs::default()
     };
 
     build::run(args).expect("permissive build should succeed");
@@ -1,7 +1,7 @@
 #![cfg(feature = "cli")]
 
-use greentic_component::cmd::doctor::{DoctorArgs, DoctorFormat, run as doctor_run};
-use greentic_component::cmd::wizard::{ExecutionMode, RunMode, WizardArgs, run as wizard_run};
+use greentic_component::cmd::doctor::{DoctorArgs, run as doctor_run};
+use greentic_component::cmd::wizard::{WizardArgs, run as wizard_run};
 
 #[test]
 fn doctor_rejects_unbuilt_wizard_scaffold() {
@@ -23,31 +23,14 @@ fn doctor_rejects_unbuilt_wizard_scaffold() {
     .unwrap();
 
     let args = WizardArgs {
-        mode: RunMode::Create,
-        execution: ExecutionMode::Execute,
-        dry_run: false,
-        validate: false,
-        apply: false,
         qa_answers: Some(answers_path),
-        answers: None,
-        qa_answers_out: None,
-        emit_answers: None,
-        schema_version: None,
-        migrate: false,
-        plan_out: None,
         project_root: temp.path().to_path_buf(),
-        template: None,
-        full_tests: false,
-        json: false,
+        ..WizardArgs::default()
     };
     wizard_run(args).unwrap();
 
     let root = temp.path().join("component");
-    let doctor_args = DoctorArgs {
-        target: root.to_string_lossy().to_string(),
-        manifest: None,
-        format: DoctorFormat::Human,
-    };
+    let doctor_args = DoctorArgs::for_target(&root.to_string_lossy());
     let err = doctor_run(doctor_args).expect_err("doctor should require a wasm artifact");
     assert!(err.to_string().contains("unable to resolve wasm"));
 }
@@ -1,10 +1,12 @@
 #![cfg(feature = "cli")]
 
 use assert_cmd::cargo::cargo_bin_cmd;
+use greentic_component::build_metadata::{BUILD_METADATA_SECTION, read_build_metadata_section};
 use greentic_component::embedded_descriptor::{
     build_embedded_manifest_projection, decode_embedded_component_descriptor_v1,
     encode_embedded_component_descriptor_v1, read_embedded_component_manifest_section_v1,
 };
+use predicates::prelude::*;
 use serde_json::Value as JsonValue;
 use std::fs;
 use std::os::unix::fs::PermissionsExt;
@@ -159,6 +161,87 @@ fn build_embeds_manifest_custom_section_into_wasm() {
     );
 }
 
+#[test]
+fn build_appends_build_metadata_section() {
+    let temp = TempDir::new().expect("tempdir");
+    let manifest_path = temp.path().join("component.manifest.json");
+    fs::write(
+        &manifest_path,
+        serde_json::to_string_pretty(&minimal_manifest()).unwrap(),
+    )
+    .expect("write manifest");
+    fs::write(
+        temp.path().join("Cargo.toml"),
+        "[package]\nname = \"example\"\nversion = \"0.1.0\"\n\n[package.metadata.greentic]\ntemplate_version = \"1.2.0\"\n",
+    )
+    .expect("write Cargo.toml");
+
+    write_component_wasm(temp.path(), "component.wasm");
+    let fake_cargo = write_fake_cargo(temp.path());
+
+    let mut cmd = cargo_bin_cmd!("greentic-component");
+    cmd.current_dir(temp.path())
+        .env("CARGO", &fake_cargo)
+        .env("GREENTIC_SKIP_NODE_EXPORT_CHECK", "1")
+        .env("SOURCE_DATE_EPOCH", "1700000000")
+        .arg("build")
+        .arg("--no-flow");
+    cmd.assert().success();
+
+    let wasm_bytes = fs::read(temp.path().join("component.wasm")).expect("read wasm");
+    let metadata = read_build_metadata_section(&wasm_bytes)
+        .expect("read build metadata")
+        .unwrap_or_else(|| panic!("{BUILD_METADATA_SECTION} section should exist"));
+    assert_eq!(metadata.build.built_at_utc.unix_timestamp(), 1_700_000_000);
+    assert_eq!(metadata.build.template_version.as_deref(), Some("1.2.0"));
+    assert!(metadata.build.builder.starts_with("greentic-component "));
+    assert!(metadata.manifest.get("hashes").is_none());
+
+    // The written manifest hash covers the section, and the recorded manifest still matches it.
+    let written: JsonValue =
+        serde_json::from_str(&fs::read_to_string(&manifest_path).unwrap()).unwrap();
+    assert_eq!(
+        written["hashes"]["component_wasm"],
+        format!("blake3:{}", blake3::hash(&wasm_bytes).to_hex())
+    );
+    assert!(metadata.manifest_matches(&written));
+}
+
+#[test]
+fn build_no_update_hashes_keeps_manifest_hash() {
+    let temp = TempDir::new().expect("tempdir");
+    let manifest_path = temp.path().join("component.manifest.json");
+    fs::write(
+        &manifest_path,
+        serde_json::to_string_pretty(&minimal_manifest()).unwrap(),
+    )
+    .expect("write manifest");
+
+    write_component_wasm(temp.path(), "component.wasm");
+    let fake_cargo = write_fake_cargo(temp.path());
+
+    let mut cmd = cargo_bin_cmd!("greentic-component");
+    cmd.current_dir(temp.path())
+        .env("CARGO", &fake_cargo)
+        .env("GREENTIC_SKIP_NODE_EXPORT_CHECK", "1")
+        .arg("build")
+        .arg("--no-flow")
+        .arg("--no-update-hashes");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("--no-update-hashes"))
+        .stderr(predicate::str::contains(
+            "hashes.component_wasm (blake3:0000",
+        ));
+
+    let written: JsonValue =
+        serde_json::from_str(&fs::read_to_string(&manifest_path).unwrap()).unwrap();
+    assert_eq!(
+        written["hashes"]["component_wasm"],
+        minimal_manifest()["hashes"]["component_wasm"]
+    );
+}
+
 #[test]
 fn decode_fails_on_payload_hash_mismatch() {
     let manifest_json = minimal_manifest();
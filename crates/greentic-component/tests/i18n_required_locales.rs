@@ -44,3 +44,24 @@ fn required_wizard_locales_exist() {
         "crate and root locale catalogs diverged"
     );
 }
+
+#[test]
+fn every_locale_has_every_en_key() {
+    let crate_i18n = Path::new(env!("CARGO_MANIFEST_DIR")).join("i18n");
+    let locales = read_locale_dir(&crate_i18n);
+    let keys = |raw: &str| {
+        serde_json::from_str::<BTreeMap<String, String>>(raw)
+            .expect("parse locale catalog")
+            .into_keys()
+            .collect::<Vec<_>>()
+    };
+    let en = keys(&locales["en.json"]);
+    for (name, raw) in &locales {
+        let catalog = keys(raw);
+        let missing = en
+            .iter()
+            .filter(|key| catalog.binary_search(key).is_err())
+            .collect::<Vec<_>>();
+        assert!(missing.is_empty(), "{name} is missing {missing:?}");
+    }
+}
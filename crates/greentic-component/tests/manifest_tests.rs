@@ -130,6 +130,38 @@ fn manifest_preserves_dev_flows() {
     validate_manifest(&serialized).expect("schema-valid manifest with dev_flows");
 }
 
+#[test]
+fn manifest_validates_state_schema() {
+    let mut value: Value = serde_json::from_str(&fixture("valid.component.json")).unwrap();
+    let schema = serde_json::json!({
+        "type": "object",
+        "properties": { "count": { "type": "integer" } }
+    });
+    value["capabilities"]["host"]["state"] = serde_json::json!({ "read": true, "write": true });
+    value["state_schema"] = schema.clone();
+    let manifest = parse_manifest(&serde_json::to_string(&value).unwrap()).expect("parses");
+    assert_eq!(manifest.state_schema, Some(schema));
+
+    let mut broken = value.clone();
+    broken["state_schema"] = serde_json::json!({ "type": "no-such-type" });
+    match parse_manifest(&serde_json::to_string(&broken).unwrap()).unwrap_err() {
+        ManifestError::InvalidStateSchema(reason) => assert!(reason.contains("does not compile")),
+        err => panic!("expected InvalidStateSchema, got {err:?}"),
+    }
+
+    let mut stateless = value;
+    stateless["capabilities"]["host"]
+        .as_object_mut()
+        .unwrap()
+        .remove("state");
+    match parse_manifest(&serde_json::to_string(&stateless).unwrap()).unwrap_err() {
+        ManifestError::InvalidStateSchema(reason) => {
+            assert!(reason.contains("capabilities.host.state"))
+        }
+        err => panic!("expected InvalidStateSchema, got {err:?}"),
+    }
+}
+
 #[test]
 fn parse_manifest_uses_host_secret_requirements_when_top_level_is_empty() {
     let mut value: Value = serde_json::from_str(&fixture("valid.component.json")).unwrap();
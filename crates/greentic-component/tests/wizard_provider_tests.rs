@@ -3,8 +3,15 @@
 use std::path::PathBuf;
 
 use greentic_component::scaffold::config_schema::ConfigSchemaInput;
+use greentic_component::scaffold::license::{LicenseKind, copyright_year};
 use greentic_component::scaffold::runtime_capabilities::RuntimeCapabilitiesInput;
-use greentic_component::wizard::{WizardRequest, WizardStep, apply_scaffold, execute_plan};
+use greentic_component::wizard::{
+    MultiComponent, MultiSpec, OverwriteChange, PlanKeystore, RemoveOptions, TemplatePack,
+    WIZARD_LOCK_PATH, WizardCi, WizardDelegate, WizardMode, WizardPlan, WizardPlanEnvelope,
+    WizardPlanMeta, WizardPlanMetadata, WizardPlanMode, WizardRequest, WizardStep, WizardTarget,
+    WizardTemplate, apply_multi_scaffold, apply_scaffold, execute_plan, lint_template_pack,
+    preview_overwrite, register_delegate, remove_scaffold, seal_plan, verify_plan,
+};
 use insta::assert_json_snapshot;
 use serde::Serialize;
 
@@ -31,9 +38,16 @@ struct StepSnapshot {
 fn scaffold_plan_snapshot_is_deterministic() {
     let request = WizardRequest {
         name: "demo-component".to_string(),
+        org: None,
+        license: LicenseKind::Mit,
+        author: None,
         abi_version: "0.6.0".to_string(),
         mode: greentic_component::wizard::WizardMode::Default,
+        template: WizardTemplate::Component,
+        template_pack: None,
         target: PathBuf::from("/tmp/wizard-provider-plan/demo-component"),
+        workspace: false,
+        ci: WizardCi::None,
         answers: None,
         required_capabilities: vec!["host.http.client".to_string()],
         provided_capabilities: vec!["telemetry.emit".to_string()],
@@ -44,7 +58,8 @@ fn scaffold_plan_snapshot_is_deterministic() {
     };
 
     let result = apply_scaffold(request, true).expect("plan should build");
-    let envelope = &result.plan;
+    let mut envelope = result.plan;
+    pin_copyright_year(&mut envelope);
     let plan = &envelope.plan;
 
     let steps = plan
@@ -74,6 +89,29 @@ fn scaffold_plan_snapshot_is_deterministic() {
                     blake3: Some(hasher.finalize().to_hex().to_string()),
                 }
             }
+            WizardStep::RemoveFiles { paths } => StepSnapshot {
+                kind: "remove_files",
+                path: paths.join(","),
+                size: None,
+                blake3: None,
+            },
+            WizardStep::UpdateWorkspaceMembers { manifest, member } => StepSnapshot {
+                kind: "update_workspace_members",
+                path: format!("{manifest}:{member}"),
+                size: None,
+                blake3: None,
+            },
+            WizardStep::WriteWorkspaceFiles {
+                manifest, files, ..
+            } => StepSnapshot {
+                kind: "write_workspace_files",
+                path: format!(
+                    "{manifest}:{}",
+                    files.keys().cloned().collect::<Vec<_>>().join(",")
+                ),
+                size: None,
+                blake3: None,
+            },
             WizardStep::RunCli { command, .. } => StepSnapshot {
                 kind: "run_cli",
                 path: command.clone(),
@@ -120,15 +158,35 @@ fn scaffold_plan_snapshot_is_deterministic() {
     assert_json_snapshot!("scaffold_plan_snapshot", snap);
 }
 
+/// `LICENSE` carries the current year; pin it and reseal so the snapshot does not age.
+fn pin_copyright_year(envelope: &mut WizardPlanEnvelope) {
+    let current = format!("Copyright (c) {} ", copyright_year());
+    for step in &mut envelope.plan.steps {
+        if let WizardStep::WriteFiles { files } = step
+            && let Some(license) = files.get_mut("LICENSE")
+        {
+            *license = license.replace(&current, "Copyright (c) 2030 ");
+        }
+    }
+    seal_plan(envelope).expect("reseal plan");
+}
+
 #[test]
 fn execute_plan_writes_expected_files() {
     let temp = tempfile::TempDir::new().expect("tempdir");
     let target = temp.path().join("exec-demo");
     let request = WizardRequest {
         name: "exec-demo".to_string(),
+        org: None,
+        license: LicenseKind::Mit,
+        author: None,
         abi_version: "0.6.0".to_string(),
         mode: greentic_component::wizard::WizardMode::Default,
+        template: WizardTemplate::Component,
+        template_pack: None,
         target: target.clone(),
+        workspace: false,
+        ci: WizardCi::None,
         answers: None,
         required_capabilities: Vec::new(),
         provided_capabilities: Vec::new(),
@@ -161,6 +219,1137 @@ fn execute_plan_writes_expected_files() {
     assert!(cargo.contains("name = \"exec-demo\""));
 }
 
+#[test]
+fn templates_shape_manifest_capabilities_and_operations() {
+    let plan_files = |template: WizardTemplate| {
+        let request = WizardRequest {
+            name: "tmpl-demo".to_string(),
+            org: None,
+            license: LicenseKind::Mit,
+            author: None,
+            abi_version: "0.6.0".to_string(),
+            mode: greentic_component::wizard::WizardMode::Default,
+            template,
+            template_pack: None,
+            target: PathBuf::from("/tmp/wizard-provider-plan/tmpl-demo"),
+            workspace: false,
+            ci: WizardCi::None,
+            answers: None,
+            required_capabilities: Vec::new(),
+            provided_capabilities: Vec::new(),
+            user_operations: Vec::new(),
+            default_operation: None,
+            runtime_capabilities: RuntimeCapabilitiesInput::default(),
+            config_schema: ConfigSchemaInput::default(),
+        };
+        let result = apply_scaffold(request, true).expect("plan should build");
+        result
+            .plan
+            .plan
+            .steps
+            .into_iter()
+            .find_map(|step| match step {
+                WizardStep::WriteFiles { files } => Some(files),
+                _ => None,
+            })
+            .expect("write_files step")
+    };
+
+    let channel = plan_files(WizardTemplate::Channel);
+    let manifest: serde_json::Value =
+        serde_json::from_str(&channel["component.manifest.json"]).expect("manifest json");
+    let ops = manifest["operations"]
+        .as_array()
+        .expect("operations")
+        .iter()
+        .filter_map(|op| op["name"].as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(&ops[..2], ["send_message", "receive_message"]);
+    assert_eq!(manifest["default_operation"], "send_message");
+    let messaging = &manifest["capabilities"]["host"]["messaging"];
+    assert_eq!(messaging["inbound"], true);
+    assert_eq!(messaging["outbound"], true);
+    assert!(channel["src/lib.rs"].contains("role: \"channel\""));
+    assert!(channel["Cargo.toml"].contains("template = \"channel-v0_6\""));
+
+    let http = plan_files(WizardTemplate::HttpTool);
+    let manifest: serde_json::Value =
+        serde_json::from_str(&http["component.manifest.json"]).expect("manifest json");
+    assert_eq!(manifest["capabilities"]["host"]["http"]["client"], true);
+    assert!(
+        manifest["supports"]
+            .as_array()
+            .expect("supports")
+            .contains(&serde_json::Value::from("http"))
+    );
+    assert!(http["src/lib.rs"].contains("http::send(&mut http::RunnerHttp, &request)"));
+
+    let stateful = plan_files(WizardTemplate::StatefulTool);
+    let manifest: serde_json::Value =
+        serde_json::from_str(&stateful["component.manifest.json"]).expect("manifest json");
+    assert_eq!(manifest["profiles"]["default"], "stateful");
+    assert_eq!(manifest["capabilities"]["host"]["state"]["write"], true);
+    assert_eq!(
+        manifest["state_schema"]["properties"]["values"]["type"],
+        "object"
+    );
+    greentic_component::manifest::validate_manifest(&stateful["component.manifest.json"])
+        .expect("stateful manifest matches the manifest schema");
+    let stateful_manifest =
+        greentic_component::manifest::parse_manifest(&stateful["component.manifest.json"])
+            .expect("stateful manifest parses");
+    assert!(stateful_manifest.state_schema.is_some());
+    assert!(stateful["src/lib.rs"].contains("pub mod state;"));
+    assert!(stateful["src/lib.rs"].contains("state::save(host, state::STATE_KEY, &current)"));
+    assert!(!stateful["src/lib.rs"].contains("STATE_SLOT"));
+    assert!(stateful["src/state.rs"].contains("state_store::write(key, bytes, None)"));
+    assert!(stateful["Cargo.toml"].contains(r#"features = ["component-v0-6", "state-store"]"#));
+    assert!(!channel["component.manifest.json"].contains("state_schema"));
+
+    assert_eq!(
+        WizardTemplate::from_id("source-v0_6"),
+        Some(WizardTemplate::Source)
+    );
+    assert_eq!(WizardTemplate::from_id("unknown"), None);
+}
+
+#[test]
+fn required_http_client_generates_host_http_example() {
+    let request = WizardRequest {
+        name: "http-demo".to_string(),
+        org: None,
+        license: LicenseKind::Mit,
+        author: None,
+        abi_version: "0.6.0".to_string(),
+        mode: WizardMode::Default,
+        template: WizardTemplate::Component,
+        template_pack: None,
+        target: PathBuf::from("/tmp/wizard-provider-plan/http-demo"),
+        workspace: false,
+        ci: WizardCi::None,
+        answers: None,
+        required_capabilities: vec![
+            greentic_component::wizard::HOST_HTTP_CLIENT.to_string(),
+            "host.unknown".to_string(),
+        ],
+        provided_capabilities: Vec::new(),
+        user_operations: Vec::new(),
+        default_operation: None,
+        runtime_capabilities: RuntimeCapabilitiesInput::default(),
+        config_schema: ConfigSchemaInput::default(),
+    };
+    let result = apply_scaffold(request, true).expect("plan should build");
+    assert!(
+        result
+            .warnings
+            .iter()
+            .any(|warning| warning.contains("`host.unknown`"))
+    );
+    let files = result
+        .plan
+        .plan
+        .steps
+        .into_iter()
+        .find_map(|step| match step {
+            WizardStep::WriteFiles { files } => Some(files),
+            _ => None,
+        })
+        .expect("write_files step");
+
+    let manifest: serde_json::Value =
+        serde_json::from_str(&files["component.manifest.json"]).expect("manifest json");
+    assert_eq!(manifest["capabilities"]["host"]["http"]["client"], true);
+    assert!(files["src/lib.rs"].contains("pub mod http;"));
+    let http = &files["src/http.rs"];
+    assert!(http.contains("pub trait HttpHost"));
+    assert!(http.contains("pub fn from_host(message: &str) -> Self"));
+    assert!(http.contains("struct FakeHost"));
+    assert!(http.contains("http_client::send(&request, None)"));
+    assert!(files["Cargo.toml"].contains(r#"features = ["component-v0-6", "http-client"]"#));
+
+    let plain = apply_scaffold(
+        WizardRequest {
+            name: "plain-demo".to_string(),
+            org: None,
+            license: LicenseKind::Mit,
+            author: None,
+            abi_version: "0.6.0".to_string(),
+            mode: WizardMode::Default,
+            template: WizardTemplate::Component,
+            template_pack: None,
+            target: PathBuf::from("/tmp/wizard-provider-plan/plain-demo"),
+            workspace: false,
+            ci: WizardCi::None,
+            answers: None,
+            required_capabilities: Vec::new(),
+            provided_capabilities: Vec::new(),
+            user_operations: Vec::new(),
+            default_operation: None,
+            runtime_capabilities: RuntimeCapabilitiesInput::default(),
+            config_schema: ConfigSchemaInput::default(),
+        },
+        true,
+    )
+    .expect("plan should build");
+    let plain = serde_json::to_string(&plain.plan).expect("plan json");
+    assert!(!plain.contains("src/http.rs"));
+    assert!(!plain.contains("http-client"));
+}
+
+#[test]
+fn secret_keys_generate_host_secrets_example() {
+    let runtime_capabilities = RuntimeCapabilitiesInput {
+        secret_keys: vec!["API_TOKEN".to_string(), "WEBHOOK_SECRET".to_string()],
+        secret_format: "json".to_string(),
+        ..RuntimeCapabilitiesInput::default()
+    };
+    let request = WizardRequest {
+        name: "secrets-demo".to_string(),
+        org: None,
+        license: LicenseKind::Mit,
+        author: None,
+        abi_version: "0.6.0".to_string(),
+        mode: WizardMode::Default,
+        template: WizardTemplate::Component,
+        template_pack: None,
+        target: PathBuf::from("/tmp/wizard-provider-plan/secrets-demo"),
+        workspace: false,
+        ci: WizardCi::None,
+        answers: None,
+        required_capabilities: Vec::new(),
+        provided_capabilities: Vec::new(),
+        user_operations: Vec::new(),
+        default_operation: None,
+        runtime_capabilities,
+        config_schema: ConfigSchemaInput::default(),
+    };
+    let files = apply_scaffold(request, true)
+        .expect("plan should build")
+        .plan
+        .plan
+        .steps
+        .into_iter()
+        .find_map(|step| match step {
+            WizardStep::WriteFiles { files } => Some(files),
+            _ => None,
+        })
+        .expect("write_files step");
+
+    let manifest: serde_json::Value =
+        serde_json::from_str(&files["component.manifest.json"]).expect("manifest json");
+    let required = manifest["capabilities"]["host"]["secrets"]["required"]
+        .as_array()
+        .expect("secrets.required");
+    assert_eq!(required.len(), 2);
+    assert_eq!(required[0]["key"], "API_TOKEN");
+    assert!(files["src/lib.rs"].contains("pub mod secrets;"));
+    let secrets = &files["src/secrets.rs"];
+    assert!(
+        secrets
+            .contains(r#"pub const DECLARED_SECRETS: &[&str] = &["API_TOKEN", "WEBHOOK_SECRET"];"#)
+    );
+    assert!(secrets.contains(r#"pub const SECRET_FORMAT: &str = "json";"#));
+    assert!(secrets.contains("Err(HostSecretError::Denied) => Err(SecretError::Denied"));
+    assert!(secrets.contains("struct FakeSecrets"));
+    assert!(files["Cargo.toml"].contains(r#"features = ["component-v0-6", "secrets"]"#));
+}
+
+#[test]
+fn telemetry_generates_helpers_and_instruments_invoke() {
+    let runtime_capabilities = RuntimeCapabilitiesInput {
+        telemetry_span_prefix: Some("component.demo".to_string()),
+        telemetry_attributes: [("team".to_string(), "core".to_string())].into(),
+        ..RuntimeCapabilitiesInput::default()
+    };
+    let request = WizardRequest {
+        name: "telemetry-demo".to_string(),
+        org: None,
+        license: LicenseKind::Mit,
+        author: None,
+        abi_version: "0.6.0".to_string(),
+        mode: WizardMode::Default,
+        template: WizardTemplate::Component,
+        template_pack: None,
+        target: PathBuf::from("/tmp/wizard-provider-plan/telemetry-demo"),
+        workspace: false,
+        ci: WizardCi::None,
+        answers: None,
+        required_capabilities: Vec::new(),
+        provided_capabilities: Vec::new(),
+        user_operations: Vec::new(),
+        default_operation: None,
+        runtime_capabilities,
+        config_schema: ConfigSchemaInput::default(),
+    };
+    let files = apply_scaffold(request, true)
+        .expect("plan should build")
+        .plan
+        .plan
+        .steps
+        .into_iter()
+        .find_map(|step| match step {
+            WizardStep::WriteFiles { files } => Some(files),
+            _ => None,
+        })
+        .expect("write_files step");
+
+    let manifest: serde_json::Value =
+        serde_json::from_str(&files["component.manifest.json"]).expect("manifest json");
+    assert_eq!(manifest["telemetry"]["span_prefix"], "component.demo");
+    assert_eq!(manifest["telemetry"]["emit_node_spans"], true);
+    assert_eq!(
+        manifest["capabilities"]["host"]["telemetry"]["scope"],
+        "node"
+    );
+    let lib = &files["src/lib.rs"];
+    assert!(lib.contains("pub mod telemetry;"));
+    assert!(lib.contains("telemetry::instrument(&mut telemetry::RunnerTelemetry, &operation"));
+    let telemetry = &files["src/telemetry.rs"];
+    assert!(telemetry.contains(r#"pub const SPAN_PREFIX: &str = "component.demo";"#));
+    assert!(telemetry.contains(r#"pub const ATTRIBUTES: &[(&str, &str)] = &[("team", "core")];"#));
+    assert!(telemetry.contains("struct FakeTelemetry"));
+    assert!(telemetry.contains("telemetry_logger::log(&span, &fields, None)"));
+    assert!(files["Cargo.toml"].contains(r#"features = ["component-v0-6", "telemetry"]"#));
+}
+
+#[test]
+fn scaffold_ships_wasmtime_integration_test() {
+    let request = WizardRequest {
+        name: "it-demo".to_string(),
+        org: None,
+        license: LicenseKind::Mit,
+        author: None,
+        abi_version: "0.6.0".to_string(),
+        mode: WizardMode::Default,
+        template: WizardTemplate::Component,
+        template_pack: None,
+        target: PathBuf::from("/tmp/wizard-provider-plan/it-demo"),
+        workspace: false,
+        ci: WizardCi::None,
+        answers: None,
+        required_capabilities: Vec::new(),
+        provided_capabilities: Vec::new(),
+        user_operations: Vec::new(),
+        default_operation: None,
+        runtime_capabilities: RuntimeCapabilitiesInput::default(),
+        config_schema: ConfigSchemaInput::default(),
+    };
+    let files = apply_scaffold(request, true)
+        .expect("plan should build")
+        .plan
+        .plan
+        .steps
+        .into_iter()
+        .find_map(|step| match step {
+            WizardStep::WriteFiles { files } => Some(files),
+            _ => None,
+        })
+        .expect("write_files step");
+
+    let it = &files["tests/component_it.rs"];
+    assert!(it.contains("use greentic_interfaces_host::component_v0_6::ComponentV0V6V0;"));
+    assert!(it.contains(r#"root.join("dist/it-demo__0_6_0.wasm")"#));
+    assert!(it.contains(".call_describe(&mut store)"));
+    assert!(it.contains(r#".call_invoke(&mut store, "handle_message""#));
+    assert!(it.contains("schema must round-trip as canonical CBOR"));
+    assert!(!it.contains("#[ignore"));
+    let cargo = &files["Cargo.toml"];
+    assert!(cargo.contains("[target.'cfg(not(target_arch = \"wasm32\"))'.dev-dependencies]"));
+    assert!(cargo.contains(r#"wasmtime-wasi = { version = "43" }"#));
+}
+
+#[test]
+fn template_pack_renders_files_over_base_template() {
+    let pack_dir = tempfile::tempdir().expect("tempdir");
+    std::fs::write(
+        pack_dir.path().join("wizard-template.json"),
+        r#"{
+  "id": "acme-tool",
+  "version": "1.2.0",
+  "base": "http-tool-v0_6",
+  "files": [
+    { "path": "README.md", "source": "README.md.hbs" },
+    { "path": "ops/{{name}}.txt", "source": "ops.txt.hbs" },
+    { "path": "LICENSE", "source": "LICENSE" }
+  ]
+}"#,
+    )
+    .expect("write manifest");
+    std::fs::write(
+        pack_dir.path().join("README.md.hbs"),
+        "# {{name}} ({{template}})\n",
+    )
+    .expect("write readme template");
+    std::fs::write(
+        pack_dir.path().join("ops.txt.hbs"),
+        "{{#each user_operations}}{{this}}\n{{/each}}",
+    )
+    .expect("write ops template");
+    std::fs::write(pack_dir.path().join("LICENSE"), "{{not rendered}}").expect("write license");
+
+    let pack = TemplatePack::from_dir(pack_dir.path()).expect("load pack");
+    assert_eq!(pack.base, WizardTemplate::HttpTool);
+    let request = WizardRequest {
+        name: "acme-demo".to_string(),
+        org: None,
+        license: LicenseKind::Mit,
+        author: None,
+        abi_version: "0.6.0".to_string(),
+        mode: greentic_component::wizard::WizardMode::Default,
+        template: pack.base,
+        template_pack: Some(pack),
+        target: PathBuf::from("/tmp/wizard-provider-plan/acme-demo"),
+        workspace: false,
+        ci: WizardCi::None,
+        answers: None,
+        required_capabilities: Vec::new(),
+        provided_capabilities: Vec::new(),
+        user_operations: Vec::new(),
+        default_operation: None,
+        runtime_capabilities: RuntimeCapabilitiesInput::default(),
+        config_schema: ConfigSchemaInput::default(),
+    };
+    let result = apply_scaffold(request, true).expect("plan should build");
+    assert_eq!(result.plan.metadata.template_version, "acme-tool@1.2.0");
+    let files = result
+        .plan
+        .plan
+        .steps
+        .into_iter()
+        .find_map(|step| match step {
+            WizardStep::WriteFiles { files } => Some(files),
+            _ => None,
+        })
+        .expect("write_files step");
+    assert_eq!(files["README.md"], "# acme-demo (http-tool-v0_6)\n");
+    assert_eq!(files["ops/acme-demo.txt"], "fetch\n");
+    assert_eq!(files["LICENSE"], "{{not rendered}}");
+    assert!(files["Cargo.toml"].contains("template = \"http-tool-v0_6\""));
+}
+
+#[test]
+fn template_lint_reports_invalid_output_and_unused_variables() {
+    let pack_dir = tempfile::tempdir().expect("tempdir");
+    std::fs::write(
+        pack_dir.path().join("wizard-template.json"),
+        r#"{
+  "id": "acme-lint",
+  "files": [
+    { "path": "README.md", "source": "README.md.hbs" },
+    { "path": "assets/i18n/{{pack.id}}.json", "source": "bundle.json" }
+  ]
+}"#,
+    )
+    .expect("write manifest");
+    std::fs::write(
+        pack_dir.path().join("README.md.hbs"),
+        "# {{name}}\n{{#each user_operations}}- {{this}}\n{{/each}}",
+    )
+    .expect("write readme template");
+    std::fs::write(pack_dir.path().join("bundle.json"), "{\"title\": ").expect("write bundle");
+
+    let lint = lint_template_pack(pack_dir.path()).expect("pack loads");
+    assert_eq!(lint.pack, "acme-lint");
+    assert!(
+        lint.files
+            .iter()
+            .any(|path| path == "assets/i18n/acme-lint.json")
+    );
+    assert_eq!(lint.errors.len(), 1, "{:?}", lint.errors);
+    assert!(lint.errors[0].starts_with("assets/i18n/acme-lint.json: invalid JSON"));
+    assert!(lint.unused_variables.contains(&"org".to_string()));
+    assert!(!lint.unused_variables.contains(&"name".to_string()));
+    assert!(!lint.unused_variables.contains(&"pack".to_string()));
+    assert!(!lint.unused_variables.contains(&"abi_version".to_string()));
+
+    std::fs::write(pack_dir.path().join("bundle.json"), "{}").expect("fix bundle");
+    std::fs::write(pack_dir.path().join("README.md.hbs"), "# {{missing_var}}\n")
+        .expect("write broken template");
+    let lint = lint_template_pack(pack_dir.path()).expect("pack loads");
+    assert!(!lint.is_ok());
+    assert!(lint.errors[0].contains("README.md"), "{:?}", lint.errors);
+}
+
+#[test]
+fn update_mode_preserves_user_edits_and_flags_conflicts() {
+    let temp = tempfile::TempDir::new().expect("tempdir");
+    let target = temp.path().join("update-demo");
+    let request = |mode, operations: &[&str]| WizardRequest {
+        name: "update-demo".to_string(),
+        org: None,
+        license: LicenseKind::Mit,
+        author: None,
+        abi_version: "0.6.0".to_string(),
+        mode,
+        template: WizardTemplate::Component,
+        template_pack: None,
+        target: target.clone(),
+        workspace: false,
+        ci: WizardCi::None,
+        answers: None,
+        required_capabilities: Vec::new(),
+        provided_capabilities: Vec::new(),
+        user_operations: operations.iter().map(|op| op.to_string()).collect(),
+        default_operation: None,
+        runtime_capabilities: RuntimeCapabilitiesInput::default(),
+        config_schema: ConfigSchemaInput::default(),
+    };
+
+    apply_scaffold(request(WizardMode::Default, &["handle_message"]), false)
+        .expect("initial scaffold");
+    assert!(target.join(WIZARD_LOCK_PATH).exists());
+    let readme = target.join("README.md");
+    std::fs::write(&readme, "# team notes\n").expect("edit readme");
+    let lib = target.join("src/lib.rs");
+    let edited_lib = std::fs::read_to_string(&lib).expect("lib.rs") + "// local tweak\n";
+    std::fs::write(&lib, &edited_lib).expect("edit lib.rs");
+
+    let result = apply_scaffold(
+        request(WizardMode::Update, &["handle_message", "render"]),
+        false,
+    )
+    .expect("update");
+    let written = result
+        .plan
+        .plan
+        .steps
+        .iter()
+        .find_map(|step| match step {
+            WizardStep::WriteFiles { files } => Some(files.keys().cloned().collect::<Vec<_>>()),
+            _ => None,
+        })
+        .expect("write_files step");
+
+    assert!(!written.contains(&"README.md".to_string()));
+    assert!(!written.contains(&"src/lib.rs".to_string()));
+    assert!(written.contains(&"component.manifest.json".to_string()));
+    assert_eq!(
+        std::fs::read_to_string(&readme).expect("readme"),
+        "# team notes\n"
+    );
+    assert_eq!(std::fs::read_to_string(&lib).expect("lib.rs"), edited_lib);
+    let manifest =
+        std::fs::read_to_string(target.join("component.manifest.json")).expect("manifest");
+    assert!(manifest.contains("\"render\""));
+    let conflict = std::fs::read_to_string(target.join("src/lib.rs.new")).expect("lib.rs.new");
+    assert!(conflict.contains("<<<<<<< current"));
+    assert!(conflict.contains("name: \"render\".to_string()"));
+    assert!(
+        result
+            .warnings
+            .iter()
+            .any(|warning| warning.contains("src/lib.rs has local changes"))
+    );
+}
+
+#[test]
+fn workspace_mode_joins_enclosing_workspace_and_inherits_settings() {
+    let temp = tempfile::TempDir::new().expect("tempdir");
+    std::fs::write(
+        temp.path().join("Cargo.toml"),
+        "[workspace]\nmembers = [\n    \"crates/core\",\n]\nresolver = \"3\"\n\n[workspace.package]\nedition = \"2024\"\nlicense = \"Apache-2.0\"\n\n[workspace.dependencies]\nserde = \"1\"\n",
+    )
+    .expect("workspace manifest");
+    let target = temp.path().join("components/ws-demo");
+    let request = WizardRequest {
+        name: "ws-demo".to_string(),
+        org: None,
+        license: LicenseKind::Mit,
+        author: None,
+        abi_version: "0.6.0".to_string(),
+        mode: WizardMode::Default,
+        template: WizardTemplate::Component,
+        template_pack: None,
+        target: target.clone(),
+        workspace: true,
+        ci: WizardCi::None,
+        answers: None,
+        required_capabilities: Vec::new(),
+        provided_capabilities: Vec::new(),
+        user_operations: Vec::new(),
+        default_operation: None,
+        runtime_capabilities: RuntimeCapabilitiesInput::default(),
+        config_schema: ConfigSchemaInput::default(),
+    };
+
+    let plan = apply_scaffold(request, true).expect("workspace plan").plan;
+    assert!(plan.plan.steps.iter().any(|step| matches!(
+        step,
+        WizardStep::UpdateWorkspaceMembers { manifest, member }
+            if manifest == "../../Cargo.toml" && member == "components/ws-demo"
+    )));
+    // The workspace manifest is edited at execution time, keeping changes made after planning.
+    let workspace_path = temp.path().join("Cargo.toml");
+    let edited = std::fs::read_to_string(&workspace_path).expect("workspace") + "# edited\n";
+    std::fs::write(&workspace_path, edited).expect("edit workspace");
+    execute_plan(&plan).expect("workspace scaffold");
+    let manifest = std::fs::read_to_string(target.join("Cargo.toml")).expect("member manifest");
+    assert!(manifest.contains("edition.workspace = true"));
+    assert!(manifest.contains("license.workspace = true"));
+    assert!(manifest.contains("version = \"0.1.0\""));
+    assert!(manifest.contains("serde = { workspace = true, features = [\"derive\"] }"));
+    assert!(manifest.contains("serde_json = \"1\""));
+    assert!(!target.join("rust-toolchain.toml").exists());
+    let workspace = std::fs::read_to_string(temp.path().join("Cargo.toml")).expect("workspace");
+    assert!(workspace.contains("    \"crates/core\",\n    \"components/ws-demo\",\n]"));
+    assert!(workspace.ends_with("# edited\n"));
+
+    let outside = tempfile::TempDir::new().expect("tempdir");
+    let request = WizardRequest {
+        name: "lonely".to_string(),
+        org: None,
+        license: LicenseKind::Mit,
+        author: None,
+        abi_version: "0.6.0".to_string(),
+        mode: WizardMode::Default,
+        template: WizardTemplate::Component,
+        template_pack: None,
+        target: outside.path().join("lonely"),
+        workspace: true,
+        ci: WizardCi::None,
+        answers: None,
+        required_capabilities: Vec::new(),
+        provided_capabilities: Vec::new(),
+        user_operations: Vec::new(),
+        default_operation: None,
+        runtime_capabilities: RuntimeCapabilitiesInput::default(),
+        config_schema: ConfigSchemaInput::default(),
+    };
+    let err = apply_scaffold(request, true).expect_err("no workspace above target");
+    assert!(err.to_string().contains("requires a Cargo workspace"));
+}
+
+#[test]
+fn workspace_mode_writes_ci_at_workspace_root_for_the_member() {
+    let temp = tempfile::TempDir::new().expect("tempdir");
+    std::fs::write(
+        temp.path().join("Cargo.toml"),
+        "[workspace]\nmembers = [\"components/*\"]\nresolver = \"3\"\n",
+    )
+    .expect("workspace manifest");
+    let request = |name: &str, ci: WizardCi| WizardRequest {
+        name: name.to_string(),
+        org: None,
+        license: LicenseKind::Mit,
+        author: None,
+        abi_version: "0.6.0".to_string(),
+        mode: WizardMode::Default,
+        template: WizardTemplate::Component,
+        template_pack: None,
+        target: temp.path().join("components").join(name),
+        workspace: true,
+        ci,
+        answers: None,
+        required_capabilities: Vec::new(),
+        provided_capabilities: Vec::new(),
+        user_operations: Vec::new(),
+        default_operation: None,
+        runtime_capabilities: RuntimeCapabilitiesInput::default(),
+        config_schema: ConfigSchemaInput::default(),
+    };
+
+    let github = apply_scaffold(request("gh-demo", WizardCi::Github), false).expect("github");
+    assert!(github.warnings.is_empty(), "{:?}", github.warnings);
+    assert!(github.plan.plan.steps.iter().any(|step| matches!(
+        step,
+        WizardStep::WriteWorkspaceFiles { manifest, member, files }
+            if manifest == "../../Cargo.toml"
+                && member == "components/gh-demo"
+                && files.contains_key(".github/workflows/gh-demo.yml")
+    )));
+    let member = temp.path().join("components/gh-demo");
+    assert!(!member.join(".github").exists());
+    let workflow = std::fs::read_to_string(temp.path().join(".github/workflows/gh-demo.yml"))
+        .expect("root workflow");
+    assert!(workflow.contains("        working-directory: components/gh-demo\n"));
+    assert!(workflow.contains("path: components/gh-demo/dist/"));
+    assert!(workflow.contains("--manifest ./component.manifest.json"));
+
+    let gitlab = apply_scaffold(request("gl-demo", WizardCi::Gitlab), false).expect("gitlab");
+    assert!(
+        gitlab.warnings[0].contains("include it from .gitlab-ci.yml"),
+        "{:?}",
+        gitlab.warnings
+    );
+    assert!(!temp.path().join(".gitlab-ci.yml").exists());
+    let job = std::fs::read_to_string(temp.path().join(".gitlab/ci/gl-demo.yml"))
+        .expect("root gitlab job");
+    assert!(job.contains("gl-demo:\n"));
+    assert!(job.contains("    - cd components/gl-demo\n"));
+    assert!(job.contains("      - components/gl-demo/dist/"));
+
+    // The root files are outside the member's lock, so removing the member leaves them.
+    let removed = apply_scaffold(
+        WizardRequest {
+            mode: WizardMode::Remove,
+            ..request("gh-demo", WizardCi::Github)
+        },
+        false,
+    )
+    .expect("remove member");
+    assert!(
+        removed
+            .plan
+            .plan
+            .steps
+            .iter()
+            .all(|step| !matches!(step, WizardStep::WriteWorkspaceFiles { .. }))
+    );
+    assert!(temp.path().join(".github/workflows/gh-demo.yml").exists());
+}
+
+#[test]
+fn multi_spec_scaffolds_workspace_with_shared_common_crate() {
+    let temp = tempfile::TempDir::new().expect("tempdir");
+    let root = temp.path().join("acme");
+    let component = |name: &str, kind: &str| MultiComponent {
+        name: name.to_string(),
+        kind: kind.to_string(),
+        capabilities: Vec::new(),
+        operations: Vec::new(),
+        default_operation: None,
+    };
+    let spec = MultiSpec {
+        workspace: "acme".to_string(),
+        org: Some("com.acme".to_string()),
+        license: Some("Apache-2.0".to_string()),
+        author: None,
+        abi_version: "0.6.0".to_string(),
+        shared_capabilities: vec!["host.http.client".to_string()],
+        components: vec![
+            component("fetcher", "http-tool"),
+            component("inbox", "source"),
+        ],
+    };
+
+    let result = apply_multi_scaffold(spec.clone(), root.clone(), true).expect("dry run");
+    let files = result
+        .plan
+        .plan
+        .steps
+        .iter()
+        .find_map(|step| match step {
+            WizardStep::WriteFiles { files } => Some(files.keys().cloned().collect::<Vec<_>>()),
+            _ => None,
+        })
+        .expect("write step");
+    for path in [
+        "Cargo.toml",
+        "rust-toolchain.toml",
+        "crates/common/src/lib.rs",
+        "components/fetcher/component.manifest.json",
+        "components/inbox/.greentic/wizard.lock.json",
+    ] {
+        assert!(files.iter().any(|file| file == path), "missing {path}");
+    }
+    assert!(
+        !files
+            .iter()
+            .any(|file| file == "components/inbox/rust-toolchain.toml")
+    );
+    assert!(!root.exists());
+
+    apply_multi_scaffold(spec.clone(), root.clone(), false).expect("scaffold");
+    let workspace = std::fs::read_to_string(root.join("Cargo.toml")).expect("workspace");
+    let parsed: toml::Table = toml::from_str(&workspace).expect("workspace toml");
+    let members = parsed["workspace"]["members"].as_array().expect("members");
+    assert_eq!(members.len(), 3);
+    assert!(workspace.contains("license = \"Apache-2.0\""));
+    let license = std::fs::read_to_string(root.join("LICENSE")).expect("license");
+    assert!(license.contains(&format!("Copyright (c) {} ", copyright_year())));
+    let common = std::fs::read_to_string(root.join("crates/common/src/lib.rs")).expect("common");
+    assert!(common.contains("pub const ORG: &str = \"com.acme\";"));
+    assert!(common.contains("&[\"host.http.client\"]"));
+    let inbox = std::fs::read_to_string(root.join("components/inbox/Cargo.toml")).expect("inbox");
+    assert!(inbox.contains("[dependencies]\ncommon = { workspace = true }\n"));
+    assert!(inbox.contains("license.workspace = true"));
+    let manifest: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(root.join("components/inbox/component.manifest.json"))
+            .expect("inbox manifest"),
+    )
+    .expect("manifest json");
+    assert_eq!(manifest["id"], "com.acme.inbox");
+    assert_eq!(manifest["capabilities"]["host"]["http"]["client"], true);
+
+    // `common` counts as a user edit, so regenerating a member keeps it.
+    let update = WizardRequest {
+        name: "inbox".to_string(),
+        org: Some("com.acme".to_string()),
+        license: LicenseKind::Apache2,
+        author: None,
+        abi_version: "0.6.0".to_string(),
+        mode: WizardMode::Update,
+        template: WizardTemplate::Source,
+        template_pack: None,
+        target: root.join("components/inbox"),
+        workspace: true,
+        ci: WizardCi::None,
+        answers: None,
+        required_capabilities: vec!["host.http.client".to_string()],
+        provided_capabilities: Vec::new(),
+        user_operations: Vec::new(),
+        default_operation: None,
+        runtime_capabilities: RuntimeCapabilitiesInput::default(),
+        config_schema: ConfigSchemaInput::default(),
+    };
+    apply_scaffold(update, false).expect("update member");
+    let inbox = std::fs::read_to_string(root.join("components/inbox/Cargo.toml")).expect("inbox");
+    assert!(inbox.contains("common = { workspace = true }"));
+
+    let mut duplicate = spec;
+    duplicate.components.push(component("fetcher", "component"));
+    let err = apply_multi_scaffold(duplicate, temp.path().join("dup"), true).unwrap_err();
+    assert!(err.to_string().contains("listed more than once"));
+}
+
+#[test]
+fn remove_mode_deletes_only_unmodified_generated_files() {
+    let temp = tempfile::TempDir::new().expect("tempdir");
+    let target = temp.path().join("remove-demo");
+    let request = WizardRequest {
+        name: "remove-demo".to_string(),
+        org: None,
+        license: LicenseKind::Mit,
+        author: None,
+        abi_version: "0.6.0".to_string(),
+        mode: WizardMode::Default,
+        template: WizardTemplate::Component,
+        template_pack: None,
+        target: target.clone(),
+        workspace: false,
+        ci: WizardCi::None,
+        answers: None,
+        required_capabilities: Vec::new(),
+        provided_capabilities: Vec::new(),
+        user_operations: Vec::new(),
+        default_operation: None,
+        runtime_capabilities: RuntimeCapabilitiesInput::default(),
+        config_schema: ConfigSchemaInput::default(),
+    };
+    apply_scaffold(request, false).expect("initial scaffold");
+    std::fs::write(target.join("README.md"), "# kept\n").expect("edit readme");
+    std::fs::write(target.join("src/user.rs"), "// authored\n").expect("user file");
+    std::fs::create_dir_all(target.join("dist")).expect("dist dir");
+    std::fs::write(target.join("dist/remove-demo.wasm"), b"\0asm").expect("dist artifact");
+
+    let preview =
+        remove_scaffold(&target, RemoveOptions { clean_dist: true }, true).expect("removal plan");
+    assert!(
+        target.join("Cargo.toml").exists(),
+        "dry run must not delete"
+    );
+    let paths = match &preview.plan.plan.steps[..] {
+        [WizardStep::RemoveFiles { paths }] => paths.clone(),
+        other => panic!("unexpected steps {other:?}"),
+    };
+    assert!(paths.contains(&"Cargo.toml".to_string()));
+    assert!(paths.contains(&"dist/remove-demo.wasm".to_string()));
+    assert!(!paths.contains(&"README.md".to_string()));
+
+    execute_plan(&preview.plan).expect("execute removal");
+    assert!(!target.join("Cargo.toml").exists());
+    assert!(!target.join("tools").exists());
+    assert!(!target.join("dist").exists());
+    assert!(!target.join(WIZARD_LOCK_PATH).exists());
+    assert!(target.join("README.md").exists());
+    assert!(target.join("src/user.rs").exists());
+    assert!(
+        preview
+            .warnings
+            .iter()
+            .any(|warning| warning.contains("README.md"))
+    );
+}
+
+struct NotesDelegate;
+
+impl WizardDelegate for NotesDelegate {
+    fn plan(
+        &self,
+        target_root: &std::path::Path,
+        answers: &serde_json::Value,
+    ) -> anyhow::Result<WizardPlanEnvelope> {
+        let mut plan = plan_with_steps(target_root, Vec::new());
+        let mut files = std::collections::BTreeMap::new();
+        files.insert(
+            "NOTES.md".to_string(),
+            answers["text"].as_str().unwrap_or_default().to_string(),
+        );
+        plan.plan.steps.push(WizardStep::WriteFiles { files });
+        Ok(plan)
+    }
+}
+
+fn plan_with_steps(target_root: &std::path::Path, steps: Vec<WizardStep>) -> WizardPlanEnvelope {
+    let mut plan = WizardPlanEnvelope {
+        plan_version: greentic_component::wizard::PLAN_VERSION,
+        metadata: WizardPlanMetadata {
+            generator: greentic_component::wizard::GENERATOR_ID.to_string(),
+            template_version: "test".to_string(),
+            template_digest_blake3: String::new(),
+            requested_abi_version: "0.6.0".to_string(),
+        },
+        target_root: target_root.to_path_buf(),
+        plan: WizardPlan {
+            meta: WizardPlanMeta {
+                id: "test.plan".to_string(),
+                target: WizardTarget::Component,
+                mode: WizardPlanMode::Scaffold,
+            },
+            steps,
+        },
+        signature: None,
+    };
+    seal_plan(&mut plan).expect("seal plan");
+    plan
+}
+
+#[test]
+fn verify_plan_covers_every_step_and_known_generators_only() {
+    let temp = tempfile::TempDir::new().expect("tempdir");
+    let plan = plan_with_steps(
+        temp.path(),
+        vec![WizardStep::EnsureDir {
+            paths: vec!["src".to_string()],
+        }],
+    );
+    verify_plan(&plan).expect("sealed plan verifies");
+
+    let mut extended = plan.clone();
+    extended.plan.steps.push(WizardStep::RunCli {
+        command: "cargo".to_string(),
+        args: vec!["--version".to_string()],
+        cwd: None,
+        timeout_secs: None,
+        continue_on_error: false,
+    });
+    let err = verify_plan(&extended).expect_err("added step must fail");
+    assert!(err.to_string().contains("digest mismatch"), "{err}");
+
+    let mut foreign = plan.clone();
+    foreign.metadata.generator = "someone-else".to_string();
+    let err = verify_plan(&foreign).expect_err("unknown generator must fail");
+    assert!(err.to_string().contains("someone-else"), "{err}");
+}
+
+#[test]
+fn execute_plan_rejects_paths_outside_target_before_writing() {
+    let temp = tempfile::TempDir::new().expect("tempdir");
+    let root = temp.path().join("out");
+    for escaping in [
+        "../escaped.txt",
+        "/tmp/escaped.txt",
+        "src/../../escaped.txt",
+    ] {
+        let mut files = std::collections::BTreeMap::new();
+        files.insert("README.md".to_string(), "inside".to_string());
+        files.insert(escaping.to_string(), "outside".to_string());
+        let plan = plan_with_steps(&root, vec![WizardStep::WriteFiles { files }]);
+        let err = execute_plan(&plan).expect_err("escaping path must fail");
+        assert!(
+            err.to_string().contains("outside the project root"),
+            "{err}"
+        );
+        assert!(!root.join("README.md").exists(), "nothing is written");
+    }
+
+    let plan = plan_with_steps(
+        &root,
+        vec![
+            WizardStep::EnsureDir {
+                paths: vec!["src".to_string()],
+            },
+            WizardStep::EnsureDir {
+                paths: vec!["../sibling".to_string()],
+            },
+        ],
+    );
+    assert!(execute_plan(&plan).is_err());
+    assert!(!root.join("src").exists());
+    assert!(!temp.path().join("sibling").exists());
+
+    // Workspace files stay inside the workspace root the member leads back to.
+    let member_root = temp.path().join("components/demo");
+    let plan = plan_with_steps(
+        &member_root,
+        vec![WizardStep::WriteWorkspaceFiles {
+            manifest: "../../Cargo.toml".to_string(),
+            member: "components/demo".to_string(),
+            files: [("../outside.yml".to_string(), "outside".to_string())].into(),
+        }],
+    );
+    let err = execute_plan(&plan).expect_err("escaping workspace file must fail");
+    assert!(
+        err.to_string().contains("outside the project root"),
+        "{err}"
+    );
+}
+
+#[test]
+fn execute_plan_runs_allowed_cli_and_registered_delegates() {
+    let temp = tempfile::TempDir::new().expect("tempdir");
+    register_delegate("test.notes", std::sync::Arc::new(NotesDelegate));
+
+    let plan = plan_with_steps(
+        temp.path(),
+        vec![
+            WizardStep::RunCli {
+                command: "git".to_string(),
+                args: vec!["init".to_string(), "--quiet".to_string()],
+                cwd: None,
+                timeout_secs: Some(60),
+                continue_on_error: false,
+            },
+            WizardStep::Delegate {
+                id: "test.notes".to_string(),
+                answers: serde_json::json!({ "text": "delegated" }),
+            },
+        ],
+    );
+    let mut unsealed = plan.clone();
+    unsealed.metadata.template_digest_blake3 = "unverified".to_string();
+    let err = execute_plan(&unsealed).expect_err("run_cli needs a verified plan");
+    assert!(err.to_string().contains("must verify"), "{err}");
+    assert!(!temp.path().join(".git").exists());
+
+    execute_plan(&plan).expect("plan should execute");
+    assert!(temp.path().join(".git").is_dir());
+    assert_eq!(
+        std::fs::read_to_string(temp.path().join("NOTES.md")).expect("notes"),
+        "delegated"
+    );
+
+    let blocked = plan_with_steps(
+        temp.path(),
+        vec![WizardStep::RunCli {
+            command: "sh".to_string(),
+            args: vec!["-c".to_string(), "true".to_string()],
+            cwd: None,
+            timeout_secs: None,
+            continue_on_error: false,
+        }],
+    );
+    assert!(execute_plan(&blocked).is_err());
+
+    // Nothing is staged, so the commit fails; the step after it still runs.
+    let reported = plan_with_steps(
+        temp.path(),
+        vec![
+            WizardStep::RunCli {
+                command: "git".to_string(),
+                args: vec!["commit".to_string(), "-m".to_string(), "empty".to_string()],
+                cwd: None,
+                timeout_secs: Some(60),
+                continue_on_error: true,
+            },
+            WizardStep::EnsureDir {
+                paths: vec!["after".to_string()],
+            },
+        ],
+    );
+    let failures = execute_plan(&reported).expect("continue_on_error failures are reported");
+    assert_eq!(failures.len(), 1, "{failures:?}");
+    assert_eq!(failures[0].command, "git commit -m empty");
+    assert!(temp.path().join("after").is_dir());
+
+    let unknown = plan_with_steps(
+        temp.path(),
+        vec![WizardStep::Delegate {
+            id: "test.missing".to_string(),
+            answers: serde_json::Value::Null,
+        }],
+    );
+    assert!(execute_plan(&unknown).is_err());
+}
+
+#[test]
+fn signed_plans_verify_against_trusted_keys() {
+    let temp = tempfile::TempDir::new().expect("tempdir");
+    let keystore = PlanKeystore::new(temp.path().join("keys"));
+    let public_key = keystore.generate("release").expect("generate key");
+    assert!(
+        keystore.generate("release").is_err(),
+        "keys are not replaced"
+    );
+
+    let mut plan = plan_with_steps(
+        &temp.path().join("out"),
+        vec![WizardStep::EnsureDir {
+            paths: vec!["src".to_string()],
+        }],
+    );
+    keystore.sign("release", &mut plan).expect("sign plan");
+    let signature = plan.signature.clone().expect("signature attached");
+    assert_eq!(signature.public_key, public_key);
+    keystore.verify_trusted(&plan).expect("trusted signature");
+
+    let round_trip: WizardPlanEnvelope =
+        serde_json::from_str(&serde_json::to_string_pretty(&plan).unwrap()).unwrap();
+    verify_plan(&round_trip).expect("signature survives serialization");
+    execute_plan(&round_trip).expect("signed plan executes");
+    assert!(temp.path().join("out/src").is_dir());
+
+    let mut tampered = round_trip.clone();
+    tampered.target_root = temp.path().join("elsewhere");
+    let err = execute_plan(&tampered).expect_err("tampered plan must fail");
+    assert!(err.to_string().contains("does not match"), "{err}");
+
+    let other = PlanKeystore::new(temp.path().join("other-keys"));
+    let err = other.verify_trusted(&plan).expect_err("unknown signer");
+    assert!(err.to_string().contains("not in the keystore"), "{err}");
+    other.trust("release", &public_key).expect("trust key");
+    other.verify_trusted(&plan).expect("trusted after import");
+    assert!(!other.list().unwrap()[0].can_sign);
+
+    let cbor = greentic_types::cbor::canonical::to_canonical_cbor_allow_floats(&plan).unwrap();
+    let from_cbor: WizardPlanEnvelope = greentic_types::cbor::canonical::from_cbor(&cbor).unwrap();
+    verify_plan(&from_cbor).expect("signature survives a CBOR plan file");
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(temp.path().join("keys/release.key"))
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o600, "secret keys are owner-only");
+    }
+
+    plan.signature = None;
+    assert!(keystore.verify_trusted(&plan).is_err());
+    let err = keystore
+        .verify_saved(&plan, false, false)
+        .expect_err("unsigned plan with a trusted key configured");
+    assert!(err.to_string().contains("not signed"), "{err}");
+    let empty = PlanKeystore::new(temp.path().join("no-keys"));
+    empty
+        .verify_saved(&plan, false, false)
+        .expect("unsigned plans pass without trusted keys");
+    assert!(empty.verify_saved(&plan, true, false).is_err());
+
+    let mut commands = plan_with_steps(
+        &temp.path().join("out"),
+        vec![WizardStep::RunCli {
+            command: "cargo".to_string(),
+            args: vec!["build".to_string()],
+            cwd: None,
+            timeout_secs: None,
+            continue_on_error: false,
+        }],
+    );
+    let err = empty
+        .verify_saved(&commands, false, false)
+        .expect_err("unsigned plans may not run commands");
+    assert!(err.to_string().contains("run_cli"), "{err}");
+    empty
+        .verify_saved(&commands, false, true)
+        .expect("explicit opt-in allows unsigned commands");
+    keystore.sign("release", &mut commands).expect("sign plan");
+    keystore
+        .verify_saved(&commands, false, false)
+        .expect("trusted signature allows commands");
+}
+
 #[test]
 fn spec_uses_namespaced_question_ids() {
     let spec =
@@ -170,4 +1359,226 @@ fn spec_uses_namespaced_question_ids() {
     assert!(ids.contains(&"component.path".to_string()));
     assert!(ids.contains(&"component.kind".to_string()));
     assert!(ids.contains(&"component.features.enabled".to_string()));
+    assert!(ids.contains(&"component.ci".to_string()));
+}
+
+#[test]
+fn ci_question_emits_workflow_for_selected_provider() {
+    let request = |ci| WizardRequest {
+        name: "ci-demo".to_string(),
+        org: None,
+        license: LicenseKind::Mit,
+        author: None,
+        abi_version: "0.6.0".to_string(),
+        mode: WizardMode::Default,
+        template: WizardTemplate::Component,
+        template_pack: None,
+        target: PathBuf::from("/tmp/wizard-provider-plan/ci-demo"),
+        workspace: false,
+        ci,
+        answers: None,
+        required_capabilities: Vec::new(),
+        provided_capabilities: Vec::new(),
+        user_operations: Vec::new(),
+        default_operation: None,
+        runtime_capabilities: RuntimeCapabilitiesInput::default(),
+        config_schema: ConfigSchemaInput::default(),
+    };
+    let written = |ci| {
+        apply_scaffold(request(ci), true)
+            .expect("plan should build")
+            .plan
+            .plan
+            .steps
+            .into_iter()
+            .find_map(|step| match step {
+                WizardStep::WriteFiles { files } => Some(files),
+                _ => None,
+            })
+            .expect("write_files step")
+    };
+
+    let github = written(WizardCi::Github);
+    let workflow = &github[".github/workflows/ci.yml"];
+    assert!(workflow.contains("make wasm"));
+    assert!(workflow.contains(
+        "greentic-component doctor dist/ci-demo__0_6_0.wasm --manifest ./component.manifest.json"
+    ));
+    assert!(workflow.contains("actions/upload-artifact@v4"));
+    assert!(!github.contains_key(".gitlab-ci.yml"));
+
+    let gitlab = written(WizardCi::Gitlab);
+    assert!(gitlab[".gitlab-ci.yml"].contains("- dist/"));
+
+    let none = written(WizardCi::None);
+    assert!(!none.contains_key(".github/workflows/ci.yml"));
+    assert!(!none.contains_key(".gitlab-ci.yml"));
+}
+
+#[test]
+fn license_and_author_answers_fill_license_cargo_and_manifest_id() {
+    let request = |license, author: Option<&str>| WizardRequest {
+        name: "licensed-demo".to_string(),
+        org: Some("ai.acme".to_string()),
+        license,
+        author: author.map(ToOwned::to_owned),
+        abi_version: "0.6.0".to_string(),
+        mode: WizardMode::Default,
+        template: WizardTemplate::Component,
+        template_pack: None,
+        target: PathBuf::from("/tmp/wizard-provider-plan/licensed-demo"),
+        workspace: false,
+        ci: WizardCi::None,
+        answers: None,
+        required_capabilities: Vec::new(),
+        provided_capabilities: Vec::new(),
+        user_operations: Vec::new(),
+        default_operation: None,
+        runtime_capabilities: RuntimeCapabilitiesInput::default(),
+        config_schema: ConfigSchemaInput::default(),
+    };
+    let written = |license, author| {
+        apply_scaffold(request(license, author), true)
+            .expect("plan should build")
+            .plan
+            .plan
+            .steps
+            .into_iter()
+            .find_map(|step| match step {
+                WizardStep::WriteFiles { files } => Some(files),
+                _ => None,
+            })
+            .expect("write_files step")
+    };
+
+    let apache = written(LicenseKind::Apache2, Some("Acme Corp"));
+    assert!(apache["LICENSE"].contains("Apache License"));
+    let year = copyright_year();
+    assert!(apache["LICENSE"].contains(&format!("Copyright (c) {year} Acme Corp")));
+    assert!(apache["Cargo.toml"].contains("license = \"Apache-2.0\""));
+    assert!(apache["Cargo.toml"].contains("authors = [\"Acme Corp\"]"));
+    assert!(apache["component.manifest.json"].contains("\"id\": \"ai.acme.licensed-demo\""));
+
+    let proprietary = written(LicenseKind::Proprietary, None);
+    assert!(proprietary["LICENSE"].starts_with(&format!(
+        "Copyright (c) {year} ai.acme. All rights reserved."
+    )));
+    assert!(proprietary["Cargo.toml"].contains("license = \"LicenseRef-Proprietary\""));
+    assert!(!proprietary["Cargo.toml"].contains("authors"));
+}
+
+#[test]
+fn abi_version_selects_world_features_and_export_macro() {
+    let request = |abi_version: &str| WizardRequest {
+        name: "abi-demo".to_string(),
+        org: None,
+        license: LicenseKind::Mit,
+        author: None,
+        abi_version: abi_version.to_string(),
+        mode: WizardMode::Default,
+        template: WizardTemplate::Component,
+        template_pack: None,
+        target: PathBuf::from("/tmp/wizard-provider-plan/abi-demo"),
+        workspace: false,
+        ci: WizardCi::None,
+        answers: None,
+        required_capabilities: Vec::new(),
+        provided_capabilities: Vec::new(),
+        user_operations: Vec::new(),
+        default_operation: None,
+        runtime_capabilities: RuntimeCapabilitiesInput::default(),
+        config_schema: ConfigSchemaInput::default(),
+    };
+
+    let files = |abi_version: &str| {
+        let result = apply_scaffold(request(abi_version), true).expect("plan should build");
+        let files = result
+            .plan
+            .plan
+            .steps
+            .into_iter()
+            .find_map(|step| match step {
+                WizardStep::WriteFiles { files } => Some(files),
+                _ => None,
+            })
+            .expect("write_files step");
+        (result.warnings, files)
+    };
+
+    let (warnings, patch) = files("0.6.2");
+    assert!(warnings.is_empty(), "{warnings:?}");
+    assert!(patch["Cargo.toml"].contains(r#"world = "greentic:component/component@0.6.0""#));
+    assert!(patch["Cargo.toml"].contains(r#"features = ["component-v0-6"]"#));
+    assert!(patch["src/lib.rs"].contains("greentic_interfaces_guest::component_v0_6::node"));
+    assert!(patch["src/lib.rs"].contains("export_component_v060!(Component)"));
+
+    for unsupported in ["0.5.0", "0.7.0"] {
+        let (warnings, fallback) = files(unsupported);
+        assert_eq!(warnings.len(), 1, "{warnings:?}");
+        assert!(
+            warnings[0].contains(&format!("no component template for ABI {unsupported}")),
+            "{warnings:?}"
+        );
+        assert!(warnings[0].contains("using 0.6.0"), "{warnings:?}");
+        assert!(fallback["Cargo.toml"].contains(r#"features = ["component-v0-6"]"#));
+        assert!(fallback["src/lib.rs"].contains("export_component_v060!(Component)"));
+        assert!(!fallback["src/lib.rs"].contains(unsupported));
+    }
+}
+
+#[test]
+fn overwrite_preview_diffs_changed_files_and_lists_removed_paths() {
+    let temp = tempfile::TempDir::new().expect("tempdir");
+    let target = temp.path().join("overwrite-demo");
+    let request = || WizardRequest {
+        name: "overwrite-demo".to_string(),
+        org: None,
+        license: LicenseKind::Mit,
+        author: None,
+        abi_version: "0.6.0".to_string(),
+        mode: WizardMode::Default,
+        template: WizardTemplate::Component,
+        template_pack: None,
+        target: target.clone(),
+        workspace: false,
+        ci: WizardCi::None,
+        answers: None,
+        required_capabilities: Vec::new(),
+        provided_capabilities: Vec::new(),
+        user_operations: Vec::new(),
+        default_operation: None,
+        runtime_capabilities: RuntimeCapabilitiesInput::default(),
+        config_schema: ConfigSchemaInput::default(),
+    };
+
+    let plan = apply_scaffold(request(), true).expect("plan").plan;
+    assert!(preview_overwrite(&plan).expect("preview").is_none());
+
+    apply_scaffold(request(), false).expect("scaffold");
+    let readme = std::fs::read_to_string(target.join("README.md")).expect("readme");
+    std::fs::write(target.join("README.md"), format!("{readme}Local notes.\n")).expect("edit");
+    std::fs::write(target.join("TODO.txt"), "todo\n").expect("extra file");
+    std::fs::create_dir_all(target.join("target/debug")).expect("target dir");
+    std::fs::write(target.join("target/debug/build.log"), "log\n").expect("build log");
+
+    let preview = preview_overwrite(&plan)
+        .expect("preview")
+        .expect("target exists");
+    let paths = preview
+        .files
+        .iter()
+        .map(|file| (file.path.as_str(), file.change))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        paths,
+        vec![
+            ("README.md", OverwriteChange::Modified),
+            ("TODO.txt", OverwriteChange::Removed),
+            ("target/", OverwriteChange::Removed),
+        ]
+    );
+    let diff = preview.files[0].diff.as_deref().expect("text diff");
+    assert!(diff.contains("--- a/README.md"), "{diff}");
+    assert!(diff.contains("-Local notes."), "{diff}");
+    assert_eq!(preview.count(OverwriteChange::Removed), 2);
 }
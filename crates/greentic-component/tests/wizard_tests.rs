@@ -2,7 +2,8 @@
 
 use assert_cmd::prelude::*;
 use greentic_component::cmd::wizard::{
-    ExecutionMode, RunMode, WizardArgs, WizardCliArgs, WizardSubcommand, run, run_cli,
+    ExecutionMode, PlanFormat, RunMode, WizardArgs, WizardCliArgs, WizardSubcommand,
+    WizardValidateAnswersArgs, run, run_cli,
 };
 use predicates::prelude::{PredicateBooleanExt, predicate};
 use serde_json::{Value, json};
@@ -304,22 +305,9 @@ fn wizard_create_execute_creates_template_files() {
     create_answers(&answers_path, "demo-component");
 
     let args = WizardArgs {
-        mode: RunMode::Create,
-        execution: ExecutionMode::Execute,
-        dry_run: false,
-        validate: false,
-        apply: false,
         qa_answers: Some(answers_path),
-        answers: None,
-        qa_answers_out: None,
-        emit_answers: None,
-        schema_version: None,
-        migrate: false,
-        plan_out: None,
         project_root: temp.path().to_path_buf(),
-        template: None,
-        full_tests: false,
-        json: false,
+        ..WizardArgs::default()
     };
 
     run(args).expect("wizard create should succeed");
@@ -342,6 +330,68 @@ fn wizard_create_execute_creates_template_files() {
     assert!(cargo_toml.contains("abi_version = \"0.6.0\""));
 }
 
+#[test]
+fn wizard_set_overrides_scaffold_without_answers_file() {
+    let temp = tempfile::TempDir::new().unwrap();
+    let root = temp.path().join("set-component");
+
+    let args = WizardArgs {
+        set: vec![
+            "component_name=set-component".to_string(),
+            format!("output_dir={}", root.display()),
+            "abi_version=0.6.0".to_string(),
+            "operation_names=render".to_string(),
+        ],
+        project_root: temp.path().to_path_buf(),
+        ..WizardArgs::default()
+    };
+
+    run(args).expect("wizard create with --set should succeed");
+
+    let cargo_toml = fs::read_to_string(root.join("Cargo.toml")).unwrap();
+    assert!(cargo_toml.contains("name = \"set-component\""));
+    let manifest: Value =
+        serde_json::from_str(&fs::read_to_string(root.join("component.manifest.json")).unwrap())
+            .unwrap();
+    assert_eq!(manifest["default_operation"], "render");
+}
+
+#[test]
+fn wizard_overwrite_clears_output_only_when_executed() {
+    let temp = tempfile::TempDir::new().unwrap();
+    let root = temp.path().join("overwrite-component");
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(root.join("src/lib.rs"), "// hand written\n").unwrap();
+    fs::write(root.join("NOTES.md"), "keep me?\n").unwrap();
+
+    let args = |execution: ExecutionMode| WizardArgs {
+        execution,
+        plan_out: Some(temp.path().join("plan.json")),
+        set: vec![
+            "component_name=overwrite-component".to_string(),
+            format!("output_dir={}", root.display()),
+            "overwrite_output=true".to_string(),
+        ],
+        project_root: temp.path().to_path_buf(),
+        ..WizardArgs::default()
+    };
+
+    run(args(ExecutionMode::DryRun)).expect("overwrite preview should plan");
+    assert_eq!(
+        fs::read_to_string(root.join("src/lib.rs")).unwrap(),
+        "// hand written\n"
+    );
+    assert!(root.join("NOTES.md").exists());
+
+    run(args(ExecutionMode::Execute)).expect("overwrite should execute");
+    assert!(!root.join("NOTES.md").exists());
+    assert!(root.join("Cargo.toml").exists());
+    assert_ne!(
+        fs::read_to_string(root.join("src/lib.rs")).unwrap(),
+        "// hand written\n"
+    );
+}
+
 #[test]
 fn wizard_create_supports_multiple_user_operations() {
     let temp = tempfile::TempDir::new().unwrap();
@@ -353,22 +403,9 @@ fn wizard_create_supports_multiple_user_operations() {
     );
 
     let args = WizardArgs {
-        mode: RunMode::Create,
-        execution: ExecutionMode::Execute,
-        dry_run: false,
-        validate: false,
-        apply: false,
         qa_answers: Some(answers_path),
-        answers: None,
-        qa_answers_out: None,
-        emit_answers: None,
-        schema_version: None,
-        migrate: false,
-        plan_out: None,
         project_root: temp.path().to_path_buf(),
-        template: None,
-        full_tests: false,
-        json: false,
+        ..WizardArgs::default()
     };
 
     run(args).expect("wizard create should support authored operations");
@@ -391,22 +428,9 @@ fn wizard_create_supports_comma_separated_operation_names() {
     create_answers_with_operation_names(&answers_path, "csv-op-component", "render, summarize");
 
     let args = WizardArgs {
-        mode: RunMode::Create,
-        execution: ExecutionMode::Execute,
-        dry_run: false,
-        validate: false,
-        apply: false,
         qa_answers: Some(answers_path),
-        answers: None,
-        qa_answers_out: None,
-        emit_answers: None,
-        schema_version: None,
-        migrate: false,
-        plan_out: None,
         project_root: temp.path().to_path_buf(),
-        template: None,
-        full_tests: false,
-        json: false,
+        ..WizardArgs::default()
     };
 
     run(args).expect("wizard create should parse comma-separated operation names");
@@ -425,22 +449,9 @@ fn wizard_create_writes_runtime_capability_fields() {
     create_answers_with_runtime_capabilities(&answers_path, "capability-component");
 
     let args = WizardArgs {
-        mode: RunMode::Create,
-        execution: ExecutionMode::Execute,
-        dry_run: false,
-        validate: false,
-        apply: false,
         qa_answers: Some(answers_path),
-        answers: None,
-        qa_answers_out: None,
-        emit_answers: None,
-        schema_version: None,
-        migrate: false,
-        plan_out: None,
         project_root: temp.path().to_path_buf(),
-        template: None,
-        full_tests: false,
-        json: false,
+        ..WizardArgs::default()
     };
 
     run(args).expect("wizard create with runtime capability fields should succeed");
@@ -467,22 +478,9 @@ fn wizard_create_writes_concrete_qa_operation_schemas() {
     create_answers(&answers_path, "qa-schema-component");
 
     let args = WizardArgs {
-        mode: RunMode::Create,
-        execution: ExecutionMode::Execute,
-        dry_run: false,
-        validate: false,
-        apply: false,
         qa_answers: Some(answers_path),
-        answers: None,
-        qa_answers_out: None,
-        emit_answers: None,
-        schema_version: None,
-        migrate: false,
-        plan_out: None,
         project_root: temp.path().to_path_buf(),
-        template: None,
-        full_tests: false,
-        json: false,
+        ..WizardArgs::default()
     };
 
     run(args).expect("wizard create should succeed");
@@ -531,22 +529,9 @@ fn wizard_create_ignores_filesystem_mounts_when_mode_is_none() {
     create_answers_with_no_filesystem_mounts(&answers_path, "no-fs-component");
 
     let args = WizardArgs {
-        mode: RunMode::Create,
-        execution: ExecutionMode::Execute,
-        dry_run: false,
-        validate: false,
-        apply: false,
         qa_answers: Some(answers_path),
-        answers: None,
-        qa_answers_out: None,
-        emit_answers: None,
-        schema_version: None,
-        migrate: false,
-        plan_out: None,
         project_root: temp.path().to_path_buf(),
-        template: None,
-        full_tests: false,
-        json: false,
+        ..WizardArgs::default()
     };
 
     run(args).expect("wizard create should succeed");
@@ -573,22 +558,9 @@ fn wizard_create_writes_config_schema_fields() {
     create_answers_with_config_fields(&answers_path, "config-component");
 
     let args = WizardArgs {
-        mode: RunMode::Create,
-        execution: ExecutionMode::Execute,
-        dry_run: false,
-        validate: false,
-        apply: false,
         qa_answers: Some(answers_path),
-        answers: None,
-        qa_answers_out: None,
-        emit_answers: None,
-        schema_version: None,
-        migrate: false,
-        plan_out: None,
         project_root: temp.path().to_path_buf(),
-        template: None,
-        full_tests: false,
-        json: false,
+        ..WizardArgs::default()
     };
 
     run(args).expect("wizard create should succeed");
@@ -617,22 +589,12 @@ fn wizard_create_writes_answers_out_when_requested() {
     create_answers(&answers_path, "answers-component");
 
     let args = WizardArgs {
-        mode: RunMode::Create,
         execution: ExecutionMode::DryRun,
-        dry_run: false,
-        validate: false,
-        apply: false,
         qa_answers: Some(answers_path),
-        answers: None,
         qa_answers_out: Some(answers_out.clone()),
-        emit_answers: None,
-        schema_version: None,
-        migrate: false,
         plan_out: Some(temp.path().join("out/plan.json")),
         project_root: temp.path().to_path_buf(),
-        template: None,
-        full_tests: false,
-        json: false,
+        ..WizardArgs::default()
     };
 
     run(args).expect("wizard dry-run should succeed");
@@ -646,22 +608,11 @@ fn wizard_create_dry_run_does_not_write_files() {
     create_answers(&answers_path, "component");
 
     let args = WizardArgs {
-        mode: RunMode::Create,
         execution: ExecutionMode::DryRun,
-        dry_run: false,
-        validate: false,
-        apply: false,
         qa_answers: Some(answers_path),
-        answers: None,
-        qa_answers_out: None,
-        emit_answers: None,
-        schema_version: None,
-        migrate: false,
         plan_out: Some(temp.path().join("plan.json")),
         project_root: temp.path().to_path_buf(),
-        template: None,
-        full_tests: false,
-        json: false,
+        ..WizardArgs::default()
     };
 
     run(args).expect("wizard dry-run should succeed");
@@ -679,22 +630,11 @@ fn wizard_validate_flag_behaves_like_dry_run() {
     create_answers(&answers_path, "component");
 
     let args = WizardArgs {
-        mode: RunMode::Create,
-        execution: ExecutionMode::Execute,
-        dry_run: false,
         validate: true,
-        apply: false,
         qa_answers: Some(answers_path),
-        answers: None,
-        qa_answers_out: None,
-        emit_answers: None,
-        schema_version: None,
-        migrate: false,
         plan_out: Some(temp.path().join("plan.json")),
         project_root: temp.path().to_path_buf(),
-        template: None,
-        full_tests: false,
-        json: false,
+        ..WizardArgs::default()
     };
 
     run(args).expect("wizard validate should succeed");
@@ -712,43 +652,17 @@ fn wizard_validate_command_alias_behaves_like_dry_run() {
     create_answers(&answers_path, "component");
 
     let args = WizardArgs {
-        mode: RunMode::Create,
-        execution: ExecutionMode::Execute,
-        dry_run: false,
-        validate: false,
-        apply: false,
         qa_answers: Some(answers_path),
-        answers: None,
-        qa_answers_out: None,
-        emit_answers: None,
-        schema_version: None,
-        migrate: false,
         plan_out: Some(temp.path().join("plan.json")),
         project_root: temp.path().to_path_buf(),
-        template: None,
-        full_tests: false,
-        json: false,
+        ..WizardArgs::default()
     };
 
     run_cli(WizardCliArgs {
         command: Some(WizardSubcommand::Validate(args)),
         args: WizardArgs {
-            mode: RunMode::Create,
-            execution: ExecutionMode::Execute,
-            dry_run: false,
-            validate: false,
-            apply: false,
-            qa_answers: None,
-            answers: None,
-            qa_answers_out: None,
-            emit_answers: None,
-            schema_version: None,
-            migrate: false,
-            plan_out: None,
             project_root: temp.path().to_path_buf(),
-            template: None,
-            full_tests: false,
-            json: false,
+            ..WizardArgs::default()
         },
     })
     .expect("wizard validate alias should succeed");
@@ -759,6 +673,60 @@ fn wizard_validate_command_alias_behaves_like_dry_run() {
     );
 }
 
+#[test]
+fn wizard_validate_answers_reports_field_errors() {
+    let temp = tempfile::TempDir::new().unwrap();
+    let valid = temp.path().join("valid.json");
+    create_answers(&valid, "component");
+    let invalid = temp.path().join("invalid.json");
+    fs::write(
+        &invalid,
+        serde_json::to_string_pretty(&json!({
+            "schema": "component-wizard-run/v1",
+            "mode": "create",
+            "fields": {
+                "component_name": "Bad Name",
+                "http_client": "yes",
+                "ci": "circle"
+            }
+        }))
+        .unwrap(),
+    )
+    .unwrap();
+
+    let validate = |answers: &Path| {
+        run_cli(WizardCliArgs {
+            command: Some(WizardSubcommand::ValidateAnswers(
+                WizardValidateAnswersArgs {
+                    answers: answers.to_path_buf(),
+                    json: false,
+                },
+            )),
+            args: WizardArgs {
+                project_root: temp.path().to_path_buf(),
+                ..WizardArgs::default()
+            },
+        })
+    };
+
+    validate(&valid).expect("valid answers should pass");
+    let message = validate(&invalid)
+        .expect_err("invalid answers should fail")
+        .to_string();
+    assert!(message.contains("/fields/component_name"), "{message}");
+    assert!(message.contains("/fields/http_client"), "{message}");
+    assert!(message.contains("/fields/ci"), "{message}");
+
+    let err = run(WizardArgs {
+        execution: ExecutionMode::DryRun,
+        answers: Some(invalid),
+        project_root: temp.path().to_path_buf(),
+        ..WizardArgs::default()
+    })
+    .expect_err("replaying invalid answers should fail");
+    assert!(err.to_string().contains("/fields/ci"));
+}
+
 #[test]
 fn wizard_answers_aliases_work() {
     let temp = tempfile::TempDir::new().unwrap();
@@ -767,22 +735,14 @@ fn wizard_answers_aliases_work() {
     create_answers(&answers_path, "answers-alias-component");
 
     let args = WizardArgs {
-        mode: RunMode::Create,
         execution: ExecutionMode::DryRun,
-        dry_run: false,
-        validate: false,
-        apply: false,
-        qa_answers: None,
         answers: Some(answers_path),
-        qa_answers_out: None,
         emit_answers: Some(answers_out.clone()),
         schema_version: Some("1.0.1".to_string()),
         migrate: true,
         plan_out: Some(temp.path().join("out/plan.json")),
         project_root: temp.path().to_path_buf(),
-        template: None,
-        full_tests: false,
-        json: false,
+        ..WizardArgs::default()
     };
 
     run(args).expect("wizard dry-run with alias flags should succeed");
@@ -807,22 +767,11 @@ fn wizard_answer_document_requires_migrate_for_schema_version_change() {
     create_answer_document(&answers_path, "doc-component", "0.9.0");
 
     let args = WizardArgs {
-        mode: RunMode::Create,
         execution: ExecutionMode::DryRun,
-        dry_run: false,
-        validate: false,
-        apply: false,
-        qa_answers: None,
         answers: Some(answers_path),
-        qa_answers_out: None,
-        emit_answers: None,
-        schema_version: None,
-        migrate: false,
         plan_out: Some(temp.path().join("plan.json")),
         project_root: temp.path().to_path_buf(),
-        template: None,
-        full_tests: false,
-        json: false,
+        ..WizardArgs::default()
     };
 
     let err = run(args).expect_err("expected schema version mismatch without --migrate");
@@ -841,22 +790,13 @@ fn wizard_answer_document_migrates_with_flag() {
     create_answer_document(&answers_path, "doc-component", "0.9.0");
 
     let args = WizardArgs {
-        mode: RunMode::Create,
         execution: ExecutionMode::DryRun,
-        dry_run: false,
-        validate: false,
-        apply: false,
-        qa_answers: None,
         answers: Some(answers_path),
-        qa_answers_out: None,
         emit_answers: Some(answers_out.clone()),
-        schema_version: None,
         migrate: true,
         plan_out: Some(temp.path().join("plan.json")),
         project_root: temp.path().to_path_buf(),
-        template: None,
-        full_tests: false,
-        json: false,
+        ..WizardArgs::default()
     };
 
     run(args).expect("wizard should migrate and continue");
@@ -876,43 +816,19 @@ fn wizard_apply_command_alias_with_migrate_executes_side_effects() {
     create_answer_document(&answers_path, "apply-doc-component", "0.9.0");
 
     let args = WizardArgs {
-        mode: RunMode::Create,
         execution: ExecutionMode::DryRun,
-        dry_run: false,
-        validate: false,
-        apply: false,
-        qa_answers: None,
         answers: Some(answers_path),
-        qa_answers_out: None,
-        emit_answers: None,
-        schema_version: None,
         migrate: true,
-        plan_out: None,
         project_root: temp.path().to_path_buf(),
-        template: None,
-        full_tests: false,
-        json: false,
+        ..WizardArgs::default()
     };
 
     run_cli(WizardCliArgs {
         command: Some(WizardSubcommand::Apply(args)),
         args: WizardArgs {
-            mode: RunMode::Create,
             execution: ExecutionMode::DryRun,
-            dry_run: false,
-            validate: false,
-            apply: false,
-            qa_answers: None,
-            answers: None,
-            qa_answers_out: None,
-            emit_answers: None,
-            schema_version: None,
-            migrate: false,
-            plan_out: None,
             project_root: temp.path().to_path_buf(),
-            template: None,
-            full_tests: false,
-            json: false,
+            ..WizardArgs::default()
         },
     })
     .expect("wizard apply alias should execute scaffold");
@@ -927,22 +843,11 @@ fn wizard_replay_answers_mode_build_test_overrides_default_create_mode() {
     create_answers_with_mode(&answers_path, "build-test");
 
     let args = WizardArgs {
-        mode: RunMode::Create,
         execution: ExecutionMode::DryRun,
-        dry_run: false,
-        validate: false,
-        apply: false,
-        qa_answers: None,
         answers: Some(answers_path),
-        qa_answers_out: None,
-        emit_answers: None,
-        schema_version: None,
-        migrate: false,
         plan_out: Some(plan_out.clone()),
         project_root: temp.path().to_path_buf(),
-        template: None,
-        full_tests: false,
-        json: false,
+        ..WizardArgs::default()
     };
 
     run(args).expect("wizard replay should adopt build-test mode from answers");
@@ -964,22 +869,11 @@ fn wizard_replay_answers_mode_doctor_overrides_default_create_mode() {
     create_answers_with_mode(&answers_path, "doctor");
 
     let args = WizardArgs {
-        mode: RunMode::Create,
         execution: ExecutionMode::DryRun,
-        dry_run: false,
-        validate: false,
-        apply: false,
-        qa_answers: None,
         answers: Some(answers_path),
-        qa_answers_out: None,
-        emit_answers: None,
-        schema_version: None,
-        migrate: false,
         plan_out: Some(plan_out.clone()),
         project_root: temp.path().to_path_buf(),
-        template: None,
-        full_tests: false,
-        json: false,
+        ..WizardArgs::default()
     };
 
     run(args).expect("wizard replay should adopt doctor mode from answers");
@@ -1001,22 +895,12 @@ fn wizard_emit_answers_preserves_replayed_mode() {
     create_answers_with_mode(&answers_path, "build-test");
 
     let args = WizardArgs {
-        mode: RunMode::Create,
         execution: ExecutionMode::DryRun,
-        dry_run: false,
-        validate: false,
-        apply: false,
-        qa_answers: None,
         answers: Some(answers_path),
-        qa_answers_out: None,
         emit_answers: Some(answers_out.clone()),
-        schema_version: None,
-        migrate: false,
         plan_out: Some(temp.path().join("plan.json")),
         project_root: temp.path().to_path_buf(),
-        template: None,
-        full_tests: false,
-        json: false,
+        ..WizardArgs::default()
     };
 
     run(args).expect("wizard replay should emit answers");
@@ -1036,22 +920,9 @@ fn wizard_add_operation_updates_manifest_and_lib() {
     create_answers(&create_answers_path, "op-edit-component");
 
     run(WizardArgs {
-        mode: RunMode::Create,
-        execution: ExecutionMode::Execute,
-        dry_run: false,
-        validate: false,
-        apply: false,
         qa_answers: Some(create_answers_path),
-        answers: None,
-        qa_answers_out: None,
-        emit_answers: None,
-        schema_version: None,
-        migrate: false,
-        plan_out: None,
         project_root: temp.path().to_path_buf(),
-        template: None,
-        full_tests: false,
-        json: false,
+        ..WizardArgs::default()
     })
     .unwrap();
 
@@ -1061,21 +932,9 @@ fn wizard_add_operation_updates_manifest_and_lib() {
 
     run(WizardArgs {
         mode: RunMode::AddOperation,
-        execution: ExecutionMode::Execute,
-        dry_run: false,
-        validate: false,
-        apply: false,
-        qa_answers: None,
         answers: Some(add_answers),
-        qa_answers_out: None,
-        emit_answers: None,
-        schema_version: None,
-        migrate: false,
-        plan_out: None,
         project_root: temp.path().to_path_buf(),
-        template: None,
-        full_tests: false,
-        json: false,
+        ..WizardArgs::default()
     })
     .unwrap();
 
@@ -1095,22 +954,9 @@ fn wizard_update_operation_renames_manifest_and_lib() {
     create_answers(&create_answers_path, "rename-op-component");
 
     run(WizardArgs {
-        mode: RunMode::Create,
-        execution: ExecutionMode::Execute,
-        dry_run: false,
-        validate: false,
-        apply: false,
         qa_answers: Some(create_answers_path),
-        answers: None,
-        qa_answers_out: None,
-        emit_answers: None,
-        schema_version: None,
-        migrate: false,
-        plan_out: None,
         project_root: temp.path().to_path_buf(),
-        template: None,
-        full_tests: false,
-        json: false,
+        ..WizardArgs::default()
     })
     .unwrap();
 
@@ -1120,21 +966,9 @@ fn wizard_update_operation_renames_manifest_and_lib() {
 
     run(WizardArgs {
         mode: RunMode::UpdateOperation,
-        execution: ExecutionMode::Execute,
-        dry_run: false,
-        validate: false,
-        apply: false,
-        qa_answers: None,
         answers: Some(update_answers),
-        qa_answers_out: None,
-        emit_answers: None,
-        schema_version: None,
-        migrate: false,
-        plan_out: None,
         project_root: temp.path().to_path_buf(),
-        template: None,
-        full_tests: false,
-        json: false,
+        ..WizardArgs::default()
     })
     .unwrap();
 
@@ -1160,22 +994,12 @@ fn wizard_full_chain_dry_run_emit_validate_replay_execute() {
     create_answers(&answers_in, component_name);
 
     let validate_args = WizardArgs {
-        mode: RunMode::Create,
-        execution: ExecutionMode::Execute,
-        dry_run: false,
         validate: true,
-        apply: false,
-        qa_answers: None,
         answers: Some(answers_in),
-        qa_answers_out: None,
         emit_answers: Some(answers_out.clone()),
-        schema_version: None,
-        migrate: false,
         plan_out: Some(plan_out.clone()),
         project_root: temp.path().to_path_buf(),
-        template: None,
-        full_tests: false,
-        json: false,
+        ..WizardArgs::default()
     };
 
     run(validate_args).expect("validate pass should succeed");
@@ -1211,22 +1035,11 @@ fn wizard_full_chain_dry_run_emit_validate_replay_execute() {
     );
 
     let replay_validate_args = WizardArgs {
-        mode: RunMode::Create,
-        execution: ExecutionMode::Execute,
-        dry_run: false,
         validate: true,
-        apply: false,
-        qa_answers: None,
         answers: Some(answers_out.clone()),
-        qa_answers_out: None,
-        emit_answers: None,
-        schema_version: None,
-        migrate: false,
         plan_out: Some(replay_plan.clone()),
         project_root: temp.path().to_path_buf(),
-        template: None,
-        full_tests: false,
-        json: false,
+        ..WizardArgs::default()
     };
     run(replay_validate_args).expect("replay validate should succeed");
     assert!(
@@ -1235,22 +1048,9 @@ fn wizard_full_chain_dry_run_emit_validate_replay_execute() {
     );
 
     let execute_args = WizardArgs {
-        mode: RunMode::Create,
-        execution: ExecutionMode::Execute,
-        dry_run: false,
-        validate: false,
-        apply: false,
-        qa_answers: None,
         answers: Some(answers_out),
-        qa_answers_out: None,
-        emit_answers: None,
-        schema_version: None,
-        migrate: false,
-        plan_out: None,
         project_root: temp.path().to_path_buf(),
-        template: None,
-        full_tests: false,
-        json: false,
+        ..WizardArgs::default()
     };
     run(execute_args).expect("execute from emitted answers should succeed");
 
@@ -1442,3 +1242,119 @@ fn wizard_emit_answers_round_trips_all_fields_and_replay_builds_component() {
         .success()
         .stderr(predicate::str::contains("describe unavailable").not());
 }
+
+#[test]
+fn wizard_apply_saved_plan_verifies_digest() {
+    let temp = tempfile::TempDir::new().unwrap();
+    let answers = temp.path().join("answers.json");
+    let plan_out = temp.path().join("plan.json");
+    let component_root = temp.path().join("saved-plan-component");
+    create_answers(&answers, "saved-plan-component");
+
+    let args = |answers: Option<std::path::PathBuf>,
+                plan_out: Option<std::path::PathBuf>,
+                plan: Option<std::path::PathBuf>| WizardArgs {
+        validate: answers.is_some(),
+        apply: plan.is_some(),
+        answers,
+        plan_out,
+        plan,
+        project_root: temp.path().to_path_buf(),
+        ..WizardArgs::default()
+    };
+
+    run(args(Some(answers), Some(plan_out.clone()), None)).expect("dry-run should write a plan");
+    assert!(!component_root.exists());
+
+    let mut tampered: Value =
+        serde_json::from_str(&fs::read_to_string(&plan_out).unwrap()).unwrap();
+    run(args(None, None, Some(plan_out.clone()))).expect("saved plan should apply");
+    assert!(component_root.join("Cargo.toml").exists());
+    assert!(component_root.join("src/lib.rs").exists());
+
+    let steps = tampered["plan"]["steps"].as_array_mut().unwrap();
+    let write_files = steps
+        .iter_mut()
+        .find(|step| step["type"] == "write_files")
+        .unwrap();
+    write_files["files"]["README.md"] = json!("edited after review");
+    let tampered_path = temp.path().join("plan.tampered.json");
+    fs::write(
+        &tampered_path,
+        serde_json::to_string_pretty(&tampered).unwrap(),
+    )
+    .unwrap();
+    let err = run(args(None, None, Some(tampered_path))).expect_err("tampered plan must fail");
+    assert!(err.to_string().contains("digest mismatch"), "{err}");
+
+    let mut injected: Value =
+        serde_json::from_str(&fs::read_to_string(&plan_out).unwrap()).unwrap();
+    injected["plan"]["steps"]
+        .as_array_mut()
+        .unwrap()
+        .push(json!({
+            "type": "run_cli",
+            "command": "cargo",
+            "args": ["--version"]
+        }));
+    let injected_path = temp.path().join("plan.injected.json");
+    fs::write(
+        &injected_path,
+        serde_json::to_string_pretty(&injected).unwrap(),
+    )
+    .unwrap();
+    let err = run(args(None, None, Some(injected_path))).expect_err("injected step must fail");
+    assert!(err.to_string().contains("digest mismatch"), "{err}");
+
+    let mut foreign: Value = serde_json::from_str(&fs::read_to_string(&plan_out).unwrap()).unwrap();
+    foreign["metadata"]["generator"] = json!("hand-written");
+    let foreign_path = temp.path().join("plan.foreign.json");
+    fs::write(
+        &foreign_path,
+        serde_json::to_string_pretty(&foreign).unwrap(),
+    )
+    .unwrap();
+    let err = run(args(None, None, Some(foreign_path))).expect_err("unknown generator must fail");
+    assert!(err.to_string().contains("hand-written"), "{err}");
+}
+
+#[test]
+fn wizard_cbor_plan_round_trips_through_apply() {
+    let temp = tempfile::TempDir::new().unwrap();
+    let answers = temp.path().join("answers.json");
+    let plan_out = temp.path().join("plan.cbor");
+    let component_root = temp.path().join("cbor-plan-component");
+    create_answers(&answers, "cbor-plan-component");
+
+    let args = |answers: Option<std::path::PathBuf>,
+                plan_out: Option<std::path::PathBuf>,
+                plan: Option<std::path::PathBuf>,
+                plan_format: PlanFormat| WizardArgs {
+        validate: answers.is_some(),
+        apply: plan.is_some(),
+        answers,
+        plan_out,
+        plan,
+        plan_format,
+        project_root: temp.path().to_path_buf(),
+        ..WizardArgs::default()
+    };
+
+    run(args(
+        Some(answers),
+        Some(plan_out.clone()),
+        None,
+        PlanFormat::Cbor,
+    ))
+    .expect("dry-run should write a CBOR plan");
+    let bytes = fs::read(&plan_out).unwrap();
+    assert!(serde_json::from_slice::<Value>(&bytes).is_err());
+    let decoded: Value = greentic_types::cbor::canonical::from_cbor(&bytes).unwrap();
+    assert_eq!(decoded["plan_version"], 1);
+
+    run(args(None, None, Some(plan_out.clone()), PlanFormat::Json))
+        .expect_err("a CBOR plan is not JSON");
+    assert!(!component_root.exists());
+    run(args(None, None, Some(plan_out), PlanFormat::Cbor)).expect("CBOR plan should apply");
+    assert!(component_root.join("Cargo.toml").exists());
+}